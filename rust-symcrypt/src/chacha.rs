@@ -1,6 +1,10 @@
 //! ChaChaPoly1305 Functions. For further documentation please refer to symcrypt.h
+//!
+//! With the `aead` feature, [`ChaCha20Poly1305`] implements the RustCrypto `aead` traits.
 
 use crate::errors::SymCryptError;
+#[cfg(feature = "aead")]
+use crate::secret::SecretArray;
 use symcrypt_sys;
 
 /// Stateless call to encrypt using ChaChaPoly1305.
@@ -91,6 +95,70 @@ pub fn chacha20_poly1305_decrypt_in_place(
     }
 }
 
+/// [`ChaCha20Poly1305`] implements the RustCrypto `aead` traits on top of [`chacha20_poly1305_encrypt_in_place()`] and
+/// [`chacha20_poly1305_decrypt_in_place()`]. It is available with the `aead` feature.
+///
+/// The key is held in a [`SecretArray`] so it is wiped when the [`ChaCha20Poly1305`] leaves scope.
+#[cfg(feature = "aead")]
+pub struct ChaCha20Poly1305(SecretArray<32>);
+
+#[cfg(feature = "aead")]
+impl aead::KeySizeUser for ChaCha20Poly1305 {
+    type KeySize = aead::consts::U32;
+}
+
+#[cfg(feature = "aead")]
+impl aead::KeyInit for ChaCha20Poly1305 {
+    fn new(key: &aead::Key<Self>) -> Self {
+        ChaCha20Poly1305(SecretArray::new((*key).into()))
+    }
+}
+
+#[cfg(feature = "aead")]
+impl aead::AeadCore for ChaCha20Poly1305 {
+    type NonceSize = aead::consts::U12;
+    type TagSize = aead::consts::U16;
+    type CiphertextOverhead = aead::consts::U0;
+}
+
+#[cfg(feature = "aead")]
+impl aead::AeadInPlace for ChaCha20Poly1305 {
+    fn encrypt_in_place_detached(
+        &self,
+        nonce: &aead::Nonce<Self>,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> aead::Result<aead::Tag<Self>> {
+        let mut tag = [0u8; 16];
+        chacha20_poly1305_encrypt_in_place(
+            self.0.as_bytes(),
+            &(*nonce).into(),
+            associated_data,
+            buffer,
+            &mut tag,
+        )
+        .map_err(|_| aead::Error)?;
+        Ok(tag.into())
+    }
+
+    fn decrypt_in_place_detached(
+        &self,
+        nonce: &aead::Nonce<Self>,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        tag: &aead::Tag<Self>,
+    ) -> aead::Result<()> {
+        chacha20_poly1305_decrypt_in_place(
+            self.0.as_bytes(),
+            &(*nonce).into(),
+            associated_data,
+            buffer,
+            &(*tag).into(),
+        )
+        .map_err(|_| aead::Error)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -211,4 +279,95 @@ mod test {
 
         assert_eq!(result.unwrap_err(), SymCryptError::AuthenticationFailure);
     }
+
+    #[cfg(feature = "aead")]
+    #[test]
+    fn test_chacha_aead() {
+        use aead::{Aead, AeadInPlace, KeyInit, Payload};
+
+        // RFC 8439 section 2.8.2, same as test_chacha_encrypt.
+        let key = hex::decode("808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9f")
+            .unwrap();
+        let nonce = hex::decode("070000004041424344454647").unwrap();
+        let auth_data = hex::decode("50515253c0c1c2c3c4c5c6c7").unwrap();
+        let plaintext = hex::decode("4c616469657320616e642047656e746c656d656e206f662074686520636c617373206f66202739393a204966204920636f756c64206f6666657220796f75206f6e6c79206f6e652074697020666f7220746865206675747572652c2073756e73637265656e20776f756c642062652069742e").unwrap();
+        let expected_cipher = "d31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d63dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b3692ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc3ff4def08e4b7a9de576d26586cec64b6116";
+        let expected_tag = "1ae10b594f09e26a7e902ecbd0600691";
+
+        let cipher = ChaCha20Poly1305::new_from_slice(&key).unwrap();
+        let payload = Payload {
+            msg: &plaintext,
+            aad: &auth_data,
+        };
+        let ciphertext = cipher.encrypt(nonce.as_slice().into(), payload).unwrap();
+        assert_eq!(
+            hex::encode(&ciphertext),
+            format!("{}{}", expected_cipher, expected_tag)
+        );
+
+        let mut buffer = plaintext.clone();
+        let tag = cipher
+            .encrypt_in_place_detached(nonce.as_slice().into(), &auth_data, &mut buffer)
+            .unwrap();
+        assert_eq!(hex::encode(&buffer), expected_cipher);
+        assert_eq!(hex::encode(tag), expected_tag);
+
+        cipher
+            .decrypt_in_place_detached(nonce.as_slice().into(), &auth_data, &mut buffer, &tag)
+            .unwrap();
+        assert_eq!(buffer, plaintext);
+
+        let mut tampered = ciphertext.clone();
+        tampered[0] ^= 1;
+        assert!(cipher
+            .decrypt(nonce.as_slice().into(), tampered.as_slice())
+            .is_err());
+    }
+
+    #[cfg(feature = "aead")]
+    #[test]
+    fn test_chacha_aead_against_chacha20poly1305_crate() {
+        use aead::{Aead, KeyInit, Payload};
+
+        let key = [0x33u8; 32];
+        let nonce = [0x44u8; 12];
+        let cipher = ChaCha20Poly1305::new_from_slice(&key).unwrap();
+        let reference = chacha20poly1305::ChaCha20Poly1305::new_from_slice(&key).unwrap();
+        for length in [0, 1, 63, 64, 65, 1000] {
+            let plaintext: Vec<u8> = (0..length).map(|i| i as u8).collect();
+            let ciphertext = cipher
+                .encrypt(
+                    &nonce.into(),
+                    Payload {
+                        msg: &plaintext,
+                        aad: b"associated data",
+                    },
+                )
+                .unwrap();
+            let expected = reference
+                .encrypt(
+                    &nonce.into(),
+                    Payload {
+                        msg: &plaintext,
+                        aad: b"associated data",
+                    },
+                )
+                .unwrap();
+            assert_eq!(ciphertext, expected);
+
+            let payload = Payload {
+                msg: &ciphertext,
+                aad: b"associated data",
+            };
+            assert_eq!(cipher.decrypt(&nonce.into(), payload).unwrap(), plaintext);
+        }
+    }
+
+    // Wycheproof vectors, as packaged in the tests of the chacha20poly1305 crate.
+    #[cfg(feature = "aead")]
+    aead::new_test!(
+        test_chacha20_poly1305_wycheproof,
+        "wycheproof_chacha20poly1305",
+        ChaCha20Poly1305
+    );
 }
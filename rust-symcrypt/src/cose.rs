@@ -0,0 +1,958 @@
+//! CBOR Object Signing and Encryption functions as defined in RFC 9052 and RFC 9053. Requires the `cose` feature.
+//!
+//! COSE_Sign1 supports ES256 and ES384, COSE_Mac0 supports HMAC 256/256 and COSE_Encrypt0 supports A128GCM, A256GCM and
+//! ChaCha20/Poly1305.
+//!
+//! EdDSA is not supported since SymCrypt does not provide Ed25519, there is no [`CoseAlgorithm`] for it and its `alg` fails with
+//! [`SymCryptError::NotImplemented`] in [`CoseAlgorithm::from_id()`].
+//!
+//! Messages are produced with the CBOR tag of their type and are accepted with or without it. Detached payloads, COSE_Key encoding and
+//! messages with more than one signer or recipient are not supported.
+//!
+//! Decrypted payloads are returned in a type that is wiped when it leaves scope, but the CBOR values that ciborium allocates while
+//! parsing and encoding are not wiped.
+
+use crate::block_ciphers::BlockCipherType;
+use crate::chacha::{chacha20_poly1305_decrypt_in_place, chacha20_poly1305_encrypt_in_place};
+use crate::ecdsa::{ecdsa_sign, ecdsa_verify};
+use crate::eckey::{CurveType, EcKey};
+use crate::errors::SymCryptError;
+use crate::gcm::GcmExpandedKey;
+use crate::hash::{sha256, sha384};
+use crate::hmac::{hmac_sha256, SHA256_HMAC_RESULT_SIZE};
+use crate::rng::fill_bytes;
+use crate::secret::{constant_time_eq, SecretBytes};
+use ciborium::value::Value;
+
+/// The CBOR tag of a COSE_Encrypt0 message.
+pub const COSE_ENCRYPT0_TAG: u64 = 16;
+
+/// The CBOR tag of a COSE_Mac0 message.
+pub const COSE_MAC0_TAG: u64 = 17;
+
+/// The CBOR tag of a COSE_Sign1 message.
+pub const COSE_SIGN1_TAG: u64 = 18;
+
+/// The `alg` header label.
+pub const HEADER_ALGORITHM: i64 = 1;
+
+/// The `crit` header label.
+pub const HEADER_CRITICAL: i64 = 2;
+
+/// The `content type` header label.
+pub const HEADER_CONTENT_TYPE: i64 = 3;
+
+/// The `kid` header label.
+pub const HEADER_KEY_ID: i64 = 4;
+
+/// The `IV` header label.
+pub const HEADER_IV: i64 = 5;
+
+/// The `Partial IV` header label.
+pub const HEADER_PARTIAL_IV: i64 = 6;
+
+/// The minimum size of a HMAC 256/256 key, which is the size of the SHA-256 result as for HS256 in JOSE.
+const HMAC_256_MIN_KEY_SIZE: usize = SHA256_HMAC_RESULT_SIZE;
+
+const AEAD_IV_SIZE: usize = 12;
+const AEAD_TAG_SIZE: usize = 16;
+
+/// The EdDSA algorithm identifier from RFC 9053, which is rejected since SymCrypt does not provide Ed25519.
+const COSE_ALGORITHM_EDDSA: i64 = -8;
+
+/// [`CoseKey`] is the key used to sign, verify, MAC, encrypt or decrypt a COSE message.
+///
+/// [`CoseKey::Ec2`] is a key on P-256 or P-384 for ES256 and ES384, it must have a private key to sign.
+///
+/// [`CoseKey::Symmetric`] is a key for HMAC 256/256, A128GCM, A256GCM or ChaCha20/Poly1305.
+pub enum CoseKey {
+    Ec2(EcKey),
+    Symmetric(SecretBytes),
+}
+
+/// [`CoseHeaders`] holds the protected and unprotected header parameters of a COSE message as CBOR label and value pairs.
+///
+/// When a message is created, the `alg` is added to the protected parameters and must not be set by the caller. When a message is
+/// decoded, the parameters are returned as they were in the message, including the `alg` and, for COSE_Encrypt0, the `IV`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CoseHeaders {
+    pub protected: Vec<(Value, Value)>,
+    pub unprotected: Vec<(Value, Value)>,
+}
+
+/// Impl for CoseHeaders struct.
+///
+/// [`new()`] returns [`CoseHeaders`] without any parameters.
+///
+/// [`get()`] returns the value of the parameter with an integer `label`, looking in the protected parameters first.
+///
+/// [`key_id()`] returns the `kid` if it is present and is a byte string.
+impl CoseHeaders {
+    pub fn new() -> Self {
+        CoseHeaders::default()
+    }
+
+    pub fn get(&self, label: i64) -> Option<&Value> {
+        find(&self.protected, label).or_else(|| find(&self.unprotected, label))
+    }
+
+    pub fn key_id(&self) -> Option<&[u8]> {
+        self.get(HEADER_KEY_ID)?
+            .as_bytes()
+            .map(|key_id| &key_id[..])
+    }
+}
+
+/// [`CoseAlgorithm`] is the `alg` of a COSE message.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum CoseAlgorithm {
+    Es256,
+    Es384,
+    Hmac256,
+    A128Gcm,
+    A256Gcm,
+    ChaCha20Poly1305,
+}
+
+impl CoseAlgorithm {
+    /// Returns the algorithm identifier as registered in RFC 9053.
+    pub fn id(&self) -> i64 {
+        match self {
+            CoseAlgorithm::Es256 => -7,
+            CoseAlgorithm::Es384 => -35,
+            CoseAlgorithm::Hmac256 => 5,
+            CoseAlgorithm::A128Gcm => 1,
+            CoseAlgorithm::A256Gcm => 3,
+            CoseAlgorithm::ChaCha20Poly1305 => 24,
+        }
+    }
+
+    /// Returns the [`CoseAlgorithm`] for an algorithm identifier. EdDSA (-8) fails with [`SymCryptError::NotImplemented`] since SymCrypt
+    /// does not provide Ed25519, other unsupported identifiers fail with [`SymCryptError::IncompatibleFormat`].
+    pub fn from_id(id: i64) -> Result<Self, SymCryptError> {
+        if id == COSE_ALGORITHM_EDDSA {
+            return Err(SymCryptError::NotImplemented);
+        }
+        [
+            CoseAlgorithm::Es256,
+            CoseAlgorithm::Es384,
+            CoseAlgorithm::Hmac256,
+            CoseAlgorithm::A128Gcm,
+            CoseAlgorithm::A256Gcm,
+            CoseAlgorithm::ChaCha20Poly1305,
+        ]
+        .into_iter()
+        .find(|algorithm| algorithm.id() == id)
+        .ok_or(SymCryptError::IncompatibleFormat)
+    }
+}
+
+/// [`sign1_sign()`] signs `payload` and `external_aad` with `key` and returns a tagged COSE_Sign1 message with the `headers`.
+///
+/// ES256 and ES384 need a [`CoseKey::Ec2`] private key on P-256 and P-384. Other keys, and `headers` that set the `alg`, fail with
+/// [`SymCryptError::InvalidArgument`].
+pub fn sign1_sign(
+    key: &CoseKey,
+    algorithm: CoseAlgorithm,
+    headers: &CoseHeaders,
+    payload: &[u8],
+    external_aad: &[u8],
+) -> Result<Vec<u8>, SymCryptError> {
+    let protected = encode_protected(algorithm, headers)?;
+    let to_be_signed = sig_structure(&protected, external_aad, payload)?;
+    let signature = match (algorithm, key) {
+        (CoseAlgorithm::Es256, CoseKey::Ec2(key))
+            if key.curve_type() == CurveType::NistP256 && key.has_private_key() =>
+        {
+            ecdsa_sign(key, &sha256(&to_be_signed))?
+        }
+        (CoseAlgorithm::Es384, CoseKey::Ec2(key))
+            if key.curve_type() == CurveType::NistP384 && key.has_private_key() =>
+        {
+            ecdsa_sign(key, &sha384(&to_be_signed))?
+        }
+        _ => return Err(SymCryptError::InvalidArgument),
+    };
+    encode_message(
+        COSE_SIGN1_TAG,
+        vec![
+            Value::Bytes(protected),
+            Value::Map(headers.unprotected.clone()),
+            Value::Bytes(payload.to_vec()),
+            Value::Bytes(signature),
+        ],
+    )
+}
+
+/// [`sign1_verify()`] verifies the COSE_Sign1 `message` and `external_aad` with `key`, and returns the headers and the payload.
+///
+/// The `alg` of the message must be `algorithm`, so that a message can not pick a weaker algorithm than expected. Headers with `crit`
+/// fail with [`SymCryptError::IncompatibleFormat`] since no extensions are understood, and a malformed message fails with
+/// [`SymCryptError::InvalidBlob`]. Any failure to verify, including a different `alg`, returns
+/// [`SymCryptError::SignatureVerificationFailure`].
+pub fn sign1_verify(
+    key: &CoseKey,
+    algorithm: CoseAlgorithm,
+    message: &[u8],
+    external_aad: &[u8],
+) -> Result<(CoseHeaders, Vec<u8>), SymCryptError> {
+    let (protected, headers, mut fields) = decode_message(message, COSE_SIGN1_TAG, 4)?;
+    let signature = bytes(fields.pop())?;
+    let payload = payload(fields.pop())?;
+    if !has_algorithm(&headers, algorithm) {
+        return Err(SymCryptError::SignatureVerificationFailure);
+    }
+    let to_be_signed = sig_structure(&protected, external_aad, &payload)?;
+    match (algorithm, key) {
+        (CoseAlgorithm::Es256, CoseKey::Ec2(key)) if key.curve_type() == CurveType::NistP256 => {
+            ecdsa_verify(key, &sha256(&to_be_signed), &signature)?
+        }
+        (CoseAlgorithm::Es384, CoseKey::Ec2(key)) if key.curve_type() == CurveType::NistP384 => {
+            ecdsa_verify(key, &sha384(&to_be_signed), &signature)?
+        }
+        _ => return Err(SymCryptError::InvalidArgument),
+    }
+    Ok((headers, payload))
+}
+
+/// [`mac0_create()`] computes the MAC of `payload` and `external_aad` with `key` and returns a tagged COSE_Mac0 message with the `headers`.
+///
+/// HMAC 256/256 needs a [`CoseKey::Symmetric`] key of at least 32 bytes. Other keys, and `headers` that set the `alg`, fail with
+/// [`SymCryptError::InvalidArgument`].
+pub fn mac0_create(
+    key: &CoseKey,
+    algorithm: CoseAlgorithm,
+    headers: &CoseHeaders,
+    payload: &[u8],
+    external_aad: &[u8],
+) -> Result<Vec<u8>, SymCryptError> {
+    let protected = encode_protected(algorithm, headers)?;
+    let tag = mac(key, algorithm, &protected, external_aad, payload)?;
+    encode_message(
+        COSE_MAC0_TAG,
+        vec![
+            Value::Bytes(protected),
+            Value::Map(headers.unprotected.clone()),
+            Value::Bytes(payload.to_vec()),
+            Value::Bytes(tag.to_vec()),
+        ],
+    )
+}
+
+/// [`mac0_verify()`] verifies the COSE_Mac0 `message` and `external_aad` with `key`, and returns the headers and the payload.
+///
+/// Headers with `crit` fail with [`SymCryptError::IncompatibleFormat`] and a malformed message fails with [`SymCryptError::InvalidBlob`].
+/// Any failure to verify, including a different `alg`, returns [`SymCryptError::AuthenticationFailure`].
+pub fn mac0_verify(
+    key: &CoseKey,
+    algorithm: CoseAlgorithm,
+    message: &[u8],
+    external_aad: &[u8],
+) -> Result<(CoseHeaders, Vec<u8>), SymCryptError> {
+    let (protected, headers, mut fields) = decode_message(message, COSE_MAC0_TAG, 4)?;
+    let tag = bytes(fields.pop())?;
+    let payload = payload(fields.pop())?;
+    if !has_algorithm(&headers, algorithm) {
+        return Err(SymCryptError::AuthenticationFailure);
+    }
+    if !constant_time_eq(
+        &mac(key, algorithm, &protected, external_aad, &payload)?,
+        &tag,
+    ) {
+        return Err(SymCryptError::AuthenticationFailure);
+    }
+    Ok((headers, payload))
+}
+
+/// [`encrypt0_encrypt()`] encrypts `plaintext` with `key` and `external_aad` as additional authenticated data, and returns a tagged
+/// COSE_Encrypt0 message with the `headers`. A random `IV` is added to the unprotected parameters.
+///
+/// A128GCM needs a [`CoseKey::Symmetric`] key of 16 bytes, A256GCM and ChaCha20/Poly1305 need a key of 32 bytes. Other keys, and
+/// `headers` that set the `alg`, `IV` or `Partial IV`, fail with [`SymCryptError::InvalidArgument`].
+pub fn encrypt0_encrypt(
+    key: &CoseKey,
+    algorithm: CoseAlgorithm,
+    headers: &CoseHeaders,
+    plaintext: &[u8],
+    external_aad: &[u8],
+) -> Result<Vec<u8>, SymCryptError> {
+    if headers.get(HEADER_IV).is_some() || headers.get(HEADER_PARTIAL_IV).is_some() {
+        return Err(SymCryptError::InvalidArgument);
+    }
+    let key = aead_key(key, algorithm)?;
+    let protected = encode_protected(algorithm, headers)?;
+    let enc_structure = enc_structure(&protected, external_aad)?;
+
+    let mut iv = [0u8; AEAD_IV_SIZE];
+    fill_bytes(&mut iv);
+    let mut ciphertext = plaintext.to_vec();
+    let mut tag = [0u8; AEAD_TAG_SIZE];
+    match algorithm {
+        CoseAlgorithm::ChaCha20Poly1305 => chacha20_poly1305_encrypt_in_place(
+            chacha_key(key)?,
+            &iv,
+            &enc_structure,
+            &mut ciphertext,
+            &mut tag,
+        )?,
+        _ => GcmExpandedKey::new(key, BlockCipherType::AesBlock)?.encrypt_in_place(
+            &iv,
+            &enc_structure,
+            &mut ciphertext,
+            &mut tag,
+        ),
+    }
+    ciphertext.extend_from_slice(&tag);
+
+    let mut unprotected = vec![(Value::from(HEADER_IV), Value::Bytes(iv.to_vec()))];
+    unprotected.extend(headers.unprotected.iter().cloned());
+    encode_message(
+        COSE_ENCRYPT0_TAG,
+        vec![
+            Value::Bytes(protected),
+            Value::Map(unprotected),
+            Value::Bytes(ciphertext),
+        ],
+    )
+}
+
+/// [`encrypt0_decrypt()`] decrypts the COSE_Encrypt0 `message` with `key` and `external_aad`, and returns the headers and the plaintext,
+/// which is wiped when it leaves scope.
+///
+/// Headers with `crit` or a `Partial IV` fail with [`SymCryptError::IncompatibleFormat`], since the base IV of a key is not known, and a
+/// malformed message fails with [`SymCryptError::InvalidBlob`]. Any failure to decrypt, including a different `alg`, returns
+/// [`SymCryptError::AuthenticationFailure`].
+pub fn encrypt0_decrypt(
+    key: &CoseKey,
+    algorithm: CoseAlgorithm,
+    message: &[u8],
+    external_aad: &[u8],
+) -> Result<(CoseHeaders, SecretBytes), SymCryptError> {
+    let (protected, headers, mut fields) = decode_message(message, COSE_ENCRYPT0_TAG, 3)?;
+    let mut ciphertext = bytes(fields.pop())?;
+    if headers.get(HEADER_PARTIAL_IV).is_some() {
+        return Err(SymCryptError::IncompatibleFormat);
+    }
+    let iv: [u8; AEAD_IV_SIZE] = match headers.get(HEADER_IV) {
+        Some(Value::Bytes(iv)) => iv
+            .as_slice()
+            .try_into()
+            .map_err(|_| SymCryptError::InvalidBlob)?,
+        _ => return Err(SymCryptError::InvalidBlob),
+    };
+    if ciphertext.len() < AEAD_TAG_SIZE {
+        return Err(SymCryptError::InvalidBlob);
+    }
+    if !has_algorithm(&headers, algorithm) {
+        return Err(SymCryptError::AuthenticationFailure);
+    }
+    let key = aead_key(key, algorithm)?;
+    let enc_structure = enc_structure(&protected, external_aad)?;
+
+    let tag: [u8; AEAD_TAG_SIZE] = ciphertext
+        .split_off(ciphertext.len() - AEAD_TAG_SIZE)
+        .try_into()
+        .map_err(|_| SymCryptError::InvalidBlob)?;
+    let mut plaintext = SecretBytes::from(ciphertext);
+    match algorithm {
+        CoseAlgorithm::ChaCha20Poly1305 => chacha20_poly1305_decrypt_in_place(
+            chacha_key(key)?,
+            &iv,
+            &enc_structure,
+            plaintext.as_mut_bytes(),
+            &tag,
+        ),
+        _ => GcmExpandedKey::new(key, BlockCipherType::AesBlock)?.decrypt_in_place(
+            &iv,
+            &enc_structure,
+            plaintext.as_mut_bytes(),
+            &tag,
+        ),
+    }
+    .map_err(|_| SymCryptError::AuthenticationFailure)?;
+    Ok((headers, plaintext))
+}
+
+/// mac computes the HMAC 256/256 tag of the MAC_structure after checking that the key matches the algorithm.
+fn mac(
+    key: &CoseKey,
+    algorithm: CoseAlgorithm,
+    protected: &[u8],
+    external_aad: &[u8],
+    payload: &[u8],
+) -> Result<[u8; SHA256_HMAC_RESULT_SIZE], SymCryptError> {
+    match (algorithm, key) {
+        (CoseAlgorithm::Hmac256, CoseKey::Symmetric(key)) if key.len() >= HMAC_256_MIN_KEY_SIZE => {
+            let mac_structure = encode(&Value::Array(vec![
+                Value::Text("MAC0".to_string()),
+                Value::Bytes(protected.to_vec()),
+                Value::Bytes(external_aad.to_vec()),
+                Value::Bytes(payload.to_vec()),
+            ]))?;
+            hmac_sha256(key, &mac_structure)
+        }
+        _ => Err(SymCryptError::InvalidArgument),
+    }
+}
+
+/// aead_key returns the key bytes after checking that the key matches the content encryption algorithm.
+fn aead_key(key: &CoseKey, algorithm: CoseAlgorithm) -> Result<&[u8], SymCryptError> {
+    match (algorithm, key) {
+        (CoseAlgorithm::A128Gcm, CoseKey::Symmetric(key)) if key.len() == 16 => Ok(key.as_bytes()),
+        (CoseAlgorithm::A256Gcm | CoseAlgorithm::ChaCha20Poly1305, CoseKey::Symmetric(key))
+            if key.len() == 32 =>
+        {
+            Ok(key.as_bytes())
+        }
+        _ => Err(SymCryptError::InvalidArgument),
+    }
+}
+
+fn chacha_key(key: &[u8]) -> Result<&[u8; 32], SymCryptError> {
+    key.try_into().map_err(|_| SymCryptError::InvalidArgument)
+}
+
+/// sig_structure encodes the Sig_structure of a COSE_Sign1 message as defined in RFC 9052 section 4.4.
+fn sig_structure(
+    protected: &[u8],
+    external_aad: &[u8],
+    payload: &[u8],
+) -> Result<Vec<u8>, SymCryptError> {
+    encode(&Value::Array(vec![
+        Value::Text("Signature1".to_string()),
+        Value::Bytes(protected.to_vec()),
+        Value::Bytes(external_aad.to_vec()),
+        Value::Bytes(payload.to_vec()),
+    ]))
+}
+
+/// enc_structure encodes the Enc_structure of a COSE_Encrypt0 message as defined in RFC 9052 section 5.3, which is the additional
+/// authenticated data of the content encryption.
+fn enc_structure(protected: &[u8], external_aad: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+    encode(&Value::Array(vec![
+        Value::Text("Encrypt0".to_string()),
+        Value::Bytes(protected.to_vec()),
+        Value::Bytes(external_aad.to_vec()),
+    ]))
+}
+
+/// encode_protected encodes the protected header with the `alg` first, followed by the protected parameters of `headers`.
+fn encode_protected(
+    algorithm: CoseAlgorithm,
+    headers: &CoseHeaders,
+) -> Result<Vec<u8>, SymCryptError> {
+    if headers.get(HEADER_ALGORITHM).is_some() {
+        return Err(SymCryptError::InvalidArgument);
+    }
+    let mut protected = vec![(Value::from(HEADER_ALGORITHM), Value::from(algorithm.id()))];
+    protected.extend(headers.protected.iter().cloned());
+    encode(&Value::Map(protected))
+}
+
+fn encode_message(tag: u64, fields: Vec<Value>) -> Result<Vec<u8>, SymCryptError> {
+    encode(&Value::Tag(tag, Box::new(Value::Array(fields))))
+}
+
+/// encode encodes a CBOR value. ciborium encodes integers and lengths in their shortest form and keeps the order of map entries, so the
+/// encoding is the same as the one used by the COSE examples.
+fn encode(value: &Value) -> Result<Vec<u8>, SymCryptError> {
+    let mut encoded = Vec::new();
+    ciborium::ser::into_writer(value, &mut encoded).map_err(|_| SymCryptError::InvalidArgument)?;
+    Ok(encoded)
+}
+
+/// decode_message decodes a COSE message with the expected CBOR `tag`, which may be left out, and `field_count` fields. It returns the
+/// encoded protected header, which is authenticated as it was received, the headers and the fields after the headers.
+fn decode_message(
+    message: &[u8],
+    tag: u64,
+    field_count: usize,
+) -> Result<(Vec<u8>, CoseHeaders, Vec<Value>), SymCryptError> {
+    let value = decode(message)?;
+    let value = match value {
+        Value::Tag(message_tag, value) if message_tag == tag => *value,
+        Value::Tag(..) => return Err(SymCryptError::InvalidBlob),
+        value => value,
+    };
+    let mut fields = match value {
+        Value::Array(fields) if fields.len() == field_count => fields.into_iter(),
+        _ => return Err(SymCryptError::InvalidBlob),
+    };
+    let protected = bytes(fields.next())?;
+    let unprotected = match fields.next() {
+        Some(Value::Map(unprotected)) => unprotected,
+        _ => return Err(SymCryptError::InvalidBlob),
+    };
+    // An empty protected header is encoded as a zero length byte string rather than an empty map.
+    let protected_map = if protected.is_empty() {
+        Vec::new()
+    } else {
+        match decode(&protected)? {
+            Value::Map(protected_map) => protected_map,
+            _ => return Err(SymCryptError::InvalidBlob),
+        }
+    };
+    // RFC 9052 section 3 does not allow a label in both the protected and unprotected parameters.
+    if protected_map
+        .iter()
+        .any(|(label, _)| unprotected.iter().any(|(other, _)| label == other))
+    {
+        return Err(SymCryptError::InvalidBlob);
+    }
+    let headers = CoseHeaders {
+        protected: protected_map,
+        unprotected,
+    };
+    if headers.get(HEADER_CRITICAL).is_some() {
+        return Err(SymCryptError::IncompatibleFormat);
+    }
+    Ok((protected, headers, fields.collect()))
+}
+
+/// decode decodes a single CBOR value, trailing bytes fail with [`SymCryptError::InvalidBlob`].
+fn decode(encoded: &[u8]) -> Result<Value, SymCryptError> {
+    let mut reader = encoded;
+    let value = ciborium::de::from_reader(&mut reader).map_err(|_| SymCryptError::InvalidBlob)?;
+    if !reader.is_empty() {
+        return Err(SymCryptError::InvalidBlob);
+    }
+    Ok(value)
+}
+
+fn bytes(value: Option<Value>) -> Result<Vec<u8>, SymCryptError> {
+    match value {
+        Some(Value::Bytes(bytes)) => Ok(bytes),
+        _ => Err(SymCryptError::InvalidBlob),
+    }
+}
+
+/// payload returns the payload of a message, a detached payload is encoded as nil and fails with [`SymCryptError::IncompatibleFormat`].
+fn payload(value: Option<Value>) -> Result<Vec<u8>, SymCryptError> {
+    match value {
+        Some(Value::Null) => Err(SymCryptError::IncompatibleFormat),
+        value => bytes(value),
+    }
+}
+
+fn has_algorithm(headers: &CoseHeaders, algorithm: CoseAlgorithm) -> bool {
+    headers.get(HEADER_ALGORITHM) == Some(&Value::from(algorithm.id()))
+}
+
+fn find(parameters: &[(Value, Value)], label: i64) -> Option<&Value> {
+    let label = Value::from(label);
+    parameters
+        .iter()
+        .find(|(other, _)| *other == label)
+        .map(|(_, value)| value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::eckey::EcKeyUsage;
+
+    const CONTENT: &[u8] = b"This is the content.";
+    const EXTERNAL_AAD: &str = "0011bbcc22dd44ee55ff660077";
+
+    // The P-256 key "11" of the COSE WG examples repository.
+    const P256_PUBLIC_KEY: &str = "bac5b11cad8f99f9c72b05cf4b9e26d244dc189f745228255a219a86d6a09eff20138bf82dc1b6d562be0fa54ab7804a3a64b6d72ccfed6b6fb6ed28bbfc117e";
+    const P256_PRIVATE_KEY: &str =
+        "57c92077664146e876760c9520d054aa93c3afb04e306705db6090308507b4d3";
+
+    // RFC 9052 section C.2.1, which is sign1-tests/sign-pass-01 of the COSE WG examples repository.
+    const SIGN1_MESSAGE: &str = "d28443a10126a10442313154546869732069732074686520636f6e74656e742e58408eb33e4ca31d1c465ab05aac34cc6b23d58fef5c083106c4d25a91aef0b0117e2af9a291aa32e14ab834dc56ed2a223444547e01f11d3b0916e5a4c345cacb36";
+
+    // The 256 bit and 128 bit symmetric keys of the COSE WG examples repository.
+    const KEY_256: &str = "849b57219dae48de646d07dbb533566e976686457c1491be3a76dcea6c427188";
+    const KEY_128: &str = "849b5786457c1491be3a76dcea6c4271";
+
+    // The first message is mac0-tests/HMac-01 of the COSE WG examples repository. The second one is not from the examples, it was
+    // computed with Python as hmac.new(KEY_256, MAC_structure, hashlib.sha256) over the RFC 9052 section 6.3 MAC_structure
+    // ["MAC0", h'a10105', h'0011bbcc22dd44ee55ff660077', payload]. The same computation without the external AAD reproduces HMac-01.
+    const MAC0_MESSAGES: [(&str, &str); 2] = [
+        ("", "d18443a10105a054546869732069732074686520636f6e74656e742e5820a1a848d3471f9d61ee49018d244c824772f223ad4f935293f1789fc3a08d8c58"),
+        (EXTERNAL_AAD, "d18443a10105a054546869732069732074686520636f6e74656e742e5820269a5429e1e79f596ddaeba9841c1fcb8d8810a3a60216887b5b8b4d1d286c2d"),
+    ];
+
+    // encrypted-tests/aes-gcm-01 of the COSE WG examples repository, the key is the first 128 bits of the 256 bit key.
+    const AES_GCM_01_KEY: &str = "849b57219dae48de646d07dbb533566e";
+    const AES_GCM_01_MESSAGE: &str = "d08343a10101a1054c02d1f7e6f26c43d4868d87ce582460973a94bb2898009ee52ecfd9ab1dd25867374b162e2c03568b41f57c3cc16f9166250a";
+
+    // The keys and the IV 02d1f7e6f26c43d4868d87ce follow the COSE WG examples, but the messages other than aes-gcm-01 are not from the
+    // examples. They were computed with the AESGCM and ChaCha20Poly1305 classes of the Python cryptography package, encrypting CONTENT
+    // with the RFC 9052 section 5.3 Enc_structure ["Encrypt0", protected, external_aad] as the associated data. The same computation
+    // reproduces aes-gcm-01 from its key.
+    const ENCRYPT0_MESSAGES: [(CoseAlgorithm, &str, &str, &str); 7] = [
+        (CoseAlgorithm::A128Gcm, AES_GCM_01_KEY, "", AES_GCM_01_MESSAGE),
+        (CoseAlgorithm::A128Gcm, KEY_128, "", "d08343a10101a1054c02d1f7e6f26c43d4868d87ce5824bc8d5ee513f90394ae5bebeac0e900cb38a8d6eb3abc917f7e8205b8a77691b736add58a"),
+        (CoseAlgorithm::A128Gcm, KEY_128, EXTERNAL_AAD, "d08343a10101a1054c02d1f7e6f26c43d4868d87ce5824bc8d5ee513f90394ae5bebeac0e900cb38a8d6eb09819553c6351a9f3b728ccda26116b4"),
+        (CoseAlgorithm::A256Gcm, KEY_256, "", "d08343a10103a1054c02d1f7e6f26c43d4868d87ce5824adcd02cc9281e101b35b7e71efe16b08a778023c96a5a2971a1622017e4c5116381289d4"),
+        (CoseAlgorithm::A256Gcm, KEY_256, EXTERNAL_AAD, "d08343a10103a1054c02d1f7e6f26c43d4868d87ce5824adcd02cc9281e101b35b7e71efe16b08a778023c555656820f26177d793ae4ed630d4838"),
+        (CoseAlgorithm::ChaCha20Poly1305, KEY_256, "", "d08344a1011818a1054c02d1f7e6f26c43d4868d87ce58243acac41d412bbc59e66c55b14028bb82abbcf43aca512cb5ddd12739b81eff5e073907d2"),
+        (CoseAlgorithm::ChaCha20Poly1305, KEY_256, EXTERNAL_AAD, "d08344a1011818a1054c02d1f7e6f26c43d4868d87ce58243acac41d412bbc59e66c55b14028bb82abbcf43a96e8a8b5ddd7a1da89768972bbcf958f"),
+    ];
+
+    fn symmetric_key(key: &str) -> CoseKey {
+        CoseKey::Symmetric(SecretBytes::from(hex::decode(key).unwrap()))
+    }
+
+    fn p256_public_key() -> CoseKey {
+        CoseKey::Ec2(
+            EcKey::from_public_key_bytes(
+                CurveType::NistP256,
+                &hex::decode(P256_PUBLIC_KEY).unwrap(),
+                EcKeyUsage::EcDsa,
+            )
+            .unwrap(),
+        )
+    }
+
+    fn p256_private_key() -> CoseKey {
+        CoseKey::Ec2(
+            EcKey::from_private_key_bytes(
+                CurveType::NistP256,
+                &hex::decode(P256_PRIVATE_KEY).unwrap(),
+                EcKeyUsage::EcDsa,
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_sign1_verify_rfc9052() {
+        let message = hex::decode(SIGN1_MESSAGE).unwrap();
+        let (headers, payload) =
+            sign1_verify(&p256_public_key(), CoseAlgorithm::Es256, &message, &[]).unwrap();
+        assert_eq!(payload, CONTENT);
+        assert_eq!(headers.key_id(), Some(&b"11"[..]));
+        assert_eq!(
+            headers.get(HEADER_ALGORITHM),
+            Some(&Value::from(CoseAlgorithm::Es256.id()))
+        );
+
+        // The untagged message is accepted.
+        sign1_verify(&p256_public_key(), CoseAlgorithm::Es256, &message[1..], &[]).unwrap();
+
+        assert_eq!(
+            sign1_verify(&p256_public_key(), CoseAlgorithm::Es256, &message, b"aad").unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+        assert_eq!(
+            sign1_verify(&p256_public_key(), CoseAlgorithm::Es384, &message, &[]).unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+        let mut modified = message.clone();
+        modified[20] ^= 1;
+        assert_eq!(
+            sign1_verify(&p256_public_key(), CoseAlgorithm::Es256, &modified, &[]).unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+    }
+
+    #[test]
+    fn test_sign1_round_trip() {
+        let external_aad = hex::decode(EXTERNAL_AAD).unwrap();
+        let headers = CoseHeaders {
+            protected: vec![(Value::from(HEADER_CONTENT_TYPE), Value::from(0))],
+            unprotected: vec![(Value::from(HEADER_KEY_ID), Value::Bytes(b"11".to_vec()))],
+        };
+        let message = sign1_sign(
+            &p256_private_key(),
+            CoseAlgorithm::Es256,
+            &headers,
+            CONTENT,
+            &external_aad,
+        )
+        .unwrap();
+        let (decoded, payload) = sign1_verify(
+            &p256_public_key(),
+            CoseAlgorithm::Es256,
+            &message,
+            &external_aad,
+        )
+        .unwrap();
+        assert_eq!(payload, CONTENT);
+        assert_eq!(decoded.unprotected, headers.unprotected);
+        assert_eq!(decoded.get(HEADER_CONTENT_TYPE), Some(&Value::from(0)));
+        assert_eq!(
+            sign1_verify(&p256_public_key(), CoseAlgorithm::Es256, &message, &[]).unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+
+        let p384_key =
+            CoseKey::Ec2(EcKey::generate(CurveType::NistP384, EcKeyUsage::EcDsa).unwrap());
+        let message = sign1_sign(
+            &p384_key,
+            CoseAlgorithm::Es384,
+            &CoseHeaders::new(),
+            CONTENT,
+            &[],
+        )
+        .unwrap();
+        let (_, payload) = sign1_verify(&p384_key, CoseAlgorithm::Es384, &message, &[]).unwrap();
+        assert_eq!(payload, CONTENT);
+    }
+
+    #[test]
+    fn test_sign1_invalid_keys() {
+        let headers = CoseHeaders::new();
+        for (key, algorithm) in [
+            (p256_public_key(), CoseAlgorithm::Es256),
+            (p256_private_key(), CoseAlgorithm::Es384),
+            (symmetric_key(KEY_256), CoseAlgorithm::Es256),
+            (p256_private_key(), CoseAlgorithm::Hmac256),
+        ] {
+            assert_eq!(
+                sign1_sign(&key, algorithm, &headers, CONTENT, &[]).unwrap_err(),
+                SymCryptError::InvalidArgument
+            );
+        }
+
+        let headers = CoseHeaders {
+            protected: vec![(Value::from(HEADER_ALGORITHM), Value::from(-7))],
+            unprotected: Vec::new(),
+        };
+        assert_eq!(
+            sign1_sign(
+                &p256_private_key(),
+                CoseAlgorithm::Es256,
+                &headers,
+                CONTENT,
+                &[]
+            )
+            .unwrap_err(),
+            SymCryptError::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn test_mac0() {
+        let key = symmetric_key(KEY_256);
+        for (external_aad, expected) in MAC0_MESSAGES {
+            let external_aad = hex::decode(external_aad).unwrap();
+            let message = mac0_create(
+                &key,
+                CoseAlgorithm::Hmac256,
+                &CoseHeaders::new(),
+                CONTENT,
+                &external_aad,
+            )
+            .unwrap();
+            assert_eq!(hex::encode(&message), expected);
+
+            let (headers, payload) =
+                mac0_verify(&key, CoseAlgorithm::Hmac256, &message, &external_aad).unwrap();
+            assert_eq!(payload, CONTENT);
+            assert!(headers.unprotected.is_empty());
+
+            let mut modified = message.clone();
+            let last = modified.len() - 1;
+            modified[last] ^= 1;
+            assert_eq!(
+                mac0_verify(&key, CoseAlgorithm::Hmac256, &modified, &external_aad).unwrap_err(),
+                SymCryptError::AuthenticationFailure
+            );
+            assert_eq!(
+                mac0_verify(&key, CoseAlgorithm::Hmac256, &message, b"aad").unwrap_err(),
+                SymCryptError::AuthenticationFailure
+            );
+        }
+        assert_eq!(
+            mac0_create(
+                &symmetric_key(KEY_128),
+                CoseAlgorithm::Hmac256,
+                &CoseHeaders::new(),
+                CONTENT,
+                &[]
+            )
+            .unwrap_err(),
+            SymCryptError::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn test_encrypt0_decrypt() {
+        for (algorithm, key, external_aad, message) in ENCRYPT0_MESSAGES {
+            let key = symmetric_key(key);
+            let external_aad = hex::decode(external_aad).unwrap();
+            let message = hex::decode(message).unwrap();
+            let (headers, plaintext) =
+                encrypt0_decrypt(&key, algorithm, &message, &external_aad).unwrap();
+            assert_eq!(plaintext.as_bytes(), CONTENT);
+            assert_eq!(
+                headers.get(HEADER_IV),
+                Some(&Value::Bytes(
+                    hex::decode("02d1f7e6f26c43d4868d87ce").unwrap()
+                ))
+            );
+
+            assert_eq!(
+                encrypt0_decrypt(&key, algorithm, &message, b"aad").unwrap_err(),
+                SymCryptError::AuthenticationFailure
+            );
+            let mut modified = message.clone();
+            let last = modified.len() - 1;
+            modified[last] ^= 1;
+            assert_eq!(
+                encrypt0_decrypt(&key, algorithm, &modified, &external_aad).unwrap_err(),
+                SymCryptError::AuthenticationFailure
+            );
+        }
+
+        let (_, key, _, message) = ENCRYPT0_MESSAGES[3];
+        assert_eq!(
+            encrypt0_decrypt(
+                &symmetric_key(key),
+                CoseAlgorithm::ChaCha20Poly1305,
+                &hex::decode(message).unwrap(),
+                &[]
+            )
+            .unwrap_err(),
+            SymCryptError::AuthenticationFailure
+        );
+    }
+
+    #[test]
+    fn test_encrypt0_round_trip() {
+        let external_aad = hex::decode(EXTERNAL_AAD).unwrap();
+        let headers = CoseHeaders {
+            protected: Vec::new(),
+            unprotected: vec![(
+                Value::from(HEADER_KEY_ID),
+                Value::Bytes(b"our-secret".to_vec()),
+            )],
+        };
+        for (algorithm, key) in [
+            (CoseAlgorithm::A128Gcm, KEY_128),
+            (CoseAlgorithm::A256Gcm, KEY_256),
+            (CoseAlgorithm::ChaCha20Poly1305, KEY_256),
+        ] {
+            let key = symmetric_key(key);
+            let message =
+                encrypt0_encrypt(&key, algorithm, &headers, CONTENT, &external_aad).unwrap();
+            let (decoded, plaintext) =
+                encrypt0_decrypt(&key, algorithm, &message, &external_aad).unwrap();
+            assert_eq!(plaintext.as_bytes(), CONTENT);
+            assert_eq!(decoded.key_id(), Some(&b"our-secret"[..]));
+
+            // The IV is random, so encrypting twice gives different messages.
+            assert_ne!(
+                encrypt0_encrypt(&key, algorithm, &headers, CONTENT, &external_aad).unwrap(),
+                message
+            );
+        }
+
+        let headers = CoseHeaders {
+            protected: Vec::new(),
+            unprotected: vec![(Value::from(HEADER_IV), Value::Bytes(vec![0u8; 12]))],
+        };
+        assert_eq!(
+            encrypt0_encrypt(
+                &symmetric_key(KEY_256),
+                CoseAlgorithm::A256Gcm,
+                &headers,
+                CONTENT,
+                &[]
+            )
+            .unwrap_err(),
+            SymCryptError::InvalidArgument
+        );
+        assert_eq!(
+            encrypt0_encrypt(
+                &symmetric_key(KEY_128),
+                CoseAlgorithm::A256Gcm,
+                &CoseHeaders::new(),
+                CONTENT,
+                &[]
+            )
+            .unwrap_err(),
+            SymCryptError::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn test_invalid_messages() {
+        let key = symmetric_key(KEY_256);
+        let (_, message) = MAC0_MESSAGES[0];
+        let message = hex::decode(message).unwrap();
+
+        // A COSE_Mac0 message is not a COSE_Sign1 message.
+        assert_eq!(
+            sign1_verify(&p256_public_key(), CoseAlgorithm::Es256, &message, &[]).unwrap_err(),
+            SymCryptError::InvalidBlob
+        );
+        for message in [
+            &[][..],
+            &message[..message.len() - 1],
+            &[&message[..], &[0u8][..]].concat(),
+            &hex::decode("d1a0").unwrap(),
+            &hex::decode("d18340a054").unwrap(),
+        ] {
+            assert_eq!(
+                mac0_verify(&key, CoseAlgorithm::Hmac256, message, &[]).unwrap_err(),
+                SymCryptError::InvalidBlob
+            );
+        }
+
+        // A label in both the protected and unprotected parameters.
+        let mut duplicated = encode_protected(CoseAlgorithm::Hmac256, &CoseHeaders::new()).unwrap();
+        let tag = mac(&key, CoseAlgorithm::Hmac256, &duplicated, &[], CONTENT).unwrap();
+        duplicated = encode_message(
+            COSE_MAC0_TAG,
+            vec![
+                Value::Bytes(duplicated),
+                Value::Map(vec![(Value::from(HEADER_ALGORITHM), Value::from(5))]),
+                Value::Bytes(CONTENT.to_vec()),
+                Value::Bytes(tag.to_vec()),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            mac0_verify(&key, CoseAlgorithm::Hmac256, &duplicated, &[]).unwrap_err(),
+            SymCryptError::InvalidBlob
+        );
+
+        let critical = CoseHeaders {
+            protected: vec![(
+                Value::from(HEADER_CRITICAL),
+                Value::Array(vec![Value::from(-65537)]),
+            )],
+            unprotected: Vec::new(),
+        };
+        let message = mac0_create(&key, CoseAlgorithm::Hmac256, &critical, CONTENT, &[]).unwrap();
+        assert_eq!(
+            mac0_verify(&key, CoseAlgorithm::Hmac256, &message, &[]).unwrap_err(),
+            SymCryptError::IncompatibleFormat
+        );
+
+        // A detached payload.
+        let detached = hex::decode("d18443a10105a0f65820").unwrap();
+        let detached = [&detached[..], &[0u8; 32][..]].concat();
+        assert_eq!(
+            mac0_verify(&key, CoseAlgorithm::Hmac256, &detached, &[]).unwrap_err(),
+            SymCryptError::IncompatibleFormat
+        );
+    }
+
+    #[test]
+    fn test_cose_algorithm_ids() {
+        for algorithm in [
+            CoseAlgorithm::Es256,
+            CoseAlgorithm::Es384,
+            CoseAlgorithm::Hmac256,
+            CoseAlgorithm::A128Gcm,
+            CoseAlgorithm::A256Gcm,
+            CoseAlgorithm::ChaCha20Poly1305,
+        ] {
+            assert_eq!(CoseAlgorithm::from_id(algorithm.id()).unwrap(), algorithm);
+        }
+        assert_eq!(
+            CoseAlgorithm::from_id(10).unwrap_err(),
+            SymCryptError::IncompatibleFormat
+        );
+        assert_eq!(
+            CoseAlgorithm::from_id(COSE_ALGORITHM_EDDSA).unwrap_err(),
+            SymCryptError::NotImplemented
+        );
+    }
+}
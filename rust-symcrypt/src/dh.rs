@@ -0,0 +1,486 @@
+//! Finite field Diffie-Hellman functions. For further documentation please refer to symcrypt.h
+
+use crate::errors::SymCryptError;
+use crate::secret::SecretBytes;
+use crate::symcrypt_init;
+use lazy_static::lazy_static;
+use symcrypt_sys;
+
+/// [`DhGroupType`] provides an enum of the named safe-prime groups that can be used when creating a [`DlGroup`].
+///
+/// The Ffdhe groups are defined in RFC 7919 and are used by TLS, the Modp groups are defined in RFC 3526 and are used by IKE.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum DhGroupType {
+    Ffdhe2048,
+    Ffdhe3072,
+    Ffdhe4096,
+    Modp2048,
+    Modp3072,
+    Modp4096,
+}
+
+/// convert_group takes in the friendly DhGroupType enum and returns the symcrypt safe-prime type along with the size of P in bits.
+fn convert_group(group: DhGroupType) -> (symcrypt_sys::SYMCRYPT_DLGROUP_DH_SAFEPRIMETYPE, u32) {
+    let tls_7919 =
+        symcrypt_sys::_SYMCRYPT_DLGROUP_DH_SAFEPRIMETYPE_SYMCRYPT_DLGROUP_DH_SAFEPRIMETYPE_TLS_7919;
+    let ike_3526 =
+        symcrypt_sys::_SYMCRYPT_DLGROUP_DH_SAFEPRIMETYPE_SYMCRYPT_DLGROUP_DH_SAFEPRIMETYPE_IKE_3526;
+    match group {
+        DhGroupType::Ffdhe2048 => (tls_7919, 2048),
+        DhGroupType::Ffdhe3072 => (tls_7919, 3072),
+        DhGroupType::Ffdhe4096 => (tls_7919, 4096),
+        DhGroupType::Modp2048 => (ike_3526, 2048),
+        DhGroupType::Modp3072 => (ike_3526, 3072),
+        DhGroupType::Modp4096 => (ike_3526, 4096),
+    }
+}
+
+/// [`DlGroup`] is a wrapper around symcrypt_sys::PSYMCRYPT_DLGROUP.
+pub struct DlGroup(symcrypt_sys::PSYMCRYPT_DLGROUP);
+
+// Named groups can be re-used across Dh calls, creating static references to save on allocations and increase perf.
+// unwraps used here since only way this could fail is via not enough memory.
+lazy_static! {
+    static ref FFDHE_2048: DlGroup = internal_new(DhGroupType::Ffdhe2048).unwrap();
+    static ref FFDHE_3072: DlGroup = internal_new(DhGroupType::Ffdhe3072).unwrap();
+    static ref FFDHE_4096: DlGroup = internal_new(DhGroupType::Ffdhe4096).unwrap();
+    static ref MODP_2048: DlGroup = internal_new(DhGroupType::Modp2048).unwrap();
+    static ref MODP_3072: DlGroup = internal_new(DhGroupType::Modp3072).unwrap();
+    static ref MODP_4096: DlGroup = internal_new(DhGroupType::Modp4096).unwrap();
+}
+
+/// allocate_group allocates an empty DlGroup, SymCryptInit must be called before any Dh operations are performed.
+fn allocate_group(bits_of_p: u32, bits_of_q: u32) -> Result<DlGroup, SymCryptError> {
+    unsafe {
+        // SAFETY: FFI calls
+        symcrypt_init(); // Will only init once, subsequent calls to symcrypt_init() will be no-ops.
+
+        // Stack allocated since will do SymCryptDlgroupAllocate.
+        let group_ptr = symcrypt_sys::SymCryptDlgroupAllocate(bits_of_p, bits_of_q);
+        if group_ptr.is_null() {
+            return Err(SymCryptError::MemoryAllocationFailure);
+        }
+        // Group needs to be wrapped to properly free the group in the case there is an error when setting the value.
+        Ok(DlGroup(group_ptr))
+    }
+}
+
+fn internal_new(group: DhGroupType) -> Result<DlGroup, SymCryptError> {
+    let (safe_prime_type, bits_of_p) = convert_group(group);
+    let dl_group = allocate_group(bits_of_p, 0)?; // 0 lets SymCrypt pick the size of Q, which is bits_of_p - 1 for safe primes.
+    unsafe {
+        // SAFETY: FFI calls
+        match symcrypt_sys::SymCryptDlgroupSetValueSafePrime(safe_prime_type, dl_group.0) {
+            symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(dl_group),
+            err => Err(err.into()),
+        }
+    }
+}
+
+/// bit_length returns the number of significant bits of a big-endian integer.
+fn bit_length(value: &[u8]) -> u32 {
+    match value.iter().position(|&byte| byte != 0) {
+        Some(index) => ((value.len() - index) * 8) as u32 - value[index].leading_zeros(),
+        None => 0,
+    }
+}
+
+/// Impl for [`DlGroup`]
+///
+/// [`new()`] returns a static [`DlGroup`] associated with the provided [`DhGroupType`].
+///
+/// [`from_parameters()`] takes in the big-endian prime P, the optional prime Q that is the order of the subgroup, and the generator G.
+/// If you do not wish to provide Q just input an empty array, peer public values can then only be range checked and not checked for
+/// membership in the subgroup.
+///
+/// [`get_size()`] returns the size of P in bytes, which is also the size of the public values and of the secret agreement.
+///
+/// [`get_prime_q_size()`] returns the size of Q in bytes.
+impl DlGroup {
+    pub fn new(group: DhGroupType) -> &'static Self {
+        let dl_group: &'static DlGroup = match group {
+            DhGroupType::Ffdhe2048 => &FFDHE_2048,
+            DhGroupType::Ffdhe3072 => &FFDHE_3072,
+            DhGroupType::Ffdhe4096 => &FFDHE_4096,
+            DhGroupType::Modp2048 => &MODP_2048,
+            DhGroupType::Modp3072 => &MODP_3072,
+            DhGroupType::Modp4096 => &MODP_4096,
+        };
+
+        dl_group
+    }
+
+    pub fn from_parameters(
+        prime_p: &[u8],
+        prime_q: &[u8],
+        generator: &[u8],
+    ) -> Result<Self, SymCryptError> {
+        let dl_group = allocate_group(bit_length(prime_p), bit_length(prime_q))?;
+        let prime_q_ptr = if prime_q.is_empty() {
+            std::ptr::null() // Q is unknown
+        } else {
+            prime_q.as_ptr()
+        };
+
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptDlgroupSetValue(
+                prime_p.as_ptr(),
+                prime_p.len() as symcrypt_sys::SIZE_T,
+                prime_q_ptr,
+                prime_q.len() as symcrypt_sys::SIZE_T,
+                generator.as_ptr(),
+                generator.len() as symcrypt_sys::SIZE_T,
+                symcrypt_sys::_SYMCRYPT_NUMBER_FORMAT_SYMCRYPT_NUMBER_FORMAT_MSB_FIRST,
+                std::ptr::null(), // no hash algorithm, seed or counter since the group was not generated per FIPS 186
+                std::ptr::null(),
+                0,
+                0,
+                symcrypt_sys::_SYMCRYPT_DLGROUP_FIPS_SYMCRYPT_DLGROUP_FIPS_NONE,
+                dl_group.0,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(dl_group),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.get_sizes().0
+    }
+
+    pub fn get_prime_q_size(&self) -> usize {
+        self.get_sizes().1
+    }
+
+    pub(crate) fn inner(&self) -> symcrypt_sys::PSYMCRYPT_DLGROUP {
+        self.0
+    }
+
+    fn get_sizes(&self) -> (usize, usize) {
+        let mut prime_p_size: symcrypt_sys::SIZE_T = 0;
+        let mut prime_q_size: symcrypt_sys::SIZE_T = 0;
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptDlgroupGetSizes(
+                self.0,
+                &mut prime_p_size,
+                &mut prime_q_size,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            );
+        }
+        (prime_p_size as usize, prime_q_size as usize)
+    }
+}
+
+unsafe impl Send for DlGroup {
+    // The group is never modified after it has been set, and is only referenced through the SymCrypt allocated pointer.
+}
+
+unsafe impl Sync for DlGroup {
+    // SymCrypt only reads the group once it has been set.
+}
+
+/// Must drop [`DlGroup`] after every [`Dh`] that references it is dropped, this is enforced by the lifetime on [`Dh`].
+impl Drop for DlGroup {
+    fn drop(&mut self) {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptDlgroupFree(self.0)
+        }
+    }
+}
+
+/// The Dh secret agreement result value. The secret is always the size of P, and is wiped when it leaves scope.
+pub type DhSecretAgreement = SecretBytes;
+
+/// Dh struct holds the SymCrypt DlKey as well as a reference to the [`DlGroup`] it was created on.
+///
+/// The DlKey is allocated by SymCrypt and is freed when Dh leaves scope.
+pub struct Dh<'a> {
+    group: &'a DlGroup,
+    key: symcrypt_sys::PSYMCRYPT_DLKEY,
+}
+
+/// allocate_key allocates an empty DlKey on the provided group.
+fn allocate_key(group: &DlGroup) -> Result<Dh<'_>, SymCryptError> {
+    unsafe {
+        // SAFETY: FFI calls
+        let key_ptr = symcrypt_sys::SymCryptDlkeyAllocate(group.0);
+        if key_ptr.is_null() {
+            return Err(SymCryptError::MemoryAllocationFailure);
+        }
+        Ok(Dh {
+            group,
+            key: key_ptr,
+        })
+    }
+}
+
+/// Impl for Dh struct.
+///
+/// [`new()`] takes in a [`DlGroup`] and returns a Dh struct who's DlKey has a private/public key pair assigned to it.
+///
+/// [`from_public_key_bytes()`] takes in a big-endian public value, and creates a Dh struct who's DlKey has only a public key attached.
+/// Values shorter than P are left-padded to the size of P, since some peers strip the leading zeros, and values longer than P fail with
+/// [`SymCryptError::WrongKeySize`]. The public value is validated by SymCrypt, values that are not in the range 1 < y < P - 1, or that
+/// are not in the subgroup of order Q when Q is known, are rejected.
+///
+/// [`get_public_key_bytes()`] returns a Vec<u8> that is the big-endian public value associated with the current DlKey, left-padded to the
+/// size of P.
+///
+/// [`dh_secret_agreement()`] takes in two Dh structs and returns the associated secret agreement. Both structs must be on the same group.
+impl<'a> Dh<'a> {
+    pub fn new(group: &'a DlGroup) -> Result<Self, SymCryptError> {
+        let dh_key = allocate_key(group)?;
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptDlkeyGenerate(
+                symcrypt_sys::SYMCRYPT_FLAG_DLKEY_DH,
+                dh_key.key,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(dh_key),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn from_public_key_bytes(
+        group: &'a DlGroup,
+        public_key: &[u8],
+    ) -> Result<Self, SymCryptError> {
+        let size = group.get_size();
+        if public_key.len() > size {
+            return Err(SymCryptError::WrongKeySize);
+        }
+        let mut padded = vec![0u8; size];
+        padded[size - public_key.len()..].copy_from_slice(public_key);
+        let dh_key = allocate_key(group)?;
+
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptDlkeySetValue(
+                std::ptr::null(), // private key set to null since none is generated
+                0,
+                padded.as_ptr(),
+                padded.len() as symcrypt_sys::SIZE_T,
+                symcrypt_sys::_SYMCRYPT_NUMBER_FORMAT_SYMCRYPT_NUMBER_FORMAT_MSB_FIRST,
+                symcrypt_sys::SYMCRYPT_FLAG_DLKEY_DH,
+                dh_key.key,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(dh_key),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn get_public_key_bytes(&self) -> Result<Vec<u8>, SymCryptError> {
+        unsafe {
+            // SAFETY: FFI calls
+            let pub_key_len = symcrypt_sys::SymCryptDlkeySizeofPublicKey(self.key);
+            let mut pub_key_bytes = vec![0u8; pub_key_len as usize];
+
+            match symcrypt_sys::SymCryptDlkeyGetValue(
+                self.key,
+                std::ptr::null_mut(), // setting private key to null since we will only access public key
+                0,
+                pub_key_bytes.as_mut_ptr(),
+                pub_key_bytes.len() as symcrypt_sys::SIZE_T,
+                symcrypt_sys::_SYMCRYPT_NUMBER_FORMAT_SYMCRYPT_NUMBER_FORMAT_MSB_FIRST,
+                0, // No flags allowed
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(pub_key_bytes),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn group(&self) -> &'a DlGroup {
+        self.group
+    }
+
+    pub fn dh_secret_agreement(
+        private: &Dh,
+        public: &Dh,
+    ) -> Result<DhSecretAgreement, SymCryptError> {
+        let mut secret = DhSecretAgreement::zeroed(private.group.get_size());
+
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptDhSecretAgreement(
+                private.key,
+                public.key,
+                symcrypt_sys::_SYMCRYPT_NUMBER_FORMAT_SYMCRYPT_NUMBER_FORMAT_MSB_FIRST,
+                0,
+                secret.as_mut_bytes().as_mut_ptr(),
+                secret.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(secret),
+                err => Err(err.into()),
+            }
+        }
+    }
+}
+
+unsafe impl Send for Dh<'_> {
+    // The DlKey is only referenced through the SymCrypt allocated pointer, which does not move.
+}
+
+unsafe impl Sync for Dh<'_> {
+    // All operations on a Dh after creation only read the DlKey.
+}
+
+impl Drop for Dh<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptDlkeyFree(self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Prime P of the ffdhe2048 group from RFC 7919 appendix A.1.
+    const FFDHE2048_P: &str = "ffffffffffffffffadf85458a2bb4a9aafdc5620273d3cf1d8b9c583ce2d3695a9e13641146433fbcc939dce249b3ef97d2fe363630c75d8f681b202aec4617ad3df1ed5d5fd65612433f51f5f066ed0856365553ded1af3b557135e7f57c935984f0c70e0e68b77e2a689daf3efe8721df158a136ade73530acca4f483a797abc0ab182b324fb61d108a94bb2c8e3fbb96adab760d7f4681d4f42a3de394df4ae56ede76372bb190b07a7c8ee0a6d709e02fce1cdf7e2ecc03404cd28342f619172fe9ce98583ff8e4f1232eef28183c3fe3b1b4c6fad733bb5fcbc2ec22005c58ef1837d1683b2c6f34a26c1b2effa886b423861285c97ffffffffffffffff";
+
+    #[test]
+    fn test_dh_secret_agreement() {
+        let group = DlGroup::new(DhGroupType::Ffdhe2048);
+        let dh_private = Dh::new(group).unwrap();
+        let dh_public = Dh::new(group).unwrap();
+
+        let public_bytes_1 = dh_private.get_public_key_bytes().unwrap();
+        let public_bytes_2 = dh_public.get_public_key_bytes().unwrap();
+        assert_eq!(public_bytes_1.len(), 256);
+
+        let dh_public_1 = Dh::from_public_key_bytes(group, &public_bytes_1).unwrap();
+        let dh_public_2 = Dh::from_public_key_bytes(group, &public_bytes_2).unwrap();
+
+        let secret_agreement_1 = Dh::dh_secret_agreement(&dh_private, &dh_public_2).unwrap();
+        let secret_agreement_2 = Dh::dh_secret_agreement(&dh_public, &dh_public_1).unwrap();
+
+        assert_eq!(secret_agreement_1.as_bytes().len(), 256);
+        assert_eq!(secret_agreement_1.as_bytes(), secret_agreement_2.as_bytes());
+    }
+
+    #[test]
+    fn test_dh_named_group_sizes() {
+        let groups = [
+            (DhGroupType::Ffdhe2048, 256),
+            (DhGroupType::Ffdhe3072, 384),
+            (DhGroupType::Ffdhe4096, 512),
+            (DhGroupType::Modp2048, 256),
+            (DhGroupType::Modp3072, 384),
+            (DhGroupType::Modp4096, 512),
+        ];
+
+        for (group_type, size) in groups {
+            let group = DlGroup::new(group_type);
+            assert_eq!(group.get_size(), size);
+
+            let dh = Dh::new(group).unwrap();
+            assert_eq!(dh.get_public_key_bytes().unwrap().len(), size);
+        }
+    }
+
+    #[test]
+    fn test_dh_import_public_key() {
+        // 2^x mod P of the RFC 7919 ffdhe2048 group for x = 0x0102...20, computed with Python's pow(2, x, p).
+        let public_key = hex::decode("0eef0c0eae9c65a3332cdc742b58561d362c0af526f8ad528b19ff39c91434312f83302ed9dc6f2b84b250482dbd80962154f8a6d683741bf8bf4f3fdee22f80541a77553d1ae7a096c521f869987ae5eca1090d93e7bdea0b4349f17fee34f4775bfb380f426dec937b25825680c6e2b8c4c9eede64b91f5cdc02ba78e75908d47cd27fda4b5188c22baf2acd1e861ed0864e9eb9346640d354eada9f7156a601387ba5f1b8609733707803a5ac866fc1bbe54b15a99934727bc5c157f893d867a89abf0ce3dd0cf3d7ce00073db8b3bbae1486faedc462f7edeb3dc44ba30336de3d4dbfb4f14f143c46b0ba93f316f50c3377b9a38a27ae048f5605a4dcbf").unwrap();
+        let group = DlGroup::new(DhGroupType::Ffdhe2048);
+
+        let dh_public = Dh::from_public_key_bytes(group, &public_key).unwrap();
+        assert_eq!(dh_public.get_public_key_bytes().unwrap(), public_key);
+    }
+
+    #[test]
+    fn test_dh_invalid_peer_public_key() {
+        let group = DlGroup::new(DhGroupType::Ffdhe2048);
+        let prime_p = hex::decode(FFDHE2048_P).unwrap();
+
+        let zero = vec![0u8; 256];
+        let mut one = vec![0u8; 256];
+        one[255] = 1;
+        let mut p_minus_one = prime_p.clone();
+        p_minus_one[255] = 0xfe;
+
+        for public_key in [zero, one, p_minus_one, prime_p] {
+            assert!(Dh::from_public_key_bytes(group, &public_key).is_err());
+        }
+    }
+
+    #[test]
+    fn test_dh_wrong_public_key_size() {
+        let group = DlGroup::new(DhGroupType::Ffdhe2048);
+        // Longer than P, even though the value itself is in range.
+        let mut public_key = vec![0u8; 257];
+        public_key[256] = 2;
+
+        let result = Dh::from_public_key_bytes(group, &public_key);
+        assert_eq!(result.err().unwrap(), SymCryptError::WrongKeySize);
+    }
+
+    #[test]
+    fn test_dh_short_public_key() {
+        let group = DlGroup::new(DhGroupType::Ffdhe2048);
+        // The generator 2 is a valid public value, and is left-padded to the size of P.
+        let mut padded = vec![0u8; 256];
+        padded[255] = 2;
+
+        for public_key in [&[2u8][..], &padded[1..]] {
+            let dh_public = Dh::from_public_key_bytes(group, public_key).unwrap();
+            assert_eq!(dh_public.get_public_key_bytes().unwrap(), padded);
+        }
+
+        let dh_private = Dh::new(group).unwrap();
+        let secret_agreement_1 = Dh::dh_secret_agreement(
+            &dh_private,
+            &Dh::from_public_key_bytes(group, &[2]).unwrap(),
+        )
+        .unwrap();
+        let secret_agreement_2 = Dh::dh_secret_agreement(
+            &dh_private,
+            &Dh::from_public_key_bytes(group, &padded).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(secret_agreement_1.as_bytes(), secret_agreement_2.as_bytes());
+    }
+
+    #[test]
+    fn test_dh_custom_group() {
+        let prime_p = hex::decode(FFDHE2048_P).unwrap();
+        // Q = (P - 1) / 2 for a safe prime, computed by shifting P right by one bit.
+        let mut prime_q = vec![0u8; prime_p.len()];
+        let mut carry = 0u8;
+        for (q, p) in prime_q.iter_mut().zip(prime_p.iter()) {
+            *q = (p >> 1) | carry;
+            carry = p << 7;
+        }
+        let group = DlGroup::from_parameters(&prime_p, &prime_q, &[2]).unwrap();
+        assert_eq!(group.get_size(), 256);
+
+        let dh_1 = Dh::new(&group).unwrap();
+        let dh_2 = Dh::new(&group).unwrap();
+        let dh_public_2 =
+            Dh::from_public_key_bytes(&group, &dh_2.get_public_key_bytes().unwrap()).unwrap();
+        let dh_public_1 =
+            Dh::from_public_key_bytes(&group, &dh_1.get_public_key_bytes().unwrap()).unwrap();
+
+        let secret_agreement_1 = Dh::dh_secret_agreement(&dh_1, &dh_public_2).unwrap();
+        let secret_agreement_2 = Dh::dh_secret_agreement(&dh_2, &dh_public_1).unwrap();
+        assert_eq!(secret_agreement_1.as_bytes(), secret_agreement_2.as_bytes());
+    }
+
+    #[test]
+    fn test_dh_mismatched_groups() {
+        let dh_2048 = Dh::new(DlGroup::new(DhGroupType::Ffdhe2048)).unwrap();
+        let dh_3072 = Dh::new(DlGroup::new(DhGroupType::Ffdhe3072)).unwrap();
+
+        let result = Dh::dh_secret_agreement(&dh_2048, &dh_3072);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,544 @@
+//! DSA functions. For further documentation please refer to symcrypt.h
+//!
+//! DSA is only provided to verify signatures from legacy systems. New signatures should use a newer algorithm.
+
+use crate::dh::DlGroup;
+use crate::errors::{map_verify_error, SymCryptError};
+use symcrypt_sys;
+
+/// [`DsaKey`] holds the SymCrypt DlKey along with the [`DlGroup`] built from the imported P, Q and G.
+///
+/// The DlKey is allocated by SymCrypt and is freed before the [`DlGroup`] when DsaKey leaves scope.
+pub struct DsaKey {
+    key: symcrypt_sys::PSYMCRYPT_DLKEY,
+    group: DlGroup,
+}
+
+/// Impl for DsaKey struct.
+///
+/// [`from_public_key()`] takes in the big-endian P, Q, G and public key Y, and returns a [`DsaKey`] that can only verify. Q is required for
+/// DSA. The public key is validated by SymCrypt before it is accepted.
+///
+/// [`from_key_pair()`] takes in the big-endian P, Q, G, public key Y and private key X, and returns a [`DsaKey`] that can sign and verify.
+///
+/// [`has_private_key()`] returns true if the key was imported with a private key.
+///
+/// [`signature_size()`] returns the size of a signature in bytes, which is R || S each padded to the size of Q.
+///
+/// [`sign()`] takes in a hashed message and returns the R || S signature. The hash is truncated to the size of Q as defined in FIPS 186-4.
+///
+/// [`verify()`] takes in a hashed message and a R || S signature. Any failure to verify, including a malformed signature, returns
+/// [`SymCryptError::SignatureVerificationFailure`].
+impl DsaKey {
+    pub fn from_public_key(
+        prime_p: &[u8],
+        prime_q: &[u8],
+        generator: &[u8],
+        public_key: &[u8],
+    ) -> Result<Self, SymCryptError> {
+        DsaKey::import(prime_p, prime_q, generator, public_key, &[])
+    }
+
+    pub fn from_key_pair(
+        prime_p: &[u8],
+        prime_q: &[u8],
+        generator: &[u8],
+        public_key: &[u8],
+        private_key: &[u8],
+    ) -> Result<Self, SymCryptError> {
+        if private_key.is_empty() {
+            return Err(SymCryptError::InvalidArgument);
+        }
+        DsaKey::import(prime_p, prime_q, generator, public_key, private_key)
+    }
+
+    fn import(
+        prime_p: &[u8],
+        prime_q: &[u8],
+        generator: &[u8],
+        public_key: &[u8],
+        private_key: &[u8],
+    ) -> Result<Self, SymCryptError> {
+        if prime_q.is_empty() {
+            return Err(SymCryptError::InvalidArgument);
+        }
+        let group = DlGroup::from_parameters(prime_p, prime_q, generator)?;
+        let private_key_ptr = if private_key.is_empty() {
+            std::ptr::null() // private key set to null since only the public key is imported
+        } else {
+            private_key.as_ptr()
+        };
+
+        unsafe {
+            // SAFETY: FFI calls
+            let key_ptr = symcrypt_sys::SymCryptDlkeyAllocate(group.inner());
+            if key_ptr.is_null() {
+                return Err(SymCryptError::MemoryAllocationFailure);
+            }
+            // Key is wrapped first so that it is freed if setting the value fails.
+            let dsa_key = DsaKey {
+                key: key_ptr,
+                group,
+            };
+
+            match symcrypt_sys::SymCryptDlkeySetValue(
+                private_key_ptr,
+                private_key.len() as symcrypt_sys::SIZE_T,
+                public_key.as_ptr(),
+                public_key.len() as symcrypt_sys::SIZE_T,
+                symcrypt_sys::_SYMCRYPT_NUMBER_FORMAT_SYMCRYPT_NUMBER_FORMAT_MSB_FIRST,
+                symcrypt_sys::SYMCRYPT_FLAG_DLKEY_DSA,
+                dsa_key.key,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(dsa_key),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn has_private_key(&self) -> bool {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptDlkeyHasPrivateKey(self.key) != 0
+        }
+    }
+
+    pub fn signature_size(&self) -> usize {
+        2 * self.group.get_prime_q_size()
+    }
+
+    pub fn sign(&self, hashed_message: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+        let mut signature = vec![0u8; self.signature_size()];
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptDsaSign(
+                self.key,
+                hashed_message.as_ptr(),
+                hashed_message.len() as symcrypt_sys::SIZE_T,
+                symcrypt_sys::_SYMCRYPT_NUMBER_FORMAT_SYMCRYPT_NUMBER_FORMAT_MSB_FIRST,
+                0, // No flags allowed
+                signature.as_mut_ptr(),
+                signature.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(signature),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn verify(&self, hashed_message: &[u8], signature: &[u8]) -> Result<(), SymCryptError> {
+        unsafe {
+            // SAFETY: FFI calls
+            map_verify_error(symcrypt_sys::SymCryptDsaVerify(
+                self.key,
+                hashed_message.as_ptr(),
+                hashed_message.len() as symcrypt_sys::SIZE_T,
+                signature.as_ptr(),
+                signature.len() as symcrypt_sys::SIZE_T,
+                symcrypt_sys::_SYMCRYPT_NUMBER_FORMAT_SYMCRYPT_NUMBER_FORMAT_MSB_FIRST,
+                0, // No flags allowed
+            ))
+        }
+    }
+}
+
+unsafe impl Send for DsaKey {
+    // The DlKey and DlGroup are only referenced through SymCrypt allocated pointers, which do not move.
+}
+
+unsafe impl Sync for DsaKey {
+    // Signing and verifying only read the DlKey.
+}
+
+/// The DlKey must be freed before the [`DlGroup`], fields are dropped after this runs.
+impl Drop for DsaKey {
+    fn drop(&mut self) {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptDlkeyFree(self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hash::sha256;
+
+    // Keys and deterministic signatures from RFC 6979 appendix A.2.1 (L = 1024, N = 160) and A.2.2 (L = 2048, N = 256). Each key
+    // signs the messages "sample" and "test".
+    const SHA1_P: &str = "86f5ca03dcfeb225063ff830a0c769b9dd9d6153ad91d7ce27f787c43278b447e6533b86b18bed6e8a48b784a14c252c5be0dbf60b86d6385bd2f12fb763ed8873abfd3f5ba2e0a8c0a59082eac056935e529daf7c610467899c77adedfc846c881870b7b19b2b58f9be0521a17002e3bdd6b86685ee90b3d9a1b02b782b1779";
+    const SHA1_Q: &str = "996f967f6c8e388d9e28d01e205fba957a5698b1";
+    const SHA1_G: &str = "07b0f92546150b62514bb771e2a0c0ce387f03bda6c56b505209ff25fd3c133d89bbcd97e904e09114d9a7defdeadfc9078ea544d2e401aeecc40bb9fbbf78fd87995a10a1c27cb7789b594ba7efb5c4326a9fe59a070e136db77175464adca417be5dce2f40d10a46a3a3943f26ab7fd9c0398ff8c76ee0a56826a8a88f1dbd";
+    const SHA1_Y: &str = "5df5e01ded31d0297e274e1691c192fe5868fef9e19a84776454b100cf16f65392195a38b90523e2542ee61871c0440cb87c322fc4b4d2ec5e1e7ec766e1be8d4ce935437dc11c3c8fd426338933ebfe739cb3465f4d3668c5e473508253b1e682f65cbdc4fae93c2ea212390e54905a86e2223170b44eaa7da5dd9ffcfb7f3b";
+    const SHA1_X: &str = "411602cb19a6ccc34494d79d98ef1e7ed5af25f7";
+    // SHA-1 of "sample" and "test".
+    const SHA1_DIGEST_SAMPLE: &str = "8151325dcdbae9e0ff95f9f9658432dbedfdb209";
+    const SHA1_DIGEST_TEST: &str = "a94a8fe5ccb19ba61c4c0873d391e987982fbbd3";
+    const SHA1_SIGNATURE_SAMPLE: &str =
+        "2e1a0c2562b2912caaf89186fb0f42001585da5529efb6b0aff2d7a68eb70ca313022253b9a88df5";
+    const SHA1_SIGNATURE_TEST: &str =
+        "42ab2052fd43e123f0607f115052a67dcd9c5c77183916b0230d45b9931491d4c6b0bd2fb4aaf088";
+
+    const SHA256_P: &str = "9db6fb5951b66bb6fe1e140f1d2ce5502374161fd6538df1648218642f0b5c48c8f7a41aadfa187324b87674fa1822b00f1ecf8136943d7c55757264e5a1a44ffe012e9936e00c1d3e9310b01c7d179805d3058b2a9f4bb6f9716bfe6117c6b5b3cc4d9be341104ad4a80ad6c94e005f4b993e14f091eb51743bf33050c38de235567e1b34c3d6a5c0ceaa1a0f368213c3d19843d0b4b09dcb9fc72d39c8de41f1bf14d4bb4563ca28371621cad3324b6a2d392145bebfac748805236f5ca2fe92b871cd8f9c36d3292b5509ca8caa77a2adfc7bfd77dda6f71125a7456fea153e433256a2261c6a06ed3693797e7995fad5aabbcfbe3eda2741e375404ae25b";
+    const SHA256_Q: &str = "f2c3119374ce76c9356990b465374a17f23f9ed35089bd969f61c6dde9998c1f";
+    const SHA256_G: &str = "5c7ff6b06f8f143fe8288433493e4769c4d988ace5be25a0e24809670716c613d7b0cee6932f8faa7c44d2cb24523da53fbe4f6ec3595892d1aa58c4328a06c46a15662e7eaa703a1decf8bbb2d05dbe2eb956c142a338661d10461c0d135472085057f3494309ffa73c611f78b32adbb5740c361c9f35be90997db2014e2ef5aa61782f52abeb8bd6432c4dd097bc5423b285dafb60dc364e8161f4a2a35aca3a10b1c4d203cc76a470a33afdcbdd92959859abd8b56e1725252d78eac66e71ba9ae3f1dd2487199874393cd4d832186800654760e1e34c09e4d155179f9ec0dc4473f996bdce6eed1cabed8b6f116f7ad9cf505df0f998e34ab27514b0ffe7";
+    const SHA256_Y: &str = "667098c654426c78d7f8201eac6c203ef030d43605032c2f1fa937e5237dbd949f34a0a2564fe126dc8b715c5141802ce0979c8246463c40e6b6bdaa2513fa611728716c2e4fd53bc95b89e69949d96512e873b9c8f8dfd499cc312882561adecb31f658e934c0c197f2c4d96b05cbad67381e7b768891e4da3843d24d94cdfb5126e9b8bf21e8358ee0e0a30ef13fd6a664c0dce3731f7fb49a4845a4fd8254687972a2d382599c9bac4e0ed7998193078913032558134976410b89d2c171d123ac35fd977219597aa7d15c1a9a428e59194f75c721ebcbcfae44696a499afa74e04299f132026601638cb87ab79190d4a0986315da8eec6561c938996beadf";
+    const SHA256_X: &str = "69c7548c21d0dfea6b9a51c9ead4e27c33d3b3f180316e5bcab92c933f0e4dbc";
+    const SHA256_SIGNATURE_SAMPLE: &str = "eace8bdbbe353c432a795d9ec556c6d021f7a03f42c36e9bc87e4ac7932cc8097081e175455f9247b812b74583e9e94f9ea79bd640dc962533b0680793a38d53";
+    const SHA256_SIGNATURE_TEST: &str = "8190012a1969f9957d56fccaad223186f423398d58ef5b3cefd5a4146a4476f07452a53f7075d417b4b013b278d1bb8bbd21863f5e7b1cee679cf2188e1ab19e";
+
+    fn sha1_key(with_private_key: bool) -> DsaKey {
+        let p = hex::decode(SHA1_P).unwrap();
+        let q = hex::decode(SHA1_Q).unwrap();
+        let g = hex::decode(SHA1_G).unwrap();
+        let y = hex::decode(SHA1_Y).unwrap();
+        if with_private_key {
+            DsaKey::from_key_pair(&p, &q, &g, &y, &hex::decode(SHA1_X).unwrap()).unwrap()
+        } else {
+            DsaKey::from_public_key(&p, &q, &g, &y).unwrap()
+        }
+    }
+
+    fn sha256_key(with_private_key: bool) -> DsaKey {
+        let p = hex::decode(SHA256_P).unwrap();
+        let q = hex::decode(SHA256_Q).unwrap();
+        let g = hex::decode(SHA256_G).unwrap();
+        let y = hex::decode(SHA256_Y).unwrap();
+        if with_private_key {
+            DsaKey::from_key_pair(&p, &q, &g, &y, &hex::decode(SHA256_X).unwrap()).unwrap()
+        } else {
+            DsaKey::from_public_key(&p, &q, &g, &y).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_dsa_verify_sha1() {
+        let key = sha1_key(false);
+
+        assert!(!key.has_private_key());
+        assert_eq!(key.signature_size(), 40);
+        for (digest, signature) in [
+            (SHA1_DIGEST_SAMPLE, SHA1_SIGNATURE_SAMPLE),
+            (SHA1_DIGEST_TEST, SHA1_SIGNATURE_TEST),
+        ] {
+            key.verify(
+                &hex::decode(digest).unwrap(),
+                &hex::decode(signature).unwrap(),
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_dsa_verify_sha256() {
+        let key = sha256_key(false);
+
+        assert_eq!(key.signature_size(), 64);
+        for (message, signature) in [
+            (b"sample".as_slice(), SHA256_SIGNATURE_SAMPLE),
+            (b"test".as_slice(), SHA256_SIGNATURE_TEST),
+        ] {
+            key.verify(&sha256(message), &hex::decode(signature).unwrap())
+                .unwrap();
+        }
+    }
+
+    // The example of FIPS 186 appendix 5 (L = 512, N = 160), which signs the SHA-1 digest of "abc". These are the published FIPS 186
+    // example values, not a CAVP SigVer file. The failing case changes the first byte of R from 8b to 8c.
+    const FIPS_186_P: &str = "8df2a494492276aa3d25759bb06869cbeac0d83afb8d0cf7cbb8324f0d7882e5d0762fc5b7210eafc2e9adac32ab7aac49693dfbf83724c2ec0736ee31c80291";
+    const FIPS_186_Q: &str = "c773218c737ec8ee993b4f2ded30f48edace915f";
+    const FIPS_186_G: &str = "626d027839ea0a13413163a55b4cb500299d5522956cefcb3bff10f399ce2c2e71cb9de5fa24babf58e5b79521925c9cc42e9f6f464b088cc572af53e6d78802";
+    const FIPS_186_Y: &str = "19131871d75b1612a819f29d78d1b0d7346f7aa77bb62a859bfd6c5675da9d212d3a36ef1672ef660b8c7c255cc0ec74858fba33f44c06699630a76b030ee333";
+    const FIPS_186_DIGEST: &str = "a9993e364706816aba3e25717850c26c9cd0d89d";
+    const FIPS_186_SIGNATURE: &str =
+        "8bac1ab66410435cb7181f95b16ab97c92b341c041e2345f1f56df2458f426d155b4ba2db6dcd8c8";
+    const FIPS_186_SIGNATURE_BAD_R: &str =
+        "8cac1ab66410435cb7181f95b16ab97c92b341c041e2345f1f56df2458f426d155b4ba2db6dcd8c8";
+
+    #[test]
+    fn test_dsa_verify_fips_186_example() {
+        let key = DsaKey::from_public_key(
+            &hex::decode(FIPS_186_P).unwrap(),
+            &hex::decode(FIPS_186_Q).unwrap(),
+            &hex::decode(FIPS_186_G).unwrap(),
+            &hex::decode(FIPS_186_Y).unwrap(),
+        )
+        .unwrap();
+        let digest = hex::decode(FIPS_186_DIGEST).unwrap();
+
+        key.verify(&digest, &hex::decode(FIPS_186_SIGNATURE).unwrap())
+            .unwrap();
+        assert_eq!(
+            key.verify(&digest, &hex::decode(FIPS_186_SIGNATURE_BAD_R).unwrap())
+                .unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+    }
+
+    // RFC 6979 appendix A.2.1 and A.2.2 also sign "sample" and "test" with the other SHA-1 and SHA-2 hashes, as (digest, R || S).
+    // Digests longer than N are truncated to the leftmost N bits by verify().
+    const SHA1_KEY_OTHER_HASHES: [(&str, &str); 8] = [
+        (
+            "9003e374bc726550c2c289447fd0533160f875709386dfa377bfd41c",
+            "4bc3b686aea70145856814a6f1bb53346f02101e410697b92295d994d21edd2f4ada85566f6f94c1",
+        ),
+        (
+            "90a3ed9e32b2aaf4c61c410eb925426119e1a9dc53d4286ade99a809",
+            "6868e9964e36c1689f6037f91f28d5f2c30610f249cec3acdc83018c5bd2674ecaad35b8cd22940f",
+        ),
+        (
+            "af2bdbe1aa9b6ec1e2ade1d694f41fc71a831d0268e9891562113d8a62add1bf",
+            "81f2f5850be5bc123c43f71a3033e9384611c5454cdd914b65eb6c66a8aaad27299bee6b035f5e89",
+        ),
+        (
+            "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+            "22518c127299b0f6fdc9872b282b9e70d07908126837ec18f150d55de95b5e29be7af5d01e4fe160",
+        ),
+        (
+            "9a9083505bc92276aec4be312696ef7bf3bf603f4bbd381196a029f340585312313bca4a9b5b890efee42c77b1ee25fe",
+            "07f2108557ee0e3921bc1774f1ca9b410b4ce65a54df70456c86fac10fab47c1949ab83f2c6f7595",
+        ),
+        (
+            "768412320f7b0aa5812fce428dc4706b3cae50e02a64caa16a782249bfe8efc4b7ef1ccb126255d196047dfedf17a0a9",
+            "854cf929b58d73c3cbfdc421e8d5430cd6db5e6691d0e0f53e22f898d158380676a871a157cda622",
+        ),
+        (
+            "39a5e04aaff7455d9850c605364f514c11324ce64016960d23d5dc57d3ffd8f49a739468ab8049bf18eef820cdb1ad6c9015f838556bc7fad4138b23fdf986c7",
+            "16c3491f9b8c3fbbdd5e7a7b667057f0d8ee8e1b02c36a127a7b89edbb72e4ffbc71dabc7d4fc69c",
+        ),
+        (
+            "ee26b0dd4af7e749aa1a8ee3c10ae9923f618980772e473f8819a5d4940e0db27ac185f8a0e1d5f84f88bc887fd67b143732c304cc5fa9ad8e6f57f50028a8ff",
+            "8ea47e475ba8ac6f2d821da3bd212d11a3deb9a07c670c7ad72b6c050c109e1790008097125433e8",
+        ),
+    ];
+    const SHA256_KEY_OTHER_HASHES: [(&str, &str); 8] = [
+        (
+            "8151325dcdbae9e0ff95f9f9658432dbedfdb209",
+            "3a1b2dbd7489d6ed7e608fd036c83af396e290dbd602408e8677daabd6e7445ad26fcba19fa3e3058ffc02ca1596cdbb6e0d20cb37b06054f7e36ded0cdbbccf",
+        ),
+        (
+            "a94a8fe5ccb19ba61c4c0873d391e987982fbbd3",
+            "c18270a93cfc6063f57a4dfa86024f700d980e4cf4e2cb65a504397273d98ea0414f22e5f31a8b6d33295c7539c1c1ba3a6160d7d68d50ac0d3a5beac2884faa",
+        ),
+        (
+            "9003e374bc726550c2c289447fd0533160f875709386dfa377bfd41c",
+            "dc9f4deada8d8ff588e98fed0ab690ffce858dc8c79376450eb6b76c24537e2ca65a9c3bc7babe286b195d5da68616da8d47fa0097f36dd19f517327dc848cec",
+        ),
+        (
+            "90a3ed9e32b2aaf4c61c410eb925426119e1a9dc53d4286ade99a809",
+            "272aba31572f6cc55e30bf616b7a265312018dd325be031be0cc82aa17870ea3e9cc286a52cce201586722d36d1e917eb96a4ebdb47932f9576ac645b3a60806",
+        ),
+        (
+            "9a9083505bc92276aec4be312696ef7bf3bf603f4bbd381196a029f340585312313bca4a9b5b890efee42c77b1ee25fe",
+            "b2da945e91858834fd9bf616ebac151edbc4b45d27d0dd4a7f6a22739f45c00b19048b63d9fd6bca1d9bae3664e1bcb97f7276c306130969f63f38fa8319021b",
+        ),
+        (
+            "768412320f7b0aa5812fce428dc4706b3cae50e02a64caa16a782249bfe8efc4b7ef1ccb126255d196047dfedf17a0a9",
+            "239e66ddbe8f8c230a3d071d601b6ffbdfb5901f94d444c6af56f732beb954be6bd737513d5e72fe85d1c750e0f73921fe299b945aad1c802f15c26a43d34961",
+        ),
+        (
+            "39a5e04aaff7455d9850c605364f514c11324ce64016960d23d5dc57d3ffd8f49a739468ab8049bf18eef820cdb1ad6c9015f838556bc7fad4138b23fdf986c7",
+            "2016ed092dc5fb669b8efb3d1f31a91eecb199879be0cf78f02ba062cb4c942ed0c76f84b5f091e141572a639a4fb8c230807eea7d55c8a154a224400aff2351",
+        ),
+        (
+            "ee26b0dd4af7e749aa1a8ee3c10ae9923f618980772e473f8819a5d4940e0db27ac185f8a0e1d5f84f88bc887fd67b143732c304cc5fa9ad8e6f57f50028a8ff",
+            "89ec4bb1400eccff8e7d9aa515cd1de7803f2daff09693ee7fd1353e90a68307c9f0bdabcc0d880bb137a994cc7f3980ce91cc10faf529fc46565b15cea854e1",
+        ),
+    ];
+
+    // Keys for the other FIPS 186-4 sizes, (L = 2048, N = 224) and (L = 3072, N = 256). The domain parameters and keys were generated
+    // with OpenSSL 3.5 `genpkey`, "sample" was signed with SHA-224 and SHA-256 respectively using the RFC 6979 nonce, and both signatures
+    // were checked with `openssl pkeyutl -verify`. Y_CHANGED is Y * G mod P, the public key of X + 1 in the same group.
+    const L2048_N224_P: &str = "8d011867bd4fc5844482a4151618681a2e25d812dc5b91e831b272674f898ba59e845998123ed71664100acb671c94d3c2983aa93cedc3ee42b2d51d3095111d5c63d283dbcd6be3af353a93103c4fcb06f5e464b5eb3f2c96aca4b5ac0734b7fa78db0b23a7e5c749b91daa5634d51d164f0c523b780f5507c08c65807ec38aeb068a45aaf49883d9f8f861dd99262b721bd51111ea6afdd69aeef1eda33fb2069f747476e9e52ad4b10d3c4905e0fe63f3d1fe789d3ae489e3281f31a667a8d6b8e300ba49799eb33c0b35ed41d1c7bb3537faded4adda843a0b8a63c5b571752eb6bcbb9014d5600273add992ca8fe60a0d09e7804dfe7546361bac517abb";
+    const L2048_N224_Q: &str = "d67fdef9b58593ed87937264e8adad071dfde55e5cfa50b4e9800355";
+    const L2048_N224_G: &str = "1572634b4c02575d12e5bf322d84ec1548ed07aba39761cda7e0bbdfce05d878ed9a67cf6d7f7a43c3e1fd063a37e13d94e822d7f85e4ad561d200033e74a7c22290c61e99683953c1d943fb2f70f7183f50a01db18c8007a66ea446f8fe8856449324cc566c732fc6de91c3a2729b9b995740fd89314f957f30dfc57b01dca37d6d1bf010a15aa429836146285577d752863066335ff1546eabbeb4f7c739af1b76b4caf2e08c53fc874bbd484a10103295b37bd1c4fe291f45376864b1f43a604e0c2d758f0bb9c3e91ad7a9fa1837f5ad2b4a6551142ff9512f8c6f66113ac2363e0788ba00f6cf0859b0b93985b9c9838b23dd0c2a9da13122064d741ba9";
+    const L2048_N224_Y: &str = "314f8a85c81f16915a1bf67ae5dc8d44cbcd5e3712dcdb41cd9471661e6f15f5870bdecbaf1343263d7820dcb6521d1630c98d103e0f9fc041a402de11252d6d1815752834c1ece8a380ba29d5c3a3e104190f9e52550ff541c0dccef527ddb3cf0198e04eec86024988d34e234fe0411c9e6bbeae38b48f173e52a08c961dc404cbd23a0a65bcc60f70e62451ea3f3467400205e5c34df37a8df0bde4be1fd70e05cdf15a06bfecb91facc0fc093df175739f8257bf29409f6c8e157e0a2ecab21a9554c43aaf23531fbe5e5093b69bb26d558879513ae3e33741ce6cabdb08034ad5b01f1d208517fd0a706e6d7fd6047d1ff682987a0bca9eec1e14215a48";
+    const L2048_N224_Y_CHANGED: &str = "66232c56139d4487c3285bf12e0439c6aa0d4baf9389009f60183d029c7089b75f525b8af80fce811806d34f842cbc04fcfe98c55aba41c53225a7bbb3c28e3c49b4a227675404bdfe2c946334ace69ee210e30a5c652a3bf0648ef73a41380b3d1e51ceb80337433cab178514817c56692f1d423fe64b9e57f5693be909947624863926e2d63d6188e95a302d8ad2195a5eddcc309a857682a40669e264be9669d5e524153107e5e3045333d0ad0f18fca853522c05d9055bc9f50d03ce56ef9502ced2861b597da3f2921f283d8aa95fcb0e3fadcec72c1864f6767b58090cf54223082aa399222be5c77b308c2501dfe733ae32b62cfa93d2f974879fa96f";
+    // SHA-224 digest of "sample" and of "test".
+    const L2048_N224_DIGEST_SAMPLE: &str =
+        "9003e374bc726550c2c289447fd0533160f875709386dfa377bfd41c";
+    const L2048_N224_DIGEST_TEST: &str = "90a3ed9e32b2aaf4c61c410eb925426119e1a9dc53d4286ade99a809";
+    const L2048_N224_SIGNATURE_SAMPLE: &str = "385bb58ff1fb2f25d337c679ebc2936cfee821197238576173cf1f4d3b0bad01b2d0eb0c933f7af80e8bc304b35b549075d073282838ce1a";
+
+    const L3072_N256_P: &str = "e37fddfcc57bd7fad02034ed25f500a6ccc292f89c55256a60e70a9d65e7e0424e9b106f1f2cfd5fa992c901fe43ce0b2f9507da5fa483933f09d3ad9b147aec387f308e8783473ff206c5cb4fe24139bc7ead637c161260a4fc08034cc9da34a51b81fe14b6ce8a69b920af482be81d84471af63e437e9fc8b195f1d5c62b9766dcb10cf2f8966b7e6251421562fd22fa306858be11b3968fb324ab23830cf60f97e6b144c85e40d9ba00876bb2256b878c582adbc420e103bef3e895621756a477d2d71295cea5fc78d711776ff816408c173e5ee336c51dd7c93524dcdbd99ff478a2f615518798e4f219d5b5b99fa10355e5fedd1431e22e7d234d899deab98d66bcdd5346aee42a4afcade3468f99a511941ee88633d55b202f28582db419582b8f366cc2495f6a8f8243b1bdc0e1ed76b42b7cd008e828c5032bc55ce2f6c642f574f293269a3adebcbd2d77ae8ebd0f19dd5693c9e7a39502c7615953ac89437b7bb0a49b830f25aebba2f8d184e5f1dfd0d09496fa62f8e22115229d";
+    const L3072_N256_Q: &str = "953e138e410f2978d285c8c6e523908b94519879f4120bad51f9cd599e09e773";
+    const L3072_N256_G: &str = "cd01c8d7bcd86bd3bf03fe86669c9a8b764ffdd15878fffe7701721fa257b34174d92b1244e84695d9e4206cb23af0238cae6ac561deaa0fd6245d3829ed0e96d3967b1d758297293c33cd147c40f088747fbe4896e5fa607810b09ed70539be678073fb6f917b75431cc98e53f1cd7171dba335a7229270c05e6736f0ff00468dcded7f0cd4c367536549ad06e94abe38981eae0036e0f6030eb4671514c15c7497fb6bcc2d6807d0b805ddae1a970f2b874e23bf68597feb73e104d105e39c3a575153ef4c748775acbdcf31c73724d3ad8a2af553c966e6f5accd6c543fc78296070160883d88969116a6f466eef5b6463deb82bafc62d80a7e1d0294b4b64696f770a54cad24fe9e875067c302ddf3cf168d655a1c34b4b45fd9607063409fb5d0bf418f01eaa355ad27c212e0cbf0cdc1992bb24e54551a5019b2168379ebb330c6036a987c8172b6ff3ba392bb02ba63e539e532557ee8996fe0851582e03a4c6d8429e08eb0a9f603aa5c62e7395563ef3ad3ff67b3e06d8aaa28a120";
+    const L3072_N256_Y: &str = "de0930dbd3a8592d9e02c420f70c04d1d6f0d68545bb3620eabd193adfc9ef187da0905cf28bdfece08728632201e79082044a49c2fec88fba8bbd429191acbfb49270e06d7138b2f4e7e00ca4912e665d21645d8fb3ac0b3ff5527cfbbca01946f9cfb88907a1856a513dc68c5084fbe0984381088d86aac82ec89caafdad5aa3b0278b01a1b18decc7ead1d06eb6847bdf4e0c94fc45b778a3755eea7fa267e4270dd4184331f337c2825d2c5d63c0591dee8ec971836bb0b8dbbe642d6b3e8ada3a3f87b3081a692e4b1fd5b97c855e53d5d6a2ffb4a776f264a556f5fe989b07cbc9e836e69686d3c86aa8905e20171b7f9ec22dcb2a6d9cff30a990b6269042c7ee4a298a1b090b1dfe8e384a204471b999f1cca791dc0993977c38302974d0ef70a65e8412e9cd1892d93b7355d19cc0844f606b9a3efb528e7b70fdc3b91af1558df0435ea6aba7a671282bb78bc3a450c799da9a6a4be9e933c8b096890ccf58b6f1bb98b4b37fa84766b91a07b4499b4f3e68c685caf17f88b6d800";
+    const L3072_N256_Y_CHANGED: &str = "62bc3e686f6acb2ea744b1e91f167d0268b0ee179cfabe114ff4e7c91e1782edc215148bccfb918bfcc56adc4d7ff47d674c8080aae3818c82eb80b0cc4462d6d7c248da8629873bcb1fa7c1014e069dbc3bdb1d170a8b63680ebd556bf9c27ef3802678fed962fe363c410e68f5b554b24ef097bb00c0d1ad955e72012cafa62461544fc87c2dcd6370b1bb7b5229de22208fb884dae8405d4df848fdb1592fbb5442da1e93fba657f3348b8f3668ce1fee3163a06aae8c6ee9fb0a815114e07d116e23837c2ae5c3eec042f08954110c2a07cfd3f19ebb91a90fff8b895533a897379e9e51bccf0f87d18c0cda2867b1e6ac012e2ca94e8d53a63b735745e3f1a1b01d8f34cca4c6baea3dccce242840378b4e09518db665fd434cd64f845a6601e8dee14c8f351b0108c331bda17b7d2d3240ac8f7e7eda00ce244d4ca5980c64a5da312fb12aee48ef2ae7ba9782eaff35e2b85d4542a2099a1273db9fd966f611c9ad8a4b729905aec03a85a622de2b2b2b46b8d154ca1f42bf4b8e33b7";
+    // SHA-256 digest of "sample" and of "test".
+    const L3072_N256_DIGEST_SAMPLE: &str =
+        "af2bdbe1aa9b6ec1e2ade1d694f41fc71a831d0268e9891562113d8a62add1bf";
+    const L3072_N256_DIGEST_TEST: &str =
+        "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+    const L3072_N256_SIGNATURE_SAMPLE: &str = "750857cbd61d335d16f824ba9b63dadbb463fe41a756cf7467485f0d6fb532d59064380d7116569c75aaff1d2b40be771d012c249c1c081a19fecd032785a6e4";
+
+    fn public_key(p: &str, q: &str, g: &str, y: &str) -> DsaKey {
+        DsaKey::from_public_key(
+            &hex::decode(p).unwrap(),
+            &hex::decode(q).unwrap(),
+            &hex::decode(g).unwrap(),
+            &hex::decode(y).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_dsa_verify_rfc6979_other_hashes() {
+        for (key, signatures) in [
+            (sha1_key(false), SHA1_KEY_OTHER_HASHES),
+            (sha256_key(false), SHA256_KEY_OTHER_HASHES),
+        ] {
+            for (digest, signature) in signatures {
+                key.verify(
+                    &hex::decode(digest).unwrap(),
+                    &hex::decode(signature).unwrap(),
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    // check_sigver takes in a key, a key with a different Y in the same group and a signature of `digest`, and checks the signature
+    // verifies and that each failure of a SigVer test, a different message, R, S or Y and R out of range, is rejected.
+    fn check_sigver(
+        key: &DsaKey,
+        changed_y_key: &DsaKey,
+        q: &str,
+        digest: &str,
+        other_digest: &str,
+        signature: &str,
+    ) {
+        let digest = hex::decode(digest).unwrap();
+        let signature = hex::decode(signature).unwrap();
+        key.verify(&digest, &signature).unwrap();
+
+        let half = signature.len() / 2;
+        let mut changed_r = signature.clone();
+        changed_r[half - 1] ^= 1;
+        let mut changed_s = signature.clone();
+        changed_s[signature.len() - 1] ^= 1;
+        let mut r_equal_to_q = signature.clone();
+        r_equal_to_q[..half].copy_from_slice(&hex::decode(q).unwrap());
+        for (key, digest, signature) in [
+            (key, hex::decode(other_digest).unwrap(), &signature),
+            (key, digest.clone(), &changed_r),
+            (key, digest.clone(), &changed_s),
+            (key, digest.clone(), &r_equal_to_q),
+            (changed_y_key, digest.clone(), &signature),
+        ] {
+            assert_eq!(
+                key.verify(&digest, signature).unwrap_err(),
+                SymCryptError::SignatureVerificationFailure
+            );
+        }
+    }
+
+    #[test]
+    fn test_dsa_sigver_l2048_n224() {
+        check_sigver(
+            &public_key(L2048_N224_P, L2048_N224_Q, L2048_N224_G, L2048_N224_Y),
+            &public_key(
+                L2048_N224_P,
+                L2048_N224_Q,
+                L2048_N224_G,
+                L2048_N224_Y_CHANGED,
+            ),
+            L2048_N224_Q,
+            L2048_N224_DIGEST_SAMPLE,
+            L2048_N224_DIGEST_TEST,
+            L2048_N224_SIGNATURE_SAMPLE,
+        );
+    }
+
+    #[test]
+    fn test_dsa_sigver_l3072_n256() {
+        check_sigver(
+            &public_key(L3072_N256_P, L3072_N256_Q, L3072_N256_G, L3072_N256_Y),
+            &public_key(
+                L3072_N256_P,
+                L3072_N256_Q,
+                L3072_N256_G,
+                L3072_N256_Y_CHANGED,
+            ),
+            L3072_N256_Q,
+            L3072_N256_DIGEST_SAMPLE,
+            L3072_N256_DIGEST_TEST,
+            L3072_N256_SIGNATURE_SAMPLE,
+        );
+    }
+
+    #[test]
+    fn test_dsa_verify_wrong_message() {
+        // The RFC 6979 signature of "sample" must not verify for "test".
+        let key = sha1_key(false);
+        let digest = hex::decode(SHA1_DIGEST_TEST).unwrap();
+        let signature = hex::decode(SHA1_SIGNATURE_SAMPLE).unwrap();
+
+        let result = key.verify(&digest, &signature);
+        assert_eq!(
+            result.unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+    }
+
+    #[test]
+    fn test_dsa_verify_modified_signature() {
+        let key = sha256_key(false);
+        let digest = sha256(b"sample");
+        let mut signature = hex::decode(SHA256_SIGNATURE_SAMPLE).unwrap();
+        signature[10] ^= 1;
+
+        let result = key.verify(&digest, &signature);
+        assert_eq!(
+            result.unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+    }
+
+    #[test]
+    fn test_dsa_verify_malformed_signature() {
+        let key = sha1_key(false);
+        let digest = hex::decode(SHA1_DIGEST_SAMPLE).unwrap();
+
+        let zero_signature = vec![0u8; 40];
+        let short_signature = hex::decode(SHA1_SIGNATURE_SAMPLE).unwrap()[..39].to_vec();
+        for signature in [zero_signature, short_signature] {
+            assert_eq!(
+                key.verify(&digest, &signature).unwrap_err(),
+                SymCryptError::SignatureVerificationFailure
+            );
+        }
+    }
+
+    #[test]
+    fn test_dsa_sign_and_verify() {
+        let digest = sha256(b"legacy archive");
+        let key = sha256_key(true);
+        assert!(key.has_private_key());
+
+        let signature = key.sign(&digest).unwrap();
+        assert_eq!(signature.len(), 64);
+
+        sha256_key(false).verify(&digest, &signature).unwrap();
+    }
+
+    #[test]
+    fn test_dsa_sign_without_private_key() {
+        let key = sha1_key(false);
+        let digest = hex::decode(SHA1_DIGEST_SAMPLE).unwrap();
+
+        assert!(key.sign(&digest).is_err());
+    }
+
+    #[test]
+    fn test_dsa_invalid_public_key() {
+        let p = hex::decode(SHA256_P).unwrap();
+        let q = hex::decode(SHA256_Q).unwrap();
+        let g = hex::decode(SHA256_G).unwrap();
+        let mut y = vec![0u8; p.len()];
+        y[p.len() - 1] = 1;
+
+        assert!(DsaKey::from_public_key(&p, &q, &g, &y).is_err());
+    }
+}
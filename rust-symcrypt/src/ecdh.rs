@@ -2,25 +2,18 @@
 
 use crate::eckey::*;
 use crate::errors::SymCryptError;
-use std::vec;
+use crate::secret::SecretBytes;
 use symcrypt_sys;
 
-/// Wrapper for the EcDh secret agreement result value. This is in place to make the return clear to the caller.
-#[derive(Debug)]
-pub struct EcDhSecretAgreement(Vec<u8>);
+/// The EcDh secret agreement result value. This is in place to make the return clear to the caller, the secret is wiped when it
+/// leaves scope.
+pub type EcDhSecretAgreement = SecretBytes;
 
-impl EcDhSecretAgreement {
-    pub fn as_bytes(&self) -> &[u8] {
-        &self.0
-    }
-}
-
-/// EcDh struct holds the EcKey as well as the associated CurveType
+/// EcDh struct holds the EcKey, which knows its associated CurveType
 ///
 /// [`EcKey`] holds the public/private key pair that is associated with the provided CurveType.
 /// EcKey is owned by EcDh struct, and will drop when EcDh leaves scope.
 pub struct EcDh {
-    curve_type: CurveType,
     key: EcKey,
 }
 
@@ -29,9 +22,24 @@ pub struct EcDh {
 ///
 /// [`new()`] takes in a curve and returns an EcDh struct who's EcKey has a private/public key pair assigned to it.
 ///
-/// ['from_public_key_bytes()'] takes in a public_key and creates a EcDh struct who's EcKey has only a public key attached.
+/// [`from_private_key_bytes()`] takes in a private key that was exported with [`private_key_bytes()`], and returns an EcDh struct who's EcKey
+/// has the private/public key pair.
+///
+/// ['from_public_key_bytes()'] takes in a public_key and creates a EcDh struct who's EcKey has only a public key attached. The public key is
+/// in the [`CurveType::default_point_format()`], which is X || Y for the NIST curves and X for Curve25519.
+///
+/// [`from_public_key_bytes_with_format()`] is [`from_public_key_bytes()`] for a public key in the provided [`PointFormat`].
+///
+/// [`from_ec_key()`] takes ownership of an [`EcKey`], such as one decoded by [`crate::key_encoding`]. The key must have been created with
+/// an [`EcKeyUsage`] that allows EcDh.
+///
+/// [`private_key_bytes()`] returns the private key so that it can be persisted, see [`EcKey::private_key_bytes()`].
+///
+/// [`ec_key()`] returns the [`EcKey`] that holds the key pair.
 ///
 /// [`get_public_key_bytes()`] returns a Vec<u8> that is the public key associated with the current EcKey
+///
+/// [`get_public_key_bytes_with_format()`] returns the public key in the provided [`PointFormat`].
 ///  
 /// [`ecdh_secret_agreement()`] takes in two EcDh structs and returns the associated secret agreement.
 impl EcDh {
@@ -44,10 +52,7 @@ impl EcDh {
                 ecdh_key.inner(),
             ) {
                 symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => {
-                    let instance = EcDh {
-                        curve_type: curve,
-                        key: ecdh_key,
-                    };
+                    let instance = EcDh { key: ecdh_key };
                     Ok(instance)
                 }
                 err => Err(err.into()),
@@ -55,88 +60,84 @@ impl EcDh {
         }
     }
 
+    pub fn from_private_key_bytes(
+        curve: CurveType,
+        private_key: &[u8],
+    ) -> Result<Self, SymCryptError> {
+        let ecdh_key = EcKey::from_private_key_bytes(curve, private_key, EcKeyUsage::EcDh)?;
+        Ok(EcDh { key: ecdh_key })
+    }
+
     pub fn from_public_key_bytes(
         curve: CurveType,
         public_key: &[u8],
     ) -> Result<Self, SymCryptError> {
-        let num_format = get_num_format(curve);
-        let ec_point_format = symcrypt_sys::_SYMCRYPT_ECPOINT_FORMAT_SYMCRYPT_ECPOINT_FORMAT_XY;
-        let edch_key = EcKey::new(curve)?;
+        EcDh::from_public_key_bytes_with_format(curve, public_key, curve.default_point_format())
+    }
 
-        unsafe {
-            // SAFETY: FFI calls
-            match symcrypt_sys::SymCryptEckeySetValue(
-                std::ptr::null(), // private key set to null since none is generated
-                0,
-                public_key.as_ptr(),
-                public_key.len() as symcrypt_sys::SIZE_T,
-                num_format,
-                ec_point_format,
-                symcrypt_sys::SYMCRYPT_FLAG_ECKEY_ECDH,
-                edch_key.inner(),
-            ) {
-                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => {
-                    let instance = EcDh {
-                        curve_type: curve,
-                        key: edch_key,
-                    };
-                    Ok(instance)
-                }
-                err => Err(err.into()),
-            }
-        }
+    pub fn from_public_key_bytes_with_format(
+        curve: CurveType,
+        public_key: &[u8],
+        format: PointFormat,
+    ) -> Result<Self, SymCryptError> {
+        let ecdh_key =
+            EcKey::from_public_key_bytes_with_format(curve, public_key, format, EcKeyUsage::EcDh)?;
+        Ok(EcDh { key: ecdh_key })
+    }
+
+    pub fn from_ec_key(key: EcKey) -> Self {
+        EcDh { key }
     }
 
     pub fn get_public_key_bytes(&self) -> Result<Vec<u8>, SymCryptError> {
-        let num_format = get_num_format(self.curve_type);
-        let ec_point_format = symcrypt_sys::_SYMCRYPT_ECPOINT_FORMAT_SYMCRYPT_ECPOINT_FORMAT_XY;
+        self.key.public_key_bytes()
+    }
 
-        unsafe {
-            // SAFETY: FFI calls
-            let pub_key_len = symcrypt_sys::SymCryptEckeySizeofPublicKey(
-                self.key.inner(),
-                symcrypt_sys::_SYMCRYPT_ECPOINT_FORMAT_SYMCRYPT_ECPOINT_FORMAT_XY,
-            );
+    pub fn get_public_key_bytes_with_format(
+        &self,
+        format: PointFormat,
+    ) -> Result<Vec<u8>, SymCryptError> {
+        self.key.public_key_bytes_with_format(format)
+    }
 
-            let mut pub_key_bytes = vec![0u8; pub_key_len as usize];
-
-            match symcrypt_sys::SymCryptEckeyGetValue(
-                self.key.inner(),
-                std::ptr::null_mut(), // setting private key to null since we will only access public key
-                0 as symcrypt_sys::SIZE_T,
-                pub_key_bytes.as_mut_ptr(),
-                pub_key_len as symcrypt_sys::SIZE_T,
-                num_format,
-                ec_point_format,
-                0, // No flags allowed
-            ) {
-                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(pub_key_bytes),
-                err => Err(err.into()),
-            }
-        }
+    pub fn private_key_bytes(&self) -> Result<EcPrivateKey, SymCryptError> {
+        self.key.private_key_bytes()
+    }
+
+    pub fn ec_key(&self) -> &EcKey {
+        &self.key
     }
 
     pub fn ecdh_secret_agreement(
         private: &EcDh,
         public: &EcDh,
     ) -> Result<EcDhSecretAgreement, SymCryptError> {
-        let num_format = get_num_format(private.curve_type);
-        let secret_length = private.key.curve().get_size();
-        let mut secret = vec![0u8; secret_length as usize];
+        ec_key_secret_agreement(&private.key, &public.key)
+    }
+}
 
-        unsafe {
-            // SAFETY: FFI calls
-            match symcrypt_sys::SymCryptEcDhSecretAgreement(
-                private.key.inner(),
-                public.key.inner(),
-                num_format,
-                0,
-                secret.as_mut_ptr(),
-                secret.len() as symcrypt_sys::SIZE_T,
-            ) {
-                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(EcDhSecretAgreement(secret)),
-                err => Err(err.into()),
-            }
+/// ec_key_secret_agreement computes the secret agreement of [`EcKey`]s that are not owned by an [`EcDh`], such as keys decoded from a
+/// JWK. Both keys must have been created with an [`EcKeyUsage`] that allows EcDh.
+pub(crate) fn ec_key_secret_agreement(
+    private: &EcKey,
+    public: &EcKey,
+) -> Result<EcDhSecretAgreement, SymCryptError> {
+    let num_format = get_num_format(private.curve_type());
+    let secret_length = private.curve().get_size();
+    let mut secret = EcDhSecretAgreement::zeroed(secret_length as usize);
+
+    unsafe {
+        // SAFETY: FFI calls
+        match symcrypt_sys::SymCryptEcDhSecretAgreement(
+            private.inner(),
+            public.inner(),
+            num_format,
+            0,
+            secret.as_mut_bytes().as_mut_ptr(),
+            secret.len() as symcrypt_sys::SIZE_T,
+        ) {
+            symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(secret),
+            err => Err(err.into()),
         }
     }
 }
@@ -210,6 +211,25 @@ mod test {
         assert_eq!(secret_agreement_1.as_bytes(), secret_agreement_2.as_bytes());
     }
 
+    #[test]
+    fn test_ecdh_curve_25519_public_key_format() {
+        // Curve25519 public keys are the 32 byte X coordinate defined in RFC 7748, this is Bob's public key from section 6.1.
+        let bob_public_key =
+            hex::decode("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+                .unwrap();
+        let ecdh_public =
+            EcDh::from_public_key_bytes(CurveType::Curve25519, &bob_public_key).unwrap();
+        assert_eq!(ecdh_public.get_public_key_bytes().unwrap(), bob_public_key);
+
+        let ecdh_private = EcDh::new(CurveType::Curve25519).unwrap();
+        assert_eq!(ecdh_private.get_public_key_bytes().unwrap().len(), 32);
+
+        // The 64 byte X || Y encoding that was used before is rejected.
+        let mut x_y = bob_public_key.clone();
+        x_y.extend_from_slice(&[0u8; 32]);
+        assert!(EcDh::from_public_key_bytes(CurveType::Curve25519, &x_y).is_err());
+    }
+
     #[test]
     fn test_ecdh_failure() {
         let ecdh_1_private = EcDh::new(CurveType::NistP384).unwrap();
@@ -226,4 +246,208 @@ mod test {
             SymCryptError::InvalidArgument
         );
     }
+
+    #[test]
+    fn test_ecdh_private_key_round_trip() {
+        for curve in [
+            CurveType::NistP256,
+            CurveType::NistP384,
+            CurveType::Curve25519,
+        ] {
+            let ecdh_1_private = EcDh::new(curve).unwrap();
+            let ecdh_2_private = EcDh::new(curve).unwrap();
+            let ecdh_2_public =
+                EcDh::from_public_key_bytes(curve, &ecdh_2_private.get_public_key_bytes().unwrap())
+                    .unwrap();
+
+            let private_key = ecdh_1_private.private_key_bytes().unwrap();
+            let restored = EcDh::from_private_key_bytes(curve, private_key.as_bytes()).unwrap();
+            assert!(restored.ec_key().has_private_key());
+
+            let secret_agreement_1 =
+                EcDh::ecdh_secret_agreement(&ecdh_1_private, &ecdh_2_public).unwrap();
+            let secret_agreement_2 =
+                EcDh::ecdh_secret_agreement(&restored, &ecdh_2_public).unwrap();
+            assert_eq!(secret_agreement_1.as_bytes(), secret_agreement_2.as_bytes());
+        }
+    }
+
+    fn check_point_format_round_trip(curve: CurveType, format: PointFormat, size: usize) {
+        let ecdh_1_private = EcDh::new(curve).unwrap();
+        let ecdh_2_private = EcDh::new(curve).unwrap();
+
+        let public_bytes_2 = ecdh_2_private
+            .get_public_key_bytes_with_format(format)
+            .unwrap();
+        assert_eq!(public_bytes_2.len(), size);
+        let ecdh_2_public =
+            EcDh::from_public_key_bytes_with_format(curve, &public_bytes_2, format).unwrap();
+        assert_eq!(
+            ecdh_2_public
+                .get_public_key_bytes_with_format(format)
+                .unwrap(),
+            public_bytes_2
+        );
+
+        let ecdh_1_public =
+            EcDh::from_public_key_bytes(curve, &ecdh_1_private.get_public_key_bytes().unwrap())
+                .unwrap();
+        let secret_agreement_1 =
+            EcDh::ecdh_secret_agreement(&ecdh_1_private, &ecdh_2_public).unwrap();
+        let secret_agreement_2 =
+            EcDh::ecdh_secret_agreement(&ecdh_2_private, &ecdh_1_public).unwrap();
+        assert_eq!(secret_agreement_1.as_bytes(), secret_agreement_2.as_bytes());
+    }
+
+    #[test]
+    fn test_ecdh_point_formats_nist_p256() {
+        check_point_format_round_trip(CurveType::NistP256, PointFormat::Xy, 64);
+        check_point_format_round_trip(CurveType::NistP256, PointFormat::Sec1Uncompressed, 65);
+        check_point_format_round_trip(CurveType::NistP256, PointFormat::Sec1Compressed, 33);
+        check_point_format_round_trip(CurveType::NistP256, PointFormat::X, 32);
+    }
+
+    #[test]
+    fn test_ecdh_point_formats_nist_p384() {
+        check_point_format_round_trip(CurveType::NistP384, PointFormat::Xy, 96);
+        check_point_format_round_trip(CurveType::NistP384, PointFormat::Sec1Uncompressed, 97);
+        check_point_format_round_trip(CurveType::NistP384, PointFormat::Sec1Compressed, 49);
+        check_point_format_round_trip(CurveType::NistP384, PointFormat::X, 48);
+    }
+
+    #[test]
+    fn test_ecdh_point_formats_nist_p521() {
+        check_point_format_round_trip(CurveType::NistP521, PointFormat::Xy, 132);
+        check_point_format_round_trip(CurveType::NistP521, PointFormat::Sec1Uncompressed, 133);
+        check_point_format_round_trip(CurveType::NistP521, PointFormat::Sec1Compressed, 67);
+        check_point_format_round_trip(CurveType::NistP521, PointFormat::X, 66);
+    }
+
+    #[test]
+    fn test_ecdh_custom_curve() {
+        lazy_static::lazy_static! {
+            // brainpoolP256r1 from RFC 5639 section 3.4.
+            static ref BRAINPOOL_P256R1: CustomCurve = CustomCurve::short_weierstrass(
+                &hex::decode("a9fb57dba1eea9bc3e660a909d838d726e3bf623d52620282013481d1f6e5377").unwrap(),
+                &hex::decode("7d5a0975fc2c3057eef67530417affe7fb8055c126dc5c6ce94a4b44f330b5d9").unwrap(),
+                &hex::decode("26dc5c6ce94a4b44f330b5d9bbd77cbf958416295cf7e1ce6bccdc18ff8c07b6").unwrap(),
+                &hex::decode("8bd2aeb9cb7e57cb2c4b482ffc81b7afb9de27e1e3bd23c23a4453bd9ace3262").unwrap(),
+                &hex::decode("547ef835c3dac4fd97f8461a14611dc9c27745132ded8e545c1d54c72f046997").unwrap(),
+                &hex::decode("a9fb57dba1eea9bc3e660a909d838d718c397aa3b561a6f7901e0e82974856a7").unwrap(),
+                &[1],
+            )
+            .unwrap();
+        }
+        let curve = CurveType::Custom(&BRAINPOOL_P256R1);
+
+        let private_key =
+            hex::decode("71c4b1234ad2a770b2914c80cebf2cb72c658662f7a83aae47cd70d183d53119")
+                .unwrap();
+        let peer_public_key = hex::decode("5dd614af2200a8dc46c76c5b96f34e8e9c023c01b07291cb1ed1c5e29a76387453d273e222acd7c4af3c5bbd155ca6164893838664ae123c6fc72dfbf8cda2b0").unwrap();
+        let expected_secret = "502642c211cd6eec444bd226e5e81302d8ba396e002b931dc94b422b7ef1106b";
+
+        let ecdh_private = EcDh::from_private_key_bytes(curve, &private_key).unwrap();
+        let ecdh_public = EcDh::from_public_key_bytes(curve, &peer_public_key).unwrap();
+        let secret_agreement = EcDh::ecdh_secret_agreement(&ecdh_private, &ecdh_public).unwrap();
+        assert_eq!(hex::encode(secret_agreement.as_bytes()), expected_secret);
+
+        check_point_format_round_trip(curve, PointFormat::Sec1Compressed, 33);
+    }
+
+    #[test]
+    fn test_ecdh_point_formats_curve_25519() {
+        check_point_format_round_trip(CurveType::Curve25519, PointFormat::X, 32);
+
+        let ecdh = EcDh::new(CurveType::Curve25519).unwrap();
+        for format in [
+            PointFormat::Xy,
+            PointFormat::Sec1Uncompressed,
+            PointFormat::Sec1Compressed,
+        ] {
+            assert_eq!(
+                ecdh.get_public_key_bytes_with_format(format).unwrap_err(),
+                SymCryptError::InvalidArgument
+            );
+        }
+    }
+
+    #[test]
+    fn test_ecdh_sec1_encodings() {
+        // Public key from RFC 6979 appendix A.2.5, Y is odd.
+        let x = "60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6";
+        let y = "7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299";
+        let compressed = hex::decode(format!("03{}", x)).unwrap();
+
+        let ecdh = EcDh::from_public_key_bytes_with_format(
+            CurveType::NistP256,
+            &compressed,
+            PointFormat::Sec1Compressed,
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(
+                ecdh.get_public_key_bytes_with_format(PointFormat::Sec1Uncompressed)
+                    .unwrap()
+            ),
+            format!("04{}{}", x, y)
+        );
+
+        // The even Y is the negated point, which is also on the curve.
+        let mut even = compressed.clone();
+        even[0] = 0x02;
+        let negated = EcDh::from_public_key_bytes_with_format(
+            CurveType::NistP256,
+            &even,
+            PointFormat::Sec1Compressed,
+        )
+        .unwrap();
+        assert_ne!(
+            negated.get_public_key_bytes().unwrap(),
+            ecdh.get_public_key_bytes().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_ecdh_invalid_encodings() {
+        let ecdh = EcDh::new(CurveType::NistP256).unwrap();
+        let mut uncompressed = ecdh
+            .get_public_key_bytes_with_format(PointFormat::Sec1Uncompressed)
+            .unwrap();
+        let mut compressed = ecdh
+            .get_public_key_bytes_with_format(PointFormat::Sec1Compressed)
+            .unwrap();
+        uncompressed[0] = 0x05;
+        compressed[0] = 0x04;
+
+        let result = EcDh::from_public_key_bytes_with_format(
+            CurveType::NistP256,
+            &uncompressed,
+            PointFormat::Sec1Uncompressed,
+        );
+        assert_eq!(result.err().unwrap(), SymCryptError::InvalidArgument);
+
+        let result = EcDh::from_public_key_bytes_with_format(
+            CurveType::NistP256,
+            &compressed,
+            PointFormat::Sec1Compressed,
+        );
+        assert_eq!(result.err().unwrap(), SymCryptError::InvalidArgument);
+
+        let result = EcDh::from_public_key_bytes_with_format(
+            CurveType::NistP256,
+            &compressed[1..],
+            PointFormat::Sec1Compressed,
+        );
+        assert_eq!(result.err().unwrap(), SymCryptError::WrongKeySize);
+
+        // X = 1 is not the X coordinate of a point on P-256.
+        let mut not_on_curve = vec![0u8; 32];
+        not_on_curve[31] = 1;
+        let result = EcDh::from_public_key_bytes_with_format(
+            CurveType::NistP256,
+            &not_on_curve,
+            PointFormat::X,
+        );
+        assert_eq!(result.err().unwrap(), SymCryptError::InvalidArgument);
+    }
 }
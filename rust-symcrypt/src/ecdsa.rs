@@ -0,0 +1,275 @@
+//! EcDsa functions. For further documentation please refer to symcrypt.h
+
+use crate::eckey::*;
+use crate::errors::{map_verify_error, SymCryptError};
+use symcrypt_sys;
+
+/// EcDsa struct holds the [`EcKey`] that signs or verifies.
+///
+/// Only the NIST curves and custom short Weierstrass curves can be used for EcDsa, Curve25519 keys fail with
+/// [`SymCryptError::InvalidArgument`]. EcKey is owned by EcDsa struct, and will drop when EcDsa leaves scope.
+pub struct EcDsa {
+    key: EcKey,
+}
+
+/// Impl for EcDsa struct.
+///
+/// [`new()`] takes in a curve and returns an EcDsa struct who's EcKey has a random private/public key pair assigned to it.
+///
+/// [`from_private_key_bytes()`] takes in a private key that was exported with [`EcKey::private_key_bytes()`], and returns an EcDsa
+/// struct that can sign and verify.
+///
+/// [`from_public_key_bytes()`] takes in a public key in the [`CurveType::default_point_format()`], and returns an EcDsa struct that can
+/// only verify. [`from_public_key_bytes_with_format()`] is [`from_public_key_bytes()`] for a public key in the provided [`PointFormat`],
+/// [`PointFormat::X`] fails with InvalidArgument since the sign of Y is needed to verify.
+///
+/// [`from_ec_key()`] takes ownership of an [`EcKey`] that was created with an [`EcKeyUsage`] that allows EcDsa.
+///
+/// [`ec_key()`] returns the [`EcKey`] that holds the key pair.
+///
+/// [`signature_size()`] returns the size of a signature in bytes, which is R || S each padded to the size of a field element.
+///
+/// [`sign()`] takes in a hashed message and returns the R || S signature. The hash is truncated to the size of the group order as
+/// defined in FIPS 186-4.
+///
+/// [`verify()`] takes in a hashed message and a R || S signature. Any failure to verify, including a malformed signature, returns
+/// [`SymCryptError::SignatureVerificationFailure`].
+impl EcDsa {
+    pub fn new(curve: CurveType) -> Result<Self, SymCryptError> {
+        check_curve(curve)?;
+        Ok(EcDsa {
+            key: EcKey::generate(curve, EcKeyUsage::EcDsa)?,
+        })
+    }
+
+    pub fn from_private_key_bytes(
+        curve: CurveType,
+        private_key: &[u8],
+    ) -> Result<Self, SymCryptError> {
+        check_curve(curve)?;
+        Ok(EcDsa {
+            key: EcKey::from_private_key_bytes(curve, private_key, EcKeyUsage::EcDsa)?,
+        })
+    }
+
+    pub fn from_public_key_bytes(
+        curve: CurveType,
+        public_key: &[u8],
+    ) -> Result<Self, SymCryptError> {
+        EcDsa::from_public_key_bytes_with_format(curve, public_key, curve.default_point_format())
+    }
+
+    pub fn from_public_key_bytes_with_format(
+        curve: CurveType,
+        public_key: &[u8],
+        format: PointFormat,
+    ) -> Result<Self, SymCryptError> {
+        check_curve(curve)?;
+        Ok(EcDsa {
+            key: EcKey::from_public_key_bytes_with_format(
+                curve,
+                public_key,
+                format,
+                EcKeyUsage::EcDsa,
+            )?,
+        })
+    }
+
+    pub fn from_ec_key(key: EcKey) -> Result<Self, SymCryptError> {
+        check_curve(key.curve_type())?;
+        Ok(EcDsa { key })
+    }
+
+    pub fn ec_key(&self) -> &EcKey {
+        &self.key
+    }
+
+    pub fn signature_size(&self) -> usize {
+        signature_size(&self.key)
+    }
+
+    pub fn sign(&self, hashed_message: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+        ecdsa_sign(&self.key, hashed_message)
+    }
+
+    pub fn verify(&self, hashed_message: &[u8], signature: &[u8]) -> Result<(), SymCryptError> {
+        ecdsa_verify(&self.key, hashed_message, signature)
+    }
+}
+
+/// check_curve fails with InvalidArgument for Curve25519, which is only defined for EcDh.
+fn check_curve(curve: CurveType) -> Result<(), SymCryptError> {
+    if curve == CurveType::Curve25519 {
+        return Err(SymCryptError::InvalidArgument);
+    }
+    Ok(())
+}
+
+fn signature_size(key: &EcKey) -> usize {
+    2 * key.curve_type().field_element_size()
+}
+
+/// ecdsa_sign signs with an [`EcKey`] that is not owned by an [`EcDsa`], such as a key decoded from a JWK or COSE key.
+pub(crate) fn ecdsa_sign(key: &EcKey, hashed_message: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+    check_curve(key.curve_type())?;
+    let mut signature = vec![0u8; signature_size(key)];
+    unsafe {
+        // SAFETY: FFI calls
+        match symcrypt_sys::SymCryptEcDsaSign(
+            key.inner(),
+            hashed_message.as_ptr(),
+            hashed_message.len() as symcrypt_sys::SIZE_T,
+            symcrypt_sys::_SYMCRYPT_NUMBER_FORMAT_SYMCRYPT_NUMBER_FORMAT_MSB_FIRST,
+            0, // No flags, the hash is truncated to the size of the group order
+            signature.as_mut_ptr(),
+            signature.len() as symcrypt_sys::SIZE_T,
+        ) {
+            symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(signature),
+            err => Err(err.into()),
+        }
+    }
+}
+
+/// ecdsa_verify verifies with an [`EcKey`] that is not owned by an [`EcDsa`], see [`ecdsa_sign()`].
+pub(crate) fn ecdsa_verify(
+    key: &EcKey,
+    hashed_message: &[u8],
+    signature: &[u8],
+) -> Result<(), SymCryptError> {
+    if key.curve_type() == CurveType::Curve25519 || signature.len() != signature_size(key) {
+        return Err(SymCryptError::SignatureVerificationFailure);
+    }
+    unsafe {
+        // SAFETY: FFI calls
+        map_verify_error(symcrypt_sys::SymCryptEcDsaVerify(
+            key.inner(),
+            hashed_message.as_ptr(),
+            hashed_message.len() as symcrypt_sys::SIZE_T,
+            signature.as_ptr(),
+            signature.len() as symcrypt_sys::SIZE_T,
+            symcrypt_sys::_SYMCRYPT_NUMBER_FORMAT_SYMCRYPT_NUMBER_FORMAT_MSB_FIRST,
+            0, // No flags, the hash is truncated to the size of the group order
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hash::{sha256, sha384};
+
+    // RFC 6979 section A.2.5, the signature of "sample" with SHA-256.
+    const P256_PRIVATE_KEY: &str =
+        "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721";
+    const P256_PUBLIC_KEY: &str = "60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb67903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299";
+    const P256_SIGNATURE: &str = "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8";
+
+    // RFC 6979 section A.2.6, the signature of "sample" with SHA-384.
+    const P384_PUBLIC_KEY: &str = "ec3a4e415b4e19a4568618029f427fa5da9a8bc4ae92e02e06aae5286b300c64def8f0ea9055866064a254515480bc138015d9b72d7d57244ea8ef9ac0c621896708a59367f9dfb9f54ca84b3f1c9db1288b231c3ae0d4fe7344fd2533264720";
+    const P384_SIGNATURE: &str = "94edbb92a5ecb8aad4736e56c691916b3f88140666ce9fa73d64c4ea95ad133c81a648152e44acf96e36dd1e80fabe4699ef4aeb15f178cea1fe40db2603138f130e740a19624526203b6351d0a3a94fa329c145786e679e7b82c71a38628ac8";
+
+    #[test]
+    fn test_ecdsa_verify_p256() {
+        let key = EcDsa::from_public_key_bytes(
+            CurveType::NistP256,
+            &hex::decode(P256_PUBLIC_KEY).unwrap(),
+        )
+        .unwrap();
+        let signature = hex::decode(P256_SIGNATURE).unwrap();
+        key.verify(&sha256(b"sample"), &signature).unwrap();
+        assert_eq!(
+            key.verify(&sha256(b"samplf"), &signature).unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+    }
+
+    #[test]
+    fn test_ecdsa_verify_p384() {
+        let key = EcDsa::from_public_key_bytes(
+            CurveType::NistP384,
+            &hex::decode(P384_PUBLIC_KEY).unwrap(),
+        )
+        .unwrap();
+        let signature = hex::decode(P384_SIGNATURE).unwrap();
+        key.verify(&sha384(b"sample"), &signature).unwrap();
+        assert_eq!(key.signature_size(), 96);
+    }
+
+    #[test]
+    fn test_ecdsa_sign() {
+        let key = EcDsa::from_private_key_bytes(
+            CurveType::NistP256,
+            &hex::decode(P256_PRIVATE_KEY).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(key.ec_key().public_key_bytes().unwrap()),
+            P256_PUBLIC_KEY
+        );
+
+        // Signatures are randomized, so they are checked by verifying them.
+        let signature = key.sign(&sha256(b"sample")).unwrap();
+        assert_eq!(signature.len(), 64);
+        key.verify(&sha256(b"sample"), &signature).unwrap();
+
+        let public_key = EcDsa::from_public_key_bytes(
+            CurveType::NistP256,
+            &hex::decode(P256_PUBLIC_KEY).unwrap(),
+        )
+        .unwrap();
+        public_key.verify(&sha256(b"sample"), &signature).unwrap();
+        assert_eq!(
+            public_key.sign(&sha256(b"sample")).unwrap_err(),
+            SymCryptError::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn test_ecdsa_generated_keys() {
+        for curve in [
+            CurveType::NistP256,
+            CurveType::NistP384,
+            CurveType::NistP521,
+        ] {
+            let key = EcDsa::new(curve).unwrap();
+            let hashed_message = sha384(b"message");
+            let signature = key.sign(&hashed_message).unwrap();
+            assert_eq!(signature.len(), key.signature_size());
+            key.verify(&hashed_message, &signature).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_ecdsa_invalid() {
+        assert_eq!(
+            EcDsa::new(CurveType::Curve25519).err(),
+            Some(SymCryptError::InvalidArgument)
+        );
+        let ecdh_key = EcKey::generate(CurveType::Curve25519, EcKeyUsage::EcDh).unwrap();
+        assert_eq!(
+            EcDsa::from_ec_key(ecdh_key).err(),
+            Some(SymCryptError::InvalidArgument)
+        );
+
+        // Both points share the X coordinate of a X-only public key, so it can not be used to verify.
+        let public_key = hex::decode(P256_PUBLIC_KEY).unwrap();
+        assert_eq!(
+            EcDsa::from_public_key_bytes_with_format(
+                CurveType::NistP256,
+                &public_key[..32],
+                PointFormat::X
+            )
+            .err(),
+            Some(SymCryptError::InvalidArgument)
+        );
+
+        let key = EcDsa::new(CurveType::NistP256).unwrap();
+        let hashed_message = sha256(b"message");
+        for signature in [&[][..], &[0u8; 64][..], &[0xffu8; 65][..]] {
+            assert_eq!(
+                key.verify(&hashed_message, signature).unwrap_err(),
+                SymCryptError::SignatureVerificationFailure
+            );
+        }
+    }
+}
@@ -1,23 +1,115 @@
 //! Friendly rust types for CurveTypes.
 
-use crate::{errors::SymCryptError, symcrypt_init};
+use crate::{errors::SymCryptError, secret::SecretBytes, symcrypt_init};
 use lazy_static::lazy_static;
 use symcrypt_sys;
 
 /// [`CurveType`] provides an enum of the curve types that can be used when creating an [`EcKey`].
+///
+/// [`CurveType::Custom`] uses a short Weierstrass curve provided by the caller, see [`CustomCurve`].
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum CurveType {
     NistP256,
     NistP384,
+    NistP521,
     Curve25519,
+    Custom(&'static CustomCurve),
 }
 
+/// Impl for [`CurveType`]
+///
+/// [`field_element_size()`] returns the size in bytes of a field element, which is the size of a X or Y coordinate.
+///
+/// [`field_modulus_bits()`] and [`group_order_bits()`] return the size in bits of the field modulus P and of the group order.
+///
+/// [`private_key_size()`] returns the size in bytes of a private key.
+///
+/// [`default_point_format()`] returns the [`PointFormat`] that public keys use when no format is provided, which is [`PointFormat::X`]
+/// for Curve25519 and [`PointFormat::Xy`] otherwise.
+impl CurveType {
+    pub fn field_element_size(&self) -> usize {
+        EcCurve::new(*self).get_size() as usize
+    }
+
+    pub fn field_modulus_bits(&self) -> u32 {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptEcurveBitsizeofFieldModulus(EcCurve::new(*self).0)
+        }
+    }
+
+    pub fn group_order_bits(&self) -> u32 {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptEcurveBitsizeofGroupOrder(EcCurve::new(*self).0)
+        }
+    }
+
+    pub fn private_key_size(&self) -> usize {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptEcurveSizeofScalarMultiplier(EcCurve::new(*self).0) as usize
+        }
+    }
+
+    pub fn default_point_format(&self) -> PointFormat {
+        match self {
+            CurveType::Curve25519 => PointFormat::X,
+            _ => PointFormat::Xy,
+        }
+    }
+}
+
+/// [`PointFormat`] provides an enum of the encodings of a public key.
+///
+/// [`PointFormat::Xy`] is X || Y without a prefix.
+///
+/// [`PointFormat::Sec1Uncompressed`] is 0x04 || X || Y and [`PointFormat::Sec1Compressed`] is 0x02 or 0x03 || X as defined in SEC 1
+/// section 2.3.3, where the prefix holds the parity of Y.
+///
+/// [`PointFormat::X`] is the X coordinate only. It is the only format for Curve25519. For the other curves the sign of Y is lost, which
+/// does not change the EcDh secret agreement since it only depends on the X coordinate of the result, but does change which signatures
+/// verify. X-only public keys on these curves can therefore only be imported with [`EcKeyUsage::EcDh`].
+///
+/// Coordinates are big-endian for the NIST curves and little-endian for Curve25519.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PointFormat {
+    Xy,
+    Sec1Uncompressed,
+    Sec1Compressed,
+    X,
+}
+
+/// [`EcKeyUsage`] provides an enum of the operations an [`EcKey`] can be used for. Curve25519 keys can only be used for EcDh.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum EcKeyUsage {
+    EcDh,
+    EcDsa,
+    EcDhAndEcDsa,
+}
+
+impl EcKeyUsage {
+    fn to_flags(self) -> u32 {
+        match self {
+            EcKeyUsage::EcDh => symcrypt_sys::SYMCRYPT_FLAG_ECKEY_ECDH,
+            EcKeyUsage::EcDsa => symcrypt_sys::SYMCRYPT_FLAG_ECKEY_ECDSA,
+            EcKeyUsage::EcDhAndEcDsa => {
+                symcrypt_sys::SYMCRYPT_FLAG_ECKEY_ECDH | symcrypt_sys::SYMCRYPT_FLAG_ECKEY_ECDSA
+            }
+        }
+    }
+}
+
+/// An exported EC private key. The key is wiped when it leaves scope.
+pub type EcPrivateKey = SecretBytes;
+
 /// [`EcKey`] is a wrapper around symcrypt_sys::PSYMCRYPT_ECKEY.
 pub struct EcKey {
     // Allocation for EcKey is handled by SymCrypt via SymCryptEcKeyAllocate, and is subsequently stored on the stack, therefore pointer will
     // not move and Box<> is not needed.
     inner: symcrypt_sys::PSYMCRYPT_ECKEY,
     curve: &'static EcCurve,
+    curve_type: CurveType,
 }
 
 /// [`EcCurve`] is a wrapper around symcrypt_sys::PSYMCRYPT_ECURVE.
@@ -25,30 +117,217 @@ pub(crate) struct EcCurve(pub(crate) symcrypt_sys::PSYMCRYPT_ECURVE);
 
 /// Impl for [`EcKey`]
 ///
-/// [`new()`] returns a new EcKey object that has the key and curve allocated. 
+/// [`new()`] returns a new EcKey object that has the key and curve allocated.
+///
+/// [`generate()`] returns a new [`EcKey`] with a random private key for the provided [`EcKeyUsage`].
+///
+/// [`from_private_key_bytes()`] takes in a private key and returns the [`EcKey`] with the public key computed from it. The private key is
+/// big-endian for the NIST curves and little-endian for Curve25519, and must be the size of [`CurveType::private_key_size()`]. Curve25519
+/// private keys are clamped as defined in RFC 7748 before they are imported, so any 32 bytes are accepted.
+///
+/// [`from_public_key_bytes()`] takes in a public key in the [`CurveType::default_point_format()`], and returns a [`EcKey`] that only has
+/// a public key. The public key is validated before it is accepted, a point that is not on the curve or is in a small subgroup fails.
+///
+/// [`from_public_key_bytes_with_format()`] is [`from_public_key_bytes()`] for a public key in the provided [`PointFormat`].
+/// [`PointFormat::X`] public keys on curves other than Curve25519 fail with InvalidArgument unless the usage is [`EcKeyUsage::EcDh`].
+///
+/// [`validate_public_key()`] runs the same validation as [`from_public_key_bytes_with_format()`] on a public key without keeping it, and
+/// fails if the point can not be decoded, is not on the curve or is in a small subgroup.
+///
+/// [`private_key_bytes()`] returns the private key in the same format that [`from_private_key_bytes()`] takes, and fails if there is no
+/// private key. Curve25519 private keys are returned clamped.
+///
+/// [`public_key_bytes()`] and [`public_key_bytes_with_format()`] return the public key in the default or the provided [`PointFormat`].
+///
+/// [`has_private_key()`] returns true if the key has a private key.
+///
+/// [`curve_type()`] returns the [`CurveType`] of the key.
 ///
 /// [`inner()`] is an accessor to the inner field of the EcKey struct. Reference is not needed here since we are working with a raw SymCrypt pointer.
 ///
 /// [`curve()`] is an accessor to the curve field of the EcKey struct. Reference is used here since EcKey should still maintain ownership of the EcCurve.
 impl EcKey {
     pub(crate) fn new(curve: CurveType) -> Result<Self, SymCryptError> {
-        let ec_curve = &EcCurve::new(curve); // Can fail here due to insufficient memory.
+        let ec_curve = EcCurve::new(curve); // Can fail here due to insufficient memory.
 
         unsafe {
             // SAFETY: FFI calls
             // Stack allocated since we will do SymCryptEckeyAllocate.
-            let key_ptr = symcrypt_sys::SymCryptEckeyAllocate(ec_curve.0); 
+            let key_ptr = symcrypt_sys::SymCryptEckeyAllocate(ec_curve.0);
             if key_ptr.is_null() {
                 return Err(SymCryptError::MemoryAllocationFailure);
             }
             let key = EcKey {
                 inner: key_ptr,
                 curve: ec_curve,
+                curve_type: curve,
             };
             Ok(key)
         }
     }
 
+    pub fn generate(curve: CurveType, usage: EcKeyUsage) -> Result<Self, SymCryptError> {
+        let key = EcKey::new(curve)?;
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptEckeySetRandom(usage.to_flags(), key.inner) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(key),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn from_private_key_bytes(
+        curve: CurveType,
+        private_key: &[u8],
+        usage: EcKeyUsage,
+    ) -> Result<Self, SymCryptError> {
+        if private_key.len() != curve.private_key_size() {
+            return Err(SymCryptError::WrongKeySize);
+        }
+        let clamped;
+        let private_key = if curve == CurveType::Curve25519 {
+            clamped = clamp_curve25519_private_key(private_key);
+            clamped.as_bytes()
+        } else {
+            private_key
+        };
+        let key = EcKey::new(curve)?;
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptEckeySetValue(
+                private_key.as_ptr(),
+                private_key.len() as symcrypt_sys::SIZE_T,
+                std::ptr::null(), // public key set to null so that SymCrypt computes it from the private key
+                0,
+                get_num_format(curve),
+                symcrypt_sys::_SYMCRYPT_ECPOINT_FORMAT_SYMCRYPT_ECPOINT_FORMAT_XY,
+                usage.to_flags(),
+                key.inner,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(key),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn from_public_key_bytes(
+        curve: CurveType,
+        public_key: &[u8],
+        usage: EcKeyUsage,
+    ) -> Result<Self, SymCryptError> {
+        EcKey::from_public_key_bytes_with_format(
+            curve,
+            public_key,
+            curve.default_point_format(),
+            usage,
+        )
+    }
+
+    pub fn from_public_key_bytes_with_format(
+        curve: CurveType,
+        public_key: &[u8],
+        format: PointFormat,
+        usage: EcKeyUsage,
+    ) -> Result<Self, SymCryptError> {
+        let public_key = decode_point(curve, public_key, format, usage)?;
+        let key = EcKey::new(curve)?;
+        unsafe {
+            // SAFETY: FFI calls
+            // Public key validation is done by SymCryptEckeySetValue since no validation opt-out flag is passed.
+            match symcrypt_sys::SymCryptEckeySetValue(
+                std::ptr::null(), // private key set to null since only the public key is imported
+                0,
+                public_key.as_ptr(),
+                public_key.len() as symcrypt_sys::SIZE_T,
+                get_num_format(curve),
+                point_format(curve),
+                usage.to_flags(),
+                key.inner,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(key),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn validate_public_key(
+        curve: CurveType,
+        public_key: &[u8],
+        format: PointFormat,
+    ) -> Result<(), SymCryptError> {
+        // Every curve can be used for EcDh, and the usage does not change how the point is validated.
+        EcKey::from_public_key_bytes_with_format(curve, public_key, format, EcKeyUsage::EcDh)?;
+        Ok(())
+    }
+
+    pub fn private_key_bytes(&self) -> Result<EcPrivateKey, SymCryptError> {
+        if !self.has_private_key() {
+            return Err(SymCryptError::InvalidArgument);
+        }
+        let mut private_key = EcPrivateKey::zeroed(self.curve_type.private_key_size());
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptEckeyGetValue(
+                self.inner,
+                private_key.as_mut_bytes().as_mut_ptr(),
+                private_key.len() as symcrypt_sys::SIZE_T,
+                std::ptr::null_mut(), // setting public key to null since we will only access private key
+                0,
+                get_num_format(self.curve_type),
+                symcrypt_sys::_SYMCRYPT_ECPOINT_FORMAT_SYMCRYPT_ECPOINT_FORMAT_XY,
+                0, // No flags allowed
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(private_key),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn public_key_bytes(&self) -> Result<Vec<u8>, SymCryptError> {
+        self.public_key_bytes_with_format(self.curve_type.default_point_format())
+    }
+
+    pub fn public_key_bytes_with_format(
+        &self,
+        format: PointFormat,
+    ) -> Result<Vec<u8>, SymCryptError> {
+        check_point_format(self.curve_type, format)?;
+        let ec_point_format = point_format(self.curve_type);
+        unsafe {
+            // SAFETY: FFI calls
+            let public_key_len =
+                symcrypt_sys::SymCryptEckeySizeofPublicKey(self.inner, ec_point_format);
+            let mut public_key = vec![0u8; public_key_len as usize];
+            match symcrypt_sys::SymCryptEckeyGetValue(
+                self.inner,
+                std::ptr::null_mut(), // setting private key to null since we will only access public key
+                0,
+                public_key.as_mut_ptr(),
+                public_key.len() as symcrypt_sys::SIZE_T,
+                get_num_format(self.curve_type),
+                ec_point_format,
+                0, // No flags allowed
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => {
+                    encode_point(self.curve_type, &public_key, format)
+                }
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn has_private_key(&self) -> bool {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptEckeyHasPrivateKey(self.inner) != 0
+        }
+    }
+
+    pub fn curve_type(&self) -> CurveType {
+        self.curve_type
+    }
+
     pub(crate) fn inner(&self) -> symcrypt_sys::PSYMCRYPT_ECKEY {
         self.inner
     }
@@ -94,6 +373,7 @@ impl Drop for EcKey {
 lazy_static! {
     static ref NIST_P256: EcCurve = internal_new(CurveType::NistP256).unwrap();
     static ref NIST_P384: EcCurve = internal_new(CurveType::NistP384).unwrap();
+    static ref NIST_P521: EcCurve = internal_new(CurveType::NistP521).unwrap();
     static ref CURVE_25519: EcCurve = internal_new(CurveType::Curve25519).unwrap();
 }
 
@@ -104,7 +384,7 @@ fn internal_new(curve: CurveType) -> Result<EcCurve, SymCryptError> {
         symcrypt_init(); // Will only init once, subsequent calls to symcrypt_init() will be no-ops.
 
         // Stack allocated since will do SymCryptEcCurveAllocate.
-        let curve_ptr = symcrypt_sys::SymCryptEcurveAllocate(convert_curve(curve), 0); 
+        let curve_ptr = symcrypt_sys::SymCryptEcurveAllocate(convert_curve(curve), 0);
         if curve_ptr.is_null() {
             return Err(SymCryptError::MemoryAllocationFailure);
         }
@@ -121,9 +401,11 @@ fn internal_new(curve: CurveType) -> Result<EcCurve, SymCryptError> {
 impl EcCurve {
     pub(crate) fn new(curve: CurveType) -> &'static Self {
         let ec_curve: &'static EcCurve = match curve {
-            CurveType::NistP256 => &*NIST_P256,
-            CurveType::NistP384 => &*NIST_P384,
-            CurveType::Curve25519 => &*CURVE_25519,
+            CurveType::NistP256 => &NIST_P256,
+            CurveType::NistP384 => &NIST_P384,
+            CurveType::NistP521 => &NIST_P521,
+            CurveType::Curve25519 => &CURVE_25519,
+            CurveType::Custom(custom_curve) => &custom_curve.curve,
         };
 
         ec_curve
@@ -148,12 +430,151 @@ impl Drop for EcCurve {
     }
 }
 
+// Size of the SYMCRYPT_ECURVE_PARAMS header that is followed by the curve values.
+const ECURVE_PARAMS_HEADER_SIZE: usize =
+    std::mem::size_of::<symcrypt_sys::SYMCRYPT_ECURVE_PARAMS>();
+
+/// [`CustomCurve`] is a short Weierstrass curve built from caller provided parameters, such as the brainpool curves from RFC 5639.
+///
+/// A [`CustomCurve`] is used through [`CurveType::Custom`] which needs a `'static` reference, since [`EcKey`]s must not outlive their
+/// curve. The curve can be kept in a `lazy_static` or leaked with [`Box::leak`].
+///
+/// Only curves with P = 3 mod 4 support [`PointFormat::Sec1Compressed`], which is the case for the brainpool curves.
+pub struct CustomCurve {
+    // SYMCRYPT_ECURVE_PARAMS blob, stored as u64s so the header is aligned.
+    params: Vec<u64>,
+    curve: EcCurve,
+}
+
+/// Impl for [`CustomCurve`]
+///
+/// [`from_params()`] takes in a SYMCRYPT_ECURVE_PARAMS blob as defined in symcrypt.h, which is the header followed by the seed, P, A, B,
+/// Gx, Gy, the group order and the cofactor. Only short Weierstrass curves are supported, other curve types will fail with
+/// [`SymCryptError::InvalidArgument`].
+///
+/// [`short_weierstrass()`] takes in the big-endian values of the curve y^2 = x^3 + Ax + B over the prime field P with the generator
+/// (Gx, Gy) of the subgroup of order `order` and `cofactor`. A, B, Gx and Gy must be the same size as P.
+impl CustomCurve {
+    pub fn from_params(params: &[u8]) -> Result<Self, SymCryptError> {
+        if params.len() < ECURVE_PARAMS_HEADER_SIZE {
+            return Err(SymCryptError::InvalidArgument);
+        }
+        let read_u32 = |offset: usize| {
+            u32::from_ne_bytes(params[offset..offset + 4].try_into().unwrap()) as usize
+        };
+        // Header is version, type, algId, cbFieldLength, cbSubgroupOrder, cbCofactor and cbSeed.
+        if read_u32(4)
+            != symcrypt_sys::_SYMCRYPT_ECURVE_TYPE_SYMCRYPT_ECURVE_TYPE_SHORT_WEIERSTRASS as usize
+        {
+            return Err(SymCryptError::InvalidArgument);
+        }
+        let field_size = read_u32(12);
+        let expected_size =
+            ECURVE_PARAMS_HEADER_SIZE + read_u32(24) + 5 * field_size + read_u32(16) + read_u32(20);
+        if field_size == 0 || params.len() != expected_size {
+            return Err(SymCryptError::InvalidArgument);
+        }
+
+        let mut blob = vec![0u64; params.len().div_ceil(8)];
+        unsafe {
+            // SAFETY: blob holds at least params.len() bytes.
+            std::ptr::copy_nonoverlapping(
+                params.as_ptr(),
+                blob.as_mut_ptr() as *mut u8,
+                params.len(),
+            );
+        }
+
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_init();
+            let curve_ptr = symcrypt_sys::SymCryptEcurveAllocate(
+                blob.as_ptr() as symcrypt_sys::PCSYMCRYPT_ECURVE_PARAMS,
+                0,
+            );
+            if curve_ptr.is_null() {
+                // SymCrypt also returns null for parameters that do not define a valid curve.
+                return Err(SymCryptError::InvalidArgument);
+            }
+            Ok(CustomCurve {
+                params: blob,
+                curve: EcCurve(curve_ptr),
+            })
+        }
+    }
+
+    pub fn short_weierstrass(
+        p: &[u8],
+        a: &[u8],
+        b: &[u8],
+        gx: &[u8],
+        gy: &[u8],
+        order: &[u8],
+        cofactor: &[u8],
+    ) -> Result<Self, SymCryptError> {
+        let field_size = p.len();
+        if [a, b, gx, gy].iter().any(|value| value.len() != field_size) {
+            return Err(SymCryptError::InvalidArgument);
+        }
+
+        let mut params = Vec::with_capacity(
+            ECURVE_PARAMS_HEADER_SIZE + 5 * field_size + order.len() + cofactor.len(),
+        );
+        for value in [
+            1, // version
+            symcrypt_sys::_SYMCRYPT_ECURVE_TYPE_SYMCRYPT_ECURVE_TYPE_SHORT_WEIERSTRASS as u32,
+            symcrypt_sys::_SYMCRYPT_ECURVE_GEN_ALG_ID_SYMCRYPT_ECURVE_GEN_ALG_ID_NULL as u32,
+            field_size as u32,
+            order.len() as u32,
+            cofactor.len() as u32,
+            0, // cbSeed
+        ] {
+            params.extend_from_slice(&value.to_ne_bytes());
+        }
+        for value in [p, a, b, gx, gy, order, cofactor] {
+            params.extend_from_slice(value);
+        }
+        Self::from_params(&params)
+    }
+
+    fn params(&self) -> symcrypt_sys::PCSYMCRYPT_ECURVE_PARAMS {
+        self.params.as_ptr() as symcrypt_sys::PCSYMCRYPT_ECURVE_PARAMS
+    }
+}
+
+/// [`CustomCurve`]s are compared by identity, two [`CustomCurve`]s with the same parameters are different [`CurveType`]s.
+impl PartialEq for CustomCurve {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl std::fmt::Debug for CustomCurve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomCurve")
+            .field("field_element_size", &self.curve.get_size())
+            .finish_non_exhaustive()
+    }
+}
+
 /// convert_curve takes in the friendly CurveType enum and returns the symcrypt equivalent.
 pub(crate) fn convert_curve(curve: CurveType) -> symcrypt_sys::PCSYMCRYPT_ECURVE_PARAMS {
     match curve {
         CurveType::NistP256 => unsafe { symcrypt_sys::SymCryptEcurveParamsNistP256 }, // SAFETY: FFI calls
         CurveType::NistP384 => unsafe { symcrypt_sys::SymCryptEcurveParamsNistP384 }, // SAFETY: FFI calls
+        CurveType::NistP521 => unsafe { symcrypt_sys::SymCryptEcurveParamsNistP521 }, // SAFETY: FFI calls
         CurveType::Curve25519 => unsafe { symcrypt_sys::SymCryptEcurveParamsCurve25519 }, // SAFETY: FFI calls
+        CurveType::Custom(custom_curve) => custom_curve.params(),
+    }
+}
+
+/// point_format returns the format SymCrypt uses for public keys, which is the X coordinate for Curve25519 as defined in RFC 7748 and
+/// X || Y otherwise.
+pub(crate) fn point_format(curve_type: CurveType) -> symcrypt_sys::SYMCRYPT_ECPOINT_FORMAT {
+    if curve_type == CurveType::Curve25519 {
+        symcrypt_sys::_SYMCRYPT_ECPOINT_FORMAT_SYMCRYPT_ECPOINT_FORMAT_X
+    } else {
+        symcrypt_sys::_SYMCRYPT_ECPOINT_FORMAT_SYMCRYPT_ECPOINT_FORMAT_XY
     }
 }
 
@@ -165,3 +586,739 @@ pub(crate) fn get_num_format(curve_type: CurveType) -> i32 {
         return symcrypt_sys::_SYMCRYPT_NUMBER_FORMAT_SYMCRYPT_NUMBER_FORMAT_MSB_FIRST;
     };
 }
+
+/// clamp_curve25519_private_key returns a copy of a Curve25519 private key with the bits cleared and set as defined in RFC 7748 section 5.
+fn clamp_curve25519_private_key(private_key: &[u8]) -> SecretBytes {
+    let mut clamped = SecretBytes::from(private_key.to_vec());
+    let bytes = clamped.as_mut_bytes();
+    bytes[0] &= 0xf8;
+    bytes[31] &= 0x7f;
+    bytes[31] |= 0x40;
+    clamped
+}
+
+/// check_point_format fails with InvalidArgument if the [`PointFormat`] can not be used with the curve, Curve25519 public keys only have
+/// a X coordinate.
+fn check_point_format(curve: CurveType, format: PointFormat) -> Result<(), SymCryptError> {
+    if curve == CurveType::Curve25519 && format != PointFormat::X {
+        return Err(SymCryptError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// encode_point converts a public key from the format SymCrypt uses, see [`point_format()`], to the provided [`PointFormat`].
+fn encode_point(
+    curve: CurveType,
+    public_key: &[u8],
+    format: PointFormat,
+) -> Result<Vec<u8>, SymCryptError> {
+    check_point_format(curve, format)?;
+    if curve == CurveType::Curve25519 {
+        return Ok(public_key.to_vec());
+    }
+    let (x, y) = public_key.split_at(public_key.len() / 2);
+    let encoded = match format {
+        PointFormat::Xy => public_key.to_vec(),
+        PointFormat::Sec1Uncompressed => [&[0x04], public_key].concat(),
+        PointFormat::Sec1Compressed => [&[0x02 | (y[y.len() - 1] & 1)], x].concat(),
+        PointFormat::X => x.to_vec(),
+    };
+    Ok(encoded)
+}
+
+/// decode_point converts a public key in the provided [`PointFormat`] to the format SymCrypt uses, and decompresses the point if Y is not
+/// included. The point is not validated here, that is done by SymCryptEckeySetValue.
+fn decode_point(
+    curve: CurveType,
+    public_key: &[u8],
+    format: PointFormat,
+    usage: EcKeyUsage,
+) -> Result<Vec<u8>, SymCryptError> {
+    check_point_format(curve, format)?;
+    if format == PointFormat::X && curve != CurveType::Curve25519 && usage != EcKeyUsage::EcDh {
+        // Only the EcDh secret agreement is the same for both points that share X, so the point can not be picked for EcDsa.
+        return Err(SymCryptError::InvalidArgument);
+    }
+    let size = curve.field_element_size();
+    match format {
+        PointFormat::Xy if public_key.len() == 2 * size => Ok(public_key.to_vec()),
+        PointFormat::Sec1Uncompressed if public_key.len() == 2 * size + 1 => match public_key[0] {
+            0x04 => Ok(public_key[1..].to_vec()),
+            _ => Err(SymCryptError::InvalidArgument),
+        },
+        PointFormat::Sec1Compressed if public_key.len() == size + 1 => match public_key[0] {
+            0x02 | 0x03 => decompress_point(curve, &public_key[1..], public_key[0] == 0x03),
+            _ => Err(SymCryptError::InvalidArgument),
+        },
+        PointFormat::X if public_key.len() == size => {
+            if curve == CurveType::Curve25519 {
+                Ok(public_key.to_vec())
+            } else {
+                // Either Y can be picked since the sign of Y does not change the EcDh secret agreement, and the usage is EcDh only.
+                decompress_point(curve, public_key, false)
+            }
+        }
+        _ => Err(SymCryptError::WrongKeySize),
+    }
+}
+
+/// decompress_point computes Y from X for a short Weierstrass curve y^2 = x^3 + ax + b mod p, and returns X || Y. The arithmetic is done
+/// by SymCrypt in the field of the curve.
+///
+/// The square root is computed as (x^3 + ax + b)^((p + 1) / 4), which requires p = 3 mod 4. This is the case for the NIST and brainpool
+/// curves, other custom curves fail with NotImplemented.
+fn decompress_point(
+    curve: CurveType,
+    x_bytes: &[u8],
+    y_is_odd: bool,
+) -> Result<Vec<u8>, SymCryptError> {
+    let equation = CurveEquation::new(curve)?;
+    let p = &equation.p;
+    if p[p.len() - 1] & 3 != 3 {
+        return Err(SymCryptError::NotImplemented);
+    }
+    // Both are big-endian and of the same size, so X >= P compares the bytes in order.
+    if x_bytes >= p.as_slice() {
+        return Err(SymCryptError::InvalidArgument);
+    }
+
+    let mut field = Field::new(EcCurve::new(curve));
+    let x = field.element(x_bytes)?;
+    let a = field.element(&equation.a)?;
+    let b = field.element(&equation.b)?;
+    let x_squared = field.mul(&x, &x)?;
+    let x_squared_plus_a = field.add(&x_squared, &a)?;
+    let x_cubed_plus_ax = field.mul(&x_squared_plus_a, &x)?;
+    let rhs = field.add(&x_cubed_plus_ax, &b)?;
+
+    // (p + 1) / 4 is p / 4 + 1 since p = 3 mod 4.
+    let mut exponent: Vec<u8> = (0..p.len())
+        .map(|i| (p[i] >> 2) | if i > 0 { p[i - 1] << 6 } else { 0 })
+        .collect();
+    for byte in exponent.iter_mut().rev() {
+        *byte = byte.wrapping_add(1);
+        if *byte != 0 {
+            break;
+        }
+    }
+
+    let mut y = field.element(&[1])?;
+    for i in 0..exponent.len() * 8 {
+        y = field.mul(&y, &y)?;
+        if (exponent[i / 8] >> (7 - i % 8)) & 1 == 1 {
+            y = field.mul(&y, &rhs)?;
+        }
+    }
+    let y_squared = field.mul(&y, &y)?;
+    if !field.equal(&y_squared, &rhs) {
+        // x^3 + ax + b has no square root, so X is not on the curve.
+        return Err(SymCryptError::InvalidArgument);
+    }
+
+    let mut y_bytes = field.to_bytes(&y, x_bytes.len())?;
+    if (y_bytes[y_bytes.len() - 1] & 1 == 1) != y_is_odd {
+        if y_bytes.iter().all(|&byte| byte == 0) {
+            return Err(SymCryptError::InvalidArgument);
+        }
+        let negated_y = field.neg(&y)?;
+        y_bytes = field.to_bytes(&negated_y, x_bytes.len())?;
+    }
+
+    let mut point = x_bytes.to_vec();
+    point.extend_from_slice(&y_bytes);
+    Ok(point)
+}
+
+/// [`CurveEquation`] holds P, A and B of a short Weierstrass curve as big-endian bytes.
+struct CurveEquation {
+    p: Vec<u8>,
+    a: Vec<u8>,
+    b: Vec<u8>,
+}
+
+impl CurveEquation {
+    fn new(curve: CurveType) -> Result<Self, SymCryptError> {
+        let params = convert_curve(curve);
+        unsafe {
+            // SAFETY: symcrypt.h defines the SYMCRYPT_ECURVE_PARAMS header to be followed by the seed, P, A, B, Gx, Gy, the group order
+            // and the cofactor, and the sizes are read from the header.
+            let header = &*params;
+            if header.type_
+                != symcrypt_sys::_SYMCRYPT_ECURVE_TYPE_SYMCRYPT_ECURVE_TYPE_SHORT_WEIERSTRASS
+            {
+                return Err(SymCryptError::NotImplemented);
+            }
+            let field_size = header.cbFieldLength as usize;
+            let offset = std::mem::size_of::<symcrypt_sys::SYMCRYPT_ECURVE_PARAMS>()
+                + header.cbSeed as usize;
+            let values =
+                std::slice::from_raw_parts((params as *const u8).add(offset), 3 * field_size);
+
+            Ok(CurveEquation {
+                p: values[..field_size].to_vec(),
+                a: values[field_size..2 * field_size].to_vec(),
+                b: values[2 * field_size..].to_vec(),
+            })
+        }
+    }
+}
+
+/// [`Field`] does modular arithmetic with SymCrypt on the field modulus of an [`EcCurve`], using the scratch space that SymCrypt sized
+/// for the common operations on that curve.
+struct Field {
+    curve: &'static EcCurve,
+    scratch: Vec<u8>,
+}
+
+/// [`FieldElement`] is a SymCrypt modular element of a [`Field`], it is freed when it leaves scope.
+struct FieldElement {
+    modulus: symcrypt_sys::PCSYMCRYPT_MODULUS,
+    inner: symcrypt_sys::PSYMCRYPT_MODELEMENT,
+}
+
+impl Drop for FieldElement {
+    fn drop(&mut self) {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptModElementFree(self.modulus, self.inner);
+        }
+    }
+}
+
+impl Field {
+    fn new(curve: &'static EcCurve) -> Self {
+        // SAFETY: cbScratchCommon is set by SymCryptEcurveAllocate and does not change for the lifetime of the curve.
+        let scratch_size = unsafe { (*curve.0).cbScratchCommon } as usize;
+        Field {
+            curve,
+            scratch: vec![0u8; scratch_size],
+        }
+    }
+
+    fn modulus(&self) -> symcrypt_sys::PCSYMCRYPT_MODULUS {
+        // SAFETY: FMod is set by SymCryptEcurveAllocate and is freed with the curve, which is 'static.
+        unsafe { (*self.curve.0).FMod as symcrypt_sys::PCSYMCRYPT_MODULUS }
+    }
+
+    fn allocate(&self) -> Result<FieldElement, SymCryptError> {
+        unsafe {
+            // SAFETY: FFI calls
+            let inner = symcrypt_sys::SymCryptModElementAllocate(self.modulus());
+            if inner.is_null() {
+                return Err(SymCryptError::MemoryAllocationFailure);
+            }
+            Ok(FieldElement {
+                modulus: self.modulus(),
+                inner,
+            })
+        }
+    }
+
+    /// element takes in a big-endian value, which must be smaller than P.
+    fn element(&mut self, value: &[u8]) -> Result<FieldElement, SymCryptError> {
+        let element = self.allocate()?;
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptModElementSetValue(
+                value.as_ptr(),
+                value.len() as symcrypt_sys::SIZE_T,
+                symcrypt_sys::_SYMCRYPT_NUMBER_FORMAT_SYMCRYPT_NUMBER_FORMAT_MSB_FIRST,
+                self.modulus(),
+                element.inner,
+                self.scratch.as_mut_ptr(),
+                self.scratch.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(element),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    fn to_bytes(&mut self, element: &FieldElement, size: usize) -> Result<Vec<u8>, SymCryptError> {
+        let mut value = vec![0u8; size];
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptModElementGetValue(
+                self.modulus(),
+                element.inner,
+                value.as_mut_ptr(),
+                value.len() as symcrypt_sys::SIZE_T,
+                symcrypt_sys::_SYMCRYPT_NUMBER_FORMAT_SYMCRYPT_NUMBER_FORMAT_MSB_FIRST,
+                self.scratch.as_mut_ptr(),
+                self.scratch.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(value),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    fn add(&mut self, a: &FieldElement, b: &FieldElement) -> Result<FieldElement, SymCryptError> {
+        let sum = self.allocate()?;
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptModAdd(
+                self.modulus(),
+                a.inner,
+                b.inner,
+                sum.inner,
+                self.scratch.as_mut_ptr(),
+                self.scratch.len() as symcrypt_sys::SIZE_T,
+            );
+        }
+        Ok(sum)
+    }
+
+    fn mul(&mut self, a: &FieldElement, b: &FieldElement) -> Result<FieldElement, SymCryptError> {
+        let product = self.allocate()?;
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptModMul(
+                self.modulus(),
+                a.inner,
+                b.inner,
+                product.inner,
+                self.scratch.as_mut_ptr(),
+                self.scratch.len() as symcrypt_sys::SIZE_T,
+            );
+        }
+        Ok(product)
+    }
+
+    fn neg(&mut self, a: &FieldElement) -> Result<FieldElement, SymCryptError> {
+        let negated = self.allocate()?;
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptModNeg(
+                self.modulus(),
+                a.inner,
+                negated.inner,
+                self.scratch.as_mut_ptr(),
+                self.scratch.len() as symcrypt_sys::SIZE_T,
+            );
+        }
+        Ok(negated)
+    }
+
+    fn equal(&self, a: &FieldElement, b: &FieldElement) -> bool {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptModElementIsEqual(self.modulus(), a.inner, b.inner) != 0
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Key pair from RFC 6979 appendix A.2.5.
+    const P256_PRIVATE_KEY: &str =
+        "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721";
+    const P256_PUBLIC_KEY: &str = "60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb67903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299";
+
+    // Alice's key pair from RFC 7748 section 6.1, and the private key clamped as X25519 does before use.
+    const X25519_PRIVATE_KEY: &str =
+        "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a";
+    const X25519_CLAMPED_PRIVATE_KEY: &str =
+        "70076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c6a";
+    const X25519_PUBLIC_KEY: &str =
+        "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a";
+
+    // Key pair from RFC 6979 appendix A.2.7.
+    const P521_PRIVATE_KEY: &str = "00fad06daa62ba3b25d2fb40133da757205de67f5bb0018fee8c86e1b68c7e75caa896eb32f1f47c70855836a6d16fcc1466f6d8fbec67db89ec0c08b0e996b83538";
+    const P521_PUBLIC_KEY: &str = "01894550d0785932e00eaa23b694f213f8c3121f86dc97a04e5a7167db4e5bcd371123d46e45db6b5d5370a7f20fb633155d38ffa16d2bd761dcac474b9a2f5023a400493101c962cd4d2fddf782285e64584139c2f91b47f87ff82354d6630f746a28a0db25741b5b34a828008b22acc23f924faafbd4d33f81ea66956dfeaa2bfdfcf5";
+
+    const BRAINPOOL_PRIVATE_KEY: &str =
+        "71c4b1234ad2a770b2914c80cebf2cb72c658662f7a83aae47cd70d183d53119";
+    const BRAINPOOL_PUBLIC_KEY: &str = "60ae3f58066bd85f79363703dfc7d926c170919abedb6cc3d7a27caedbdf376f512e662c9d4c812b7069a666d78c5b2c69708105eb0c0f352aa0f67efccc2c48";
+
+    // brainpoolP256r1 from RFC 5639 section 3.4.
+    fn brainpool_p256r1() -> Result<CustomCurve, SymCryptError> {
+        CustomCurve::short_weierstrass(
+            &hex::decode("a9fb57dba1eea9bc3e660a909d838d726e3bf623d52620282013481d1f6e5377")
+                .unwrap(),
+            &hex::decode("7d5a0975fc2c3057eef67530417affe7fb8055c126dc5c6ce94a4b44f330b5d9")
+                .unwrap(),
+            &hex::decode("26dc5c6ce94a4b44f330b5d9bbd77cbf958416295cf7e1ce6bccdc18ff8c07b6")
+                .unwrap(),
+            &hex::decode("8bd2aeb9cb7e57cb2c4b482ffc81b7afb9de27e1e3bd23c23a4453bd9ace3262")
+                .unwrap(),
+            &hex::decode("547ef835c3dac4fd97f8461a14611dc9c27745132ded8e545c1d54c72f046997")
+                .unwrap(),
+            &hex::decode("a9fb57dba1eea9bc3e660a909d838d718c397aa3b561a6f7901e0e82974856a7")
+                .unwrap(),
+            &[1],
+        )
+    }
+
+    lazy_static! {
+        static ref BRAINPOOL_P256R1: CustomCurve = brainpool_p256r1().unwrap();
+    }
+
+    #[test]
+    fn test_curve_info() {
+        assert_eq!(CurveType::NistP256.field_element_size(), 32);
+        assert_eq!(CurveType::NistP256.field_modulus_bits(), 256);
+        assert_eq!(CurveType::NistP256.group_order_bits(), 256);
+        assert_eq!(CurveType::NistP256.private_key_size(), 32);
+
+        assert_eq!(CurveType::NistP384.field_element_size(), 48);
+        assert_eq!(CurveType::NistP384.field_modulus_bits(), 384);
+        assert_eq!(CurveType::NistP384.group_order_bits(), 384);
+        assert_eq!(CurveType::NistP384.private_key_size(), 48);
+
+        assert_eq!(CurveType::NistP521.field_element_size(), 66);
+        assert_eq!(CurveType::NistP521.field_modulus_bits(), 521);
+        assert_eq!(CurveType::NistP521.group_order_bits(), 521);
+        assert_eq!(CurveType::NistP521.private_key_size(), 66);
+
+        let brainpool = CurveType::Custom(&BRAINPOOL_P256R1);
+        assert_eq!(brainpool.field_element_size(), 32);
+        assert_eq!(brainpool.field_modulus_bits(), 256);
+        assert_eq!(brainpool.group_order_bits(), 256);
+        assert_eq!(brainpool.private_key_size(), 32);
+        assert_eq!(brainpool.default_point_format(), PointFormat::Xy);
+
+        assert_eq!(CurveType::Curve25519.field_element_size(), 32);
+        assert_eq!(CurveType::Curve25519.field_modulus_bits(), 255);
+        assert_eq!(CurveType::Curve25519.private_key_size(), 32);
+    }
+
+    #[test]
+    fn test_from_private_key_bytes_nist_p256() {
+        let private_key = hex::decode(P256_PRIVATE_KEY).unwrap();
+        let key =
+            EcKey::from_private_key_bytes(CurveType::NistP256, &private_key, EcKeyUsage::EcDsa)
+                .unwrap();
+
+        assert!(key.has_private_key());
+        assert_eq!(key.curve_type(), CurveType::NistP256);
+        assert_eq!(
+            hex::encode(key.public_key_bytes().unwrap()),
+            P256_PUBLIC_KEY
+        );
+        assert_eq!(key.private_key_bytes().unwrap().as_bytes(), private_key);
+    }
+
+    #[test]
+    fn test_from_private_key_bytes_nist_p521() {
+        let private_key = hex::decode(P521_PRIVATE_KEY).unwrap();
+        let key =
+            EcKey::from_private_key_bytes(CurveType::NistP521, &private_key, EcKeyUsage::EcDsa)
+                .unwrap();
+
+        assert_eq!(key.curve_type(), CurveType::NistP521);
+        assert_eq!(
+            hex::encode(key.public_key_bytes().unwrap()),
+            P521_PUBLIC_KEY
+        );
+        assert_eq!(key.private_key_bytes().unwrap().as_bytes(), private_key);
+    }
+
+    #[test]
+    fn test_from_private_key_bytes_custom_curve() {
+        let curve = CurveType::Custom(&BRAINPOOL_P256R1);
+        let private_key = hex::decode(BRAINPOOL_PRIVATE_KEY).unwrap();
+        let key = EcKey::from_private_key_bytes(curve, &private_key, EcKeyUsage::EcDh).unwrap();
+
+        assert_eq!(key.curve_type(), curve);
+        assert_eq!(
+            hex::encode(key.public_key_bytes().unwrap()),
+            BRAINPOOL_PUBLIC_KEY
+        );
+
+        // The brainpool P is 3 mod 4 so compressed points can be decoded.
+        let compressed = key
+            .public_key_bytes_with_format(PointFormat::Sec1Compressed)
+            .unwrap();
+        let public_key = EcKey::from_public_key_bytes_with_format(
+            curve,
+            &compressed,
+            PointFormat::Sec1Compressed,
+            EcKeyUsage::EcDh,
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(public_key.public_key_bytes().unwrap()),
+            BRAINPOOL_PUBLIC_KEY
+        );
+    }
+
+    #[test]
+    fn test_custom_curve_identity() {
+        let other = Box::leak(Box::new(brainpool_p256r1().unwrap()));
+        assert_eq!(
+            CurveType::Custom(&BRAINPOOL_P256R1),
+            CurveType::Custom(&BRAINPOOL_P256R1)
+        );
+        assert_ne!(
+            CurveType::Custom(&BRAINPOOL_P256R1),
+            CurveType::Custom(other)
+        );
+    }
+
+    #[test]
+    fn test_custom_curve_invalid_params() {
+        let p = [0xffu8; 32];
+        let short = [0x01u8; 31];
+        let result = CustomCurve::short_weierstrass(&p, &short, &p, &p, &p, &p, &[1]);
+        assert_eq!(result.err().unwrap(), SymCryptError::InvalidArgument);
+
+        assert_eq!(
+            CustomCurve::from_params(&[0u8; 8]).err().unwrap(),
+            SymCryptError::InvalidArgument
+        );
+
+        // Curve25519 parameters are Montgomery, not short Weierstrass.
+        let curve25519_params = unsafe {
+            std::slice::from_raw_parts(
+                convert_curve(CurveType::Curve25519) as *const u8,
+                ECURVE_PARAMS_HEADER_SIZE,
+            )
+        };
+        assert_eq!(
+            CustomCurve::from_params(curve25519_params).err().unwrap(),
+            SymCryptError::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn test_from_private_key_bytes_curve_25519() {
+        let private_key = hex::decode(X25519_PRIVATE_KEY).unwrap();
+        let key =
+            EcKey::from_private_key_bytes(CurveType::Curve25519, &private_key, EcKeyUsage::EcDh)
+                .unwrap();
+
+        assert_eq!(
+            hex::encode(key.public_key_bytes().unwrap()),
+            X25519_PUBLIC_KEY
+        );
+        assert_eq!(
+            hex::encode(key.private_key_bytes().unwrap().as_bytes()),
+            X25519_CLAMPED_PRIVATE_KEY
+        );
+
+        // Importing the clamped key gives the same key pair.
+        let clamped_private_key = hex::decode(X25519_CLAMPED_PRIVATE_KEY).unwrap();
+        let clamped_key = EcKey::from_private_key_bytes(
+            CurveType::Curve25519,
+            &clamped_private_key,
+            EcKeyUsage::EcDh,
+        )
+        .unwrap();
+        assert_eq!(
+            clamped_key.public_key_bytes().unwrap(),
+            key.public_key_bytes().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_generate_round_trip() {
+        for curve in [
+            CurveType::NistP256,
+            CurveType::NistP384,
+            CurveType::NistP521,
+        ] {
+            let key = EcKey::generate(curve, EcKeyUsage::EcDhAndEcDsa).unwrap();
+            let private_key = key.private_key_bytes().unwrap();
+            assert_eq!(private_key.as_bytes().len(), curve.private_key_size());
+
+            let restored = EcKey::from_private_key_bytes(
+                curve,
+                private_key.as_bytes(),
+                EcKeyUsage::EcDhAndEcDsa,
+            )
+            .unwrap();
+            assert_eq!(
+                restored.public_key_bytes().unwrap(),
+                key.public_key_bytes().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_public_key_only() {
+        let public_key = hex::decode(P256_PUBLIC_KEY).unwrap();
+        let key = EcKey::from_public_key_bytes(CurveType::NistP256, &public_key, EcKeyUsage::EcDsa)
+            .unwrap();
+
+        assert!(!key.has_private_key());
+        assert_eq!(key.public_key_bytes().unwrap(), public_key);
+        assert_eq!(
+            key.private_key_bytes().err().unwrap(),
+            SymCryptError::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn test_public_key_validation() {
+        // Changing the last byte of Y moves the point off the curve.
+        let mut off_curve = hex::decode(P256_PUBLIC_KEY).unwrap();
+        off_curve[63] ^= 1;
+        let zero_point = vec![0u8; 64];
+
+        for public_key in [off_curve, zero_point] {
+            let result =
+                EcKey::from_public_key_bytes(CurveType::NistP256, &public_key, EcKeyUsage::EcDh);
+            assert!(result.is_err());
+            assert!(
+                EcKey::validate_public_key(CurveType::NistP256, &public_key, PointFormat::Xy)
+                    .is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_public_key() {
+        let public_key = hex::decode(P256_PUBLIC_KEY).unwrap();
+        EcKey::validate_public_key(CurveType::NistP256, &public_key, PointFormat::Xy).unwrap();
+
+        let sec1_public_key = [&[0x04], public_key.as_slice()].concat();
+        EcKey::validate_public_key(
+            CurveType::NistP256,
+            &sec1_public_key,
+            PointFormat::Sec1Uncompressed,
+        )
+        .unwrap();
+        assert_eq!(
+            EcKey::validate_public_key(CurveType::NistP256, &public_key[..32], PointFormat::Xy)
+                .unwrap_err(),
+            SymCryptError::WrongKeySize
+        );
+
+        let x25519_public_key = hex::decode(X25519_PUBLIC_KEY).unwrap();
+        EcKey::validate_public_key(CurveType::Curve25519, &x25519_public_key, PointFormat::X)
+            .unwrap();
+        assert_eq!(
+            EcKey::validate_public_key(CurveType::Curve25519, &x25519_public_key, PointFormat::Xy)
+                .unwrap_err(),
+            SymCryptError::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn test_x_only_public_key_requires_ecdh() {
+        let public_key = hex::decode(P256_PUBLIC_KEY).unwrap();
+
+        // The sign of Y is lost, so the point can not be imported for EcDsa.
+        for usage in [EcKeyUsage::EcDsa, EcKeyUsage::EcDhAndEcDsa] {
+            let result = EcKey::from_public_key_bytes_with_format(
+                CurveType::NistP256,
+                &public_key[..32],
+                PointFormat::X,
+                usage,
+            );
+            assert_eq!(result.err().unwrap(), SymCryptError::InvalidArgument);
+        }
+
+        let key = EcKey::from_public_key_bytes_with_format(
+            CurveType::NistP256,
+            &public_key[..32],
+            PointFormat::X,
+            EcKeyUsage::EcDh,
+        )
+        .unwrap();
+        assert_eq!(
+            key.public_key_bytes_with_format(PointFormat::X).unwrap(),
+            &public_key[..32]
+        );
+    }
+
+    #[test]
+    fn test_decompress_generators() {
+        // Generators from SEC 2 section 2.4 and RFC 5639 section 3.4, with Y and P - Y, which are decoded from the two SEC 1 prefixes.
+        let generators = [
+            (
+                CurveType::NistP256,
+                "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296",
+                "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+                "b01cbd1c01e58065711814b583f061e9d431cca994cea1313449bf97c840ae0a",
+            ),
+            (
+                CurveType::NistP384,
+                "aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a385502f25dbf55296c3a545e3872760ab7",
+                "3617de4a96262c6f5d9e98bf9292dc29f8f41dbd289a147ce9da3113b5f0b8c00a60b1ce1d7e819d7a431d7c90ea0e5f",
+                "c9e821b569d9d390a26167406d6d23d6070be242d765eb831625ceec4a0f473ef59f4e30e2817e6285bce2846f15f1a0",
+            ),
+            (
+                CurveType::NistP521,
+                "00c6858e06b70404e9cd9e3ecb662395b4429c648139053fb521f828af606b4d3dbaa14b5e77efe75928fe1dc127a2ffa8de3348b3c1856a429bf97e7e31c2e5bd66",
+                "011839296a789a3bc0045c8a5fb42c7d1bd998f54449579b446817afbd17273e662c97ee72995ef42640c550b9013fad0761353c7086a272c24088be94769fd16650",
+                "00e7c6d6958765c43ffba375a04bd382e426670abbb6a864bb97e85042e8d8c199d368118d66a10bd9bf3aaf46fec052f89ecac38f795d8d3dbf77416b89602e99af",
+            ),
+            (
+                CurveType::Custom(&BRAINPOOL_P256R1),
+                "8bd2aeb9cb7e57cb2c4b482ffc81b7afb9de27e1e3bd23c23a4453bd9ace3262",
+                "547ef835c3dac4fd97f8461a14611dc9c27745132ded8e545c1d54c72f046997",
+                "557c5fa5de13e4bea66dc47689226fa8abc4b110a73891d3c3f5f355f069e9e0",
+            ),
+        ];
+
+        for (curve, x, y, negated_y) in generators {
+            let x = hex::decode(x).unwrap();
+            let y_is_odd = hex::decode(y).unwrap().last().unwrap() & 1 == 1;
+            for (expected_y, odd) in [(y, y_is_odd), (negated_y, !y_is_odd)] {
+                let compressed = [&[0x02 | odd as u8], x.as_slice()].concat();
+                let key = EcKey::from_public_key_bytes_with_format(
+                    curve,
+                    &compressed,
+                    PointFormat::Sec1Compressed,
+                    EcKeyUsage::EcDh,
+                )
+                .unwrap();
+
+                assert_eq!(
+                    hex::encode(key.public_key_bytes_with_format(PointFormat::Xy).unwrap()),
+                    hex::encode(&x) + expected_y
+                );
+                assert_eq!(
+                    key.public_key_bytes_with_format(PointFormat::Sec1Compressed)
+                        .unwrap(),
+                    compressed
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_decompress_not_on_curve() {
+        // x^3 + ax + b has no square root for these X, and X = P is not a field element.
+        let cases = [
+            (CurveType::NistP256, 1, 32),
+            (CurveType::NistP384, 1, 48),
+            (CurveType::NistP521, 3, 66),
+            (CurveType::Custom(&BRAINPOOL_P256R1), 0, 32),
+        ];
+        for (curve, x, size) in cases {
+            let mut compressed = vec![0u8; size + 1];
+            compressed[0] = 0x02;
+            compressed[size] = x;
+            let result = EcKey::from_public_key_bytes_with_format(
+                curve,
+                &compressed,
+                PointFormat::Sec1Compressed,
+                EcKeyUsage::EcDh,
+            );
+            assert_eq!(result.err().unwrap(), SymCryptError::InvalidArgument);
+        }
+
+        let p = hex::decode("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff")
+            .unwrap();
+        let result = EcKey::from_public_key_bytes_with_format(
+            CurveType::NistP256,
+            &[&[0x03], p.as_slice()].concat(),
+            PointFormat::Sec1Compressed,
+            EcKeyUsage::EcDh,
+        );
+        assert_eq!(result.err().unwrap(), SymCryptError::InvalidArgument);
+    }
+
+    #[test]
+    fn test_wrong_private_key_size() {
+        let private_key = hex::decode(P256_PRIVATE_KEY).unwrap();
+        let result =
+            EcKey::from_private_key_bytes(CurveType::NistP384, &private_key, EcKeyUsage::EcDh);
+        assert_eq!(result.err().unwrap(), SymCryptError::WrongKeySize);
+    }
+}
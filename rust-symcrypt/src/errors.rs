@@ -107,3 +107,15 @@ impl fmt::Display for SymCryptError {
         write!(f, "{}", message)
     }
 }
+
+impl std::error::Error for SymCryptError {}
+
+/// map_verify_error takes in the result of a SymCrypt signature verification and returns it as a Result. SymCrypt can reject a malformed
+/// signature, such as one of the wrong size or with a value out of range, with other errors before the signature is checked. Callers only
+/// need to know that the signature is not valid, so every error is returned as [`SymCryptError::SignatureVerificationFailure`].
+pub(crate) fn map_verify_error(err: symcrypt_sys::SYMCRYPT_ERROR) -> Result<(), SymCryptError> {
+    match err {
+        symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(()),
+        _ => Err(SymCryptError::SignatureVerificationFailure),
+    }
+}
@@ -1,4 +1,6 @@
 //! Galois Counter Mode functions. For further documentation please refer to symcrypt.h
+//!
+//! With the `aead` feature, [`Aes128Gcm`] and [`Aes256Gcm`] implement the RustCrypto `aead` traits.
 
 use crate::block_ciphers::*;
 use crate::errors::SymCryptError;
@@ -160,6 +162,89 @@ pub fn validate_gcm_parameters(
     }
 }
 
+/// impl_aes_gcm defines an AES-GCM type for the RustCrypto `aead` traits with a [`$key_size`] key, [`$doc`] is the doc comment of the type.
+#[cfg(feature = "aead")]
+macro_rules! impl_aes_gcm {
+    ($name:ident, $key_size:ty, $doc:expr) => {
+        #[doc = $doc]
+        ///
+        /// It implements the RustCrypto `aead` traits on top of [`GcmExpandedKey`], with a 12 byte nonce and a 16 byte tag, and is available
+        /// with the `aead` feature.
+        ///
+        /// `KeyInit::new()` expands the key, and `AeadInPlace` provides the detached tag in-place variants. `Aead` is provided by the aead
+        /// crate on top of `AeadInPlace`, and appends the tag to the ciphertext.
+        pub struct $name(GcmExpandedKey);
+
+        impl aead::KeySizeUser for $name {
+            type KeySize = $key_size;
+        }
+
+        impl aead::KeyInit for $name {
+            fn new(key: &aead::Key<Self>) -> Self {
+                $name(
+                    GcmExpandedKey::new(key, BlockCipherType::AesBlock)
+                        .expect("the AES key size is checked by the type system"),
+                )
+            }
+        }
+
+        impl aead::AeadCore for $name {
+            type NonceSize = aead::consts::U12;
+            type TagSize = aead::consts::U16;
+            type CiphertextOverhead = aead::consts::U0;
+        }
+
+        impl aead::AeadInPlace for $name {
+            fn encrypt_in_place_detached(
+                &self,
+                nonce: &aead::Nonce<Self>,
+                associated_data: &[u8],
+                buffer: &mut [u8],
+            ) -> aead::Result<aead::Tag<Self>> {
+                let nonce: [u8; 12] = (*nonce).into();
+                let mut tag = aead::Tag::<Self>::default();
+                validate_gcm_parameters(
+                    BlockCipherType::AesBlock,
+                    &nonce,
+                    associated_data,
+                    buffer,
+                    &tag,
+                )
+                .map_err(|_| aead::Error)?;
+                self.0
+                    .encrypt_in_place(&nonce, associated_data, buffer, &mut tag);
+                Ok(tag)
+            }
+
+            fn decrypt_in_place_detached(
+                &self,
+                nonce: &aead::Nonce<Self>,
+                associated_data: &[u8],
+                buffer: &mut [u8],
+                tag: &aead::Tag<Self>,
+            ) -> aead::Result<()> {
+                let nonce: [u8; 12] = (*nonce).into();
+                self.0
+                    .decrypt_in_place(&nonce, associated_data, buffer, tag)
+                    .map_err(|_| aead::Error)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "aead")]
+impl_aes_gcm!(
+    Aes128Gcm,
+    aead::consts::U16,
+    "[`Aes128Gcm`] is AES-GCM with a 16 byte key."
+);
+#[cfg(feature = "aead")]
+impl_aes_gcm!(
+    Aes256Gcm,
+    aead::consts::U32,
+    "[`Aes256Gcm`] is AES-GCM with a 32 byte key."
+);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -286,4 +371,90 @@ mod test {
         let gcm_state = GcmExpandedKey::new(&p_key, cipher).unwrap();
         assert_eq!(gcm_state.key_len(), 16);
     }
+
+    #[cfg(feature = "aead")]
+    fn check_aead_against_rustcrypto<A, R>(key: &[u8])
+    where
+        A: aead::Aead + aead::AeadInPlace + aead::KeyInit,
+        R: aead::Aead + aead::KeyInit,
+    {
+        let cipher = A::new_from_slice(key).unwrap();
+        let reference = R::new_from_slice(key).unwrap();
+        let nonce = [0x24u8; 12];
+        for length in [0, 1, 15, 16, 17, 64, 1000] {
+            let plaintext: Vec<u8> = (0..length).map(|i| i as u8).collect();
+            let payload = || aead::Payload {
+                msg: &plaintext,
+                aad: b"associated data",
+            };
+
+            let ciphertext = cipher.encrypt(nonce.as_slice().into(), payload()).unwrap();
+            let expected = reference
+                .encrypt(nonce.as_slice().into(), payload())
+                .unwrap();
+            assert_eq!(ciphertext, expected);
+
+            let decrypted = cipher
+                .decrypt(
+                    nonce.as_slice().into(),
+                    aead::Payload {
+                        msg: &ciphertext,
+                        aad: b"associated data",
+                    },
+                )
+                .unwrap();
+            assert_eq!(decrypted, plaintext);
+
+            // The associated data is authenticated.
+            assert!(cipher
+                .decrypt(nonce.as_slice().into(), ciphertext.as_slice())
+                .is_err());
+        }
+    }
+
+    #[cfg(feature = "aead")]
+    #[test]
+    fn test_aes_gcm_aead_kat() {
+        use aead::{AeadInPlace, KeyInit};
+
+        // Same test case as test_gcm_encrypt, through the aead traits.
+        let key = hex::decode("feffe9928665731c6d6a8f9467308308").unwrap();
+        let nonce = hex::decode("cafebabefacedbaddecaf888").unwrap();
+        let auth_data = hex::decode("feedfacedeadbeeffeedfacedeadbeefabaddad2").unwrap();
+        let plaintext = hex::decode("d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39").unwrap();
+
+        let cipher = Aes128Gcm::new_from_slice(&key).unwrap();
+        let mut buffer = plaintext.clone();
+        let tag = cipher
+            .encrypt_in_place_detached(nonce.as_slice().into(), &auth_data, &mut buffer)
+            .unwrap();
+        assert_eq!(hex::encode(&buffer), "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091");
+        assert_eq!(hex::encode(tag), "5bc94fbc3221a5db94fae95ae7121a47");
+
+        cipher
+            .decrypt_in_place_detached(nonce.as_slice().into(), &auth_data, &mut buffer, &tag)
+            .unwrap();
+        assert_eq!(buffer, plaintext);
+
+        let mut wrong_tag = tag;
+        wrong_tag[0] ^= 1;
+        assert!(cipher
+            .decrypt_in_place_detached(nonce.as_slice().into(), &auth_data, &mut buffer, &wrong_tag)
+            .is_err());
+
+        assert!(Aes128Gcm::new_from_slice(&[0u8; 32]).is_err());
+    }
+
+    #[cfg(feature = "aead")]
+    #[test]
+    fn test_aes_gcm_aead_against_aes_gcm_crate() {
+        check_aead_against_rustcrypto::<Aes128Gcm, aes_gcm::Aes128Gcm>(&[0x11u8; 16]);
+        check_aead_against_rustcrypto::<Aes256Gcm, aes_gcm::Aes256Gcm>(&[0x22u8; 32]);
+    }
+
+    // Wycheproof vectors, as packaged in the tests of the aes-gcm crate.
+    #[cfg(feature = "aead")]
+    aead::new_test!(test_aes128_gcm_wycheproof, "wycheproof-128", Aes128Gcm);
+    #[cfg(feature = "aead")]
+    aead::new_test!(test_aes256_gcm_wycheproof, "wycheproof-256", Aes256Gcm);
 }
@@ -1,6 +1,13 @@
 //! Hashing functions. For further documentation please refer to symcrypt.h
+//!
+//! With the `digest` feature, [`Sha256State`] and [`Sha384State`] implement the RustCrypto `digest` traits, so they can be used with
+//! crates that are generic over `digest::Digest` in place of the sha2 crate.
+//!
+//! [`Sha256State`] and [`Sha384State`] implement `std::io::Write`, so `std::io::copy` can feed a reader into a hash state directly.
+//! [`HashReader`] hashes data as it is read, and [`sha256_reader()`] and [`sha384_reader()`] hash everything a reader returns.
 
 use core::ffi::c_void;
+use std::io::{self, Read, Write};
 use std::mem;
 use std::pin::Pin;
 use std::ptr;
@@ -9,6 +16,32 @@ use symcrypt_sys;
 pub const SHA256_RESULT_SIZE: usize = symcrypt_sys::SYMCRYPT_SHA256_RESULT_SIZE as usize;
 pub const SHA384_RESULT_SIZE: usize = symcrypt_sys::SYMCRYPT_SHA384_RESULT_SIZE as usize;
 
+/// [`HashAlgorithm`] provides an enum of the hash algorithms that can be passed to SymCrypt functions that are generic over
+/// the underlying hash, such as RSA and HashML-DSA.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum HashAlgorithm {
+    Sha256,
+    Sha384,
+}
+
+impl HashAlgorithm {
+    /// Returns the size of the hash result in bytes.
+    pub fn result_size(&self) -> usize {
+        match self {
+            HashAlgorithm::Sha256 => SHA256_RESULT_SIZE,
+            HashAlgorithm::Sha384 => SHA384_RESULT_SIZE,
+        }
+    }
+}
+
+/// convert_hash takes in the friendly HashAlgorithm enum and returns the symcrypt equivalent.
+pub(crate) fn convert_hash(hash: HashAlgorithm) -> symcrypt_sys::PCSYMCRYPT_HASH {
+    match hash {
+        HashAlgorithm::Sha256 => unsafe { symcrypt_sys::SymCryptSha256Algorithm }, // SAFETY: FFI calls
+        HashAlgorithm::Sha384 => unsafe { symcrypt_sys::SymCryptSha384Algorithm }, // SAFETY: FFI calls
+    }
+}
+
 /// Generic trait for stateful hashing
 ///
 /// [`append()`] appends to be hashed data to the state, this operation can be done multiple times.
@@ -41,6 +74,12 @@ impl Sha256State {
     }
 }
 
+impl Default for Sha256State {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl HashState for Sha256State {
     type Result = [u8; SHA256_RESULT_SIZE];
 
@@ -89,6 +128,18 @@ impl Drop for Sha256State {
     }
 }
 
+/// Write appends the written data to the [`Sha256State`]. Writes do not fail and always consume the whole buffer.
+impl Write for Sha256State {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.append(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 /// Stateless hash function for SHA256
 pub fn sha256(data: &[u8]) -> [u8; SHA256_RESULT_SIZE] {
     let mut result = [0; SHA256_RESULT_SIZE];
@@ -122,6 +173,12 @@ impl Sha384State {
     }
 }
 
+impl Default for Sha384State {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl HashState for Sha384State {
     type Result = [u8; SHA384_RESULT_SIZE];
 
@@ -170,6 +227,18 @@ impl Drop for Sha384State {
     }
 }
 
+/// Write appends the written data to the [`Sha384State`]. Writes do not fail and always consume the whole buffer.
+impl Write for Sha384State {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.append(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 /// Stateless hash function for SHA384.
 pub fn sha384(data: &[u8]) -> [u8; SHA384_RESULT_SIZE] {
     let mut result = [0; SHA384_RESULT_SIZE];
@@ -184,6 +253,102 @@ pub fn sha384(data: &[u8]) -> [u8; SHA384_RESULT_SIZE] {
     result
 }
 
+/// Hashes everything [`reader`] returns until end of file with SHA256. Errors from [`reader`] are returned to the caller.
+pub fn sha256_reader(mut reader: impl Read) -> io::Result<[u8; SHA256_RESULT_SIZE]> {
+    let mut state = Sha256State::new();
+    io::copy(&mut reader, &mut state)?;
+    Ok(state.result())
+}
+
+/// Hashes everything [`reader`] returns until end of file with SHA384. Errors from [`reader`] are returned to the caller.
+pub fn sha384_reader(mut reader: impl Read) -> io::Result<[u8; SHA384_RESULT_SIZE]> {
+    let mut state = Sha384State::new();
+    io::copy(&mut reader, &mut state)?;
+    Ok(state.result())
+}
+
+/// [`HashReader`] wraps a reader and appends every byte read through it to a hash or Hmac state. Any state that implements `Write`
+/// can be used, which includes [`Sha256State`], [`Sha384State`] and the Hmac states from hmac.rs.
+pub struct HashReader<R, H> {
+    reader: R,
+    state: H,
+}
+
+/// Impl for [`HashReader`]
+///
+/// [`new()`] takes the reader to wrap and the state that will receive the data.
+///
+/// [`state()`] returns the state so far, and [`into_inner()`] returns the reader and the state so the result can be computed once all
+/// the data has been read.
+impl<R: Read, H: Write> HashReader<R, H> {
+    pub fn new(reader: R, state: H) -> Self {
+        HashReader { reader, state }
+    }
+
+    pub fn state(&self) -> &H {
+        &self.state
+    }
+
+    pub fn into_inner(self) -> (R, H) {
+        (self.reader, self.state)
+    }
+}
+
+impl<R: Read, H: Write> Read for HashReader<R, H> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.reader.read(buf)?;
+        self.state.write_all(&buf[..read])?;
+        Ok(read)
+    }
+}
+
+/// impl_digest implements the RustCrypto `digest` traits for a [`HashState`], [`output_size`] and [`block_size`] are typenums from
+/// `digest::consts`. Adding a new hash state only needs another invocation.
+#[cfg(feature = "digest")]
+macro_rules! impl_digest {
+    ($state:ty, $output_size:ty, $block_size:ty) => {
+        impl digest::HashMarker for $state {}
+
+        impl digest::OutputSizeUser for $state {
+            type OutputSize = $output_size;
+        }
+
+        impl digest::core_api::BlockSizeUser for $state {
+            type BlockSize = $block_size;
+        }
+
+        impl digest::Update for $state {
+            fn update(&mut self, data: &[u8]) {
+                self.append(data);
+            }
+        }
+
+        impl digest::FixedOutput for $state {
+            fn finalize_into(mut self, out: &mut digest::Output<Self>) {
+                out.copy_from_slice(&self.result());
+            }
+        }
+
+        impl digest::FixedOutputReset for $state {
+            fn finalize_into_reset(&mut self, out: &mut digest::Output<Self>) {
+                // result() re-initializes the state.
+                out.copy_from_slice(&self.result());
+            }
+        }
+
+        impl digest::Reset for $state {
+            fn reset(&mut self) {
+                *self = Self::new();
+            }
+        }
+    };
+}
+
+#[cfg(feature = "digest")]
+impl_digest!(Sha256State, digest::consts::U32, digest::consts::U64);
+#[cfg(feature = "digest")]
+impl_digest!(Sha384State, digest::consts::U48, digest::consts::U128);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -286,4 +451,100 @@ mod test {
 
         test_generic_state_multiple_append(Sha384State::new(), &data_1, &data_2, expected);
     }
+
+    #[cfg(feature = "digest")]
+    fn check_digest_against_sha2<D, R>()
+    where
+        D: digest::Digest + digest::FixedOutputReset,
+        R: digest::Digest,
+    {
+        // Lengths around the block sizes of SHA-256 and SHA-384.
+        for length in [0, 1, 55, 56, 64, 111, 112, 128, 1000] {
+            let data: Vec<u8> = (0..length).map(|i| i as u8).collect();
+            let expected = R::digest(&data);
+            assert_eq!(D::digest(&data).as_slice(), expected.as_slice());
+
+            let mut hasher = D::new();
+            for chunk in data.chunks(7) {
+                digest::Digest::update(&mut hasher, chunk);
+            }
+            assert_eq!(hasher.finalize_reset().as_slice(), expected.as_slice());
+            digest::Digest::update(&mut hasher, &data);
+            assert_eq!(hasher.finalize().as_slice(), expected.as_slice());
+        }
+
+        let mut hasher = D::new();
+        digest::Digest::update(&mut hasher, b"discarded");
+        digest::Digest::reset(&mut hasher);
+        assert_eq!(hasher.finalize().as_slice(), R::digest(b"").as_slice());
+    }
+
+    #[cfg(feature = "digest")]
+    #[test]
+    fn test_digest_sha256() {
+        check_digest_against_sha2::<Sha256State, sha2::Sha256>();
+    }
+
+    #[cfg(feature = "digest")]
+    #[test]
+    fn test_digest_sha384() {
+        check_digest_against_sha2::<Sha384State, sha2::Sha384>();
+    }
+
+    #[test]
+    fn test_io_copy_into_hash_state() {
+        let data: Vec<u8> = (0..1000).map(|i| i as u8).collect();
+
+        let mut sha256_state = Sha256State::new();
+        io::copy(&mut data.as_slice(), &mut sha256_state).unwrap();
+        assert_eq!(sha256_state.result(), sha256(&data));
+
+        let mut sha384_state = Sha384State::new();
+        sha384_state.write_all(&data[..10]).unwrap();
+        sha384_state.write_all(&data[10..]).unwrap();
+        assert_eq!(sha384_state.result(), sha384(&data));
+    }
+
+    #[test]
+    fn test_sha_reader() {
+        let data = hex::decode("641ec2cf711e").unwrap();
+        assert_eq!(
+            hex::encode(sha256_reader(data.as_slice()).unwrap()),
+            "cfdbd6c9acf9842ce04e8e6a0421838f858559cf22d2ea8a38bd07d5e4692233"
+        );
+        assert_eq!(
+            hex::encode(sha384_reader(io::empty()).unwrap()),
+            "38b060a751ac96384cd9327eb1b1e36a21fdb71114be07434c0cc7bf63f6e1da274edebfe76f65fbd51ad2f14898b95b"
+        );
+    }
+
+    #[test]
+    fn test_hash_reader() {
+        let data: Vec<u8> = (0..5000).map(|i| (i * 7) as u8).collect();
+
+        let mut reader = HashReader::new(data.as_slice(), Sha256State::new());
+        let mut copy = Vec::new();
+        reader.read_to_end(&mut copy).unwrap();
+        assert_eq!(copy, data);
+
+        let (remaining, mut state) = reader.into_inner();
+        assert!(remaining.is_empty());
+        assert_eq!(state.result(), sha256(&data));
+    }
+
+    #[test]
+    fn test_hash_reader_error() {
+        struct FailingReader;
+
+        impl Read for FailingReader {
+            fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("read failed"))
+            }
+        }
+
+        assert!(sha256_reader(FailingReader).is_err());
+        assert!(HashReader::new(FailingReader, Sha384State::new())
+            .read(&mut [0u8; 16])
+            .is_err());
+    }
 }
@@ -0,0 +1,1800 @@
+//! Stateful hash-based signatures. LMS and HSS are defined in RFC 8554, XMSS is defined in RFC 8391. For further documentation please
+//! refer to symcrypt.h
+//!
+//! Every signature uses a one-time key, signing twice with the same one-time key breaks the security of the scheme. The private key types
+//! are not Clone, and [`LmsPrivateKey::sign()`], [`HssPrivateKey::sign()`] and [`XmssPrivateKey::sign()`] take a `persist` callback that
+//! is given the key state after the one-time key has been used. The signature is only returned once `persist` has succeeded, so a state
+//! that can sign with the same one-time key again is never on persistent storage when the signature is released. The key states are only
+//! handed out through `persist`, `generate()` passes the first one, and a private key is restored by consuming a state that was read back
+//! from persistent storage. There is no way to take a copy of the state of a private key and restore it after it has signed.
+//!
+//! The key states hold the index of the next one-time key in front of the SymCrypt private key blob, and are checked when they are read
+//! back with `from_bytes()`. SymCrypt has no HSS signing, so [`HssPrivateKey`] signs with a LMS private key per level as defined in
+//! RFC 8554 section 6.
+
+use crate::errors::{map_verify_error, SymCryptError};
+use crate::secret::{wipe, SecretBytes};
+use crate::symcrypt_init;
+use symcrypt_sys;
+
+/// Size of the LMS key identifier I.
+const LMS_IDENTIFIER_SIZE: usize = 16;
+
+/// Maximum number of levels of a HSS key as defined in RFC 8554 section 6.
+const HSS_MAX_LEVELS: u32 = 8;
+
+/// Size of the [`LmsKeyState`] header: the LMS and LM-OTS typecodes and the index of the next one-time key.
+const LMS_STATE_HEADER_SIZE: usize = 12;
+
+/// Size of the [`XmssKeyState`] header: the XMSS OID and the index of the next one-time key.
+const XMSS_STATE_HEADER_SIZE: usize = 8;
+
+/// Offset of the index q of the next one-time key in the SymCrypt LMS private key blob, which starts with the LMS and LM-OTS typecodes
+/// and the key identifier I.
+const LMS_PRIVATE_KEY_INDEX_OFFSET: usize = 8 + LMS_IDENTIFIER_SIZE;
+
+/// read_u32 reads a big-endian u32 at `offset`, and fails with InvalidBlob if the buffer is too short.
+fn read_u32(buffer: &[u8], offset: usize) -> Result<u32, SymCryptError> {
+    buffer
+        .get(offset..offset + 4)
+        .map(|bytes| u32::from_be_bytes(bytes.try_into().unwrap())) // slice is always 4 bytes
+        .ok_or(SymCryptError::InvalidBlob)
+}
+
+/// read_chunk reads a big-endian u32 length at `offset` followed by that many bytes, and moves `offset` past them.
+fn read_chunk<'a>(buffer: &'a [u8], offset: &mut usize) -> Result<&'a [u8], SymCryptError> {
+    let length = read_u32(buffer, *offset)? as usize;
+    let chunk = buffer
+        .get(*offset + 4..*offset + 4 + length)
+        .ok_or(SymCryptError::InvalidBlob)?;
+    *offset += 4 + length;
+    Ok(chunk)
+}
+
+/// [`LmsAlgorithm`] provides an enum of the LMS parameter sets, named after the hash, the hash size M and the tree height H.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum LmsAlgorithm {
+    Sha256M32H5,
+    Sha256M32H10,
+    Sha256M32H15,
+    Sha256M32H20,
+    Sha256M32H25,
+    Sha256M24H5,
+    Sha256M24H10,
+    Sha256M24H15,
+    Sha256M24H20,
+    Sha256M24H25,
+}
+
+/// Impl for [`LmsAlgorithm`]
+///
+/// [`id()`] and [`from_id()`] convert to and from the IANA typecode that is used in public keys and signatures.
+impl LmsAlgorithm {
+    pub fn id(&self) -> u32 {
+        match self {
+            LmsAlgorithm::Sha256M32H5 => 0x05,
+            LmsAlgorithm::Sha256M32H10 => 0x06,
+            LmsAlgorithm::Sha256M32H15 => 0x07,
+            LmsAlgorithm::Sha256M32H20 => 0x08,
+            LmsAlgorithm::Sha256M32H25 => 0x09,
+            LmsAlgorithm::Sha256M24H5 => 0x0a,
+            LmsAlgorithm::Sha256M24H10 => 0x0b,
+            LmsAlgorithm::Sha256M24H15 => 0x0c,
+            LmsAlgorithm::Sha256M24H20 => 0x0d,
+            LmsAlgorithm::Sha256M24H25 => 0x0e,
+        }
+    }
+
+    pub fn from_id(id: u32) -> Result<Self, SymCryptError> {
+        match id {
+            0x05 => Ok(LmsAlgorithm::Sha256M32H5),
+            0x06 => Ok(LmsAlgorithm::Sha256M32H10),
+            0x07 => Ok(LmsAlgorithm::Sha256M32H15),
+            0x08 => Ok(LmsAlgorithm::Sha256M32H20),
+            0x09 => Ok(LmsAlgorithm::Sha256M32H25),
+            0x0a => Ok(LmsAlgorithm::Sha256M24H5),
+            0x0b => Ok(LmsAlgorithm::Sha256M24H10),
+            0x0c => Ok(LmsAlgorithm::Sha256M24H15),
+            0x0d => Ok(LmsAlgorithm::Sha256M24H20),
+            0x0e => Ok(LmsAlgorithm::Sha256M24H25),
+            _ => Err(SymCryptError::InvalidBlob),
+        }
+    }
+
+    fn hash_size(&self) -> usize {
+        match self.id() {
+            0x05..=0x09 => 32,
+            _ => 24,
+        }
+    }
+
+    fn height(&self) -> usize {
+        match self.id() {
+            0x05 | 0x0a => 5,
+            0x06 | 0x0b => 10,
+            0x07 | 0x0c => 15,
+            0x08 | 0x0d => 20,
+            _ => 25,
+        }
+    }
+
+    fn public_key_size(&self) -> usize {
+        8 + LMS_IDENTIFIER_SIZE + self.hash_size()
+    }
+
+    // Number of one-time keys of the tree.
+    fn capacity(&self) -> u32 {
+        1 << self.height()
+    }
+}
+
+/// [`LmotsAlgorithm`] provides an enum of the LM-OTS parameter sets, named after the hash, the hash size N and the Winternitz width W.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum LmotsAlgorithm {
+    Sha256N32W1,
+    Sha256N32W2,
+    Sha256N32W4,
+    Sha256N32W8,
+    Sha256N24W1,
+    Sha256N24W2,
+    Sha256N24W4,
+    Sha256N24W8,
+}
+
+/// Impl for [`LmotsAlgorithm`]
+///
+/// [`id()`] and [`from_id()`] convert to and from the IANA typecode that is used in public keys and signatures.
+impl LmotsAlgorithm {
+    pub fn id(&self) -> u32 {
+        match self {
+            LmotsAlgorithm::Sha256N32W1 => 0x01,
+            LmotsAlgorithm::Sha256N32W2 => 0x02,
+            LmotsAlgorithm::Sha256N32W4 => 0x03,
+            LmotsAlgorithm::Sha256N32W8 => 0x04,
+            LmotsAlgorithm::Sha256N24W1 => 0x05,
+            LmotsAlgorithm::Sha256N24W2 => 0x06,
+            LmotsAlgorithm::Sha256N24W4 => 0x07,
+            LmotsAlgorithm::Sha256N24W8 => 0x08,
+        }
+    }
+
+    pub fn from_id(id: u32) -> Result<Self, SymCryptError> {
+        match id {
+            0x01 => Ok(LmotsAlgorithm::Sha256N32W1),
+            0x02 => Ok(LmotsAlgorithm::Sha256N32W2),
+            0x03 => Ok(LmotsAlgorithm::Sha256N32W4),
+            0x04 => Ok(LmotsAlgorithm::Sha256N32W8),
+            0x05 => Ok(LmotsAlgorithm::Sha256N24W1),
+            0x06 => Ok(LmotsAlgorithm::Sha256N24W2),
+            0x07 => Ok(LmotsAlgorithm::Sha256N24W4),
+            0x08 => Ok(LmotsAlgorithm::Sha256N24W8),
+            _ => Err(SymCryptError::InvalidBlob),
+        }
+    }
+
+    fn hash_size(&self) -> usize {
+        match self.id() {
+            0x01..=0x04 => 32,
+            _ => 24,
+        }
+    }
+
+    // Number of hash chains p, as listed in RFC 8554 section 4.1 and NIST SP 800-208 section 4.1.
+    fn chain_count(&self) -> usize {
+        match self {
+            LmotsAlgorithm::Sha256N32W1 => 265,
+            LmotsAlgorithm::Sha256N32W2 => 133,
+            LmotsAlgorithm::Sha256N32W4 => 67,
+            LmotsAlgorithm::Sha256N32W8 => 34,
+            LmotsAlgorithm::Sha256N24W1 => 200,
+            LmotsAlgorithm::Sha256N24W2 => 101,
+            LmotsAlgorithm::Sha256N24W4 => 51,
+            LmotsAlgorithm::Sha256N24W8 => 26,
+        }
+    }
+}
+
+/// lms_signature_size returns the size of a LMS signature as defined in RFC 8554 section 5.4.
+fn lms_signature_size(lms: LmsAlgorithm, lmots: LmotsAlgorithm) -> usize {
+    let lmots_signature_size = 4 + lmots.hash_size() * (lmots.chain_count() + 1);
+    4 + lmots_signature_size + 4 + lms.hash_size() * lms.height()
+}
+
+/// [`LmsKey`] is a wrapper around symcrypt_sys::PSYMCRYPT_LMS_KEY along with the parameters it was allocated with.
+struct LmsKey {
+    inner: symcrypt_sys::PSYMCRYPT_LMS_KEY,
+    _params: Box<symcrypt_sys::SYMCRYPT_LMS_PARAMS>, // boxing here so that the memory is not moved while the key references it
+    lms: LmsAlgorithm,
+    lmots: LmotsAlgorithm,
+}
+
+impl LmsKey {
+    // SymCryptInit must be called before any LMS operations are performed.
+    fn new(lms: LmsAlgorithm, lmots: LmotsAlgorithm) -> Result<Self, SymCryptError> {
+        let mut params = Box::new(symcrypt_sys::SYMCRYPT_LMS_PARAMS::default());
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_init(); // Will only init once, subsequent calls to symcrypt_init() will be no-ops.
+
+            match symcrypt_sys::SymCryptLmsParamsFromAlgId(
+                lms.id() as symcrypt_sys::SYMCRYPT_LMS_ALGID,
+                lmots.id() as symcrypt_sys::SYMCRYPT_LMOTS_ALGID,
+                &mut *params,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => {
+                    let key_ptr = symcrypt_sys::SymCryptLmskeyAllocate(&*params, 0);
+                    if key_ptr.is_null() {
+                        return Err(SymCryptError::MemoryAllocationFailure);
+                    }
+                    Ok(LmsKey {
+                        inner: key_ptr,
+                        _params: params,
+                        lms,
+                        lmots,
+                    })
+                }
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    fn set_value(
+        &self,
+        key: &[u8],
+        key_type: symcrypt_sys::SYMCRYPT_LMSKEY_TYPE,
+    ) -> Result<(), SymCryptError> {
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptLmskeySetValue(
+                key.as_ptr(),
+                key.len() as symcrypt_sys::SIZE_T,
+                key_type,
+                0,
+                self.inner,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(()),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    fn blob_size(
+        &self,
+        key_type: symcrypt_sys::SYMCRYPT_LMSKEY_TYPE,
+    ) -> Result<usize, SymCryptError> {
+        let mut key_size: symcrypt_sys::SIZE_T = 0;
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptLmsSizeofKeyBlobFromParams(
+                &*self._params,
+                key_type,
+                &mut key_size,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(key_size as usize),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    fn get_value(
+        &self,
+        key_type: symcrypt_sys::SYMCRYPT_LMSKEY_TYPE,
+    ) -> Result<Vec<u8>, SymCryptError> {
+        let mut key = vec![0u8; self.blob_size(key_type)?];
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptLmskeyGetValue(
+                self.inner,
+                key_type,
+                0,
+                key.as_mut_ptr(),
+                key.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(key),
+                err => {
+                    wipe(&mut key);
+                    Err(err.into())
+                }
+            }
+        }
+    }
+}
+
+impl Drop for LmsKey {
+    fn drop(&mut self) {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptLmskeyFree(self.inner);
+        }
+    }
+}
+
+/// [`LmsPublicKey`] holds a single level LMS public key.
+pub struct LmsPublicKey(LmsKey);
+
+/// Impl for [`LmsPublicKey`]
+///
+/// [`from_bytes()`] takes in a public key encoded as defined in RFC 8554 section 5.3, the parameter sets are read from the encoding.
+///
+/// [`to_bytes()`] returns the encoded public key.
+///
+/// [`verify()`] takes in a message and a signature. Any failure to verify, including a malformed signature, returns
+/// [`SymCryptError::SignatureVerificationFailure`].
+impl LmsPublicKey {
+    pub fn from_bytes(public_key: &[u8]) -> Result<Self, SymCryptError> {
+        let lms = LmsAlgorithm::from_id(read_u32(public_key, 0)?)?;
+        let lmots = LmotsAlgorithm::from_id(read_u32(public_key, 4)?)?;
+        if public_key.len() != lms.public_key_size() {
+            return Err(SymCryptError::WrongKeySize);
+        }
+        let key = LmsKey::new(lms, lmots)?;
+        key.set_value(
+            public_key,
+            symcrypt_sys::_SYMCRYPT_LMSKEY_TYPE_SYMCRYPT_LMSKEY_TYPE_PUBLIC,
+        )?;
+        Ok(LmsPublicKey(key))
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SymCryptError> {
+        self.0
+            .get_value(symcrypt_sys::_SYMCRYPT_LMSKEY_TYPE_SYMCRYPT_LMSKEY_TYPE_PUBLIC)
+    }
+
+    pub fn algorithms(&self) -> (LmsAlgorithm, LmotsAlgorithm) {
+        (self.0.lms, self.0.lmots)
+    }
+
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), SymCryptError> {
+        unsafe {
+            // SAFETY: FFI calls
+            map_verify_error(symcrypt_sys::SymCryptLmsVerify(
+                self.0.inner,
+                message.as_ptr(),
+                message.len() as symcrypt_sys::SIZE_T,
+                0,
+                signature.as_ptr(),
+                signature.len() as symcrypt_sys::SIZE_T,
+            ))
+        }
+    }
+}
+
+/// [`HssPublicKey`] holds a HSS public key, which is the number of levels followed by the LMS public key of the top level tree.
+pub struct HssPublicKey {
+    levels: u32,
+    root: LmsPublicKey,
+}
+
+/// Impl for [`HssPublicKey`]
+///
+/// [`from_bytes()`] takes in a public key encoded as defined in RFC 8554 section 6.
+///
+/// [`verify()`] takes in a message and a HSS signature, and checks each signed public key of the chain before checking the signature of
+/// the message with the bottom level key. Any failure to verify returns [`SymCryptError::SignatureVerificationFailure`].
+impl HssPublicKey {
+    pub fn from_bytes(public_key: &[u8]) -> Result<Self, SymCryptError> {
+        let levels = read_u32(public_key, 0)?;
+        if levels == 0 || levels > HSS_MAX_LEVELS {
+            return Err(SymCryptError::InvalidBlob);
+        }
+        let root = LmsPublicKey::from_bytes(&public_key[4..])?;
+        Ok(HssPublicKey { levels, root })
+    }
+
+    pub fn levels(&self) -> u32 {
+        self.levels
+    }
+
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), SymCryptError> {
+        self.verify_chain(message, signature)
+            .map_err(|_| SymCryptError::SignatureVerificationFailure)
+    }
+
+    fn verify_chain(&self, message: &[u8], signature: &[u8]) -> Result<(), SymCryptError> {
+        let signed_public_keys = read_u32(signature, 0)?;
+        if signed_public_keys != self.levels - 1 {
+            return Err(SymCryptError::InvalidBlob);
+        }
+
+        let mut offset = 4;
+        let mut child;
+        let mut current = &self.root;
+        for _ in 0..signed_public_keys {
+            // Each signed public key is the LMS signature of the child public key followed by the child public key.
+            let signature_size = LmsSignatureInfo::read(signature, offset)?.size;
+            let lms_signature = &signature[offset..offset + signature_size];
+            offset += signature_size;
+
+            let lms = LmsAlgorithm::from_id(read_u32(signature, offset)?)?;
+            let public_key = signature
+                .get(offset..offset + lms.public_key_size())
+                .ok_or(SymCryptError::InvalidBlob)?;
+            offset += public_key.len();
+
+            current.verify(public_key, lms_signature)?;
+            child = LmsPublicKey::from_bytes(public_key)?;
+            current = &child;
+        }
+
+        if LmsSignatureInfo::read(signature, offset)?.size != signature.len() - offset {
+            return Err(SymCryptError::InvalidBlob);
+        }
+        current.verify(message, &signature[offset..])
+    }
+}
+
+/// LmsSignatureInfo reads the parameter sets from an encoded LMS signature to find where it ends.
+struct LmsSignatureInfo {
+    size: usize,
+}
+
+impl LmsSignatureInfo {
+    fn read(signature: &[u8], offset: usize) -> Result<Self, SymCryptError> {
+        let lmots = LmotsAlgorithm::from_id(read_u32(signature, offset + 4)?)?;
+        let lms_type_offset = offset + 8 + lmots.hash_size() * (lmots.chain_count() + 1);
+        let lms = LmsAlgorithm::from_id(read_u32(signature, lms_type_offset)?)?;
+        let size = lms_signature_size(lms, lmots);
+        if offset + size > signature.len() {
+            return Err(SymCryptError::InvalidBlob);
+        }
+        Ok(LmsSignatureInfo { size })
+    }
+}
+
+/// [`LmsKeyState`] is the serialized private key of a [`LmsPrivateKey`]. It holds the LMS and LM-OTS typecodes and the index of the
+/// next one-time key as big-endian u32s, followed by the SymCrypt private key blob.
+///
+/// The state is not Clone, and is wiped when it leaves scope.
+pub struct LmsKeyState {
+    lms: LmsAlgorithm,
+    lmots: LmotsAlgorithm,
+    index: u32,
+    bytes: SecretBytes,
+}
+
+/// Impl for [`LmsKeyState`]
+///
+/// [`as_bytes()`] returns the serialized private key to write to persistent storage.
+///
+/// [`from_bytes()`] takes in a serialized private key that was read back from persistent storage. Unknown parameter sets, an index past
+/// the last one-time key, an index that differs from the q stored in the private key and a private key that SymCrypt does not accept
+/// fail with [`SymCryptError::InvalidBlob`], and a private key of the wrong size fails with [`SymCryptError::WrongKeySize`].
+///
+/// [`index()`] returns the index of the next one-time key, and [`remaining()`] returns the number of signatures that can still be made.
+impl LmsKeyState {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, SymCryptError> {
+        let bytes = SecretBytes::from(bytes);
+        let lms = LmsAlgorithm::from_id(read_u32(&bytes, 0)?)?;
+        let lmots = LmotsAlgorithm::from_id(read_u32(&bytes, 4)?)?;
+        let index = read_u32(&bytes, 8)?;
+        if index > lms.capacity() {
+            return Err(SymCryptError::InvalidBlob);
+        }
+        let key = LmsKey::new(lms, lmots)?;
+        let private_key = &bytes[LMS_STATE_HEADER_SIZE..];
+        if private_key.len()
+            != key.blob_size(symcrypt_sys::_SYMCRYPT_LMSKEY_TYPE_SYMCRYPT_LMSKEY_TYPE_PRIVATE)?
+        {
+            return Err(SymCryptError::WrongKeySize);
+        }
+        if read_u32(private_key, LMS_PRIVATE_KEY_INDEX_OFFSET)? != index {
+            return Err(SymCryptError::InvalidBlob);
+        }
+        key.set_value(
+            private_key,
+            symcrypt_sys::_SYMCRYPT_LMSKEY_TYPE_SYMCRYPT_LMSKEY_TYPE_PRIVATE,
+        )
+        .map_err(|_| SymCryptError::InvalidBlob)?;
+        Ok(LmsKeyState {
+            lms,
+            lmots,
+            index,
+            bytes,
+        })
+    }
+
+    pub fn algorithms(&self) -> (LmsAlgorithm, LmotsAlgorithm) {
+        (self.lms, self.lmots)
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn remaining(&self) -> u32 {
+        self.lms.capacity() - self.index
+    }
+}
+
+/// [`LmsPrivateKey`] holds a single level LMS private key along with the index of its next one-time key.
+pub struct LmsPrivateKey {
+    key: LmsKey,
+    index: u32,
+}
+
+/// Impl for [`LmsPrivateKey`]
+///
+/// [`generate()`] returns a new random [`LmsPrivateKey`] for the provided parameter sets. Its first [`LmsKeyState`] is passed to
+/// `persist`, which must write it to persistent storage, and the private key is only returned if `persist` succeeds.
+///
+/// [`from_state()`] consumes a [`LmsKeyState`] and returns the [`LmsPrivateKey`] it holds.
+///
+/// [`public_key()`] returns the [`LmsPublicKey`] associated with the private key.
+///
+/// [`index()`] returns the index of the next one-time key, and [`remaining()`] returns the number of signatures that can still be made.
+///
+/// [`sign()`] signs the message with the next one-time key and passes the following [`LmsKeyState`] to `persist`, which must write it to
+/// persistent storage. The signature is only returned if `persist` succeeds, otherwise its error is returned. Signing fails with
+/// [`SymCryptError::InvalidArgument`] once every one-time key has been used, and with [`SymCryptError::InvalidBlob`] if the index of the
+/// state does not match the one-time key that SymCrypt used.
+impl LmsPrivateKey {
+    pub fn generate<F>(
+        lms: LmsAlgorithm,
+        lmots: LmotsAlgorithm,
+        persist: F,
+    ) -> Result<Self, SymCryptError>
+    where
+        F: FnOnce(&LmsKeyState) -> Result<(), SymCryptError>,
+    {
+        let private_key = LmsPrivateKey::generate_unpersisted(lms, lmots)?;
+        persist(&private_key.state()?)?;
+        Ok(private_key)
+    }
+
+    // Generates a key without persisting its state, the caller must persist it before the key is used.
+    fn generate_unpersisted(
+        lms: LmsAlgorithm,
+        lmots: LmotsAlgorithm,
+    ) -> Result<Self, SymCryptError> {
+        let key = LmsKey::new(lms, lmots)?;
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptLmskeyGenerate(key.inner, 0) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => {
+                    Ok(LmsPrivateKey { key, index: 0 })
+                }
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn from_state(state: LmsKeyState) -> Result<Self, SymCryptError> {
+        let key = LmsKey::new(state.lms, state.lmots)?;
+        key.set_value(
+            &state.bytes[LMS_STATE_HEADER_SIZE..],
+            symcrypt_sys::_SYMCRYPT_LMSKEY_TYPE_SYMCRYPT_LMSKEY_TYPE_PRIVATE,
+        )?;
+        Ok(LmsPrivateKey {
+            key,
+            index: state.index,
+        })
+    }
+
+    fn state(&self) -> Result<LmsKeyState, SymCryptError> {
+        let private_key = SecretBytes::from(
+            self.key
+                .get_value(symcrypt_sys::_SYMCRYPT_LMSKEY_TYPE_SYMCRYPT_LMSKEY_TYPE_PRIVATE)?,
+        );
+        let mut bytes = Vec::with_capacity(LMS_STATE_HEADER_SIZE + private_key.len());
+        bytes.extend_from_slice(&self.key.lms.id().to_be_bytes());
+        bytes.extend_from_slice(&self.key.lmots.id().to_be_bytes());
+        bytes.extend_from_slice(&self.index.to_be_bytes());
+        bytes.extend_from_slice(&private_key);
+        Ok(LmsKeyState {
+            lms: self.key.lms,
+            lmots: self.key.lmots,
+            index: self.index,
+            bytes: bytes.into(),
+        })
+    }
+
+    pub fn public_key(&self) -> Result<LmsPublicKey, SymCryptError> {
+        LmsPublicKey::from_bytes(
+            &self
+                .key
+                .get_value(symcrypt_sys::_SYMCRYPT_LMSKEY_TYPE_SYMCRYPT_LMSKEY_TYPE_PUBLIC)?,
+        )
+    }
+
+    pub fn algorithms(&self) -> (LmsAlgorithm, LmotsAlgorithm) {
+        (self.key.lms, self.key.lmots)
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn remaining(&self) -> u32 {
+        self.key.lms.capacity() - self.index
+    }
+
+    pub fn sign<F>(&mut self, message: &[u8], persist: F) -> Result<Vec<u8>, SymCryptError>
+    where
+        F: FnOnce(&LmsKeyState) -> Result<(), SymCryptError>,
+    {
+        let signature = self.sign_one_time(message)?;
+        persist(&self.state()?)?;
+        Ok(signature)
+    }
+
+    // Signs with the next one-time key without persisting the state, the caller must persist it before the signature is released.
+    fn sign_one_time(&mut self, message: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+        if self.remaining() == 0 {
+            return Err(SymCryptError::InvalidArgument);
+        }
+        let mut signature = vec![0u8; lms_signature_size(self.key.lms, self.key.lmots)];
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptLmsSign(
+                self.key.inner,
+                message.as_ptr(),
+                message.len() as symcrypt_sys::SIZE_T,
+                0,
+                signature.as_mut_ptr(),
+                signature.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => (),
+                err => return Err(err.into()),
+            }
+        }
+        // SymCryptLmsSign has moved the key to the next one-time key, the signature starts with the index q that was used.
+        if read_u32(&signature, 0)? != self.index {
+            return Err(SymCryptError::InvalidBlob);
+        }
+        self.index += 1;
+        Ok(signature)
+    }
+}
+
+unsafe impl Send for LmsPublicKey {
+    // The key is only referenced through the SymCrypt allocated pointer, which does not move.
+}
+
+unsafe impl Sync for LmsPublicKey {
+    // Verification only reads the key.
+}
+
+unsafe impl Send for LmsPrivateKey {
+    // The key is only referenced through the SymCrypt allocated pointer, which does not move.
+}
+
+/// hss_remaining returns the number of HSS signatures that can still be made, from the remaining one-time keys and the capacity of each
+/// level starting at the top. A one-time key left on an upper level signs a new tree for the level below it, as in RFC 8554 section 6.2.
+fn hss_remaining(levels: &[(u32, u32)]) -> u64 {
+    let mut remaining = 0u64;
+    let mut multiplier = 1u64;
+    for &(level_remaining, capacity) in levels.iter().rev() {
+        remaining = remaining.saturating_add((level_remaining as u64).saturating_mul(multiplier));
+        multiplier = multiplier.saturating_mul(capacity as u64);
+    }
+    remaining
+}
+
+/// parse_hss_state reads the LMS key states of each level and the signed public keys of the levels below the top one.
+fn parse_hss_state(bytes: &[u8]) -> Result<(Vec<LmsKeyState>, Vec<Vec<u8>>), SymCryptError> {
+    let levels = read_u32(bytes, 0)?;
+    if levels == 0 || levels > HSS_MAX_LEVELS {
+        return Err(SymCryptError::InvalidBlob);
+    }
+    let mut offset = 4;
+    let mut states = Vec::with_capacity(levels as usize);
+    for _ in 0..levels {
+        states.push(LmsKeyState::from_bytes(
+            read_chunk(bytes, &mut offset)?.to_vec(),
+        )?);
+    }
+    let mut signed_public_keys = Vec::with_capacity(levels as usize - 1);
+    for level in 1..levels as usize {
+        let signed_public_key = read_chunk(bytes, &mut offset)?;
+        let (lms, lmots) = states[level - 1].algorithms();
+        if signed_public_key.len()
+            != lms_signature_size(lms, lmots) + states[level].lms.public_key_size()
+        {
+            return Err(SymCryptError::InvalidBlob);
+        }
+        signed_public_keys.push(signed_public_key.to_vec());
+    }
+    if offset != bytes.len() {
+        return Err(SymCryptError::InvalidBlob);
+    }
+    Ok((states, signed_public_keys))
+}
+
+/// [`HssKeyState`] is the serialized private key of a [`HssPrivateKey`]. It holds the number of levels as a big-endian u32, the
+/// [`LmsKeyState`] of each level and then the signed public key of each level below the top one, each prefixed with its length as a
+/// big-endian u32.
+///
+/// The state is not Clone, and is wiped when it leaves scope.
+pub struct HssKeyState {
+    levels: u32,
+    remaining: u64,
+    bytes: SecretBytes,
+}
+
+/// Impl for [`HssKeyState`]
+///
+/// [`as_bytes()`] returns the serialized private key to write to persistent storage.
+///
+/// [`from_bytes()`] takes in a serialized private key that was read back from persistent storage, and checks it the same way as
+/// [`LmsKeyState::from_bytes()`] for each level. A malformed state fails with [`SymCryptError::InvalidBlob`].
+///
+/// [`remaining()`] returns the number of signatures that can still be made, saturated at u64::MAX.
+impl HssKeyState {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, SymCryptError> {
+        let bytes = SecretBytes::from(bytes);
+        let (states, _) = parse_hss_state(&bytes)?;
+        let levels: Vec<(u32, u32)> = states
+            .iter()
+            .map(|state| (state.remaining(), state.lms.capacity()))
+            .collect();
+        Ok(HssKeyState {
+            levels: states.len() as u32,
+            remaining: hss_remaining(&levels),
+            bytes,
+        })
+    }
+
+    pub fn levels(&self) -> u32 {
+        self.levels
+    }
+
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+}
+
+/// [`HssPrivateKey`] holds a HSS private key, which is a [`LmsPrivateKey`] for each level and the signed public keys that link them.
+pub struct HssPrivateKey {
+    levels: Vec<LmsPrivateKey>,
+    signed_public_keys: Vec<Vec<u8>>,
+}
+
+/// Impl for [`HssPrivateKey`]
+///
+/// [`generate()`] returns a new random [`HssPrivateKey`] with one level for each pair of parameter sets, starting at the top. Between 1 and
+/// 8 levels are supported, other counts fail with [`SymCryptError::InvalidArgument`]. Its first [`HssKeyState`] is passed to `persist`,
+/// which must write it to persistent storage, and the private key is only returned if `persist` succeeds.
+///
+/// [`from_state()`] consumes a [`HssKeyState`] and returns the [`HssPrivateKey`] it holds. The signed public keys are verified, and a
+/// state where they do not match the levels fails with [`SymCryptError::InvalidBlob`].
+///
+/// [`public_key()`] returns the [`HssPublicKey`] associated with the private key.
+///
+/// [`remaining()`] returns the number of signatures that can still be made, saturated at u64::MAX.
+///
+/// [`sign()`] signs the message and passes the following [`HssKeyState`] to `persist`, which must write it to persistent storage. When
+/// the bottom level tree has no one-time keys left, new trees are generated below the lowest level that still has one. The signature is
+/// only returned if `persist` succeeds, otherwise its error is returned. Signing fails with [`SymCryptError::InvalidArgument`] once
+/// every one-time key of every level has been used.
+impl HssPrivateKey {
+    pub fn generate<F>(
+        levels: &[(LmsAlgorithm, LmotsAlgorithm)],
+        persist: F,
+    ) -> Result<Self, SymCryptError>
+    where
+        F: FnOnce(&HssKeyState) -> Result<(), SymCryptError>,
+    {
+        if levels.is_empty() || levels.len() > HSS_MAX_LEVELS as usize {
+            return Err(SymCryptError::InvalidArgument);
+        }
+        let mut private_keys: Vec<LmsPrivateKey> = Vec::with_capacity(levels.len());
+        let mut signed_public_keys = Vec::with_capacity(levels.len() - 1);
+        for &(lms, lmots) in levels {
+            let private_key = LmsPrivateKey::generate_unpersisted(lms, lmots)?;
+            if let Some(parent) = private_keys.last_mut() {
+                signed_public_keys.push(sign_public_key(parent, &private_key)?);
+            }
+            private_keys.push(private_key);
+        }
+        let private_key = HssPrivateKey {
+            levels: private_keys,
+            signed_public_keys,
+        };
+        persist(&private_key.state()?)?;
+        Ok(private_key)
+    }
+
+    pub fn from_state(state: HssKeyState) -> Result<Self, SymCryptError> {
+        let (states, signed_public_keys) = parse_hss_state(&state.bytes)?;
+        let levels = states
+            .into_iter()
+            .map(LmsPrivateKey::from_state)
+            .collect::<Result<Vec<_>, _>>()?;
+        for (level, signed_public_key) in signed_public_keys.iter().enumerate() {
+            let (lms, lmots) = levels[level].algorithms();
+            let (signature, public_key) =
+                signed_public_key.split_at(lms_signature_size(lms, lmots));
+            levels[level]
+                .public_key()?
+                .verify(public_key, signature)
+                .map_err(|_| SymCryptError::InvalidBlob)?;
+            if levels[level + 1].public_key()?.to_bytes()? != public_key {
+                return Err(SymCryptError::InvalidBlob);
+            }
+        }
+        Ok(HssPrivateKey {
+            levels,
+            signed_public_keys,
+        })
+    }
+
+    fn state(&self) -> Result<HssKeyState, SymCryptError> {
+        let states = self
+            .levels
+            .iter()
+            .map(LmsPrivateKey::state)
+            .collect::<Result<Vec<_>, _>>()?;
+        let size = 4
+            + states
+                .iter()
+                .map(|state| 4 + state.bytes.len())
+                .sum::<usize>()
+            + self
+                .signed_public_keys
+                .iter()
+                .map(|signed| 4 + signed.len())
+                .sum::<usize>();
+        let mut bytes = Vec::with_capacity(size);
+        bytes.extend_from_slice(&(self.levels.len() as u32).to_be_bytes());
+        for state in &states {
+            bytes.extend_from_slice(&(state.bytes.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(&state.bytes);
+        }
+        for signed_public_key in &self.signed_public_keys {
+            bytes.extend_from_slice(&(signed_public_key.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(signed_public_key);
+        }
+        Ok(HssKeyState {
+            levels: self.levels.len() as u32,
+            remaining: self.remaining(),
+            bytes: bytes.into(),
+        })
+    }
+
+    pub fn public_key(&self) -> Result<HssPublicKey, SymCryptError> {
+        Ok(HssPublicKey {
+            levels: self.levels.len() as u32,
+            root: self.levels[0].public_key()?,
+        })
+    }
+
+    pub fn levels(&self) -> u32 {
+        self.levels.len() as u32
+    }
+
+    pub fn remaining(&self) -> u64 {
+        let levels: Vec<(u32, u32)> = self
+            .levels
+            .iter()
+            .map(|level| (level.remaining(), level.key.lms.capacity()))
+            .collect();
+        hss_remaining(&levels)
+    }
+
+    pub fn sign<F>(&mut self, message: &[u8], persist: F) -> Result<Vec<u8>, SymCryptError>
+    where
+        F: FnOnce(&HssKeyState) -> Result<(), SymCryptError>,
+    {
+        let bottom = self.levels.len() - 1;
+        let mut level = bottom;
+        while self.levels[level].remaining() == 0 {
+            if level == 0 {
+                return Err(SymCryptError::InvalidArgument);
+            }
+            level -= 1;
+        }
+        for child in level + 1..=bottom {
+            let (lms, lmots) = self.levels[child].algorithms();
+            let private_key = LmsPrivateKey::generate_unpersisted(lms, lmots)?;
+            self.signed_public_keys[child - 1] =
+                sign_public_key(&mut self.levels[child - 1], &private_key)?;
+            self.levels[child] = private_key;
+        }
+
+        let message_signature = self.levels[bottom].sign_one_time(message)?;
+        persist(&self.state()?)?;
+
+        let mut signature = (bottom as u32).to_be_bytes().to_vec();
+        for signed_public_key in &self.signed_public_keys {
+            signature.extend_from_slice(signed_public_key);
+        }
+        signature.extend_from_slice(&message_signature);
+        Ok(signature)
+    }
+}
+
+/// sign_public_key signs the public key of `child` with `parent` and returns the signature followed by the public key, as they appear
+/// in a HSS signature.
+fn sign_public_key(
+    parent: &mut LmsPrivateKey,
+    child: &LmsPrivateKey,
+) -> Result<Vec<u8>, SymCryptError> {
+    let public_key = child.public_key()?.to_bytes()?;
+    let mut signed_public_key = parent.sign_one_time(&public_key)?;
+    signed_public_key.extend_from_slice(&public_key);
+    Ok(signed_public_key)
+}
+
+/// [`XmssAlgorithm`] provides an enum of the single tree XMSS parameter sets, named after the hash, the tree height and the hash size in bits.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum XmssAlgorithm {
+    Sha2_10_256,
+    Sha2_16_256,
+    Sha2_20_256,
+    Sha2_10_512,
+    Sha2_16_512,
+    Sha2_20_512,
+    Sha2_10_192,
+    Sha2_16_192,
+    Sha2_20_192,
+}
+
+/// Impl for [`XmssAlgorithm`]
+///
+/// [`id()`] and [`from_id()`] convert to and from the IANA OID that is used in public keys.
+impl XmssAlgorithm {
+    pub fn id(&self) -> u32 {
+        match self {
+            XmssAlgorithm::Sha2_10_256 => 0x01,
+            XmssAlgorithm::Sha2_16_256 => 0x02,
+            XmssAlgorithm::Sha2_20_256 => 0x03,
+            XmssAlgorithm::Sha2_10_512 => 0x04,
+            XmssAlgorithm::Sha2_16_512 => 0x05,
+            XmssAlgorithm::Sha2_20_512 => 0x06,
+            XmssAlgorithm::Sha2_10_192 => 0x0d,
+            XmssAlgorithm::Sha2_16_192 => 0x0e,
+            XmssAlgorithm::Sha2_20_192 => 0x0f,
+        }
+    }
+
+    pub fn from_id(id: u32) -> Result<Self, SymCryptError> {
+        match id {
+            0x01 => Ok(XmssAlgorithm::Sha2_10_256),
+            0x02 => Ok(XmssAlgorithm::Sha2_16_256),
+            0x03 => Ok(XmssAlgorithm::Sha2_20_256),
+            0x04 => Ok(XmssAlgorithm::Sha2_10_512),
+            0x05 => Ok(XmssAlgorithm::Sha2_16_512),
+            0x06 => Ok(XmssAlgorithm::Sha2_20_512),
+            0x0d => Ok(XmssAlgorithm::Sha2_10_192),
+            0x0e => Ok(XmssAlgorithm::Sha2_16_192),
+            0x0f => Ok(XmssAlgorithm::Sha2_20_192),
+            _ => Err(SymCryptError::InvalidBlob),
+        }
+    }
+
+    fn hash_size(&self) -> usize {
+        match self {
+            XmssAlgorithm::Sha2_10_256
+            | XmssAlgorithm::Sha2_16_256
+            | XmssAlgorithm::Sha2_20_256 => 32,
+            XmssAlgorithm::Sha2_10_512
+            | XmssAlgorithm::Sha2_16_512
+            | XmssAlgorithm::Sha2_20_512 => 64,
+            _ => 24,
+        }
+    }
+
+    fn public_key_size(&self) -> usize {
+        4 + 2 * self.hash_size()
+    }
+
+    // The SymCrypt private key blob starts with the public key, followed by the index idx of the next one-time key.
+    fn private_key_index_offset(&self) -> usize {
+        self.public_key_size()
+    }
+
+    fn height(&self) -> usize {
+        match self {
+            XmssAlgorithm::Sha2_10_256
+            | XmssAlgorithm::Sha2_10_512
+            | XmssAlgorithm::Sha2_10_192 => 10,
+            XmssAlgorithm::Sha2_16_256
+            | XmssAlgorithm::Sha2_16_512
+            | XmssAlgorithm::Sha2_16_192 => 16,
+            _ => 20,
+        }
+    }
+
+    // Number of one-time keys of the tree.
+    fn capacity(&self) -> u32 {
+        1 << self.height()
+    }
+}
+
+/// [`XmssKey`] is a wrapper around symcrypt_sys::PSYMCRYPT_XMSS_KEY along with the parameters it was allocated with.
+struct XmssKey {
+    inner: symcrypt_sys::PSYMCRYPT_XMSS_KEY,
+    params: Box<symcrypt_sys::SYMCRYPT_XMSS_PARAMS>, // boxing here so that the memory is not moved while the key references it
+    algorithm: XmssAlgorithm,
+}
+
+impl XmssKey {
+    // SymCryptInit must be called before any XMSS operations are performed.
+    fn new(algorithm: XmssAlgorithm) -> Result<Self, SymCryptError> {
+        let mut params = Box::new(symcrypt_sys::SYMCRYPT_XMSS_PARAMS::default());
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_init(); // Will only init once, subsequent calls to symcrypt_init() will be no-ops.
+
+            match symcrypt_sys::SymCryptXmssParamsFromAlgId(
+                algorithm.id() as symcrypt_sys::SYMCRYPT_XMSS_ALGID,
+                &mut *params,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => {
+                    let key_ptr = symcrypt_sys::SymCryptXmsskeyAllocate(&*params, 0);
+                    if key_ptr.is_null() {
+                        return Err(SymCryptError::MemoryAllocationFailure);
+                    }
+                    Ok(XmssKey {
+                        inner: key_ptr,
+                        params,
+                        algorithm,
+                    })
+                }
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    fn set_value(
+        &self,
+        key: &[u8],
+        key_type: symcrypt_sys::SYMCRYPT_XMSSKEY_TYPE,
+    ) -> Result<(), SymCryptError> {
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptXmsskeySetValue(
+                key.as_ptr(),
+                key.len() as symcrypt_sys::SIZE_T,
+                key_type,
+                0,
+                self.inner,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(()),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    fn blob_size(
+        &self,
+        key_type: symcrypt_sys::SYMCRYPT_XMSSKEY_TYPE,
+    ) -> Result<usize, SymCryptError> {
+        let mut key_size: symcrypt_sys::SIZE_T = 0;
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptXmssSizeofKeyBlobFromParams(
+                &*self.params,
+                key_type,
+                &mut key_size,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(key_size as usize),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    fn get_value(
+        &self,
+        key_type: symcrypt_sys::SYMCRYPT_XMSSKEY_TYPE,
+    ) -> Result<Vec<u8>, SymCryptError> {
+        let mut key = vec![0u8; self.blob_size(key_type)?];
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptXmsskeyGetValue(
+                self.inner,
+                key_type,
+                0,
+                key.as_mut_ptr(),
+                key.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(key),
+                err => {
+                    wipe(&mut key);
+                    Err(err.into())
+                }
+            }
+        }
+    }
+
+    fn signature_size(&self) -> Result<usize, SymCryptError> {
+        let mut signature_size: symcrypt_sys::SIZE_T = 0;
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptXmssSizeofSignatureFromParams(
+                &*self.params,
+                &mut signature_size,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(signature_size as usize),
+                err => Err(err.into()),
+            }
+        }
+    }
+}
+
+impl Drop for XmssKey {
+    fn drop(&mut self) {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptXmsskeyFree(self.inner);
+        }
+    }
+}
+
+/// [`XmssPublicKey`] holds a XMSS public key.
+pub struct XmssPublicKey(XmssKey);
+
+/// Impl for [`XmssPublicKey`]
+///
+/// [`from_bytes()`] takes in a public key encoded as defined in RFC 8391 appendix B.3, the parameter set is read from the OID.
+///
+/// [`to_bytes()`] returns the encoded public key.
+///
+/// [`verify()`] takes in a message and a signature. Any failure to verify, including a malformed signature, returns
+/// [`SymCryptError::SignatureVerificationFailure`].
+impl XmssPublicKey {
+    pub fn from_bytes(public_key: &[u8]) -> Result<Self, SymCryptError> {
+        let algorithm = XmssAlgorithm::from_id(read_u32(public_key, 0)?)?;
+        if public_key.len() != algorithm.public_key_size() {
+            return Err(SymCryptError::WrongKeySize);
+        }
+        let key = XmssKey::new(algorithm)?;
+        key.set_value(
+            public_key,
+            symcrypt_sys::_SYMCRYPT_XMSSKEY_TYPE_SYMCRYPT_XMSSKEY_TYPE_PUBLIC,
+        )?;
+        Ok(XmssPublicKey(key))
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SymCryptError> {
+        self.0
+            .get_value(symcrypt_sys::_SYMCRYPT_XMSSKEY_TYPE_SYMCRYPT_XMSSKEY_TYPE_PUBLIC)
+    }
+
+    pub fn algorithm(&self) -> XmssAlgorithm {
+        self.0.algorithm
+    }
+
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), SymCryptError> {
+        unsafe {
+            // SAFETY: FFI calls
+            map_verify_error(symcrypt_sys::SymCryptXmssVerify(
+                self.0.inner,
+                message.as_ptr(),
+                message.len() as symcrypt_sys::SIZE_T,
+                0,
+                signature.as_ptr(),
+                signature.len() as symcrypt_sys::SIZE_T,
+            ))
+        }
+    }
+}
+
+/// [`XmssKeyState`] is the serialized private key of a [`XmssPrivateKey`]. It holds the XMSS OID and the index of the next one-time key
+/// as big-endian u32s, followed by the SymCrypt private key blob.
+///
+/// The state is not Clone, and is wiped when it leaves scope.
+pub struct XmssKeyState {
+    algorithm: XmssAlgorithm,
+    index: u32,
+    bytes: SecretBytes,
+}
+
+/// Impl for [`XmssKeyState`]
+///
+/// [`as_bytes()`] returns the serialized private key to write to persistent storage.
+///
+/// [`from_bytes()`] takes in a serialized private key that was read back from persistent storage. An unknown parameter set, an index past
+/// the last one-time key, an index that differs from the idx stored in the private key and a private key that SymCrypt does not accept
+/// fail with [`SymCryptError::InvalidBlob`], and a private key of the wrong size fails with [`SymCryptError::WrongKeySize`].
+///
+/// [`index()`] returns the index of the next one-time key, and [`remaining()`] returns the number of signatures that can still be made.
+impl XmssKeyState {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, SymCryptError> {
+        let bytes = SecretBytes::from(bytes);
+        let algorithm = XmssAlgorithm::from_id(read_u32(&bytes, 0)?)?;
+        let index = read_u32(&bytes, 4)?;
+        if index > algorithm.capacity() {
+            return Err(SymCryptError::InvalidBlob);
+        }
+        let key = XmssKey::new(algorithm)?;
+        let private_key = &bytes[XMSS_STATE_HEADER_SIZE..];
+        if private_key.len()
+            != key.blob_size(symcrypt_sys::_SYMCRYPT_XMSSKEY_TYPE_SYMCRYPT_XMSSKEY_TYPE_PRIVATE)?
+        {
+            return Err(SymCryptError::WrongKeySize);
+        }
+        if read_u32(private_key, algorithm.private_key_index_offset())? != index {
+            return Err(SymCryptError::InvalidBlob);
+        }
+        key.set_value(
+            private_key,
+            symcrypt_sys::_SYMCRYPT_XMSSKEY_TYPE_SYMCRYPT_XMSSKEY_TYPE_PRIVATE,
+        )
+        .map_err(|_| SymCryptError::InvalidBlob)?;
+        Ok(XmssKeyState {
+            algorithm,
+            index,
+            bytes,
+        })
+    }
+
+    pub fn algorithm(&self) -> XmssAlgorithm {
+        self.algorithm
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn remaining(&self) -> u32 {
+        self.algorithm.capacity() - self.index
+    }
+}
+
+/// [`XmssPrivateKey`] holds a XMSS private key along with the index of its next one-time key.
+pub struct XmssPrivateKey {
+    key: XmssKey,
+    index: u32,
+}
+
+/// Impl for [`XmssPrivateKey`]
+///
+/// [`generate()`] returns a new random [`XmssPrivateKey`] for the provided parameter set. Key generation computes the whole tree, and is
+/// slow for the larger tree heights. Its first [`XmssKeyState`] is passed to `persist`, which must write it to persistent storage, and
+/// the private key is only returned if `persist` succeeds.
+///
+/// [`from_state()`] consumes a [`XmssKeyState`] and returns the [`XmssPrivateKey`] it holds.
+///
+/// [`public_key()`] returns the [`XmssPublicKey`] associated with the private key.
+///
+/// [`index()`] returns the index of the next one-time key, and [`remaining()`] returns the number of signatures that can still be made.
+///
+/// [`sign()`] signs the message with the next one-time key and passes the following [`XmssKeyState`] to `persist`, which must write it
+/// to persistent storage. The signature is only returned if `persist` succeeds, otherwise its error is returned. Signing fails with
+/// [`SymCryptError::InvalidArgument`] once every one-time key has been used, and with [`SymCryptError::InvalidBlob`] if the index of the
+/// state does not match the one-time key that SymCrypt used.
+impl XmssPrivateKey {
+    pub fn generate<F>(algorithm: XmssAlgorithm, persist: F) -> Result<Self, SymCryptError>
+    where
+        F: FnOnce(&XmssKeyState) -> Result<(), SymCryptError>,
+    {
+        let key = XmssKey::new(algorithm)?;
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptXmsskeyGenerate(key.inner, 0) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => (),
+                err => return Err(err.into()),
+            }
+        }
+        let private_key = XmssPrivateKey { key, index: 0 };
+        persist(&private_key.state()?)?;
+        Ok(private_key)
+    }
+
+    pub fn from_state(state: XmssKeyState) -> Result<Self, SymCryptError> {
+        let key = XmssKey::new(state.algorithm)?;
+        key.set_value(
+            &state.bytes[XMSS_STATE_HEADER_SIZE..],
+            symcrypt_sys::_SYMCRYPT_XMSSKEY_TYPE_SYMCRYPT_XMSSKEY_TYPE_PRIVATE,
+        )?;
+        Ok(XmssPrivateKey {
+            key,
+            index: state.index,
+        })
+    }
+
+    fn state(&self) -> Result<XmssKeyState, SymCryptError> {
+        let private_key = SecretBytes::from(
+            self.key
+                .get_value(symcrypt_sys::_SYMCRYPT_XMSSKEY_TYPE_SYMCRYPT_XMSSKEY_TYPE_PRIVATE)?,
+        );
+        let mut bytes = Vec::with_capacity(XMSS_STATE_HEADER_SIZE + private_key.len());
+        bytes.extend_from_slice(&self.key.algorithm.id().to_be_bytes());
+        bytes.extend_from_slice(&self.index.to_be_bytes());
+        bytes.extend_from_slice(&private_key);
+        Ok(XmssKeyState {
+            algorithm: self.key.algorithm,
+            index: self.index,
+            bytes: bytes.into(),
+        })
+    }
+
+    pub fn public_key(&self) -> Result<XmssPublicKey, SymCryptError> {
+        XmssPublicKey::from_bytes(
+            &self
+                .key
+                .get_value(symcrypt_sys::_SYMCRYPT_XMSSKEY_TYPE_SYMCRYPT_XMSSKEY_TYPE_PUBLIC)?,
+        )
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn remaining(&self) -> u32 {
+        self.key.algorithm.capacity() - self.index
+    }
+
+    pub fn sign<F>(&mut self, message: &[u8], persist: F) -> Result<Vec<u8>, SymCryptError>
+    where
+        F: FnOnce(&XmssKeyState) -> Result<(), SymCryptError>,
+    {
+        if self.remaining() == 0 {
+            return Err(SymCryptError::InvalidArgument);
+        }
+        let mut signature = vec![0u8; self.key.signature_size()?];
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptXmssSign(
+                self.key.inner,
+                message.as_ptr(),
+                message.len() as symcrypt_sys::SIZE_T,
+                0,
+                signature.as_mut_ptr(),
+                signature.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => (),
+                err => return Err(err.into()),
+            }
+        }
+        // SymCryptXmssSign has moved the key to the next one-time key, the signature starts with the index that was used.
+        if read_u32(&signature, 0)? != self.index {
+            return Err(SymCryptError::InvalidBlob);
+        }
+        self.index += 1;
+        persist(&self.state()?)?;
+        Ok(signature)
+    }
+}
+
+unsafe impl Send for XmssPublicKey {
+    // The key is only referenced through the SymCrypt allocated pointer, which does not move.
+}
+
+unsafe impl Sync for XmssPublicKey {
+    // Verification only reads the key.
+}
+
+unsafe impl Send for XmssPrivateKey {
+    // The key is only referenced through the SymCrypt allocated pointer, which does not move.
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Public key, message and signature of RFC 8554 appendix F test case 2, signed with one-time key 4 of the top level tree and
+    // one-time key 10 of the bottom level tree.
+    const HSS_PUBLIC_KEY: &str = "000000020000000600000003d08fabd4a2091ff0a8cb4ed834e7453432a58885cd9ba0431235466bff9651c6c92124404d45fa53cf161c28f1ad5a8e";
+    const HSS_MESSAGE: &[u8] = b"The powers not delegated to the United States by the Constitution, nor prohibited by it to the States, are reserved to the States respectively, or to the people.\n";
+    const HSS_SIGNATURE: &str = "0000000100000004000000030703c491e7558b35011ece3592eaa5da4d918786771233e8353bc4f62323185c28505b7cbcd06139dbd637e87c88245c191e4713a3d13299de511a5c1a4df23d19721db3ee604e201d96d36372eac047c1aa5efdf3c9508a64cca2ddb4e35afc6babf33d7888c3b3a2c8bb58bd3649a884e9ac3b1daaa7cd7edb34df08f79759380b1444885c2165e5987bb43dd6a368718f9eaff7af52da8632470b95b9073e793f17761d158b806463205a518dcb4c99b9889980a88f9ab7888aa3fb15040c14492ed03658afbeb09d9f9ed175fa76a1250e45a422484c5498304f40d8c08e309e0ceda64ee99c092d045fde043b6aff63e4eca31e00d8de471d53d9467ac4a56e2d086bf0878679064e9388a54179a9125b55bef80a99396a0fbb3adce66303c5b01586286c0128dfbd2ecad9aaa0d5182f3dd52dbe98e69d7b108b347746417c276978db4389db715c0e0b30cd1c4b2ddc83a8f6ad530fbf8ccd8dd91db11e491b9d7cfcfea81b367a072027c10516d28d70450c8161ce98f141e8da482c91146ad5e824f88593009222c5b55c4547593bbd68b28599875f38fe0d24f5cd47b3052c28643ac3a30d513140e2382988a2e5eb6ea6721e2d7baaf8151fd69bd5b83f9e7c400a58ec9bffbc285f126f9efd81ad7bf4b7f0f0e892b8de2082a1b3db6b30a973e0ee8ab36bd0c817f927c671acc5e4c676b1dd90271310c1d15011af116041a24485424bdf328b1ff2103cd593705d923ae25ca59432b529b9eae4d4b8374801aabc9042dc1652af5611579c495c2e1be68c0963169040e41b47c589226e6c2fa0ce6e9cee7cf0f12924a574a19306544ac9137a1becef9874def0ee90dc9b099d38beccfbbb09323811e91f12749e85af56bbdd6d5e4800e46dfcccdf4786ace7f12b0b21143687b9bdae662f17065a66c1a893dbf364282ea059889570c7e7c50fe0221d1aaeb8731828688c6fdade7ec951fac055c0777c6903af20626ac158a226b1c9e726605109ff255bfa80600e2c52b0d8af5a2b1ae04f1ea246b4f88e78f3d4bae86266858993516d4ed6e0e6ed7056a173ca423fcc705cb8c9486b686ee3a513d070ddd75e03b1f8ec6e4dc7032c2be55c8ce6957ce11b54287ef4a9460829405113c0c62a827a6840d97f87f4f558f28f70673ae1dda78d8db05839be4750a10e971244ce0208d9ddcdcf2c24c1e9e8be48b4f9a15388a54208d3f445292af3e97bd53f6bc985b74cabbd70afa6bc88ed6adebd31f378822203dd28f987f2f9f9ca585165f5b975cf3dcd08b2c74a9e483758bda3d37d77b57ce60d57cf480285d13148145b713678d56f3792228accc9f13217c11dbf511a4db2aed79713bf7bf0587eb0d3b8e840d0f88976f8382d5977f4643fbc10a7b8ba90566c7ad7deac5e9fe19fac7970b1178b9a33441591b0c7431cebd96b860791fff854e1a9319ed1f302646fefee3bcaf20a0a0606a88e6f3c1f8da5cab89e3a0ea7d60ad999cc01b0b675e53dc33fdf4b67f235f4cc210557b99f38879b58d08caa1ebad8651059b87715d7396f8780384759c033e0dfa634cadb7ba3a3fe294b585879300eb705a542595d49820902770bfb2c735a476747969f58cdbcaea05b324d532fb7b89d5d24a0b1ca30963b5d7de728bb0ac9537854b96a68f436dc1ae80fafc3c660afdb268b352db0d42764663bb2b074539ae21abb75d52c1bc178e38fb63c626c7cea499e3b098e2a7510af49d5bac1d6bdbbcf02b1c5dd1dcf79760e78288abbc3573d39f8adba85fd61cef0381a66fb80a4b43116c2555a776dde1de79952bf79b2ec5f22404d3b81d6c6dfc6ee2774954205070133309b56ac8b691fa1cc6e5ad7e3bf71a12de6e7c8298004d9ece82287ca6258dea85b805de68da4b85cfa7860652e63aff8da2379a1ed6c82a515a9ae520ef278d14eac745e410cbf15671421ada0d6f094de849bdfb0af5402c97cf5ce32c5f2107a8dc6e29f4f4f4ae9d9d77802057de80418543b249e4d803dae7d393dd7bb09371e3cc2f6f2c58d085480348fa25b3843cbb970995f134e5e82212f9195c445b975069cf9eede335780d1994242c8c30519232eb122880141180b3ab42971ea1e41f1b8716ff6f3421e57312047dc859ef3f5f94a70271d966d23143fb7b0cb2409fb8a0753cb1716a9eafb8457ab344c1379e79ce85973588ba2905e4d872f31a48e909ed1ec852feb3efb4b11be48865a955d29e9e564669c042a374c86c5a17f9faa2e8201498466c714b85ae095ecccd7d9b8ab93280c6724070bece392fdcc8bbcc42420747c994d9f69b8f88615dbf44b89e5d05c6cab6a5969cc34c25dde5cd3dfec84a52dd0704e535dff41caa75d76981208a531b147312477392ee6daaea839255fa5cd9223eccaae7a1812134db92be5da4d61b86bf55d8940aaf167f99645b527af4763592f6c1a2e01e79ec4597b8bd36afe353635e7439fa5f471d6424ac59d99cd72d6ea6f804491915e113fbc7ec8cfd57161285664c3a7759029c87486192fd53cfe851d4419dd379354f15e5ccdd5c2470f3db0c21c1acb6f2842b34a376a2d1c63277a0343eaafb989abd1a759003138c9f15eb0726c8e6691440a9f1ef1b70c1254ab23539a59669862ba47bcdc5d16551acddf9923345a9dd87b2a55062c6bd04d4d075e7252308a705ff169a068a3367566741b769c7be3716dab405991a2080f11fdbcd5c83b4c06ed2b2ac9835469a66b8ec738226833afa23ac552e52d2dad890c629e96ba00e12a629828c6134aa7a201c5d23451a69ec7c6eb970214454feb755324ea963e8da92ea19708f506e802b1a76ea9b266b1ca66f4799cab4b10d2f8cea8eabca3229704d34adefbfe2f90c1a300e9b7d1efcdf2796e73ecb3f2df83de615a14808ec5e7f313a19def60dc167265ad287a9d506385b90db82c4d46efa9d54976b69e386d39db1e882837b3b95f0814842d774a8959984d42e82edb48a77f8d3539ed6e0995cebe58b466d732ddaeee47bfcd9a150759069327efb193863cc0cb6f25ef2fc54616b1c3c80f000000066f5ad77d8c9815af8a3acbea93ab024e4482cf8466fb212b7eefa5ae81cfdf1fb6b09e79030a9d031991592d1c8b613bf652ff1fe59e857b907ca2239bc251e734f9c294aa7f8c4fc0c247dd8b5c96903ae73abc0035b7fb09ebaec235c416c2686d16621a80816bfdb5bdc56211d72ca70b81f1117d129529a7570cf79cf52a7028a48538ecdd3b38d3d5d62d26246595c4fb73a525a5ed2c30524ebb1d8cc82e0c19bc4977c6898ff95fd3d310b0bae71696cef93c6a552456bf96e9d075e383bb7543c675842bafbfc7cdb88483b3276c29d4f0a341c2d406e40d4653b7e4d045851acf6a0a0ea9c710b805cced4635ee8c107362f0fc8d80c14d0ac49c516703d26d14752f34c1c0d2c4247581c18c2cf4de48e9ce949be7c888e9caebe4a415e291fd107d21dc1f084b1158208249f28f4f7c7e931ba7b3bd0d824a45700000000500000004215f83b7ccb9acbcd08db97b0d04dc2ba1cd035833e0e90059603f26e07ad2aad152338e7a5e5984bcd5f7bb4eba40b70000000a000000040eb1ed54a2460d512388cad533138d240534e97b1e82d33bd927d201dfc24ebbef652dcf1e93aef7f0ed71dbf7b9d317261180cb0ceb740a0495dfd911e26098a411e4d932f6c390a76116200edd0ed4a26847f61a42d679c5c45069734db359ec8c3e44b37cc2dd16fee638ad6729a827470f6dcd71e1d387ae80420d156dc4803cf223d9bfdacc7e507ec16309701eed7ecedac088fc457ad3662515732687243702e2eb885e1ba9313c624d11d2745cba144d5869ad2e7bd9c766fc160f88cac2e7d15a826c2182e8ca1ae0d682da68b8e01c43e669c4b75cdbda4b7e30e12f2a908ec39239371fc0289ac58b2cc9eb055c635819f7cdd9d6876f0bce058c9caa6717e1e5f7662b1a9a0d61974e78f5d5ddb03fc556e43eb2ee0692d772baeb86861cc7e98ac359d645544b32d7daa0d6f51eb43b2798f9b06c442ae759052d119781ed442a35d9cd89852ed366b6a777f550d0ad88de4168429003ed0fe5d4bef7e05aa17ad45b5ced5a5ef83e0dfc589292d9cf732b4a5544cc33a5fe567dd4dc546dae7b2d1bb41299553a350094e525a6aa5e29deee914cd8e8941ee230616fca7e54d0a988e257fe13d3e5e0735cdfe87b066c90ed0433bf46a92e690d51a500f90c4c248c0da620bf248fd5f423765be4786262275f8412357e2e00089108a0339d074fb3751627b206239d83ca23a525360b6f617e549b2ec4144483c91d2934067396ba24d70fe46ed4d6919614130ca3db4b3be0c5ae6d72ebc56545d722741a52566e62d9a4de037803143bb9d3e1acc96c6e338ced9bbeac550d8b6905ed9203145c5ad85fc9e1b2a82808ca62e2018de3cb7f32651dca71a15fda4a024a374d1fe4334fad58df4d200e7522f7d77d106c9a176ab61cc9c8cbb7985d043aab42ec1d1bad89f68eec380f7a0202f2cad453bcf040fe0f280e17c3600a5aa4eb5e21c3f442a2583c72430fb62a968e3e67a07cfb1fd4dc6f60009a6b8fde8a84dbf90830d52700772b3abc1c78cafb26032934b2bf74830b97d0b3d4bb1cadb0380ed46f81f4ac96a50e746979c3ff00c7aa94acea0e9c126b2ef3f34094e10721818efc94e6c64273fe1e1d7c08b3e2098e8326291207e0dc77cd2ee776fdc084620a81b97ede89056567fb259164249d33b4914562be254b5246c71df6a9953cd9c52d996e2edc6c41793b581ff57e2f66be68cd11c78116c6317b577fd443bcb09c0b4ebea47970307502afe69b54df55ff955b5dbc7f058d44850d9c69f55854f2b33af57f11702dd995fc9d2a467c6505679457e2957a61375652399bde5e44ce09bd01d35572f0d0cd959bb2da81dba28ba38a35c5e1dfe1e8ea011af1be5b2a41197c6368509d49d2f5d721a11d749ba1f0fe6c1f5b162b36b37c662df7edf02f407810a5be8d414bbbb474c3108ec3263606dbb6430e76ae72326e9699390045fdaa14f24d93ebfcf6b67bd04027a344a762e8d65623562da6f816126023c3605b819fc7e807b435a0bdb42c61ac3fb2570fce1df32245b473a9bfbd5c9193e22ac9309126818cdfd311d09348c62c1d00aff981ef9f000000055656ce83d1191385d9c28095447048b42bf1ecf1610c1616a2c7583d728ecd893213f003d5b0c8c6e2697bdb4437f93ee198ae981d5ab0a34ae47beddf6e0bbbbcb258c828f34935c26e28890d970519224c703f3734d479660c0bc61decb0d7f5d0b3f687ffc87ed577f3d6b63e22fec28117a07e40d8d4b97da277c0eebbc9e4041d95398a6f7f3e0ee97cc1591849d4ed236338b147abde9f51ef9fd4e1c1";
+
+    // XMSS-SHA2_10_256 key with SK_SEED = 00..1f, SK_PRF = 20..3f and PUB_SEED = 40..5f, signed with the one-time key at index 5. The
+    // WOTS+ private keys are derived with PRF_keygen as in NIST SP 800-208 section 7.2.1, and the signature follows RFC 8391
+    // section 4.1.9, so it can be reproduced from these seeds with any implementation of SP 800-208.
+    const XMSS_PUBLIC_KEY: &str = "000000019d898033e37af48e6a116f8b15651cc26773467007ad19375d38c23c690c3483404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f";
+    const XMSS_MESSAGE: &[u8] = b"firmware image v1.2.3";
+    const XMSS_SIGNATURE: &str = "000000052dc27b1d1eaaecbf1d6538cce0b72c4f0c2eeaed991448d7a550639acbd45452613d62b107e9f75dceebad1ea53b1b0730ddbfcda86d7196c67851ba298599c8a7b96cec03923776cf3702b56af43b54e3198cc6d186a6ad28e3e8d5fad4f02d5b74046d2e51847e554aad6ee19e726a1d17d2abb2b75cf1d4239d46a752789a6327c9218e088467fd9203274b72619399f37c4001c6344cc4d5037c9642ec19069641a6b5b1d30769d39801ae654983166aab2920a2a44487000cd277d2876fc8d98368d58ec194236dad838c0611a391130a35abeb4e86e05212568fe9b091cd0f1e88f8b25ef5ff0d9ee5f9e16ea1d66c7bd8114da4a5a27ae87e48508737ddf1c622121ee61a1cb5c1c2e2b9316f035c8f5c19cdd0e968be70d13f5f6f90422cf7c8be00f259a3a1b74428b33201d4fa16cb28f31ca35b6e9c07d57e016d26a7452baa0d1f33fb75f269dda09619635de33eab5a852714fb5b606160905fdd769fe578a22f5815bd0f892d348609a9f17496a2f2d408dd6b047c454c5a26ce6c2c4c2e436eabdeb89b0691f82a0dc95651b9e237c3e93afc965e3a4f70967dfc58ad93f2620ec503880c77dab5e31bc61fa80a2a9f3ae229d71f851ca3538fa37607c9a4669246739d54305ae6fb47456bf8689a44766dd3979182a4d2e5ccb8eb4d1ad06eef3d9f457a0ad2698d3e7c09f717bdf3dbcee4ef2c720d709dcf7ed7a922c20163f2d707e426e621602e07301f45adc304bf01d47ee0f0e0d991e18e87be42a6c41e26f9361e31b98a149130272a1dd429fa253e0b25c40e113cfa07a618f3afdb2a5f23435e860025499c828ff6989f8079596a052a7af0eb71afd759478fc18a4d662ff4c8c0ea7ba0e775ade9ecea970e8832a185184d15e976c4b3901ad2165dfda5c49c540f02cb110e17836493d530b5be69e64004e7f086a27b7ca5e5ab7858667dce201ded147f88b8f775a900655ac4bb2bf1d762b2b0af2148632b5bdaed1a5421ac96563d087e67df605489109c82c8717410864d38d72fc243d0f954c62108ee6ac0cb2deb44a1608ee8e1a84e4996d7a43aea9878e163d57d2e7cf84b17e32e3d7a438dea5a30bd58a9eb06bb72378ce485570a0773eccac4b83c1ccb5ecb3b5df9044e123955ef08c5e31e8fe28de9b404ea336fd4bfc8278b91c89869d2e6a852a02471a3916a2853cdda148095f3349940a36edf51db156d686e5d5d793034b092df92ddfa4deb259fde23d6649d53a3804f3d88ef11398801e64cfee7f0e92a790e92b9b201fb14a7cd3be11a91c10b5e3edf6536aeb2353651cc3f20cfe4a1f83235e82e6094cb2b470ab9ff5b28479dd187f4a33a3edd2b1580754412efd037011dea447aca463a85674b03cbca7c676a70986d11ded63e7ed566539859adee2b14b3cfda4d5e9d0425e0aa64d1a4c764055dbc09cd6d2a6546af06076af85f13bddc4fbfdc2decc1f8186c0480f90871e94df277718cdcad313a8b382a7c48817fe17b959e72ca42004392665d3bf5b13e898220f7672c6cbdcc90b05b54210940039c70936a5300c490692c6bd25dce7733c4ef3e552335c160281772b179a6db9262dc0f0b11f6914abf64dafb4faf37c6eedc056307989523ec5f8c55060d44c6271821a4f5744d3a9e10df141a78137345fbee17053addf73899eca186a1d17686ad4c5471515b319883c1f17bb26374056fdce6c760b106660022642cb54287ada9acd382808397624af98fe3d43fc974839c0c86c02266ece8a9298a4e3d3bb94085edfd7ec13c5016ca50c9ab737b19b897805ea86d2042d8e15211a482684d6f628ef9e742329f90cd4043db43ffc4ddc53d023b5b51f5bc3bbcfe0e6fdc0d8bf66a889c34a8876db26421997a7a4e002d552e0126eb5de4535bc27822c412e7128f2c0af148412d756d8f3a866f745982a67ea7d516cff6b8d96bdb31d9bdb6915ee06111a5eb1f550f750579aec0ae92e67b0262887aecef14e3e81d00caaf24dad62344d0ffbbdf0e75fc5b36f6a21eaa7af5aa4c2f3467b66c84f35fe9c6fa09608bc66d13d45e85a3ed43585a4963663636f841ab987e6e64311f8a2cb93d022fb556ec1e240dafb6d45d90ea4fb2fe7afe4bff5a599d7428db393a4ad1c66327e742cb32409e3bfadf340c97b0092aed5c94dd0a1450a876443d62f8a9433a939523588e594942f9460a4d5d8bbba0537ffe3f730466bbb60f3ab8120024689d057a222ed87de8be0fc05e9cbc781cabc0cc078b193b4dcf92680b27fef4af06112d97d53e75a89fab1f33f6a03d14ea90a2b6d3f42bf390d8d845109d2aac09e4d8c7c6aa73cf2b6a685e75da0407213c878c90547bb94b3bf5d6dd595bf79d1f78397b3f5a53564677705713986592cae71b173a8d0264758905989b0416f93c37522f332a2cfa67824f621bf6faa10620649db734a2c7cae023153dc07bf618de60612d0f99f9c067074e4de229c725a8b87412d29a87a823e23238769da2d6ee8258c4218ae45dd9824e18c18fafe6807f3e922bd28ae96753b566c11b7e6e272d7934475f313279c6f80008524c472818a5ea0015c7ed742c0c2fc23cea580cb939858de63bc64a11646e179e4d77aa7b2fc2305c85d96fe8452c86006fea47f9008defd5bff81976b5d4c11a59654178596c4c58685911b52b58088eaa10bafe7eddc8026e95c7010c48e372617205c6507937d843591c3fa5c02d18b14df1fa6409fea07fa1b3a1407ebfc84ae3fb8a45227b73ac9d0a8e86b196777fd66f298fa4e4e91a8498aac337c2a7c08d5a7bbb352b9e0deaa6781015867bf0448d581256e6057222d116d9563682f51c29d19f775d7795ccc578695d880f75cdec9d25366bfc7b9a18901c098dd5f2b136d09d0722fe366401b2e3346c08ba65d07ad912cbe64d81e917a15a6d453a89246922a7f361510d34099524e86f72ff28dd6ba59e1f152148237102e2bd4432c13d714536259134755551aa0ebb176bd0e32b75aa1375f47480b6207fef44c45a862b0f792f1b2f27e58300e666d00ef9d3c4931a0808e2a4e8a3cd0527cb5583ab73f89796679c1ac4844880a3c69aec1c655f1f45fa375b4946adfffe5cf3c99d14d14c0acc4ebd51cf12082faa1fe0edfdd56901b808716fd60cef6ff802b665f1efc2e8587f9f2b09117d9a3ec2749dc73a6fd03c31445bb4276d1805b9aee42431509bc7d496734e306d605af485da027c9249f25a6280768c352e9522ae97f2b5d5f608ba67a4429010741f6697c6433eb24d1a1d9f00c012cd5617962e7a32983aa8b5c78915183d2fe3b9d4841aa307dd1fa8f798afba0ad5d11e9bed24dc3cb40a38a00a7b63ad3f3b8abe8830f1920d3065c2c9131eb5963d455c0daed1995401f5b3904372f813f9c433c953c04bc14c05fc2052be72755274cbcaa8a7a52ed59a0a6601806efc2d83986aab2df5327fa2b515570a0f867c2234e3d14706c95766b095cccd9f1a2b8bc42a5b9683d89b71";
+
+    #[test]
+    fn test_hss_verify_rfc8554() {
+        let public_key = HssPublicKey::from_bytes(&hex::decode(HSS_PUBLIC_KEY).unwrap()).unwrap();
+        let signature = hex::decode(HSS_SIGNATURE).unwrap();
+
+        assert_eq!(public_key.levels(), 2);
+        public_key.verify(HSS_MESSAGE, &signature).unwrap();
+    }
+
+    #[test]
+    fn test_hss_verify_failures() {
+        let public_key = HssPublicKey::from_bytes(&hex::decode(HSS_PUBLIC_KEY).unwrap()).unwrap();
+        let signature = hex::decode(HSS_SIGNATURE).unwrap();
+
+        let mut modified_signature = signature.clone();
+        modified_signature[200] ^= 1;
+        let mut wrong_levels = signature.clone();
+        wrong_levels[3] = 2;
+        let truncated = signature[..signature.len() - 1].to_vec();
+
+        assert_eq!(
+            public_key.verify(b"wrong message", &signature).unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+        for signature in [modified_signature, wrong_levels, truncated] {
+            assert_eq!(
+                public_key.verify(HSS_MESSAGE, &signature).unwrap_err(),
+                SymCryptError::SignatureVerificationFailure
+            );
+        }
+    }
+
+    #[test]
+    fn test_lms_verify_rfc8554_bottom_level() {
+        // The bottom level of the HSS signature is a LMS_SHA256_M32_H5 / LMOTS_SHA256_N32_W8 public key and signature.
+        let signature = hex::decode(HSS_SIGNATURE).unwrap();
+        let signed_public_key_end =
+            4 + lms_signature_size(LmsAlgorithm::Sha256M32H10, LmotsAlgorithm::Sha256N32W4);
+        let lms_public_key = &signature[signed_public_key_end..signed_public_key_end + 56];
+        let lms_signature = &signature[signed_public_key_end + 56..];
+
+        let public_key = LmsPublicKey::from_bytes(lms_public_key).unwrap();
+        assert_eq!(
+            public_key.algorithms(),
+            (LmsAlgorithm::Sha256M32H5, LmotsAlgorithm::Sha256N32W8)
+        );
+        assert_eq!(public_key.to_bytes().unwrap(), lms_public_key);
+        public_key.verify(HSS_MESSAGE, lms_signature).unwrap();
+    }
+
+    #[test]
+    fn test_lms_sign_and_verify() {
+        let mut private_key = LmsPrivateKey::generate(
+            LmsAlgorithm::Sha256M32H5,
+            LmotsAlgorithm::Sha256N32W8,
+            |_| Ok(()),
+        )
+        .unwrap();
+        let public_key = private_key.public_key().unwrap();
+        assert_eq!(private_key.index(), 0);
+        assert_eq!(private_key.remaining(), 32);
+
+        // A real signer writes the state to persistent storage in the callback, the signature is only returned once it has been written.
+        let mut persisted = Vec::new();
+        let signature_1 = private_key
+            .sign(b"first message", |state| {
+                persisted = state.as_bytes().to_vec();
+                Ok(())
+            })
+            .unwrap();
+        drop(private_key);
+
+        let state = LmsKeyState::from_bytes(persisted).unwrap();
+        assert_eq!(
+            state.algorithms(),
+            (LmsAlgorithm::Sha256M32H5, LmotsAlgorithm::Sha256N32W8)
+        );
+        assert_eq!(state.index(), 1);
+        assert_eq!(state.remaining(), 31);
+        let mut private_key = LmsPrivateKey::from_state(state).unwrap();
+        let signature_2 = private_key.sign(b"second message", |_| Ok(())).unwrap();
+        assert_eq!(private_key.index(), 2);
+
+        assert_eq!(signature_1.len(), 1292);
+        assert_eq!(read_u32(&signature_1, 0).unwrap(), 0);
+        assert_eq!(read_u32(&signature_2, 0).unwrap(), 1);
+        public_key.verify(b"first message", &signature_1).unwrap();
+        public_key.verify(b"second message", &signature_2).unwrap();
+        assert_eq!(
+            public_key
+                .verify(b"second message", &signature_1)
+                .unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+    }
+
+    #[test]
+    fn test_lms_sign_persist_failure() {
+        let mut private_key = LmsPrivateKey::generate(
+            LmsAlgorithm::Sha256M32H5,
+            LmotsAlgorithm::Sha256N32W8,
+            |_| Ok(()),
+        )
+        .unwrap();
+        assert_eq!(
+            private_key
+                .sign(b"message", |_| Err(SymCryptError::ExternalFailure))
+                .unwrap_err(),
+            SymCryptError::ExternalFailure
+        );
+        // The one-time key was used even though the signature was not released, so it is not used again.
+        assert_eq!(private_key.index(), 1);
+        let signature = private_key.sign(b"message", |_| Ok(())).unwrap();
+        assert_eq!(read_u32(&signature, 0).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_generate_persist_failure() {
+        assert_eq!(
+            LmsPrivateKey::generate(
+                LmsAlgorithm::Sha256M32H5,
+                LmotsAlgorithm::Sha256N32W8,
+                |_| Err(SymCryptError::ExternalFailure)
+            )
+            .err(),
+            Some(SymCryptError::ExternalFailure)
+        );
+        assert_eq!(
+            HssPrivateKey::generate(
+                &[(LmsAlgorithm::Sha256M32H5, LmotsAlgorithm::Sha256N32W8)],
+                |_| Err(SymCryptError::ExternalFailure)
+            )
+            .err(),
+            Some(SymCryptError::ExternalFailure)
+        );
+    }
+
+    #[test]
+    fn test_lms_sign_exhausted() {
+        let mut private_key = LmsPrivateKey::generate(
+            LmsAlgorithm::Sha256M32H5,
+            LmotsAlgorithm::Sha256N32W8,
+            |_| Ok(()),
+        )
+        .unwrap();
+        for _ in 0..32 {
+            private_key.sign(b"message", |_| Ok(())).unwrap();
+        }
+        assert_eq!(private_key.remaining(), 0);
+        assert_eq!(
+            private_key.sign(b"message", |_| Ok(())).unwrap_err(),
+            SymCryptError::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn test_lms_key_state_from_bytes_failures() {
+        let mut state = Vec::new();
+        LmsPrivateKey::generate(
+            LmsAlgorithm::Sha256M32H5,
+            LmotsAlgorithm::Sha256N32W8,
+            |first_state| {
+                state = first_state.as_bytes().to_vec();
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        let mut unknown_type = state.clone();
+        unknown_type[3] = 0xff;
+        let mut index_past_end = state.clone();
+        index_past_end[8..12].copy_from_slice(&33u32.to_be_bytes());
+        for bytes in [unknown_type, index_past_end, state[..10].to_vec()] {
+            assert_eq!(
+                LmsKeyState::from_bytes(bytes).err(),
+                Some(SymCryptError::InvalidBlob)
+            );
+        }
+        assert_eq!(
+            LmsKeyState::from_bytes(state[..state.len() - 1].to_vec()).err(),
+            Some(SymCryptError::WrongKeySize)
+        );
+
+        // A state whose index does not match the q of the SymCrypt private key is rejected when it is loaded.
+        let mut private_key =
+            LmsPrivateKey::from_state(LmsKeyState::from_bytes(state).unwrap()).unwrap();
+        let mut signed_state = Vec::new();
+        private_key
+            .sign(b"message", |state| {
+                signed_state = state.as_bytes().to_vec();
+                Ok(())
+            })
+            .unwrap();
+        let mut stale_index = signed_state.clone();
+        stale_index[8..12].copy_from_slice(&0u32.to_be_bytes());
+        let mut index_ahead = signed_state.clone();
+        index_ahead[8..12].copy_from_slice(&2u32.to_be_bytes());
+        for bytes in [stale_index, index_ahead] {
+            assert_eq!(
+                LmsKeyState::from_bytes(bytes).err(),
+                Some(SymCryptError::InvalidBlob)
+            );
+        }
+        assert_eq!(LmsKeyState::from_bytes(signed_state).unwrap().index(), 1);
+    }
+
+    #[test]
+    fn test_hss_sign_and_verify() {
+        let levels = [
+            (LmsAlgorithm::Sha256M32H5, LmotsAlgorithm::Sha256N32W8),
+            (LmsAlgorithm::Sha256M32H5, LmotsAlgorithm::Sha256N32W8),
+        ];
+        let mut private_key = HssPrivateKey::generate(&levels, |_| Ok(())).unwrap();
+        let public_key = private_key.public_key().unwrap();
+        assert_eq!(public_key.levels(), 2);
+        // One one-time key of the top level signed the bottom level tree.
+        assert_eq!(private_key.remaining(), 32 + 31 * 32);
+
+        let mut persisted = Vec::new();
+        let mut signature = Vec::new();
+        for _ in 0..33 {
+            signature = private_key
+                .sign(HSS_MESSAGE, |state| {
+                    persisted = state.as_bytes().to_vec();
+                    Ok(())
+                })
+                .unwrap();
+        }
+        // The 33rd signature uses a new bottom level tree, signed with the next one-time key of the top level.
+        public_key.verify(HSS_MESSAGE, &signature).unwrap();
+        assert_eq!(read_u32(&signature, 4).unwrap(), 1);
+
+        let state = HssKeyState::from_bytes(persisted).unwrap();
+        assert_eq!(state.levels(), 2);
+        assert_eq!(state.remaining(), 31 + 30 * 32);
+        let mut private_key = HssPrivateKey::from_state(state).unwrap();
+        let signature = private_key.sign(b"another message", |_| Ok(())).unwrap();
+        public_key.verify(b"another message", &signature).unwrap();
+        assert_eq!(private_key.remaining(), 30 + 30 * 32);
+    }
+
+    #[test]
+    fn test_hss_key_state_failures() {
+        assert_eq!(
+            HssPrivateKey::generate(&[], |_| Ok(())).err(),
+            Some(SymCryptError::InvalidArgument)
+        );
+
+        let levels = [
+            (LmsAlgorithm::Sha256M32H5, LmotsAlgorithm::Sha256N32W8),
+            (LmsAlgorithm::Sha256M32H5, LmotsAlgorithm::Sha256N32W8),
+        ];
+        let mut state = Vec::new();
+        HssPrivateKey::generate(&levels, |first_state| {
+            state = first_state.as_bytes().to_vec();
+            Ok(())
+        })
+        .unwrap();
+
+        let mut trailing = state.clone();
+        trailing.push(0);
+        let mut no_levels = state.clone();
+        no_levels[..4].copy_from_slice(&0u32.to_be_bytes());
+        // The index in the header of the top level state, after the level count and the length of the state. The top level has used
+        // one one-time key to sign the bottom level tree.
+        let mut stale_index = state.clone();
+        stale_index[16..20].copy_from_slice(&0u32.to_be_bytes());
+        for bytes in [
+            trailing,
+            no_levels,
+            stale_index,
+            state[..state.len() - 1].to_vec(),
+        ] {
+            assert_eq!(
+                HssKeyState::from_bytes(bytes).err(),
+                Some(SymCryptError::InvalidBlob)
+            );
+        }
+
+        // A signed public key that does not verify with the level above it.
+        let mut modified = state.clone();
+        let last = modified.len() - 1;
+        modified[last] ^= 1;
+        let state = HssKeyState::from_bytes(modified).unwrap();
+        assert_eq!(
+            HssPrivateKey::from_state(state).err(),
+            Some(SymCryptError::InvalidBlob)
+        );
+    }
+
+    #[test]
+    fn test_lms_unknown_public_key_type() {
+        let mut public_key = hex::decode(HSS_PUBLIC_KEY).unwrap()[4..].to_vec();
+        public_key[3] = 0xff;
+
+        let result = LmsPublicKey::from_bytes(&public_key);
+        assert_eq!(result.err().unwrap(), SymCryptError::InvalidBlob);
+    }
+
+    #[test]
+    fn test_xmss_verify() {
+        let public_key = XmssPublicKey::from_bytes(&hex::decode(XMSS_PUBLIC_KEY).unwrap()).unwrap();
+        let signature = hex::decode(XMSS_SIGNATURE).unwrap();
+
+        assert_eq!(public_key.algorithm(), XmssAlgorithm::Sha2_10_256);
+        public_key.verify(XMSS_MESSAGE, &signature).unwrap();
+    }
+
+    #[test]
+    fn test_xmss_verify_failures() {
+        let public_key = XmssPublicKey::from_bytes(&hex::decode(XMSS_PUBLIC_KEY).unwrap()).unwrap();
+        let signature = hex::decode(XMSS_SIGNATURE).unwrap();
+        let mut modified_signature = signature.clone();
+        modified_signature[100] ^= 1;
+
+        assert_eq!(
+            public_key.verify(b"wrong message", &signature).unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+        assert_eq!(
+            public_key
+                .verify(XMSS_MESSAGE, &modified_signature)
+                .unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+    }
+
+    #[test]
+    fn test_xmss_sign_and_verify() {
+        let mut private_key =
+            XmssPrivateKey::generate(XmssAlgorithm::Sha2_10_256, |_| Ok(())).unwrap();
+        let public_key = private_key.public_key().unwrap();
+        assert_eq!(public_key.to_bytes().unwrap().len(), 68);
+        assert_eq!(private_key.remaining(), 1024);
+
+        let mut persisted = Vec::new();
+        let signature_1 = private_key
+            .sign(XMSS_MESSAGE, |state| {
+                persisted = state.as_bytes().to_vec();
+                Ok(())
+            })
+            .unwrap();
+        drop(private_key);
+
+        let state = XmssKeyState::from_bytes(persisted).unwrap();
+        assert_eq!(state.algorithm(), XmssAlgorithm::Sha2_10_256);
+        assert_eq!(state.index(), 1);
+        assert_eq!(state.remaining(), 1023);
+        let mut private_key = XmssPrivateKey::from_state(state).unwrap();
+        let signature_2 = private_key.sign(XMSS_MESSAGE, |_| Ok(())).unwrap();
+
+        assert_eq!(signature_1.len(), 2500);
+        assert_eq!(read_u32(&signature_1, 0).unwrap(), 0);
+        assert_eq!(read_u32(&signature_2, 0).unwrap(), 1);
+        public_key.verify(XMSS_MESSAGE, &signature_1).unwrap();
+        public_key.verify(XMSS_MESSAGE, &signature_2).unwrap();
+
+        assert_eq!(
+            private_key
+                .sign(XMSS_MESSAGE, |_| Err(SymCryptError::ExternalFailure))
+                .unwrap_err(),
+            SymCryptError::ExternalFailure
+        );
+        assert_eq!(private_key.index(), 3);
+    }
+
+    #[test]
+    fn test_xmss_key_state_from_bytes_failures() {
+        let mut state = Vec::new();
+        XmssPrivateKey::generate(XmssAlgorithm::Sha2_10_256, |first_state| {
+            state = first_state.as_bytes().to_vec();
+            Ok(())
+        })
+        .unwrap();
+
+        let mut unknown_oid = state.clone();
+        unknown_oid[3] = 0xff;
+        let mut index_past_end = state.clone();
+        index_past_end[4..8].copy_from_slice(&1025u32.to_be_bytes());
+        for bytes in [unknown_oid, index_past_end, state[..6].to_vec()] {
+            assert_eq!(
+                XmssKeyState::from_bytes(bytes).err(),
+                Some(SymCryptError::InvalidBlob)
+            );
+        }
+        assert_eq!(
+            XmssKeyState::from_bytes(state[..state.len() - 1].to_vec()).err(),
+            Some(SymCryptError::WrongKeySize)
+        );
+
+        // A state whose index does not match the idx of the SymCrypt private key is rejected when it is loaded.
+        let mut private_key =
+            XmssPrivateKey::from_state(XmssKeyState::from_bytes(state).unwrap()).unwrap();
+        let mut signed_state = Vec::new();
+        private_key
+            .sign(b"message", |state| {
+                signed_state = state.as_bytes().to_vec();
+                Ok(())
+            })
+            .unwrap();
+        let mut stale_index = signed_state.clone();
+        stale_index[4..8].copy_from_slice(&0u32.to_be_bytes());
+        assert_eq!(
+            XmssKeyState::from_bytes(stale_index).err(),
+            Some(SymCryptError::InvalidBlob)
+        );
+        assert_eq!(XmssKeyState::from_bytes(signed_state).unwrap().index(), 1);
+    }
+}
@@ -0,0 +1,197 @@
+//! HKDF functions as defined in RFC 5869. For further documentation please refer to symcrypt.h
+
+use crate::errors::SymCryptError;
+use crate::hmac::{convert_mac, HmacAlgorithm};
+use crate::secret::SecretBytes;
+use core::ffi::c_void;
+use std::mem;
+use std::ptr;
+use symcrypt_sys;
+
+/// Wrapping the expanded key so that it is wiped when it leaves scope.
+struct HkdfExpandedKey(symcrypt_sys::SYMCRYPT_HKDF_EXPANDED_KEY);
+
+impl Drop for HkdfExpandedKey {
+    fn drop(&mut self) {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptWipe(
+                ptr::addr_of_mut!(self.0) as *mut c_void,
+                mem::size_of_val(&self.0) as symcrypt_sys::SIZE_T,
+            );
+        }
+    }
+}
+
+/// Stateless HKDF function that runs both the extract and the expand step.
+///
+/// `key_material` is the input keying material.
+/// `salt` is an optional parameter that can be provided, if you do not wish to provide a salt just input an empty array.
+/// `info` is an optional parameter that can be provided, if you do not wish to provide info just input an empty array.
+/// `output_length` is the number of bytes to derive, this can be at most 255 times the [`HmacAlgorithm`] result size.
+///
+/// The derived key is returned as [`SecretBytes`] so it is wiped when it leaves scope, the same applies to [`hkdf_extract()`] and
+/// [`hkdf_expand()`].
+pub fn hkdf(
+    hmac_algorithm: HmacAlgorithm,
+    key_material: &[u8],
+    salt: &[u8],
+    info: &[u8],
+    output_length: usize,
+) -> Result<SecretBytes, SymCryptError> {
+    let mut output = SecretBytes::zeroed(output_length);
+    unsafe {
+        // SAFETY: FFI calls
+        match symcrypt_sys::SymCryptHkdf(
+            convert_mac(hmac_algorithm),
+            key_material.as_ptr(),
+            key_material.len() as symcrypt_sys::SIZE_T,
+            salt.as_ptr(),
+            salt.len() as symcrypt_sys::SIZE_T,
+            info.as_ptr(),
+            info.len() as symcrypt_sys::SIZE_T,
+            output.as_mut_bytes().as_mut_ptr(),
+            output.len() as symcrypt_sys::SIZE_T,
+        ) {
+            symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(output),
+            err => Err(err.into()),
+        }
+    }
+}
+
+/// HKDF-Extract. Returns the pseudorandom key, which is always the size of the [`HmacAlgorithm`] result.
+///
+/// An empty `salt` is treated as a string of zeros the size of the [`HmacAlgorithm`] result, as defined in RFC 5869.
+pub fn hkdf_extract(
+    hmac_algorithm: HmacAlgorithm,
+    key_material: &[u8],
+    salt: &[u8],
+) -> Result<SecretBytes, SymCryptError> {
+    let mut prk = SecretBytes::zeroed(hmac_algorithm.result_size());
+    unsafe {
+        // SAFETY: FFI calls
+        match symcrypt_sys::SymCryptHkdfExtractPrk(
+            convert_mac(hmac_algorithm),
+            key_material.as_ptr(),
+            key_material.len() as symcrypt_sys::SIZE_T,
+            salt.as_ptr(),
+            salt.len() as symcrypt_sys::SIZE_T,
+            prk.as_mut_bytes().as_mut_ptr(),
+            prk.len() as symcrypt_sys::SIZE_T,
+        ) {
+            symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(prk),
+            err => Err(err.into()),
+        }
+    }
+}
+
+/// HKDF-Expand. Takes in a pseudorandom key, usually the result of [`hkdf_extract()`], and derives `output_length` bytes from it.
+pub fn hkdf_expand(
+    hmac_algorithm: HmacAlgorithm,
+    prk: &[u8],
+    info: &[u8],
+    output_length: usize,
+) -> Result<SecretBytes, SymCryptError> {
+    let mut output = SecretBytes::zeroed(output_length);
+    unsafe {
+        // SAFETY: FFI calls
+        let mut expanded_key = HkdfExpandedKey(symcrypt_sys::SYMCRYPT_HKDF_EXPANDED_KEY::default());
+        match symcrypt_sys::SymCryptHkdfPrkExpandKey(
+            &mut expanded_key.0,
+            convert_mac(hmac_algorithm),
+            prk.as_ptr(),
+            prk.len() as symcrypt_sys::SIZE_T,
+        ) {
+            symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => {
+                match symcrypt_sys::SymCryptHkdfDerive(
+                    &expanded_key.0,
+                    info.as_ptr(),
+                    info.len() as symcrypt_sys::SIZE_T,
+                    output.as_mut_bytes().as_mut_ptr(),
+                    output.len() as symcrypt_sys::SIZE_T,
+                ) {
+                    symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(output),
+                    err => Err(err.into()),
+                }
+            }
+            err => Err(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hkdf_sha256_rfc5869() {
+        let key_material = hex::decode("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b").unwrap();
+        let salt = hex::decode("000102030405060708090a0b0c").unwrap();
+        let info = hex::decode("f0f1f2f3f4f5f6f7f8f9").unwrap();
+        let expected =
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865";
+
+        let result = hkdf(HmacAlgorithm::HmacSha256, &key_material, &salt, &info, 42).unwrap();
+        assert_eq!(hex::encode(result), expected);
+    }
+
+    #[test]
+    fn test_hkdf_sha256_extract_then_expand() {
+        let key_material = hex::decode("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b").unwrap();
+        let salt = hex::decode("000102030405060708090a0b0c").unwrap();
+        let info = hex::decode("f0f1f2f3f4f5f6f7f8f9").unwrap();
+        let expected_prk = "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5";
+        let expected =
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865";
+
+        let prk = hkdf_extract(HmacAlgorithm::HmacSha256, &key_material, &salt).unwrap();
+        assert_eq!(hex::encode(&prk), expected_prk);
+
+        let result = hkdf_expand(HmacAlgorithm::HmacSha256, &prk, &info, 42).unwrap();
+        assert_eq!(hex::encode(result), expected);
+    }
+
+    #[test]
+    fn test_hkdf_sha256_no_salt_no_info() {
+        let key_material = hex::decode("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b").unwrap();
+        let expected_prk = "19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04";
+        let expected =
+            "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8";
+
+        let prk = hkdf_extract(HmacAlgorithm::HmacSha256, &key_material, &[]).unwrap();
+        assert_eq!(hex::encode(&prk), expected_prk);
+
+        let result = hkdf(HmacAlgorithm::HmacSha256, &key_material, &[], &[], 42).unwrap();
+        assert_eq!(hex::encode(result), expected);
+    }
+
+    #[test]
+    fn test_hkdf_sha384() {
+        let key_material = hex::decode("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b").unwrap();
+        let salt = hex::decode("000102030405060708090a0b0c").unwrap();
+        let info = hex::decode("f0f1f2f3f4f5f6f7f8f9").unwrap();
+        let expected_prk = "704b39990779ce1dc548052c7dc39f303570dd13fb39f7acc564680bef80e8dec70ee9a7e1f3e293ef68eceb072a5ade";
+        let expected =
+            "9b5097a86038b805309076a44b3a9f38063e25b516dcbf369f394cfab43685f748b6457763e4f0204fc5";
+
+        let prk = hkdf_extract(HmacAlgorithm::HmacSha384, &key_material, &salt).unwrap();
+        assert_eq!(hex::encode(&prk), expected_prk);
+
+        let result = hkdf(HmacAlgorithm::HmacSha384, &key_material, &salt, &info, 42).unwrap();
+        assert_eq!(hex::encode(result), expected);
+    }
+
+    #[test]
+    fn test_hkdf_output_too_long() {
+        let key_material = hex::decode("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b").unwrap();
+
+        let result = hkdf(
+            HmacAlgorithm::HmacSha256,
+            &key_material,
+            &[],
+            &[],
+            255 * 32 + 1,
+        );
+        assert!(result.is_err());
+    }
+}
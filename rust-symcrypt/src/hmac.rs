@@ -1,7 +1,11 @@
 //! Hmac functions. For further documentation please refer to symcrypt.h
+//!
+//! [`HmacSha256State`] and [`HmacSha384State`] implement `std::io::Write`, so they can be fed with `std::io::copy` or through a
+//! [`crate::hash::HashReader`].
 
 use crate::errors::SymCryptError;
 use core::ffi::c_void;
+use std::io::{self, Write};
 use std::mem;
 use std::pin::Pin;
 use std::ptr;
@@ -11,6 +15,32 @@ use symcrypt_sys;
 pub const SHA256_HMAC_RESULT_SIZE: usize = symcrypt_sys::SYMCRYPT_SHA256_RESULT_SIZE as usize;
 pub const SHA384_HMAC_RESULT_SIZE: usize = symcrypt_sys::SYMCRYPT_SHA384_RESULT_SIZE as usize;
 
+/// [`HmacAlgorithm`] provides an enum of the Hmac algorithms that can be passed to SymCrypt functions that are generic over
+/// the underlying MAC, such as HKDF.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum HmacAlgorithm {
+    HmacSha256,
+    HmacSha384,
+}
+
+impl HmacAlgorithm {
+    /// Returns the size of the Hmac result in bytes.
+    pub fn result_size(&self) -> usize {
+        match self {
+            HmacAlgorithm::HmacSha256 => SHA256_HMAC_RESULT_SIZE,
+            HmacAlgorithm::HmacSha384 => SHA384_HMAC_RESULT_SIZE,
+        }
+    }
+}
+
+/// convert_mac takes in the friendly HmacAlgorithm enum and returns the symcrypt equivalent.
+pub(crate) fn convert_mac(hmac: HmacAlgorithm) -> symcrypt_sys::PCSYMCRYPT_MAC {
+    match hmac {
+        HmacAlgorithm::HmacSha256 => unsafe { symcrypt_sys::SymCryptHmacSha256Algorithm }, // SAFETY: FFI calls
+        HmacAlgorithm::HmacSha384 => unsafe { symcrypt_sys::SymCryptHmacSha384Algorithm }, // SAFETY: FFI calls
+    }
+}
+
 /// Generic trait for stateful Hmac functions
 ///
 /// [`append()`] appends data to the HmacShaXXXState, this operation can be done multiple times.
@@ -161,6 +191,18 @@ impl Drop for HmacSha256State {
     }
 }
 
+/// Write appends the written data to the [`HmacSha256State`]. Writes do not fail and always consume the whole buffer.
+impl Write for HmacSha256State {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.append(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 /// Stateless Hmac functions for Sha256, using Result<> here for more friendly rust
 pub fn hmac_sha256(
     key: &[u8],
@@ -325,6 +367,18 @@ impl Drop for HmacSha384State {
     }
 }
 
+/// Write appends the written data to the [`HmacSha384State`]. Writes do not fail and always consume the whole buffer.
+impl Write for HmacSha384State {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.append(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 /// Stateless Hmac functions for Sha384, using Result<> here for more friendly rust
 pub fn hmac_sha384(
     key: &[u8],
@@ -483,4 +537,31 @@ mod test {
         let result = hmac_sha384(&p_key, &data).unwrap();
         assert_eq!(hex::encode(result), expected);
     }
+
+    #[test]
+    fn test_hmac_state_write() {
+        let p_key = hex::decode("0a71d5cf99849bc13d73832dcd864244").unwrap();
+        let data = hex::decode("17f1ee0c6767a1f3f04bb3c1b7a4e0d4f0e59e5963c1a3bf1540a76b25136baef425faf488722e3e331c77d26fbbd8300df532498f50c5ecd243f481f09348f964ddb8056f6e2886bb5b2f453fcf1de5629f3d166324570bf849792d35e3f711b041b1a7e30494b5d1316484ed85b8da37094627a8e66003d079bfd8beaa80dc").unwrap();
+
+        let mut hmac_state = HmacSha256State::new(&p_key).unwrap();
+        io::copy(&mut data.as_slice(), &mut hmac_state).unwrap();
+        assert_eq!(hmac_state.result(), hmac_sha256(&p_key, &data).unwrap());
+
+        let mut hmac_state = HmacSha384State::new(&p_key).unwrap();
+        hmac_state.write_all(&data).unwrap();
+        assert_eq!(hmac_state.result(), hmac_sha384(&p_key, &data).unwrap());
+    }
+
+    #[test]
+    fn test_hmac_hash_reader() {
+        use crate::hash::HashReader;
+
+        let p_key = hex::decode("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b").unwrap();
+        let data = b"data read through a HashReader".to_vec();
+
+        let mut reader = HashReader::new(data.as_slice(), HmacSha256State::new(&p_key).unwrap());
+        io::copy(&mut reader, &mut io::sink()).unwrap();
+        let (_, hmac_state) = reader.into_inner();
+        assert_eq!(hmac_state.result(), hmac_sha256(&p_key, &data).unwrap());
+    }
 }
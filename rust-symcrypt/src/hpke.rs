@@ -0,0 +1,1303 @@
+//! Hybrid Public Key Encryption as defined in RFC 9180, built on the EcDh, HKDF, AES-GCM and ChaCha20-Poly1305 functions of this
+//! crate.
+//!
+//! A [`HpkeSuite`] is a KEM, a KDF and an AEAD. The sender calls [`HpkeSuite::setup_sender()`] with the public key of the recipient and
+//! sends the returned encapsulated key along with the messages sealed by the [`HpkeSenderContext`]. The recipient passes the
+//! encapsulated key to [`HpkeSuite::setup_receiver()`] to get the matching [`HpkeReceiverContext`]. [`HpkeSuite::seal()`] and
+//! [`HpkeSuite::open()`] are the single-shot versions for a single message.
+//!
+//! The four modes of the RFC are selected with [`HpkeSenderMode`] and [`HpkeReceiverMode`]. In the Auth modes the recipient is assured
+//! that the sender held the private key of [`HpkeReceiverMode::Auth::sender_public_key`], in the PSK modes that the sender held the
+//! pre-shared key.
+//!
+//! Both contexts can export secrets with `export()`, as can contexts set up with [`HpkeAead::ExportOnly`], which can not seal or open
+//! messages.
+
+use crate::block_ciphers::BlockCipherType;
+use crate::chacha::{chacha20_poly1305_decrypt_in_place, chacha20_poly1305_encrypt_in_place};
+use crate::ecdh::EcDh;
+use crate::eckey::{CurveType, PointFormat};
+use crate::errors::SymCryptError;
+use crate::gcm::GcmExpandedKey;
+use crate::hkdf::{hkdf_expand, hkdf_extract};
+use crate::hmac::HmacAlgorithm;
+use crate::secret::{SecretArray, SecretBytes};
+
+/// Size of the nonce of every HPKE AEAD.
+pub const HPKE_NONCE_SIZE: usize = 12;
+
+/// Size of the tag of every HPKE AEAD, a sealed message is this many bytes longer than the plaintext.
+pub const HPKE_TAG_SIZE: usize = 16;
+
+const HPKE_VERSION_LABEL: &[u8] = b"HPKE-v1";
+
+// Group orders, used to reject out of range candidates in DeriveKeyPair.
+const NIST_P256_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xbc, 0xe6, 0xfa, 0xad, 0xa7, 0x17, 0x9e, 0x84, 0xf3, 0xb9, 0xca, 0xc2, 0xfc, 0x63, 0x25, 0x51,
+];
+const NIST_P384_ORDER: [u8; 48] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xc7, 0x63, 0x4d, 0x81, 0xf4, 0x37, 0x2d, 0xdf,
+    0x58, 0x1a, 0x0d, 0xb2, 0x48, 0xb0, 0xa7, 0x7a, 0xec, 0xec, 0x19, 0x6a, 0xcc, 0xc5, 0x29, 0x73,
+];
+
+/// [`HpkeKem`] provides an enum of the supported KEMs. The value of each variant is its identifier in the RFC 9180 registry.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum HpkeKem {
+    DhKemP256HkdfSha256 = 0x0010,
+    DhKemP384HkdfSha384 = 0x0011,
+    DhKemX25519HkdfSha256 = 0x0020,
+}
+
+impl HpkeKem {
+    /// Returns the size in bytes of a public key and of an encapsulated key.
+    pub fn public_key_size(&self) -> usize {
+        match self {
+            HpkeKem::DhKemP256HkdfSha256 => 65,
+            HpkeKem::DhKemP384HkdfSha384 => 97,
+            HpkeKem::DhKemX25519HkdfSha256 => 32,
+        }
+    }
+
+    /// Returns the size in bytes of a private key.
+    pub fn private_key_size(&self) -> usize {
+        match self {
+            HpkeKem::DhKemP256HkdfSha256 | HpkeKem::DhKemX25519HkdfSha256 => 32,
+            HpkeKem::DhKemP384HkdfSha384 => 48,
+        }
+    }
+
+    fn curve(&self) -> CurveType {
+        match self {
+            HpkeKem::DhKemP256HkdfSha256 => CurveType::NistP256,
+            HpkeKem::DhKemP384HkdfSha384 => CurveType::NistP384,
+            HpkeKem::DhKemX25519HkdfSha256 => CurveType::Curve25519,
+        }
+    }
+
+    // SerializePublicKey is the uncompressed SEC 1 encoding for the NIST curves and the RFC 7748 encoding for X25519.
+    fn point_format(&self) -> PointFormat {
+        match self {
+            HpkeKem::DhKemX25519HkdfSha256 => PointFormat::X,
+            _ => PointFormat::Sec1Uncompressed,
+        }
+    }
+
+    fn hmac_algorithm(&self) -> HmacAlgorithm {
+        match self {
+            HpkeKem::DhKemP256HkdfSha256 | HpkeKem::DhKemX25519HkdfSha256 => {
+                HmacAlgorithm::HmacSha256
+            }
+            HpkeKem::DhKemP384HkdfSha384 => HmacAlgorithm::HmacSha384,
+        }
+    }
+
+    fn suite_id(&self) -> [u8; 5] {
+        let id = (*self as u16).to_be_bytes();
+        [b'K', b'E', b'M', id[0], id[1]]
+    }
+}
+
+/// [`HpkeKdf`] provides an enum of the supported KDFs. The value of each variant is its identifier in the RFC 9180 registry.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum HpkeKdf {
+    HkdfSha256 = 0x0001,
+    HkdfSha384 = 0x0002,
+}
+
+impl HpkeKdf {
+    fn hmac_algorithm(&self) -> HmacAlgorithm {
+        match self {
+            HpkeKdf::HkdfSha256 => HmacAlgorithm::HmacSha256,
+            HpkeKdf::HkdfSha384 => HmacAlgorithm::HmacSha384,
+        }
+    }
+}
+
+/// [`HpkeAead`] provides an enum of the supported AEADs. The value of each variant is its identifier in the RFC 9180 registry.
+///
+/// [`HpkeAead::ExportOnly`] sets up contexts that can only export secrets.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum HpkeAead {
+    Aes128Gcm = 0x0001,
+    Aes256Gcm = 0x0002,
+    ChaCha20Poly1305 = 0x0003,
+    ExportOnly = 0xffff,
+}
+
+impl HpkeAead {
+    /// Returns the size of the key in bytes, which is 0 for [`HpkeAead::ExportOnly`].
+    pub fn key_size(&self) -> usize {
+        match self {
+            HpkeAead::Aes128Gcm => 16,
+            HpkeAead::Aes256Gcm | HpkeAead::ChaCha20Poly1305 => 32,
+            HpkeAead::ExportOnly => 0,
+        }
+    }
+}
+
+/// [`HpkeKeyPair`] holds a KEM key pair, or only the public key.
+pub struct HpkeKeyPair {
+    kem: HpkeKem,
+    key: EcDh,
+}
+
+/// Impl for [`HpkeKeyPair`]
+///
+/// [`generate()`] returns a new random key pair for `kem`.
+///
+/// [`derive()`] is DeriveKeyPair from RFC 9180 section 7.1.3, it deterministically derives a key pair from the input keying material
+/// `ikm`, which should hold at least [`HpkeKem::private_key_size()`] bytes of entropy.
+///
+/// [`from_private_key_bytes()`] and [`from_public_key_bytes()`] import a key in the encoding of RFC 9180 section 7.1.1.
+/// X25519 private keys are clamped as defined in RFC 7748 by [`EcKey`](crate::eckey::EcKey) when imported.
+///
+/// [`public_key_bytes()`] and [`private_key_bytes()`] export the key in the same encoding. [`private_key_bytes()`] fails with
+/// InvalidArgument if only the public key is held.
+impl HpkeKeyPair {
+    pub fn generate(kem: HpkeKem) -> Result<Self, SymCryptError> {
+        Ok(HpkeKeyPair {
+            kem,
+            key: EcDh::new(kem.curve())?,
+        })
+    }
+
+    pub fn derive(kem: HpkeKem, ikm: &[u8]) -> Result<Self, SymCryptError> {
+        let hmac = kem.hmac_algorithm();
+        let suite_id = kem.suite_id();
+        let dkp_prk = labeled_extract(hmac, &suite_id, &[], b"dkp_prk", ikm)?;
+        let size = kem.private_key_size() as u16;
+        match kem {
+            HpkeKem::DhKemX25519HkdfSha256 => {
+                let sk = labeled_expand(hmac, &suite_id, &dkp_prk, b"sk", &[], size)?;
+                HpkeKeyPair::from_private_key_bytes(kem, &sk)
+            }
+            HpkeKem::DhKemP256HkdfSha256 | HpkeKem::DhKemP384HkdfSha384 => {
+                let order: &[u8] = match kem {
+                    HpkeKem::DhKemP256HkdfSha256 => &NIST_P256_ORDER,
+                    _ => &NIST_P384_ORDER,
+                };
+                for counter in 0..=255u8 {
+                    let sk =
+                        labeled_expand(hmac, &suite_id, &dkp_prk, b"candidate", &[counter], size)?;
+                    // The bitmask is 0xff for P-256 and P-384, so the candidate is used as is. Both are big-endian so the byte
+                    // comparison is the integer comparison.
+                    if sk.iter().any(|&byte| byte != 0) && sk.as_bytes() < order {
+                        return HpkeKeyPair::from_private_key_bytes(kem, &sk);
+                    }
+                }
+                Err(SymCryptError::InvalidArgument)
+            }
+        }
+    }
+
+    pub fn from_private_key_bytes(kem: HpkeKem, private_key: &[u8]) -> Result<Self, SymCryptError> {
+        if private_key.len() != kem.private_key_size() {
+            return Err(SymCryptError::WrongKeySize);
+        }
+        Ok(HpkeKeyPair {
+            kem,
+            key: EcDh::from_private_key_bytes(kem.curve(), private_key)?,
+        })
+    }
+
+    pub fn from_public_key_bytes(kem: HpkeKem, public_key: &[u8]) -> Result<Self, SymCryptError> {
+        if public_key.len() != kem.public_key_size() {
+            return Err(SymCryptError::WrongDataSize);
+        }
+        Ok(HpkeKeyPair {
+            kem,
+            key: EcDh::from_public_key_bytes_with_format(
+                kem.curve(),
+                public_key,
+                kem.point_format(),
+            )?,
+        })
+    }
+
+    pub fn public_key_bytes(&self) -> Result<Vec<u8>, SymCryptError> {
+        self.key
+            .get_public_key_bytes_with_format(self.kem.point_format())
+    }
+
+    pub fn private_key_bytes(&self) -> Result<SecretBytes, SymCryptError> {
+        self.key.private_key_bytes()
+    }
+
+    pub fn kem(&self) -> HpkeKem {
+        self.kem
+    }
+}
+
+/// [`HpkeSenderMode`] selects the mode of [`HpkeSuite::setup_sender()`] and [`HpkeSuite::seal()`].
+///
+/// [`HpkeSenderMode::Auth::sender_key`] must hold a private key. In the PSK modes `psk` and `psk_id` must not be empty, RFC 9180
+/// section 9.5 recommends that `psk` holds at least 32 bytes of entropy.
+#[derive(Copy, Clone)]
+pub enum HpkeSenderMode<'a> {
+    Base,
+    Psk {
+        psk: &'a [u8],
+        psk_id: &'a [u8],
+    },
+    Auth {
+        sender_key: &'a HpkeKeyPair,
+    },
+    AuthPsk {
+        sender_key: &'a HpkeKeyPair,
+        psk: &'a [u8],
+        psk_id: &'a [u8],
+    },
+}
+
+/// [`HpkeReceiverMode`] selects the mode of [`HpkeSuite::setup_receiver()`] and [`HpkeSuite::open()`]. It must match the
+/// [`HpkeSenderMode`] used by the sender.
+///
+/// [`HpkeReceiverMode::Auth::sender_public_key`] is the serialized public key of the sender.
+#[derive(Copy, Clone)]
+pub enum HpkeReceiverMode<'a> {
+    Base,
+    Psk {
+        psk: &'a [u8],
+        psk_id: &'a [u8],
+    },
+    Auth {
+        sender_public_key: &'a [u8],
+    },
+    AuthPsk {
+        sender_public_key: &'a [u8],
+        psk: &'a [u8],
+        psk_id: &'a [u8],
+    },
+}
+
+// Mode identifiers from RFC 9180 section 5.
+const MODE_BASE: u8 = 0x00;
+const MODE_PSK: u8 = 0x01;
+const MODE_AUTH: u8 = 0x02;
+const MODE_AUTH_PSK: u8 = 0x03;
+
+/// [`HpkeSuite`] holds the KEM, KDF and AEAD of a HPKE cipher suite.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct HpkeSuite {
+    kem: HpkeKem,
+    kdf: HpkeKdf,
+    aead: HpkeAead,
+}
+
+/// Impl for [`HpkeSuite`]
+///
+/// [`new()`] returns the suite for `kem`, `kdf` and `aead`.
+///
+/// [`setup_sender()`] encapsulates a fresh shared secret to `recipient_public_key`, and returns the encapsulated key to send to the
+/// recipient along with the [`HpkeSenderContext`].
+///
+/// [`setup_receiver()`] decapsulates `enc` with `recipient_key`, and returns the [`HpkeReceiverContext`]. It fails with
+/// InvalidArgument if `recipient_key` is not a `kem` key pair with a private key.
+///
+/// [`seal()`] is the single-shot version of [`setup_sender()`] followed by [`HpkeSenderContext::seal()`], it returns the encapsulated
+/// key and the ciphertext. [`open()`] is the single-shot version of [`setup_receiver()`] followed by [`HpkeReceiverContext::open()`].
+///
+/// `info` is bound to the key schedule by both sides, it is usually a protocol specific label.
+impl HpkeSuite {
+    pub fn new(kem: HpkeKem, kdf: HpkeKdf, aead: HpkeAead) -> Self {
+        HpkeSuite { kem, kdf, aead }
+    }
+
+    pub fn kem(&self) -> HpkeKem {
+        self.kem
+    }
+
+    pub fn kdf(&self) -> HpkeKdf {
+        self.kdf
+    }
+
+    pub fn aead(&self) -> HpkeAead {
+        self.aead
+    }
+
+    pub fn setup_sender(
+        &self,
+        recipient_public_key: &[u8],
+        info: &[u8],
+        mode: HpkeSenderMode,
+    ) -> Result<(Vec<u8>, HpkeSenderContext), SymCryptError> {
+        let ephemeral_key = HpkeKeyPair::generate(self.kem)?;
+        self.setup_sender_with_ephemeral_key(recipient_public_key, info, mode, &ephemeral_key)
+    }
+
+    pub fn setup_receiver(
+        &self,
+        enc: &[u8],
+        recipient_key: &HpkeKeyPair,
+        info: &[u8],
+        mode: HpkeReceiverMode,
+    ) -> Result<HpkeReceiverContext, SymCryptError> {
+        self.check_private_key(recipient_key)?;
+        let enc_key = HpkeKeyPair::from_public_key_bytes(self.kem, enc)?;
+        let recipient_public_key = recipient_key.public_key_bytes()?;
+
+        let (sender_public_key, psk, psk_id, mode_id) = match mode {
+            HpkeReceiverMode::Base => (None, &[][..], &[][..], MODE_BASE),
+            HpkeReceiverMode::Psk { psk, psk_id } => (None, psk, psk_id, MODE_PSK),
+            HpkeReceiverMode::Auth { sender_public_key } => {
+                (Some(sender_public_key), &[][..], &[][..], MODE_AUTH)
+            }
+            HpkeReceiverMode::AuthPsk {
+                sender_public_key,
+                psk,
+                psk_id,
+            } => (Some(sender_public_key), psk, psk_id, MODE_AUTH_PSK),
+        };
+
+        // Decap and AuthDecap from RFC 9180 section 4.1.
+        let mut dh_secret = dh(&recipient_key.key, &enc_key.key, self.kem)?
+            .as_bytes()
+            .to_vec();
+        let mut kem_context = [enc, &recipient_public_key].concat();
+        if let Some(sender_public_key) = sender_public_key {
+            let sender_key = HpkeKeyPair::from_public_key_bytes(self.kem, sender_public_key)?;
+            dh_secret.extend_from_slice(&dh(&recipient_key.key, &sender_key.key, self.kem)?);
+            kem_context.extend_from_slice(sender_public_key);
+        }
+        let shared_secret = self.extract_and_expand(SecretBytes::from(dh_secret), &kem_context)?;
+
+        let context = self.key_schedule(mode_id, &shared_secret, info, psk, psk_id)?;
+        Ok(HpkeReceiverContext(context))
+    }
+
+    pub fn seal(
+        &self,
+        recipient_public_key: &[u8],
+        info: &[u8],
+        aad: &[u8],
+        plaintext: &[u8],
+        mode: HpkeSenderMode,
+    ) -> Result<(Vec<u8>, Vec<u8>), SymCryptError> {
+        let (enc, mut context) = self.setup_sender(recipient_public_key, info, mode)?;
+        let ciphertext = context.seal(aad, plaintext)?;
+        Ok((enc, ciphertext))
+    }
+
+    pub fn open(
+        &self,
+        enc: &[u8],
+        recipient_key: &HpkeKeyPair,
+        info: &[u8],
+        aad: &[u8],
+        ciphertext: &[u8],
+        mode: HpkeReceiverMode,
+    ) -> Result<Vec<u8>, SymCryptError> {
+        let mut context = self.setup_receiver(enc, recipient_key, info, mode)?;
+        context.open(aad, ciphertext)
+    }
+
+    // Encap and AuthEncap from RFC 9180 section 4.1 with a provided ephemeral key, followed by the key schedule. Only the tests use a
+    // fixed ephemeral key, to check against the RFC 9180 test vectors.
+    fn setup_sender_with_ephemeral_key(
+        &self,
+        recipient_public_key: &[u8],
+        info: &[u8],
+        mode: HpkeSenderMode,
+        ephemeral_key: &HpkeKeyPair,
+    ) -> Result<(Vec<u8>, HpkeSenderContext), SymCryptError> {
+        let recipient_key = HpkeKeyPair::from_public_key_bytes(self.kem, recipient_public_key)?;
+
+        let (sender_key, psk, psk_id, mode_id) = match mode {
+            HpkeSenderMode::Base => (None, &[][..], &[][..], MODE_BASE),
+            HpkeSenderMode::Psk { psk, psk_id } => (None, psk, psk_id, MODE_PSK),
+            HpkeSenderMode::Auth { sender_key } => (Some(sender_key), &[][..], &[][..], MODE_AUTH),
+            HpkeSenderMode::AuthPsk {
+                sender_key,
+                psk,
+                psk_id,
+            } => (Some(sender_key), psk, psk_id, MODE_AUTH_PSK),
+        };
+
+        let enc = ephemeral_key.public_key_bytes()?;
+        let mut dh_secret = dh(&ephemeral_key.key, &recipient_key.key, self.kem)?
+            .as_bytes()
+            .to_vec();
+        let mut kem_context = [&enc[..], recipient_public_key].concat();
+        if let Some(sender_key) = sender_key {
+            self.check_private_key(sender_key)?;
+            dh_secret.extend_from_slice(&dh(&sender_key.key, &recipient_key.key, self.kem)?);
+            kem_context.extend_from_slice(&sender_key.public_key_bytes()?);
+        }
+        let shared_secret = self.extract_and_expand(SecretBytes::from(dh_secret), &kem_context)?;
+
+        let context = self.key_schedule(mode_id, &shared_secret, info, psk, psk_id)?;
+        Ok((enc, HpkeSenderContext(context)))
+    }
+
+    fn check_private_key(&self, key: &HpkeKeyPair) -> Result<(), SymCryptError> {
+        if key.kem != self.kem || !key.key.ec_key().has_private_key() {
+            return Err(SymCryptError::InvalidArgument);
+        }
+        Ok(())
+    }
+
+    fn suite_id(&self) -> [u8; 10] {
+        let kem = (self.kem as u16).to_be_bytes();
+        let kdf = (self.kdf as u16).to_be_bytes();
+        let aead = (self.aead as u16).to_be_bytes();
+        [
+            b'H', b'P', b'K', b'E', kem[0], kem[1], kdf[0], kdf[1], aead[0], aead[1],
+        ]
+    }
+
+    // ExtractAndExpand from RFC 9180 section 4.1, which uses the KDF of the KEM rather than the KDF of the suite.
+    fn extract_and_expand(
+        &self,
+        dh: SecretBytes,
+        kem_context: &[u8],
+    ) -> Result<SecretBytes, SymCryptError> {
+        let hmac = self.kem.hmac_algorithm();
+        let suite_id = self.kem.suite_id();
+        let eae_prk = labeled_extract(hmac, &suite_id, &[], b"eae_prk", &dh)?;
+        labeled_expand(
+            hmac,
+            &suite_id,
+            &eae_prk,
+            b"shared_secret",
+            kem_context,
+            hmac.result_size() as u16,
+        )
+    }
+
+    // KeySchedule from RFC 9180 section 5.1, including VerifyPSKInputs.
+    fn key_schedule(
+        &self,
+        mode: u8,
+        shared_secret: &[u8],
+        info: &[u8],
+        psk: &[u8],
+        psk_id: &[u8],
+    ) -> Result<HpkeContext, SymCryptError> {
+        let psk_mode = mode == MODE_PSK || mode == MODE_AUTH_PSK;
+        if psk_mode && (psk.is_empty() || psk_id.is_empty()) {
+            return Err(SymCryptError::InvalidArgument);
+        }
+
+        let hmac = self.kdf.hmac_algorithm();
+        let suite_id = self.suite_id();
+        let psk_id_hash = labeled_extract(hmac, &suite_id, &[], b"psk_id_hash", psk_id)?;
+        let info_hash = labeled_extract(hmac, &suite_id, &[], b"info_hash", info)?;
+        let key_schedule_context = [&[mode][..], &psk_id_hash, &info_hash].concat();
+
+        let secret = labeled_extract(hmac, &suite_id, shared_secret, b"secret", psk)?;
+        let cipher = match self.aead {
+            HpkeAead::ExportOnly => None,
+            aead => {
+                let key = labeled_expand(
+                    hmac,
+                    &suite_id,
+                    &secret,
+                    b"key",
+                    &key_schedule_context,
+                    aead.key_size() as u16,
+                )?;
+                let base_nonce = labeled_expand(
+                    hmac,
+                    &suite_id,
+                    &secret,
+                    b"base_nonce",
+                    &key_schedule_context,
+                    HPKE_NONCE_SIZE as u16,
+                )?;
+                let mut nonce = SecretArray::<HPKE_NONCE_SIZE>::zeroed();
+                nonce.as_mut_bytes().copy_from_slice(&base_nonce);
+                Some((ContextCipher::new(aead, &key)?, nonce))
+            }
+        };
+        let exporter_secret = labeled_expand(
+            hmac,
+            &suite_id,
+            &secret,
+            b"exp",
+            &key_schedule_context,
+            hmac.result_size() as u16,
+        )?;
+
+        Ok(HpkeContext {
+            suite_id,
+            hmac,
+            cipher,
+            seq: 0,
+            exporter_secret,
+        })
+    }
+}
+
+/// LabeledExtract from RFC 9180 section 4.
+fn labeled_extract(
+    hmac: HmacAlgorithm,
+    suite_id: &[u8],
+    salt: &[u8],
+    label: &[u8],
+    ikm: &[u8],
+) -> Result<SecretBytes, SymCryptError> {
+    let labeled_ikm = SecretBytes::from([HPKE_VERSION_LABEL, suite_id, label, ikm].concat());
+    hkdf_extract(hmac, &labeled_ikm, salt)
+}
+
+/// LabeledExpand from RFC 9180 section 4.
+fn labeled_expand(
+    hmac: HmacAlgorithm,
+    suite_id: &[u8],
+    prk: &[u8],
+    label: &[u8],
+    info: &[u8],
+    length: u16,
+) -> Result<SecretBytes, SymCryptError> {
+    let labeled_info = [
+        &length.to_be_bytes()[..],
+        HPKE_VERSION_LABEL,
+        suite_id,
+        label,
+        info,
+    ]
+    .concat();
+    hkdf_expand(hmac, prk, &labeled_info, length as usize)
+}
+
+/// dh runs the EcDh secret agreement. RFC 9180 section 7.1.4 requires an all zero X25519 shared secret to be rejected.
+fn dh(private: &EcDh, public: &EcDh, kem: HpkeKem) -> Result<SecretBytes, SymCryptError> {
+    let secret = EcDh::ecdh_secret_agreement(private, public)?;
+    if kem == HpkeKem::DhKemX25519HkdfSha256 && secret.iter().all(|&byte| byte == 0) {
+        return Err(SymCryptError::InvalidArgument);
+    }
+    Ok(secret)
+}
+
+/// [`ContextCipher`] holds the AEAD key of a context.
+enum ContextCipher {
+    Gcm(GcmExpandedKey),
+    ChaCha20Poly1305(SecretArray<32>),
+}
+
+impl ContextCipher {
+    fn new(aead: HpkeAead, key: &[u8]) -> Result<Self, SymCryptError> {
+        match aead {
+            HpkeAead::ChaCha20Poly1305 => {
+                let mut chacha_key = SecretArray::<32>::zeroed();
+                chacha_key.as_mut_bytes().copy_from_slice(key);
+                Ok(ContextCipher::ChaCha20Poly1305(chacha_key))
+            }
+            _ => Ok(ContextCipher::Gcm(GcmExpandedKey::new(
+                key,
+                BlockCipherType::AesBlock,
+            )?)),
+        }
+    }
+}
+
+/// [`HpkeContext`] is the encryption context of RFC 9180 section 5.2, shared by [`HpkeSenderContext`] and [`HpkeReceiverContext`].
+/// `cipher` holds the AEAD key and the base nonce, it is None for [`HpkeAead::ExportOnly`].
+struct HpkeContext {
+    suite_id: [u8; 10],
+    hmac: HmacAlgorithm,
+    cipher: Option<(ContextCipher, SecretArray<HPKE_NONCE_SIZE>)>,
+    seq: u64,
+    exporter_secret: SecretBytes,
+}
+
+impl HpkeContext {
+    // ComputeNonce from RFC 9180 section 5.2. The sequence number is 8 bytes here, so it is XORed into the last 8 bytes of the nonce.
+    fn nonce(
+        &self,
+        base_nonce: &[u8; HPKE_NONCE_SIZE],
+    ) -> Result<[u8; HPKE_NONCE_SIZE], SymCryptError> {
+        if self.seq == u64::MAX {
+            return Err(SymCryptError::ValueTooLarge);
+        }
+        let mut nonce = *base_nonce;
+        for (byte, seq) in nonce[HPKE_NONCE_SIZE - 8..]
+            .iter_mut()
+            .zip(self.seq.to_be_bytes())
+        {
+            *byte ^= seq;
+        }
+        Ok(nonce)
+    }
+
+    fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+        let (cipher, base_nonce) = self.cipher.as_ref().ok_or(SymCryptError::InvalidArgument)?;
+        let nonce = self.nonce(base_nonce)?;
+        let mut buffer = plaintext.to_vec();
+        let mut tag = [0u8; HPKE_TAG_SIZE];
+        match cipher {
+            ContextCipher::Gcm(key) => key.encrypt_in_place(&nonce, aad, &mut buffer, &mut tag),
+            ContextCipher::ChaCha20Poly1305(key) => chacha20_poly1305_encrypt_in_place(
+                key.as_bytes(),
+                &nonce,
+                aad,
+                &mut buffer,
+                &mut tag,
+            )?,
+        }
+        buffer.extend_from_slice(&tag);
+        self.seq += 1;
+        Ok(buffer)
+    }
+
+    fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+        let (cipher, base_nonce) = self.cipher.as_ref().ok_or(SymCryptError::InvalidArgument)?;
+        if ciphertext.len() < HPKE_TAG_SIZE {
+            return Err(SymCryptError::WrongDataSize);
+        }
+        let nonce = self.nonce(base_nonce)?;
+        let (ciphertext, tag) = ciphertext.split_at(ciphertext.len() - HPKE_TAG_SIZE);
+        let tag: &[u8; HPKE_TAG_SIZE] = tag.try_into().unwrap(); // tag is HPKE_TAG_SIZE bytes per the split above
+        let mut buffer = ciphertext.to_vec();
+        match cipher {
+            ContextCipher::Gcm(key) => key.decrypt_in_place(&nonce, aad, &mut buffer, tag),
+            ContextCipher::ChaCha20Poly1305(key) => {
+                chacha20_poly1305_decrypt_in_place(key.as_bytes(), &nonce, aad, &mut buffer, tag)
+            }
+        }?;
+        // The sequence number is only incremented on success, so a forged message does not desynchronize the context.
+        self.seq += 1;
+        Ok(buffer)
+    }
+
+    fn export(&self, exporter_context: &[u8], length: usize) -> Result<SecretBytes, SymCryptError> {
+        let length = u16::try_from(length).map_err(|_| SymCryptError::InvalidArgument)?;
+        labeled_expand(
+            self.hmac,
+            &self.suite_id,
+            &self.exporter_secret,
+            b"sec",
+            exporter_context,
+            length,
+        )
+    }
+}
+
+/// [`HpkeSenderContext`] is the sender side of a HPKE context, returned by [`HpkeSuite::setup_sender()`].
+pub struct HpkeSenderContext(HpkeContext);
+
+/// Impl for [`HpkeSenderContext`]
+///
+/// [`seal()`] encrypts `plaintext` with `aad` as the associated data, and returns the ciphertext followed by the [`HPKE_TAG_SIZE`]
+/// byte tag. Messages must be opened by the recipient in the order they were sealed.
+///
+/// [`export()`] derives `length` bytes of secret from `exporter_context`, the recipient derives the same secret. `length` can be at
+/// most 255 times the hash size of the KDF.
+///
+/// [`seal()`] fails with InvalidArgument for [`HpkeAead::ExportOnly`] contexts.
+impl HpkeSenderContext {
+    pub fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+        self.0.seal(aad, plaintext)
+    }
+
+    pub fn export(
+        &self,
+        exporter_context: &[u8],
+        length: usize,
+    ) -> Result<SecretBytes, SymCryptError> {
+        self.0.export(exporter_context, length)
+    }
+}
+
+/// [`HpkeReceiverContext`] is the recipient side of a HPKE context, returned by [`HpkeSuite::setup_receiver()`].
+pub struct HpkeReceiverContext(HpkeContext);
+
+/// Impl for [`HpkeReceiverContext`]
+///
+/// [`open()`] decrypts the output of [`HpkeSenderContext::seal()`] and returns the plaintext. It fails with AuthenticationFailure if the
+/// ciphertext or `aad` were modified, or if the message is not the next one the sender sealed.
+///
+/// [`export()`] is [`HpkeSenderContext::export()`] for the recipient.
+///
+/// [`open()`] fails with InvalidArgument for [`HpkeAead::ExportOnly`] contexts.
+impl HpkeReceiverContext {
+    pub fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+        self.0.open(aad, ciphertext)
+    }
+
+    pub fn export(
+        &self,
+        exporter_context: &[u8],
+        length: usize,
+    ) -> Result<SecretBytes, SymCryptError> {
+        self.0.export(exporter_context, length)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INFO: &str = "4f6465206f6e2061204772656369616e2055726e";
+    const PSK: &str = "0247fd33b913760fa1fa51e1892d9f307fbe65eb171e8132c2af18555a738b82";
+    const PSK_ID: &str = "456e6e796e20447572696e206172616e204d6f726961";
+    const PLAINTEXT: &[u8] = b"Beauty is truth, truth beauty";
+
+    struct TestVector {
+        suite: HpkeSuite,
+        ikm_e: &'static str,
+        ikm_r: &'static str,
+        ikm_s: Option<&'static str>,
+        psk: bool,
+        pk_r: &'static str,
+        enc: &'static str,
+        // Ciphertexts of PLAINTEXT for sequence numbers 0 and 1, with "Count-0" and "Count-1" as the associated data.
+        ciphertexts: [&'static str; 2],
+        // Exported secrets of 32 bytes for the exporter contexts "", "00" and "TestContext".
+        exports: [&'static str; 3],
+    }
+
+    const EXPORTER_CONTEXTS: [&str; 3] = ["", "00", "54657374436f6e74657874"];
+
+    fn check_vector(vector: &TestVector) {
+        let suite = vector.suite;
+        let ephemeral_key =
+            HpkeKeyPair::derive(suite.kem(), &hex::decode(vector.ikm_e).unwrap()).unwrap();
+        let recipient_key =
+            HpkeKeyPair::derive(suite.kem(), &hex::decode(vector.ikm_r).unwrap()).unwrap();
+        let sender_key = vector
+            .ikm_s
+            .map(|ikm| HpkeKeyPair::derive(suite.kem(), &hex::decode(ikm).unwrap()).unwrap());
+        let sender_public_key = sender_key
+            .as_ref()
+            .map(|key| key.public_key_bytes().unwrap());
+        let psk = hex::decode(PSK).unwrap();
+        let psk_id = hex::decode(PSK_ID).unwrap();
+        let info = hex::decode(INFO).unwrap();
+
+        let pk_r = recipient_key.public_key_bytes().unwrap();
+        assert_eq!(hex::encode(&pk_r), vector.pk_r);
+
+        let sender_mode = match (&sender_key, vector.psk) {
+            (None, false) => HpkeSenderMode::Base,
+            (None, true) => HpkeSenderMode::Psk {
+                psk: &psk,
+                psk_id: &psk_id,
+            },
+            (Some(sender_key), false) => HpkeSenderMode::Auth { sender_key },
+            (Some(sender_key), true) => HpkeSenderMode::AuthPsk {
+                sender_key,
+                psk: &psk,
+                psk_id: &psk_id,
+            },
+        };
+        let receiver_mode = match (&sender_public_key, vector.psk) {
+            (None, false) => HpkeReceiverMode::Base,
+            (None, true) => HpkeReceiverMode::Psk {
+                psk: &psk,
+                psk_id: &psk_id,
+            },
+            (Some(sender_public_key), false) => HpkeReceiverMode::Auth { sender_public_key },
+            (Some(sender_public_key), true) => HpkeReceiverMode::AuthPsk {
+                sender_public_key,
+                psk: &psk,
+                psk_id: &psk_id,
+            },
+        };
+
+        let (enc, mut sender) = suite
+            .setup_sender_with_ephemeral_key(&pk_r, &info, sender_mode, &ephemeral_key)
+            .unwrap();
+        assert_eq!(hex::encode(&enc), vector.enc);
+        let mut receiver = suite
+            .setup_receiver(&enc, &recipient_key, &info, receiver_mode)
+            .unwrap();
+
+        for (seq, expected) in vector.ciphertexts.iter().enumerate() {
+            let aad = format!("Count-{}", seq);
+            let ciphertext = sender.seal(aad.as_bytes(), PLAINTEXT).unwrap();
+            assert_eq!(hex::encode(&ciphertext), *expected);
+            let plaintext = receiver.open(aad.as_bytes(), &ciphertext).unwrap();
+            assert_eq!(plaintext, PLAINTEXT);
+        }
+
+        for (exporter_context, expected) in EXPORTER_CONTEXTS.iter().zip(vector.exports) {
+            let exporter_context = hex::decode(exporter_context).unwrap();
+            let sender_secret = sender.export(&exporter_context, 32).unwrap();
+            let receiver_secret = receiver.export(&exporter_context, 32).unwrap();
+            assert_eq!(hex::encode(sender_secret.as_bytes()), expected);
+            assert_eq!(sender_secret, receiver_secret);
+        }
+    }
+
+    #[test]
+    fn test_hpke_rfc9180_a_1_1_base() {
+        check_vector(&TestVector {
+            suite: HpkeSuite::new(
+                HpkeKem::DhKemX25519HkdfSha256,
+                HpkeKdf::HkdfSha256,
+                HpkeAead::Aes128Gcm,
+            ),
+            ikm_e: "7268600d403fce431561aef583ee1613527cff655c1343f29812e66706df3234",
+            ikm_r: "6db9df30aa07dd42ee5e8181afdb977e538f5e1fec8a06223f33f7013e525037",
+            ikm_s: None,
+            psk: false,
+            pk_r: "3948cfe0ad1ddb695d780e59077195da6c56506b027329794ab02bca80815c4d",
+            enc: "37fda3567bdbd628e88668c3c8d7e97d1d1253b6d4ea6d44c150f741f1bf4431",
+            ciphertexts: [
+                "f938558b5d72f1a23810b4be2ab4f84331acc02fc97babc53a52ae8218a355a96d8770ac83d07bea87e13c512a",
+                "af2d7e9ac9ae7e270f46ba1f975be53c09f8d875bdc8535458c2494e8a6eab251c03d0c22a56b8ca42c2063b84",
+            ],
+            exports: [
+                "3853fe2b4035195a573ffc53856e77058e15d9ea064de3e59f4961d0095250ee",
+                "2e8f0b54673c7029649d4eb9d5e33bf1872cf76d623ff164ac185da9e88c21a5",
+                "e9e43065102c3836401bed8c3c3c75ae46be1639869391d62c61f1ec7af54931",
+            ],
+        });
+    }
+
+    #[test]
+    fn test_hpke_rfc9180_a_1_2_psk() {
+        check_vector(&TestVector {
+            suite: HpkeSuite::new(
+                HpkeKem::DhKemX25519HkdfSha256,
+                HpkeKdf::HkdfSha256,
+                HpkeAead::Aes128Gcm,
+            ),
+            ikm_e: "78628c354e46f3e169bd231be7b2ff1c77aa302460a26dbfa15515684c00130b",
+            ikm_r: "d4a09d09f575fef425905d2ab396c1449141463f698f8efdb7accfaff8995098",
+            ikm_s: None,
+            psk: true,
+            pk_r: "9fed7e8c17387560e92cc6462a68049657246a09bfa8ade7aefe589672016366",
+            enc: "0ad0950d9fb9588e59690b74f1237ecdf1d775cd60be2eca57af5a4b0471c91b",
+            ciphertexts: [
+                "e52c6fed7f758d0cf7145689f21bc1be6ec9ea097fef4e959440012f4feb73fb611b946199e681f4cfc34db8ea",
+                "49f3b19b28a9ea9f43e8c71204c00d4a490ee7f61387b6719db765e948123b45b61633ef059ba22cd62437c8ba",
+            ],
+            exports: [
+                "dff17af354c8b41673567db6259fd6029967b4e1aad13023c2ae5df8f4f43bf6",
+                "6a847261d8207fe596befb52928463881ab493da345b10e1dcc645e3b94e2d95",
+                "8aff52b45a1be3a734bc7a41e20b4e055ad4c4d22104b0c20285a7c4302401cd",
+            ],
+        });
+    }
+
+    #[test]
+    fn test_hpke_rfc9180_a_1_3_auth() {
+        check_vector(&TestVector {
+            suite: HpkeSuite::new(
+                HpkeKem::DhKemX25519HkdfSha256,
+                HpkeKdf::HkdfSha256,
+                HpkeAead::Aes128Gcm,
+            ),
+            ikm_e: "6e6d8f200ea2fb20c30b003a8b4f433d2f4ed4c2658d5bc8ce2fef718059c9f7",
+            ikm_r: "f1d4a30a4cef8d6d4e3b016e6fd3799ea057db4f345472ed302a67ce1c20cdec",
+            ikm_s: Some("94b020ce91d73fca4649006c7e7329a67b40c55e9e93cc907d282bbbff386f58"),
+            psk: false,
+            pk_r: "1632d5c2f71c2b38d0a8fcc359355200caa8b1ffdf28618080466c909cb69b2e",
+            enc: "23fb952571a14a25e3d678140cd0e5eb47a0961bb18afcf85896e5453c312e76",
+            ciphertexts: [
+                "5fd92cc9d46dbf8943e72a07e42f363ed5f721212cd90bcfd072bfd9f44e06b80fd17824947496e21b680c141b",
+                "d3736bb256c19bfa93d79e8f80b7971262cb7c887e35c26370cfed62254369a1b52e3d505b79dd699f002bc8ed",
+            ],
+            exports: [
+                "28c70088017d70c896a8420f04702c5a321d9cbf0279fba899b59e51bac72c85",
+                "25dfc004b0892be1888c3914977aa9c9bbaf2c7471708a49e1195af48a6f29ce",
+                "5a0131813abc9a522cad678eb6bafaabc43389934adb8097d23c5ff68059eb64",
+            ],
+        });
+    }
+
+    #[test]
+    fn test_hpke_rfc9180_a_1_4_auth_psk() {
+        check_vector(&TestVector {
+            suite: HpkeSuite::new(
+                HpkeKem::DhKemX25519HkdfSha256,
+                HpkeKdf::HkdfSha256,
+                HpkeAead::Aes128Gcm,
+            ),
+            ikm_e: "4303619085a20ebcf18edd22782952b8a7161e1dbae6e46e143a52a96127cf84",
+            ikm_r: "4b16221f3b269a88e207270b5e1de28cb01f847841b344b8314d6a622fe5ee90",
+            ikm_s: Some("62f77dcf5df0dd7eac54eac9f654f426d4161ec850cc65c54f8b65d2e0b4e345"),
+            psk: true,
+            pk_r: "1d11a3cd247ae48e901939659bd4d79b6b959e1f3e7d66663fbc9412dd4e0976",
+            enc: "820818d3c23993492cc5623ab437a48a0a7ca3e9639c140fe1e33811eb844b7c",
+            ciphertexts: [
+                "a84c64df1e11d8fd11450039d4fe64ff0c8a99fca0bd72c2d4c3e0400bc14a40f27e45e141a24001697737533e",
+                "4d19303b848f424fc3c3beca249b2c6de0a34083b8e909b6aa4c3688505c05ffe0c8f57a0a4c5ab9da127435d9",
+            ],
+            exports: [
+                "08f7e20644bb9b8af54ad66d2067457c5f9fcb2a23d9f6cb4445c0797b330067",
+                "52e51ff7d436557ced5265ff8b94ce69cf7583f49cdb374e6aad801fc063b010",
+                "a30c20370c026bbea4dca51cb63761695132d342bae33a6a11527d3e7679436d",
+            ],
+        });
+    }
+
+    #[test]
+    fn test_hpke_rfc9180_a_2_1_chacha20_poly1305() {
+        check_vector(&TestVector {
+            suite: HpkeSuite::new(
+                HpkeKem::DhKemX25519HkdfSha256,
+                HpkeKdf::HkdfSha256,
+                HpkeAead::ChaCha20Poly1305,
+            ),
+            ikm_e: "909a9b35d3dc4713a5e72a4da274b55d3d3821a37e5d099e74a647db583a904b",
+            ikm_r: "1ac01f181fdf9f352797655161c58b75c656a6cc2716dcb66372da835542e1df",
+            ikm_s: None,
+            psk: false,
+            pk_r: "4310ee97d88cc1f088a5576c77ab0cf5c3ac797f3d95139c6c84b5429c59662a",
+            enc: "1afa08d3dec047a643885163f1180476fa7ddb54c6a8029ea33f95796bf2ac4a",
+            ciphertexts: [
+                "1c5250d8034ec2b784ba2cfd69dbdb8af406cfe3ff938e131f0def8c8b60b4db21993c62ce81883d2dd1b51a28",
+                "6b53c051e4199c518de79594e1c4ab18b96f081549d45ce015be002090bb119e85285337cc95ba5f59992dc98c",
+            ],
+            exports: [
+                "4bbd6243b8bb54cec311fac9df81841b6fd61f56538a775e7c80a9f40160606e",
+                "8c1df14732580e5501b00f82b10a1647b40713191b7c1240ac80e2b68808ba69",
+                "5acb09211139c43b3090489a9da433e8a30ee7188ba8b0a9a1ccf0c229283e53",
+            ],
+        });
+    }
+
+    #[test]
+    fn test_hpke_rfc9180_a_3_1_p256() {
+        check_vector(&TestVector {
+            suite: HpkeSuite::new(
+                HpkeKem::DhKemP256HkdfSha256,
+                HpkeKdf::HkdfSha256,
+                HpkeAead::Aes128Gcm,
+            ),
+            ikm_e: "4270e54ffd08d79d5928020af4686d8f6b7d35dbe470265f1f5aa22816ce860e",
+            ikm_r: "668b37171f1072f3cf12ea8a236a45df23fc13b82af3609ad1e354f6ef817550",
+            ikm_s: None,
+            psk: false,
+            pk_r: "04fe8c19ce0905191ebc298a9245792531f26f0cece2460639e8bc39cb7f706a826a779b4cf969b8a0e539c7f62fb3d30ad6aa8f80e30f1d128aafd68a2ce72ea0",
+            enc: "04a92719c6195d5085104f469a8b9814d5838ff72b60501e2c4466e5e67b325ac98536d7b61a1af4b78e5b7f951c0900be863c403ce65c9bfcb9382657222d18c4",
+            ciphertexts: [
+                "5ad590bb8baa577f8619db35a36311226a896e7342a6d836d8b7bcd2f20b6c7f9076ac232e3ab2523f39513434",
+                "fa6f037b47fc21826b610172ca9637e82d6e5801eb31cbd3748271affd4ecb06646e0329cbdf3c3cd655b28e82",
+            ],
+            exports: [
+                "5e9bc3d236e1911d95e65b576a8a86d478fb827e8bdfe77b741b289890490d4d",
+                "6cff87658931bda83dc857e6353efe4987a201b849658d9b047aab4cf216e796",
+                "d8f1ea7942adbba7412c6d431c62d01371ea476b823eb697e1f6e6cae1dab85a",
+            ],
+        });
+    }
+
+    // RFC 9180 and the CFRG HPKE test vectors have no P-384 suite. This message was encrypted to the RFC 6979 appendix A.2.6 P-384 key
+    // with hpke.Suite(KEM.P384, KDF.HKDF_SHA384, AEAD.AES_256_GCM).encrypt(PLAINTEXT, public_key, INFO) of pyca/cryptography 48, which
+    // is a Base mode seal with an empty aad, and returns enc followed by the ciphertext.
+    const P384_PRIVATE_KEY: &str = "6b9d3dad2e1b8c1c05b19875b6659f4de23c3b667bf297ba9aa47740787137d896d5724e4c70a825f872c9ea60d2edf5";
+    const P384_ENC: &str = "049c60ef7e0fc644b60ddff6a35a4fac7198788dea712ba43131de45b9d1e321cbedc37920807b30d1d38dd7631d24f163646bfb3b461580cf086ef15ea1763d570c9b3ff2eea28f7c28909718fe6c9c3205b937df8dfe87c69ca5d918e86f59e8";
+    const P384_CIPHERTEXT: &str = "ad15b8d0f9e1b4690f1f2ce72721cb5c2acc20976a06c8d3e553e5e959a13b000eeda5544fdf2900112fb4fcd6";
+
+    #[test]
+    fn test_hpke_p384_aes256gcm() {
+        let suite = HpkeSuite::new(
+            HpkeKem::DhKemP384HkdfSha384,
+            HpkeKdf::HkdfSha384,
+            HpkeAead::Aes256Gcm,
+        );
+        let recipient_key = HpkeKeyPair::from_private_key_bytes(
+            HpkeKem::DhKemP384HkdfSha384,
+            &hex::decode(P384_PRIVATE_KEY).unwrap(),
+        )
+        .unwrap();
+
+        let plaintext = suite
+            .open(
+                &hex::decode(P384_ENC).unwrap(),
+                &recipient_key,
+                &hex::decode(INFO).unwrap(),
+                b"",
+                &hex::decode(P384_CIPHERTEXT).unwrap(),
+                HpkeReceiverMode::Base,
+            )
+            .unwrap();
+        assert_eq!(plaintext, PLAINTEXT);
+    }
+
+    // RFC 9180 section A.7.1, which only lists skEm and not ikmE, so the ephemeral key is imported from skEm.
+    #[test]
+    fn test_hpke_rfc9180_a_7_1_export_only() {
+        let suite = HpkeSuite::new(
+            HpkeKem::DhKemX25519HkdfSha256,
+            HpkeKdf::HkdfSha256,
+            HpkeAead::ExportOnly,
+        );
+        let ephemeral_key = HpkeKeyPair::from_private_key_bytes(
+            suite.kem(),
+            &hex::decode("095182b502f1f91f63ba584c7c3ec473d617b8b4c2cec3fad5af7fa6748165ed")
+                .unwrap(),
+        )
+        .unwrap();
+        let recipient_key = HpkeKeyPair::derive(
+            suite.kem(),
+            &hex::decode("683ae0da1d22181e74ed2e503ebf82840deb1d5e872cade20f4b458d99783e31")
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(recipient_key.private_key_bytes().unwrap().as_bytes()),
+            "33d196c830a12f9ac65d6e565a590d80f04ee9b19c83c87f2c170d972a812848"
+        );
+        let pk_r = recipient_key.public_key_bytes().unwrap();
+        assert_eq!(
+            hex::encode(&pk_r),
+            "194141ca6c3c3beb4792cd97ba0ea1faff09d98435012345766ee33aae2d7664"
+        );
+
+        let info = hex::decode(INFO).unwrap();
+        let (enc, sender) = suite
+            .setup_sender_with_ephemeral_key(&pk_r, &info, HpkeSenderMode::Base, &ephemeral_key)
+            .unwrap();
+        assert_eq!(
+            hex::encode(&enc),
+            "e5e8f9bfff6c2f29791fc351d2c25ce1299aa5eaca78a757c0b4fb4bcd830918"
+        );
+        let receiver = suite
+            .setup_receiver(&enc, &recipient_key, &info, HpkeReceiverMode::Base)
+            .unwrap();
+
+        let exports = [
+            "7a36221bd56d50fb51ee65edfd98d06a23c4dc87085aa5866cb7087244bd2a36",
+            "d5535b87099c6c3ce80dc112a2671c6ec8e811a2f284f948cec6dd1708ee33f0",
+            "ffaabc85a776136ca0c378e5d084c9140ab552b78f039d2e8775f26efff4c70e",
+        ];
+        for (exporter_context, expected) in EXPORTER_CONTEXTS.iter().zip(exports) {
+            let exporter_context = hex::decode(exporter_context).unwrap();
+            let sender_secret = sender.export(&exporter_context, 32).unwrap();
+            let receiver_secret = receiver.export(&exporter_context, 32).unwrap();
+            assert_eq!(hex::encode(sender_secret.as_bytes()), expected);
+            assert_eq!(sender_secret, receiver_secret);
+        }
+    }
+
+    #[test]
+    fn test_hpke_export_only_can_not_seal() {
+        let suite = HpkeSuite::new(
+            HpkeKem::DhKemX25519HkdfSha256,
+            HpkeKdf::HkdfSha256,
+            HpkeAead::ExportOnly,
+        );
+        let recipient_key = HpkeKeyPair::generate(suite.kem()).unwrap();
+        let (enc, mut sender) = suite
+            .setup_sender(
+                &recipient_key.public_key_bytes().unwrap(),
+                b"info",
+                HpkeSenderMode::Base,
+            )
+            .unwrap();
+        assert_eq!(
+            sender.seal(b"", b"message").unwrap_err(),
+            SymCryptError::InvalidArgument
+        );
+        let mut receiver = suite
+            .setup_receiver(&enc, &recipient_key, b"info", HpkeReceiverMode::Base)
+            .unwrap();
+        assert_eq!(
+            receiver.open(b"", &[0u8; HPKE_TAG_SIZE]).unwrap_err(),
+            SymCryptError::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn test_hpke_single_shot() {
+        for kem in [
+            HpkeKem::DhKemP256HkdfSha256,
+            HpkeKem::DhKemP384HkdfSha384,
+            HpkeKem::DhKemX25519HkdfSha256,
+        ] {
+            for aead in [
+                HpkeAead::Aes128Gcm,
+                HpkeAead::Aes256Gcm,
+                HpkeAead::ChaCha20Poly1305,
+            ] {
+                let suite = HpkeSuite::new(kem, HpkeKdf::HkdfSha256, aead);
+                let recipient_key = HpkeKeyPair::generate(kem).unwrap();
+                let public_key = recipient_key.public_key_bytes().unwrap();
+                assert_eq!(public_key.len(), kem.public_key_size());
+
+                let (enc, ciphertext) = suite
+                    .seal(
+                        &public_key,
+                        b"info",
+                        b"aad",
+                        PLAINTEXT,
+                        HpkeSenderMode::Base,
+                    )
+                    .unwrap();
+                assert_eq!(enc.len(), kem.public_key_size());
+                assert_eq!(ciphertext.len(), PLAINTEXT.len() + HPKE_TAG_SIZE);
+                let plaintext = suite
+                    .open(
+                        &enc,
+                        &recipient_key,
+                        b"info",
+                        b"aad",
+                        &ciphertext,
+                        HpkeReceiverMode::Base,
+                    )
+                    .unwrap();
+                assert_eq!(plaintext, PLAINTEXT);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hpke_open_failures() {
+        let suite = HpkeSuite::new(
+            HpkeKem::DhKemX25519HkdfSha256,
+            HpkeKdf::HkdfSha256,
+            HpkeAead::Aes128Gcm,
+        );
+        let recipient_key = HpkeKeyPair::generate(suite.kem()).unwrap();
+        let public_key = recipient_key.public_key_bytes().unwrap();
+        let (enc, mut sender) = suite
+            .setup_sender(&public_key, b"info", HpkeSenderMode::Base)
+            .unwrap();
+        let first = sender.seal(b"aad", b"first").unwrap();
+        let second = sender.seal(b"aad", b"second").unwrap();
+
+        // A different info gives a different key.
+        let mut receiver = suite
+            .setup_receiver(&enc, &recipient_key, b"other", HpkeReceiverMode::Base)
+            .unwrap();
+        assert_eq!(
+            receiver.open(b"aad", &first).unwrap_err(),
+            SymCryptError::AuthenticationFailure
+        );
+
+        let mut receiver = suite
+            .setup_receiver(&enc, &recipient_key, b"info", HpkeReceiverMode::Base)
+            .unwrap();
+        // Out of order, and modified associated data.
+        assert!(receiver.open(b"aad", &second).is_err());
+        assert!(receiver.open(b"other", &first).is_err());
+        assert_eq!(
+            receiver
+                .open(b"aad", &first[..HPKE_TAG_SIZE - 1])
+                .unwrap_err(),
+            SymCryptError::WrongDataSize
+        );
+        // Failures do not move the sequence number forward.
+        assert_eq!(receiver.open(b"aad", &first).unwrap(), b"first");
+        assert_eq!(receiver.open(b"aad", &second).unwrap(), b"second");
+    }
+
+    #[test]
+    fn test_hpke_auth_wrong_sender() {
+        let suite = HpkeSuite::new(
+            HpkeKem::DhKemP256HkdfSha256,
+            HpkeKdf::HkdfSha256,
+            HpkeAead::Aes128Gcm,
+        );
+        let recipient_key = HpkeKeyPair::generate(suite.kem()).unwrap();
+        let sender_key = HpkeKeyPair::generate(suite.kem()).unwrap();
+        let other_key = HpkeKeyPair::generate(suite.kem()).unwrap();
+        let (enc, ciphertext) = suite
+            .seal(
+                &recipient_key.public_key_bytes().unwrap(),
+                b"info",
+                b"aad",
+                PLAINTEXT,
+                HpkeSenderMode::Auth {
+                    sender_key: &sender_key,
+                },
+            )
+            .unwrap();
+
+        let open = |sender_public_key: &[u8]| {
+            suite.open(
+                &enc,
+                &recipient_key,
+                b"info",
+                b"aad",
+                &ciphertext,
+                HpkeReceiverMode::Auth { sender_public_key },
+            )
+        };
+        assert_eq!(
+            open(&sender_key.public_key_bytes().unwrap()).unwrap(),
+            PLAINTEXT
+        );
+        assert!(open(&other_key.public_key_bytes().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_hpke_invalid_inputs() {
+        let suite = HpkeSuite::new(
+            HpkeKem::DhKemX25519HkdfSha256,
+            HpkeKdf::HkdfSha256,
+            HpkeAead::Aes128Gcm,
+        );
+        let recipient_key = HpkeKeyPair::generate(suite.kem()).unwrap();
+        let public_key = recipient_key.public_key_bytes().unwrap();
+
+        // PSK modes need both a psk and a psk_id.
+        assert_eq!(
+            suite
+                .setup_sender(
+                    &public_key,
+                    b"info",
+                    HpkeSenderMode::Psk {
+                        psk: &[1u8; 32],
+                        psk_id: &[],
+                    },
+                )
+                .err(),
+            Some(SymCryptError::InvalidArgument)
+        );
+
+        // The recipient key must be a private key of the suite KEM.
+        let public_only = HpkeKeyPair::from_public_key_bytes(suite.kem(), &public_key).unwrap();
+        let (enc, _) = suite
+            .setup_sender(&public_key, b"info", HpkeSenderMode::Base)
+            .unwrap();
+        assert_eq!(
+            suite
+                .setup_receiver(&enc, &public_only, b"info", HpkeReceiverMode::Base)
+                .err(),
+            Some(SymCryptError::InvalidArgument)
+        );
+        let p256_key = HpkeKeyPair::generate(HpkeKem::DhKemP256HkdfSha256).unwrap();
+        assert_eq!(
+            suite
+                .setup_receiver(&enc, &p256_key, b"info", HpkeReceiverMode::Base)
+                .err(),
+            Some(SymCryptError::InvalidArgument)
+        );
+
+        // The all zero X25519 public key is a small order point, it gives an all zero shared secret.
+        assert!(suite
+            .setup_sender(&[0u8; 32], b"info", HpkeSenderMode::Base)
+            .is_err());
+        assert_eq!(
+            suite
+                .setup_sender(&public_key[..31], b"info", HpkeSenderMode::Base)
+                .err(),
+            Some(SymCryptError::WrongDataSize)
+        );
+    }
+
+    #[test]
+    fn test_hpke_key_pair_round_trip() {
+        // skRm of RFC 9180 A.3.1.
+        let private_key =
+            hex::decode("f3ce7fdae57e1a310d87f1ebbde6f328be0a99cdbcadf4d6589cf29de4b8ffd2")
+                .unwrap();
+        let derived = HpkeKeyPair::derive(
+            HpkeKem::DhKemP256HkdfSha256,
+            &hex::decode("668b37171f1072f3cf12ea8a236a45df23fc13b82af3609ad1e354f6ef817550")
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(derived.private_key_bytes().unwrap().as_bytes(), private_key);
+
+        let imported =
+            HpkeKeyPair::from_private_key_bytes(HpkeKem::DhKemP256HkdfSha256, &private_key)
+                .unwrap();
+        assert_eq!(
+            imported.public_key_bytes().unwrap(),
+            derived.public_key_bytes().unwrap()
+        );
+        assert_eq!(
+            HpkeKeyPair::from_private_key_bytes(HpkeKem::DhKemP384HkdfSha384, &private_key).err(),
+            Some(SymCryptError::WrongKeySize)
+        );
+    }
+}
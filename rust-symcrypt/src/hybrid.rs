@@ -0,0 +1,228 @@
+//! Hybrid post-quantum key exchange. X25519MLKEM768 is the TLS named group defined in draft-ietf-tls-ecdhe-mlkem.
+//!
+//! The client share is the ML-KEM-768 encapsulation key followed by the X25519 public key, the server share is the ML-KEM-768 ciphertext
+//! followed by the X25519 public key, and the shared secret is the ML-KEM-768 shared secret followed by the X25519 shared secret.
+
+use crate::ecdh::EcDh;
+use crate::eckey::CurveType;
+use crate::errors::SymCryptError;
+use crate::mlkem::{MlKemKey, MlKemParams, MLKEM_SHARED_SECRET_SIZE};
+use crate::secret::SecretBytes;
+
+/// The TLS NamedGroup code point for X25519MLKEM768.
+pub const X25519_MLKEM768_NAMED_GROUP: u16 = 0x11ec;
+
+const MLKEM768_ENCAPSULATION_KEY_SIZE: usize = 1184;
+const MLKEM768_CIPHERTEXT_SIZE: usize = 1088;
+
+/// The combined X25519MLKEM768 shared secret. The secret is wiped when it leaves scope.
+pub type X25519MlKem768SharedSecret = SecretBytes;
+
+/// x25519_size returns the size of a X25519 public key, which is also the size of a X25519 shared secret, as encoded by [`EcDh`].
+fn x25519_size() -> usize {
+    CurveType::Curve25519.field_element_size()
+}
+
+/// Returns the size of the key share sent by the client.
+pub fn x25519_mlkem768_client_share_size() -> usize {
+    MLKEM768_ENCAPSULATION_KEY_SIZE + x25519_size()
+}
+
+/// Returns the size of the key share sent by the server.
+pub fn x25519_mlkem768_server_share_size() -> usize {
+    MLKEM768_CIPHERTEXT_SIZE + x25519_size()
+}
+
+/// Returns the size of the combined shared secret.
+pub fn x25519_mlkem768_shared_secret_size() -> usize {
+    MLKEM_SHARED_SECRET_SIZE + x25519_size()
+}
+
+/// combine_secrets concatenates the two shared secrets in the order defined for X25519MLKEM768, ML-KEM first.
+fn combine_secrets(
+    mlkem_secret: &[u8],
+    x25519_secret: &[u8],
+) -> Result<X25519MlKem768SharedSecret, SymCryptError> {
+    // RFC 8446 section 7.4.2 requires an all zero X25519 shared secret to be rejected.
+    if x25519_secret.iter().all(|&byte| byte == 0) {
+        return Err(SymCryptError::InvalidArgument);
+    }
+    let mut secret = X25519MlKem768SharedSecret::zeroed(mlkem_secret.len() + x25519_secret.len());
+    secret.as_mut_bytes()[..mlkem_secret.len()].copy_from_slice(mlkem_secret);
+    secret.as_mut_bytes()[mlkem_secret.len()..].copy_from_slice(x25519_secret);
+    Ok(secret)
+}
+
+/// [`X25519MlKem768`] holds the client half of a X25519MLKEM768 key exchange, which is a ML-KEM-768 [`MlKemKey`] and a Curve25519 [`EcDh`].
+pub struct X25519MlKem768 {
+    mlkem: MlKemKey,
+    x25519: EcDh,
+}
+
+/// Impl for [`X25519MlKem768`]
+///
+/// [`new()`] generates both halves of the client key share.
+///
+/// [`client_share()`] returns the serialized client key share to send to the server.
+///
+/// [`server_respond()`] is called by the server with the client key share. It encapsulates to the ML-KEM-768 encapsulation key, generates
+/// a X25519 key pair and returns the serialized server key share along with the combined shared secret.
+///
+/// [`client_finish()`] takes in the server key share, and returns the combined shared secret.
+impl X25519MlKem768 {
+    pub fn new() -> Result<Self, SymCryptError> {
+        Ok(X25519MlKem768 {
+            mlkem: MlKemKey::generate(MlKemParams::MlKem768)?,
+            x25519: EcDh::new(CurveType::Curve25519)?,
+        })
+    }
+
+    pub fn client_share(&self) -> Result<Vec<u8>, SymCryptError> {
+        let mut share = self.mlkem.encapsulation_key_bytes()?;
+        share.extend_from_slice(&self.x25519.get_public_key_bytes()?);
+        Ok(share)
+    }
+
+    pub fn server_respond(
+        client_share: &[u8],
+    ) -> Result<(Vec<u8>, X25519MlKem768SharedSecret), SymCryptError> {
+        if client_share.len() != x25519_mlkem768_client_share_size() {
+            return Err(SymCryptError::WrongDataSize);
+        }
+        let (encapsulation_key, client_x25519) =
+            client_share.split_at(MLKEM768_ENCAPSULATION_KEY_SIZE);
+
+        let client_mlkem =
+            MlKemKey::from_encapsulation_key(MlKemParams::MlKem768, encapsulation_key)?;
+        let (mlkem_secret, mut share) = client_mlkem.encapsulate()?;
+
+        let server_x25519 = EcDh::new(CurveType::Curve25519)?;
+        let client_x25519 = EcDh::from_public_key_bytes(CurveType::Curve25519, client_x25519)?;
+        let x25519_secret = EcDh::ecdh_secret_agreement(&server_x25519, &client_x25519)?;
+        share.extend_from_slice(&server_x25519.get_public_key_bytes()?);
+
+        let secret = combine_secrets(mlkem_secret.as_bytes(), x25519_secret.as_bytes())?;
+        Ok((share, secret))
+    }
+
+    pub fn client_finish(
+        &self,
+        server_share: &[u8],
+    ) -> Result<X25519MlKem768SharedSecret, SymCryptError> {
+        if server_share.len() != x25519_mlkem768_server_share_size() {
+            return Err(SymCryptError::WrongDataSize);
+        }
+        let (ciphertext, server_x25519) = server_share.split_at(MLKEM768_CIPHERTEXT_SIZE);
+
+        let mlkem_secret = self.mlkem.decapsulate(ciphertext)?;
+        let server_x25519 = EcDh::from_public_key_bytes(CurveType::Curve25519, server_x25519)?;
+        let x25519_secret = EcDh::ecdh_secret_agreement(&self.x25519, &server_x25519)?;
+
+        combine_secrets(mlkem_secret.as_bytes(), x25519_secret.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mlkem::MLKEM_SEED_SIZE;
+
+    // Interop capture from the X25519MLKEM768 KEM of OpenSSL 3.5. The client key is the ML-KEM-768 seed d || z = 00 01 .. 3f and the
+    // X25519 private key 40 41 .. 5f. OpenSSL returned the client share for that key, encapsulated to the share to produce the server
+    // share and shared secret, and decapsulated the server share with the client key to the same secret.
+    const CLIENT_SHARE: &str = "298aa10d423c8dda069d02bc59e6cdf03a096b8b3da4cab9b80ca4a14907672ccef1ec4faf234a0bc5b7e9d473f2b3133b3b26a1d175cb67a7805919699c02f76531b99c5f89180704bb4ca4535c5b8972679c660a07c5e514b87009c862eb8f5157695efb3fc40a9def6b81c1cc02a249ae4f094ad0d9bd3485c1c1c68080520a7c8c632032cee738154e5c5176c07da56024776a430fe76eacf665a3f7b832102215bc82f10939c8355704336a8fac1d81e4bb0485aa5d7c74d6b59bbe5c5e972a0d8bac411b55b5d5557cd680a1a8f71b4eb86bc48c9a0509731a54bd9d7290b27963e4372dc9b199cfdcac0b01acd28a62395112e4c43648d622c48c8234d01440e8cc376c927f23a5afc9ac0474c662274e424525c8552ece3b3fe26516de901bc7d515bde89558e626c95c80b93342f8010004f39e6c6c94871c5e344cab3966c835f9a96a59afd31c40286b38b1c1a78470bab947518934453ce86736a919f1f5a6d510a86f5454fc3980cb5c765bd2bd5f7b36b1410d6635c8ceb47c4dda0d76a28eac939c71c3024804866c71626658442163c2c22117e50acefce6378a985652302a4ef0c2ce0cc716b7796e2b6b2e3777dfa1ac3da259a31b5a9b530f8cb638a81a62ac301849abaf95a7301bda30068909bfdb7e67dbccbb38a5551a25b1a3a0f685748ad5753d8880f0016c627486166384c5571fe2365900364d038311e2d875db366686932b5ec602430a369e87a6ef5c338786657825bd4c057aceb923eb0935e6905e63b4ced7f80857a773dd64b150d26612ea9ac12052db2017bf1843ccb4b3281b690dc728adfa85c00281b8e3c09287335f856b4fc2892f69a2f57921ada01914c40988662d57769662a786351b9b66493dab79594d986de2100d65ba0ff4ea58b81538d24a4435a258fac25404aa7f41f658b1385065e158dcb60115732720f40459aaac15e406953a90ac52997d1ccd070060efc65db9e653354467fad56ec713c86e7540c423acf2669f52fa6f4ac6888d871ef3e847c029a8aafbb92e17b24aa079b1f419ba6175b442afb11909d4a56b70a0335b28739218aa7c9348e2c3c2f3eb3d15a41e6417c0dd94bfeb21419b311a7bb13a180bbe833218a9a6b17447cc85f225859587a73077049acbcfd44d0f025438e15d1538270d586e1bf83192a9459cf63c0e972f85297679831ecf121509851cb8340f6f107b0fa1a0efd1b36a8189bc085c4f5cb784e553f41b918f80397ce1956f785bee377ca9aa8be6998ada30c26b7c3d8c6b55254cc96203b20c42aee0ac4e1ebb408e49a9e3f879d0ab0785eb7025425d1305a2299c015e120d163b0e19494ce57253d0246d182745cb8197ab7438b3c1bb7972bec5a306eba3567855c014699fef65ae54c770a0d85c18400cf642aedc660777ba4b138502bd5a7812f621f84a48296b98dd4322b6f15828b8a8f0e00a8ba44a53c3a8b143571b0740abd567daf1cde9c79c204b6d5e259d1766a31bbbcb4e6a05cf4502176b301c1c2f41247750157bcec85e809b30a4d60d7747cdd0f5b99aa8c826987517793aaa8080a0b124a8558df72bbe37b75f4edbb6be8216d6c633fb2b2280e25113d8695e43481c3eeb397eb192505229b67a201ea893c3e2cb32da8bc342fa4dea057879a631eede1bf9c98f12032cdeadd0e7a079398fc786b88cc846ec89af85a51a";
+    const SERVER_SHARE: &str = "b087e41365815096d839f888364283dbeabcf7023708f1268e66bd73a4893498b3f11b79a7c92bd9ccb707e7df45125e94ec194a40c66eeb5fc114e2b08b008aba8e1ffa75058e3dbac6640a9854ec6682f7531b90f177c31f93c07ccf24509a797cf28ff9f5b4684f067a7329e11233fceb277f9fde6f322caaa9042c23eadb0b2cec4e40c9ed52a2aadd44b615f10692b08228dc00f51b7346ef5565c313899e9b1fefd10951ddf6e1f28c9231c9cbb2c2fcaf0218451eaad1fe76e9cfa71950cff21df64122e40f57b45d859f326aaa65e941d85d74bc0e0e27be8b9d783d0a083246b03690593d45d034a2e8c8020ed0a73df0d77e6473293237ffc7ccbe27384444faf317d5adad8fb372925959858a9bd96bc7329f268d0cee61fe37f1fe1b7456f64b33bb870ea69042ab04a2bf879bdae798e2bc509f675c147bfb3019a542d3ae72cf2aa449e4fc240b424224e8702ff6872a4c4f53299355aa5bf6f005f92e0e7da69e5df3f6530bd8583a23c24fb94a4e557aba16c7e375bbdd0b9a289b459cb2a0417b7b007a30d24c2152a5bd3ba59e54e7e6df89bc02a62b6d2c8123590cd316e5808f16483c7e82bbd5507a5fc5283e9ba1cfa05925ff073ee07fd5f2439f95c6a3882179e36d024d3cdfeead788584262ecd24f4f564593ebe3756b0820f028edd5ab424d1f8d6d9be1a4dd0da06cede76d4cad3313918edc5b67f2a759a7dd42295390d7abad48f5f0759bb999c183a1d017028eb2cd8738e578f0704ee343617967a09feec0166e116b074f86350d756c6fe0c0dc72ea8d5565129f7850415da3466d38774183df77e5c3be30ad5185c4a4fee9aee55293d9a201963b8ed2863584e2707ffead7247c7a8908888a19b05db9e9946e8c8640c3a3630470d5d1adb09f16d044e7cb80666a71807d114b2e62f2f2b450ee90644b02037c99fde30f889fd9d50375f260bbde5523d048f13df86d21a31148b0345fbeaa00833bdaa611568d5eeb73652b5ad33a1d3a6af08c6b62c389522b138c494d1082100ebfa7c518511825382053c3e00b074182366b3f44d1b115b98c319138b27d83c3adea3559626eb9f9f63c22e677fc7a719b98bf096d413dd01976418fb3cdc4f6528a4bd07e72b2fab4b07db4d2662ada5d32c44c7b0310d2bf59c7ce9ae92d46c3716a61c611646073d6dad951649dbde10d2a253024866813d9c67b03208a0bc36daac1ef393adce340f8deabb62c7d5b502c9464e09d90a0b48a28c36bdc2e5e4558271d6b2334619f0d702430b5f8af8c60b71674840dad0616a879f16df6964496e1b4e11f31fd0eb081d913c9ed002c29ae177b7ac92af8d89b26376d84cb19b5bd2900b79720a03ec4f28a11076937265ce53b3fda63ed429bf5876f2e71c225094dc833203073fbf242bf7d0d2e883c9875209394f305c593b09bed28c73d07c60f28612f81f88111ddd678c5e2cb7ab0d7c740693a8f206ecf1da1c7dde331a0118597ceef0f14b48572d61c237963d9c0be2ecc1237073e5c183572e68a6b50b9b15cfa11ae42d5f93152662efac85b6a36baef03";
+    const SHARED_SECRET: &str = "afd991cc4362d732355e593fc26dd75de68272c6cfe1e34749f9fbf0ea62fcd047a8a18e54c1294828704db46a6bd92e07db4359cebe6fd0ed7323978b122403";
+
+    #[test]
+    fn test_x25519_mlkem768_known_answer() {
+        let seed: [u8; MLKEM_SEED_SIZE] = core::array::from_fn(|i| i as u8);
+        let x25519_private_key: Vec<u8> = (0x40..0x60).collect();
+        let client = X25519MlKem768 {
+            mlkem: MlKemKey::from_seed(MlKemParams::MlKem768, &seed).unwrap(),
+            x25519: EcDh::from_private_key_bytes(CurveType::Curve25519, &x25519_private_key)
+                .unwrap(),
+        };
+
+        assert_eq!(hex::encode(client.client_share().unwrap()), CLIENT_SHARE);
+        let secret = client
+            .client_finish(&hex::decode(SERVER_SHARE).unwrap())
+            .unwrap();
+        assert_eq!(hex::encode(secret.as_bytes()), SHARED_SECRET);
+    }
+
+    #[test]
+    fn test_x25519_mlkem768_key_exchange() {
+        let client = X25519MlKem768::new().unwrap();
+        let client_share = client.client_share().unwrap();
+        assert_eq!(client_share.len(), x25519_mlkem768_client_share_size());
+
+        let (server_share, server_secret) = X25519MlKem768::server_respond(&client_share).unwrap();
+        assert_eq!(server_share.len(), x25519_mlkem768_server_share_size());
+
+        let client_secret = client.client_finish(&server_share).unwrap();
+        assert_eq!(client_secret.len(), x25519_mlkem768_shared_secret_size());
+        assert_eq!(client_secret.as_bytes(), server_secret.as_bytes());
+    }
+
+    #[test]
+    fn test_x25519_mlkem768_share_layout() {
+        let client = X25519MlKem768::new().unwrap();
+        let client_share = client.client_share().unwrap();
+
+        assert_eq!(
+            &client_share[..MLKEM768_ENCAPSULATION_KEY_SIZE],
+            client.mlkem.encapsulation_key_bytes().unwrap().as_slice()
+        );
+        assert_eq!(
+            &client_share[MLKEM768_ENCAPSULATION_KEY_SIZE..],
+            client.x25519.get_public_key_bytes().unwrap().as_slice()
+        );
+    }
+
+    #[test]
+    fn test_x25519_mlkem768_secret_order() {
+        let client = X25519MlKem768::new().unwrap();
+        let (server_share, server_secret) =
+            X25519MlKem768::server_respond(&client.client_share().unwrap()).unwrap();
+
+        let (ciphertext, server_x25519) = server_share.split_at(MLKEM768_CIPHERTEXT_SIZE);
+        let mlkem_secret = client.mlkem.decapsulate(ciphertext).unwrap();
+        let server_x25519 =
+            EcDh::from_public_key_bytes(CurveType::Curve25519, server_x25519).unwrap();
+        let x25519_secret = EcDh::ecdh_secret_agreement(&client.x25519, &server_x25519).unwrap();
+
+        assert_eq!(
+            &server_secret.as_bytes()[..MLKEM_SHARED_SECRET_SIZE],
+            mlkem_secret.as_bytes()
+        );
+        assert_eq!(
+            &server_secret.as_bytes()[MLKEM_SHARED_SECRET_SIZE..],
+            x25519_secret.as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_x25519_mlkem768_wrong_share_size() {
+        let client = X25519MlKem768::new().unwrap();
+        let client_share = client.client_share().unwrap();
+
+        let result = X25519MlKem768::server_respond(&client_share[1..]);
+        assert_eq!(result.err().unwrap(), SymCryptError::WrongDataSize);
+
+        let result = client.client_finish(&vec![0u8; x25519_mlkem768_server_share_size() - 1]);
+        assert_eq!(result.err().unwrap(), SymCryptError::WrongDataSize);
+    }
+
+    #[test]
+    fn test_x25519_mlkem768_tampered_server_share() {
+        let client = X25519MlKem768::new().unwrap();
+        let (mut server_share, server_secret) =
+            X25519MlKem768::server_respond(&client.client_share().unwrap()).unwrap();
+        server_share[0] ^= 1;
+
+        // ML-KEM implicitly rejects the modified ciphertext, so the secrets no longer match.
+        let client_secret = client.client_finish(&server_share).unwrap();
+        assert_ne!(client_secret.as_bytes(), server_secret.as_bytes());
+    }
+}
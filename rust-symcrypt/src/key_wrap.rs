@@ -0,0 +1,222 @@
+//! AES Key Wrap functions as defined in RFC 3394. For further documentation please refer to symcrypt.h
+//!
+//! The key wrap is built on the single block AES functions from SymCrypt, since SymCrypt does not provide RFC 3394 directly.
+
+use crate::errors::SymCryptError;
+use crate::secret::{constant_time_eq, SecretBytes};
+use core::ffi::c_void;
+use std::mem;
+use std::pin::Pin;
+use std::ptr;
+use symcrypt_sys;
+
+/// The default initial value from RFC 3394 section 2.2.3.1.
+const DEFAULT_IV: [u8; 8] = [0xa6; 8];
+
+const SEMIBLOCK_SIZE: usize = 8;
+
+/// [`aes_key_wrap()`] wraps `key` with the key encryption key `kek`, which must be 16, 24 or 32 bytes.
+///
+/// `key` must be a multiple of 8 bytes and at least 16 bytes. The wrapped key is 8 bytes longer than `key`.
+pub fn aes_key_wrap(kek: &[u8], key: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+    if key.len() < 2 * SEMIBLOCK_SIZE || !key.len().is_multiple_of(SEMIBLOCK_SIZE) {
+        return Err(SymCryptError::WrongDataSize);
+    }
+    let expanded_key = AesExpandedKey::new(kek)?;
+    let n = key.len() / SEMIBLOCK_SIZE;
+    let mut wrapped = Vec::with_capacity(key.len() + SEMIBLOCK_SIZE);
+    wrapped.extend_from_slice(&DEFAULT_IV);
+    wrapped.extend_from_slice(key);
+
+    // The block holds part of the key, so it is wiped when it leaves scope.
+    let mut block = SecretBytes::zeroed(16);
+    for j in 0..6 {
+        for i in 1..=n {
+            block.as_mut_bytes()[..8].copy_from_slice(&wrapped[..8]);
+            block.as_mut_bytes()[8..].copy_from_slice(&wrapped[i * 8..(i + 1) * 8]);
+            expanded_key.encrypt_block(block.as_mut_bytes());
+            let t = (n * j + i) as u64;
+            for (a, t) in block.as_mut_bytes()[..8].iter_mut().zip(t.to_be_bytes()) {
+                *a ^= t;
+            }
+            wrapped[..8].copy_from_slice(&block[..8]);
+            wrapped[i * 8..(i + 1) * 8].copy_from_slice(&block[8..]);
+        }
+    }
+    Ok(wrapped)
+}
+
+/// [`aes_key_unwrap()`] unwraps `wrapped` with the key encryption key `kek`, which must be 16, 24 or 32 bytes.
+///
+/// Fails with [`SymCryptError::AuthenticationFailure`] if the integrity check of the unwrapped key fails, which happens when the wrong
+/// `kek` is used or `wrapped` was modified.
+pub fn aes_key_unwrap(kek: &[u8], wrapped: &[u8]) -> Result<SecretBytes, SymCryptError> {
+    if wrapped.len() < 3 * SEMIBLOCK_SIZE || !wrapped.len().is_multiple_of(SEMIBLOCK_SIZE) {
+        return Err(SymCryptError::WrongDataSize);
+    }
+    let expanded_key = AesExpandedKey::new(kek)?;
+    let n = wrapped.len() / SEMIBLOCK_SIZE - 1;
+    let mut a = [0u8; 8];
+    a.copy_from_slice(&wrapped[..8]);
+    let mut key = SecretBytes::from(wrapped[8..].to_vec());
+
+    let mut block = SecretBytes::zeroed(16);
+    for j in (0..6).rev() {
+        for i in (1..=n).rev() {
+            let t = (n * j + i) as u64;
+            for ((b, a), t) in block.as_mut_bytes()[..8]
+                .iter_mut()
+                .zip(a)
+                .zip(t.to_be_bytes())
+            {
+                *b = a ^ t;
+            }
+            block.as_mut_bytes()[8..].copy_from_slice(&key[(i - 1) * 8..i * 8]);
+            expanded_key.decrypt_block(block.as_mut_bytes());
+            a.copy_from_slice(&block[..8]);
+            key.as_mut_bytes()[(i - 1) * 8..i * 8].copy_from_slice(&block[8..]);
+        }
+    }
+
+    if !constant_time_eq(&a, &DEFAULT_IV) {
+        return Err(SymCryptError::AuthenticationFailure);
+    }
+    Ok(key)
+}
+
+/// [`AesExpandedKey`] holds the AES expanded key from SymCrypt, which is Pin<Box<>>'d for the same reason as [`crate::gcm::GcmExpandedKey`]
+/// and wiped when it leaves scope.
+struct AesExpandedKey {
+    expanded_key: Pin<Box<symcrypt_sys::SYMCRYPT_AES_EXPANDED_KEY>>,
+}
+
+impl AesExpandedKey {
+    fn new(key: &[u8]) -> Result<Self, SymCryptError> {
+        let mut expanded_key = Box::pin(symcrypt_sys::SYMCRYPT_AES_EXPANDED_KEY::default()); // boxing here so that the memory is not moved
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptAesExpandKey(
+                &mut *expanded_key,
+                key.as_ptr(),
+                key.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => {
+                    Ok(AesExpandedKey { expanded_key })
+                }
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        debug_assert_eq!(block.len(), 16);
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptAesEncrypt(
+                &*self.expanded_key,
+                block.as_ptr(),
+                block.as_mut_ptr(),
+            );
+        }
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        debug_assert_eq!(block.len(), 16);
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptAesDecrypt(
+                &*self.expanded_key,
+                block.as_ptr(),
+                block.as_mut_ptr(),
+            );
+        }
+    }
+}
+
+impl Drop for AesExpandedKey {
+    fn drop(&mut self) {
+        unsafe {
+            // SAFETY: FFI calls
+            let expanded_key = self.expanded_key.as_mut().get_unchecked_mut();
+            symcrypt_sys::SymCryptWipe(
+                ptr::addr_of_mut!(*expanded_key) as *mut c_void,
+                mem::size_of_val(expanded_key) as symcrypt_sys::SIZE_T,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // RFC 3394 section 4 test vectors.
+    const KEK_128: &str = "000102030405060708090a0b0c0d0e0f";
+    const KEK_256: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+    const KEY_128: &str = "00112233445566778899aabbccddeeff";
+    const KEY_256: &str = "00112233445566778899aabbccddeeff000102030405060708090a0b0c0d0e0f";
+
+    #[test]
+    fn test_aes_key_wrap_rfc3394() {
+        for (kek, key, expected) in [
+            (
+                KEK_128,
+                KEY_128,
+                "1fa68b0a8112b447aef34bd8fb5a7b829d3e862371d2cfe5",
+            ),
+            (
+                KEK_256,
+                KEY_128,
+                "64e8c3f9ce0f5ba263e9777905818a2a93c8191e7d6e8ae7",
+            ),
+            (
+                KEK_256,
+                KEY_256,
+                "28c9f404c4b810f4cbccb35cfb87f8263f5786e2d80ed326cbc7f0e71a99f43bfb988b9b7a02dd21",
+            ),
+        ] {
+            let kek = hex::decode(kek).unwrap();
+            let wrapped = aes_key_wrap(&kek, &hex::decode(key).unwrap()).unwrap();
+            assert_eq!(hex::encode(&wrapped), expected);
+
+            let unwrapped = aes_key_unwrap(&kek, &wrapped).unwrap();
+            assert_eq!(hex::encode(unwrapped.as_bytes()), key);
+        }
+    }
+
+    #[test]
+    fn test_aes_key_unwrap_authentication_failure() {
+        let kek = hex::decode(KEK_128).unwrap();
+        let mut wrapped = hex::decode("1fa68b0a8112b447aef34bd8fb5a7b829d3e862371d2cfe5").unwrap();
+        wrapped[20] ^= 1;
+        assert_eq!(
+            aes_key_unwrap(&kek, &wrapped).unwrap_err(),
+            SymCryptError::AuthenticationFailure
+        );
+
+        let wrong_kek = hex::decode(KEK_256).unwrap();
+        wrapped[20] ^= 1;
+        assert_eq!(
+            aes_key_unwrap(&wrong_kek[16..], &wrapped).unwrap_err(),
+            SymCryptError::AuthenticationFailure
+        );
+    }
+
+    #[test]
+    fn test_aes_key_wrap_invalid_sizes() {
+        let kek = hex::decode(KEK_128).unwrap();
+        assert_eq!(
+            aes_key_wrap(&kek, &[0u8; 8]).unwrap_err(),
+            SymCryptError::WrongDataSize
+        );
+        assert_eq!(
+            aes_key_wrap(&kek, &[0u8; 20]).unwrap_err(),
+            SymCryptError::WrongDataSize
+        );
+        assert_eq!(
+            aes_key_unwrap(&kek, &[0u8; 16]).unwrap_err(),
+            SymCryptError::WrongDataSize
+        );
+        assert!(aes_key_wrap(&kek[..15], &[0u8; 16]).is_err());
+    }
+}
@@ -16,21 +16,45 @@ pub fn symcrypt_init() {
     }
 }
 
-/// Takes in a rand_length and returns a Vec<u8> with rand_length random bytes
+/// Takes in a rand_length and returns a Vec<u8> with rand_length random bytes. Use [`rng::fill_bytes()`] to fill an existing buffer.
 pub fn symcrypt_random(rand_length: u64) -> Vec<u8> {
     let mut random_buffer: Vec<u8> = vec![0; rand_length as usize];
-    unsafe {
-        // SAFETY: FFI calls
-        symcrypt_sys::SymCryptRandom(random_buffer.as_mut_ptr(), rand_length);
-    }
+    rng::fill_bytes(&mut random_buffer);
     random_buffer
 }
 
 pub mod block_ciphers;
 pub mod chacha;
+#[cfg(feature = "cose")]
+pub mod cose;
+pub mod dh;
+pub mod dsa;
 pub mod ecdh;
+pub mod ecdsa;
 pub mod eckey;
 pub mod errors;
 pub mod gcm;
 pub mod hash;
+pub mod hbs;
+pub mod hkdf;
 pub mod hmac;
+pub mod hpke;
+pub mod hybrid;
+#[cfg(feature = "jose")]
+pub mod jose;
+pub mod key_encoding;
+pub mod key_wrap;
+pub mod mldsa;
+pub mod mlkem;
+pub mod noise;
+pub mod rng;
+pub mod rsa;
+#[cfg(feature = "rustls-provider")]
+pub mod rustls_provider;
+pub mod secret;
+pub mod signature;
+pub mod srtp_kdf;
+pub mod ssh_kdf;
+pub mod stream;
+pub mod tls13;
+pub mod tls_prf;
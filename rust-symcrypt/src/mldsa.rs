@@ -0,0 +1,637 @@
+//! ML-DSA functions as defined in FIPS 204. For further documentation please refer to symcrypt.h
+//!
+//! Signatures are hedged by default, which mixes fresh randomness into the signing nonce. Deterministic signing is available for
+//! environments without a trustworthy random source. Pure ML-DSA signs the message itself, while HashML-DSA signs a hash of the message
+//! and produces signatures that are not interchangeable with pure ML-DSA signatures.
+
+use crate::errors::{map_verify_error, SymCryptError};
+use crate::hash::HashAlgorithm;
+use crate::secret::SecretBytes;
+use crate::symcrypt_init;
+use symcrypt_sys;
+
+/// Size of the private seed xi that a [`MlDsaKey`] can be generated from.
+pub const MLDSA_SEED_SIZE: usize = 32;
+
+/// Size of the randomness rnd that is used by [`MlDsaKey::sign_with_randomness()`].
+pub const MLDSA_SIGNING_RANDOMNESS_SIZE: usize = 32;
+
+/// Maximum size of the context string as defined in FIPS 204 section 5.2.
+pub const MLDSA_MAX_CONTEXT_SIZE: usize = 255;
+
+/// [`MlDsaParams`] provides an enum of the ML-DSA parameter sets.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum MlDsaParams {
+    MlDsa44,
+    MlDsa65,
+    MlDsa87,
+}
+
+/// Impl for [`MlDsaParams`]
+///
+/// [`public_key_size()`], [`private_key_size()`] and [`signature_size()`] return the encoded sizes in bytes as defined in FIPS 204
+/// section 4.
+impl MlDsaParams {
+    pub fn public_key_size(&self) -> usize {
+        match self {
+            MlDsaParams::MlDsa44 => 1312,
+            MlDsaParams::MlDsa65 => 1952,
+            MlDsaParams::MlDsa87 => 2592,
+        }
+    }
+
+    pub fn private_key_size(&self) -> usize {
+        match self {
+            MlDsaParams::MlDsa44 => 2560,
+            MlDsaParams::MlDsa65 => 4032,
+            MlDsaParams::MlDsa87 => 4896,
+        }
+    }
+
+    pub fn signature_size(&self) -> usize {
+        match self {
+            MlDsaParams::MlDsa44 => 2420,
+            MlDsaParams::MlDsa65 => 3309,
+            MlDsaParams::MlDsa87 => 4627,
+        }
+    }
+}
+
+/// convert_params takes in the friendly MlDsaParams enum and returns the symcrypt equivalent.
+fn convert_params(params: MlDsaParams) -> symcrypt_sys::SYMCRYPT_MLDSA_PARAMS {
+    match params {
+        MlDsaParams::MlDsa44 => symcrypt_sys::_SYMCRYPT_MLDSA_PARAMS_SYMCRYPT_MLDSA_PARAMS_MLDSA44,
+        MlDsaParams::MlDsa65 => symcrypt_sys::_SYMCRYPT_MLDSA_PARAMS_SYMCRYPT_MLDSA_PARAMS_MLDSA65,
+        MlDsaParams::MlDsa87 => symcrypt_sys::_SYMCRYPT_MLDSA_PARAMS_SYMCRYPT_MLDSA_PARAMS_MLDSA87,
+    }
+}
+
+/// convert_hash_id takes in the friendly HashAlgorithm enum and returns the SymCrypt PQDSA hash id that HashML-DSA takes.
+fn convert_hash_id(hash: HashAlgorithm) -> symcrypt_sys::SYMCRYPT_PQDSA_HASH_ID {
+    match hash {
+        HashAlgorithm::Sha256 => {
+            symcrypt_sys::_SYMCRYPT_PQDSA_HASH_ID_SYMCRYPT_PQDSA_HASH_ID_SHA256
+        }
+        HashAlgorithm::Sha384 => {
+            symcrypt_sys::_SYMCRYPT_PQDSA_HASH_ID_SYMCRYPT_PQDSA_HASH_ID_SHA384
+        }
+    }
+}
+
+/// check_context fails with InvalidArgument if the context string is longer than FIPS 204 allows.
+fn check_context(context: &[u8]) -> Result<(), SymCryptError> {
+    if context.len() > MLDSA_MAX_CONTEXT_SIZE {
+        return Err(SymCryptError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// An exported ML-DSA private key. The key is wiped when it leaves scope.
+pub type MlDsaPrivateKey = SecretBytes;
+
+/// [`MlDsaKey`] is a wrapper around symcrypt_sys::PSYMCRYPT_MLDSAKEY.
+///
+/// The key is allocated by SymCrypt and is wiped and freed when MlDsaKey leaves scope.
+pub struct MlDsaKey {
+    inner: symcrypt_sys::PSYMCRYPT_MLDSAKEY,
+    params: MlDsaParams,
+    has_private_key: bool,
+}
+
+/// Impl for [`MlDsaKey`]
+///
+/// [`generate()`] returns a new random [`MlDsaKey`].
+///
+/// [`from_seed()`] takes in the 32 byte private seed xi and returns the [`MlDsaKey`] that ML-DSA.KeyGen derives from it.
+///
+/// [`from_private_key()`] and [`from_public_key()`] take in an encoded key, a key created from a public key can only verify.
+///
+/// [`public_key_bytes()`] returns the encoded public key. [`private_key_bytes()`] returns the encoded private key, and fails if the key
+/// was created from a public key.
+///
+/// [`sign()`] returns a hedged signature of the message. [`sign_deterministic()`] returns the deterministic signature, which is always the
+/// same for a given key, message and context. [`sign_with_randomness()`] is ML-DSA.Sign with caller provided randomness, and is intended
+/// for known-answer tests.
+///
+/// [`verify()`] takes in a message, a context and a signature. Any failure to verify, including a malformed signature, returns
+/// [`SymCryptError::SignatureVerificationFailure`].
+///
+/// [`sign_prehashed()`] and [`verify_prehashed()`] are HashML-DSA, and take in the hash of the message along with the [`HashAlgorithm`]
+/// that produced it.
+///
+/// Every signing and verifying function takes in a context string of at most [`MLDSA_MAX_CONTEXT_SIZE`] bytes, which can be empty.
+impl MlDsaKey {
+    pub fn generate(params: MlDsaParams) -> Result<Self, SymCryptError> {
+        let mut key = MlDsaKey::allocate(params)?;
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptMlDsakeyGenerate(key.inner, 0) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => {
+                    key.has_private_key = true;
+                    Ok(key)
+                }
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn from_seed(
+        params: MlDsaParams,
+        seed: &[u8; MLDSA_SEED_SIZE],
+    ) -> Result<Self, SymCryptError> {
+        MlDsaKey::set_value(
+            params,
+            seed,
+            symcrypt_sys::_SYMCRYPT_MLDSAKEY_FORMAT_SYMCRYPT_MLDSAKEY_FORMAT_PRIVATE_SEED,
+        )
+    }
+
+    pub fn from_private_key(
+        params: MlDsaParams,
+        private_key: &[u8],
+    ) -> Result<Self, SymCryptError> {
+        if private_key.len() != params.private_key_size() {
+            return Err(SymCryptError::WrongKeySize);
+        }
+        MlDsaKey::set_value(
+            params,
+            private_key,
+            symcrypt_sys::_SYMCRYPT_MLDSAKEY_FORMAT_SYMCRYPT_MLDSAKEY_FORMAT_PRIVATE_KEY,
+        )
+    }
+
+    pub fn from_public_key(params: MlDsaParams, public_key: &[u8]) -> Result<Self, SymCryptError> {
+        if public_key.len() != params.public_key_size() {
+            return Err(SymCryptError::WrongKeySize);
+        }
+        MlDsaKey::set_value(
+            params,
+            public_key,
+            symcrypt_sys::_SYMCRYPT_MLDSAKEY_FORMAT_SYMCRYPT_MLDSAKEY_FORMAT_PUBLIC_KEY,
+        )
+    }
+
+    pub fn params(&self) -> MlDsaParams {
+        self.params
+    }
+
+    pub fn has_private_key(&self) -> bool {
+        self.has_private_key
+    }
+
+    pub fn public_key_bytes(&self) -> Result<Vec<u8>, SymCryptError> {
+        let mut public_key = vec![0u8; self.params.public_key_size()];
+        self.get_value(
+            &mut public_key,
+            symcrypt_sys::_SYMCRYPT_MLDSAKEY_FORMAT_SYMCRYPT_MLDSAKEY_FORMAT_PUBLIC_KEY,
+        )?;
+        Ok(public_key)
+    }
+
+    pub fn private_key_bytes(&self) -> Result<MlDsaPrivateKey, SymCryptError> {
+        let mut private_key = MlDsaPrivateKey::zeroed(self.params.private_key_size());
+        self.get_value(
+            private_key.as_mut_bytes(),
+            symcrypt_sys::_SYMCRYPT_MLDSAKEY_FORMAT_SYMCRYPT_MLDSAKEY_FORMAT_PRIVATE_KEY,
+        )?;
+        Ok(private_key)
+    }
+
+    pub fn sign(&self, message: &[u8], context: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+        check_context(context)?;
+        let mut signature = vec![0u8; self.params.signature_size()];
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptMlDsaSign(
+                self.inner,
+                message.as_ptr(),
+                message.len() as symcrypt_sys::SIZE_T,
+                context.as_ptr(),
+                context.len() as symcrypt_sys::SIZE_T,
+                0, // No flags allowed
+                signature.as_mut_ptr(),
+                signature.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(signature),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn sign_deterministic(
+        &self,
+        message: &[u8],
+        context: &[u8],
+    ) -> Result<Vec<u8>, SymCryptError> {
+        // FIPS 204 section 3.4 defines the deterministic variant as signing with rnd set to all zeros.
+        self.sign_with_randomness(message, context, &[0u8; MLDSA_SIGNING_RANDOMNESS_SIZE])
+    }
+
+    pub fn sign_with_randomness(
+        &self,
+        message: &[u8],
+        context: &[u8],
+        randomness: &[u8; MLDSA_SIGNING_RANDOMNESS_SIZE],
+    ) -> Result<Vec<u8>, SymCryptError> {
+        check_context(context)?;
+        let mut signature = vec![0u8; self.params.signature_size()];
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptMlDsaSignEx(
+                self.inner,
+                randomness.as_ptr(),
+                randomness.len() as symcrypt_sys::SIZE_T,
+                message.as_ptr(),
+                message.len() as symcrypt_sys::SIZE_T,
+                context.as_ptr(),
+                context.len() as symcrypt_sys::SIZE_T,
+                0, // No flags allowed
+                signature.as_mut_ptr(),
+                signature.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(signature),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn verify(
+        &self,
+        message: &[u8],
+        context: &[u8],
+        signature: &[u8],
+    ) -> Result<(), SymCryptError> {
+        check_context(context)?;
+        unsafe {
+            // SAFETY: FFI calls
+            map_verify_error(symcrypt_sys::SymCryptMlDsaVerify(
+                self.inner,
+                message.as_ptr(),
+                message.len() as symcrypt_sys::SIZE_T,
+                context.as_ptr(),
+                context.len() as symcrypt_sys::SIZE_T,
+                signature.as_ptr(),
+                signature.len() as symcrypt_sys::SIZE_T,
+                0, // No flags allowed
+            ))
+        }
+    }
+
+    pub fn sign_prehashed(
+        &self,
+        hash_algorithm: HashAlgorithm,
+        hashed_message: &[u8],
+        context: &[u8],
+    ) -> Result<Vec<u8>, SymCryptError> {
+        check_context(context)?;
+        if hashed_message.len() != hash_algorithm.result_size() {
+            return Err(SymCryptError::WrongDataSize);
+        }
+        let mut signature = vec![0u8; self.params.signature_size()];
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptHashMlDsaSign(
+                self.inner,
+                convert_hash_id(hash_algorithm),
+                hashed_message.as_ptr(),
+                hashed_message.len() as symcrypt_sys::SIZE_T,
+                context.as_ptr(),
+                context.len() as symcrypt_sys::SIZE_T,
+                0, // No flags allowed
+                signature.as_mut_ptr(),
+                signature.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(signature),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn verify_prehashed(
+        &self,
+        hash_algorithm: HashAlgorithm,
+        hashed_message: &[u8],
+        context: &[u8],
+        signature: &[u8],
+    ) -> Result<(), SymCryptError> {
+        check_context(context)?;
+        if hashed_message.len() != hash_algorithm.result_size() {
+            return Err(SymCryptError::WrongDataSize);
+        }
+        unsafe {
+            // SAFETY: FFI calls
+            map_verify_error(symcrypt_sys::SymCryptHashMlDsaVerify(
+                self.inner,
+                convert_hash_id(hash_algorithm),
+                hashed_message.as_ptr(),
+                hashed_message.len() as symcrypt_sys::SIZE_T,
+                context.as_ptr(),
+                context.len() as symcrypt_sys::SIZE_T,
+                signature.as_ptr(),
+                signature.len() as symcrypt_sys::SIZE_T,
+                0, // No flags allowed
+            ))
+        }
+    }
+
+    // SymCryptInit must be called before any ML-DSA operations are performed.
+    fn allocate(params: MlDsaParams) -> Result<Self, SymCryptError> {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_init(); // Will only init once, subsequent calls to symcrypt_init() will be no-ops.
+
+            let key_ptr = symcrypt_sys::SymCryptMlDsakeyAllocate(convert_params(params), 0);
+            if key_ptr.is_null() {
+                return Err(SymCryptError::MemoryAllocationFailure);
+            }
+            Ok(MlDsaKey {
+                inner: key_ptr,
+                params,
+                has_private_key: false,
+            })
+        }
+    }
+
+    fn set_value(
+        params: MlDsaParams,
+        key: &[u8],
+        format: symcrypt_sys::SYMCRYPT_MLDSAKEY_FORMAT,
+    ) -> Result<Self, SymCryptError> {
+        let mut mldsa_key = MlDsaKey::allocate(params)?;
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptMlDsakeySetValue(
+                key.as_ptr(),
+                key.len() as symcrypt_sys::SIZE_T,
+                format,
+                0,
+                mldsa_key.inner,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => {
+                    mldsa_key.has_private_key = format
+                        != symcrypt_sys::_SYMCRYPT_MLDSAKEY_FORMAT_SYMCRYPT_MLDSAKEY_FORMAT_PUBLIC_KEY;
+                    Ok(mldsa_key)
+                }
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    fn get_value(
+        &self,
+        buffer: &mut [u8],
+        format: symcrypt_sys::SYMCRYPT_MLDSAKEY_FORMAT,
+    ) -> Result<(), SymCryptError> {
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptMlDsakeyGetValue(
+                self.inner,
+                buffer.as_mut_ptr(),
+                buffer.len() as symcrypt_sys::SIZE_T,
+                format,
+                0, // No flags allowed
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(()),
+                err => Err(err.into()),
+            }
+        }
+    }
+}
+
+impl Drop for MlDsaKey {
+    fn drop(&mut self) {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptMlDsakeyFree(self.inner);
+        }
+    }
+}
+
+unsafe impl Send for MlDsaKey {
+    // The key is only referenced through the SymCrypt allocated pointer, which does not move.
+}
+
+unsafe impl Sync for MlDsaKey {
+    // Signing and verifying only read the key.
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hash::{sha256, sha384};
+
+    // Public keys derived from SEED by OpenSSL 3.5 (`openssl genpkey -algorithm ML-DSA-<n> -pkeyopt hexseed:00..1f`), only the
+    // SHA-256 hash of each is stored.
+    const SEED: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+    const MLDSA44_PUBLIC_KEY_SHA256: &str =
+        "9f107644c1084526af3bc8098680b05499a2325a644e388fb4f970e058d19d46";
+    const MLDSA65_PUBLIC_KEY_SHA256: &str =
+        "d666806e11cee19a7c989f7445f90dd419cf4d2d51db8c0fdb4c0f0a542238c9";
+    const MLDSA87_PUBLIC_KEY_SHA256: &str =
+        "91dc389cfaa01470b7f66eee45a4ae9026d154817c754dfe22298b3fa241ffcd";
+
+    // Deterministic ML-DSA-44 signature over MESSAGE with CONTEXT from the SEED key, produced by OpenSSL 3.5 with
+    // `openssl pkeyutl -sign -rawin -pkeyopt context-string:code-signing -pkeyopt deterministic:1`.
+    const MESSAGE: &[u8] = b"release-manifest.json";
+    const CONTEXT: &[u8] = b"code-signing";
+    const MLDSA44_PUBLIC_KEY: &str = "d7b2b47254aae0db45e7930d4a98d2c97d8f1397d1789dafa17024b316e9bec94fc9946d42f19b79a7413bbaa33e7149cb42ed5115693ac041facb988adeb5fe0e1d8631184995b592c397d2294e2e14f90aa414ba3826899ac43f4cccacbc26e9a832b95118d5cb433cbef9660b00138e0817f61e762ca274c36ad554eb22aac1162e4ab01acba1e38c4efd8f80b65b333d0f72e55dfe71ce9c1ebb9889e7c56106c0fd73803a2aecfeafded7aa3cb2ceda54d12bd8cd36a78cf975943b47abd25e880ac452e5742ed1e8d1a82afa86e590c758c15ae4d2840d92bca1a5090f40496597fca7d8b9513f1a1bda6e950aaa98de467507d4a4f5a4f0599216582c3572f62eda8905ab3581670c4a02777a33e0ca7295fd8f4ff6d1a0a3a7683d65f5f5f7fc60da023e826c5f92144c02f7d1ba1075987553ea9367fcd76d990b7fa99cd45afdb8836d43e459f5187df058479709a01ea6835935fa70460990cd3dc1ba401ba94bab1dde41ac67ab3319dcaca06048d4c4eef27ee13a9c17d0538f430f2d642dc2415660de78877d8d8abc72523978c042e4285f4319846c44126242976844c10e556ba215b5a719e59d0c6b2a96d39859071fdcc2cde7524a7bedae54e85b318e854e8fe2b2f3edfac9719128270aafd1e5044c3a4fdafd9ff31f90784b8e8e4596144a0daf586511d3d9962b9ea95af197b4e5fc60f2b1ed15de3a5bef5f89bdc79d91051d9b2816e74fa54531efdc1cbe74d448857f476bcd58f21c0b653b3b76a4e076a6559a302718555cc63f74859aabab925f023861ca8cd0f7badb2871f67d55326d7451135ad45f4a1ba69118fbb2c8a30eec9392ef3f977066c9add5c710cc647b1514d217d958c7017c3e90fd20c04e674b90486e9370a31a001d32f473979e4906749e7e477fa0b74508f8a5f2378312b83c25bd388ca0b0fff7478baf42b71667edaac97c46b129643e586e5b055a0c211946d4f36e675bed5860fa042a315d9826164d6a9237c35a5fbf495490a5bd4df248b95c4aae7784b605673166ac4245b5b4b082a09e9323e62f2078c5b76783446defd736ad3a3702d49b089844900a61833397bc4419b30d7a97a0b387c1911474c4d41b53e32a977acb6f0ea75db65bb39e59e701e76957def6f2d44559c31a77122b5204e3b5c219f1688b14ed0bc0b801b3e6e82dcd43e9c0e9f41744cd9815bd1bc8820d8bb123f04facd1b1b685dd5a2b1b8dbbf3ed933670f095a180b4f192d08b10b8fabbdfcc2b24518e32eea0a5e0c904ca844780083f3b0cd2d0b8b6af67bc355b9494025dc7b0a78fa80e3a2dbfeb51328851d6078198e9493651ae787ec0251f922ba30e9f51df62a6d72784cf3dd205393176dfa324a512bd94970a36dd34a514a86791f0eb36f0145b09ab64651b4a0313b299611a2a1c48891627598768a3114060ba4443486df51522a1ce88b30985c216f8e6ed178dd567b304a0d4cafba882a28342f17a9aa26ae58db630083d2c358fdf566c3f5d62a428567bc9ea8ce95caa0f35474b0bfa8f339a250ab4dfcf2083be8eefbc1055e18fe15370eecb260566d83ff06b211aaec43ca29b54ccd00f8815a2465ef0b46515cc7e41f3124f09efff739309ab58b29a1459a00bce5038e938c9678f72eb0e4ee5fdaae66d9f8573fc97fc42b4959f4bf8b61d78433e86b0335d6e9191c4d8bf487b3905c108cfd6ac24b0ceb7dcb7cf51f84d0ed687b95eaeb1c533c06f0d97023d92a70825837b59ba6cb7d4e56b0a87c203862ae8f315ba5925e8edefa679369a2202766151f16a965f9f81ece76cc070b55869e4db9784cf05c830b3242c8312";
+    const MLDSA44_SIGNATURE: &str = "d2455bbc42de9f760815e549151ecc0673f83e3ae7f7d1a70759a1cb77d0fe61b06324ae66cd979f0e8a8628c57759fd97d4c09debb8daeb7672954ff4d8f1f6225b23e0ab250f4b3c35d8c28d2a63d4714a5a664d91f4448f43329ec5771f99e3cb38cf5086c412e84403715917d29543b45ebda62c3d0a0b4211d7fa40ad2d50087bb64875f141e2ec420da092f9a5e7f13b5ebed1a334c76f487c67f7a003262b5a7f51b74817fb0b284baaa1f0dc11363a6f902aa943564ea8a7cbdbeaac8b82dd7510a9a91d5817106dc849db8c0a5fe18bcef88ff11cb0d841fcc4cf6b05d1b3d8effdd75a290a2779cbf7472d55f2c9def3f1e6b9d56f76af54cc684c4561d3464d57192ba43f30afde38819f75a6ebc722d0a0e635c0634c62c6ffa421261065168de9ba343c00abcb885a48b61c7c13c935cba5bcf91a2ae586e9e41803a423808296d837c8ad220e9961a5a460b0c2093bd25fe34c13102c44870c51f40db5befe2351628a50bf8527451dc96e29f9a89879ab11a3bc1184f68e29f1c1a6391e8858536f11bca1034791d30f6a694c1c9aa20ff8964fd010ebfe6e1c4f3e1e1945d668c4c864a09979321bc42df119c22d9dc910b4e097b6f974690047ed8ac0cd267a2e4dc1e46701f2231bff19980068c975bd920fea2a238587ad531775027ab51aaacf4f59ce913616142120198451c54840e77e5fce78484f33d5182309f6e34a129573e1c327d7034ab3894a5ce59437f6dc11143e2890f47c13977709dcfadb27cdf448db1da4dd7038c9085b8d44107eeffcde2a1d267996c3c491ced22090cd35fecf327f32d836ddd09c3bc88aaebefde959d6d8022e4a06d2d170397f764e287c3d6452d1b5c7e3b8796a4503ba0563f1d75f9905361fbd90657538dc3d1eb3884b9c92a03bd37ce65554cd8a432d87a6271ca038818ca3e34de565ab2c4c2062dafe24c94a11c18cf7e05efa6d3fd99fca78093ac8d14403218518a4c7c373c0936c037d7d871f751bb6b45424f1ab6628e9b7bd6e5bee9bd270c3a0a83091f63dd10a3777a3dc246cb44cb42e26aa290940245305d86fbd1ef068c2b27d2c71ae201e244ca2d42f6f44d81c6565326b8a9e539c1ca4a578d6eb162ded91db013fdbe0925cdd303c2cbfb906e23933efb2b932a95b232328532f7798f8221ac5b3727462f0049268daedc514d6c0e39f832cd1d262c0040414119dc4130c577cd1a13a4e2f9b26c146309dfe3801ba2ae487bdc779983e04065457d1ea23c241b1ee95929a64cf585aa98d21b4f24187cb43731750fbbc9b41d03343d69f0172c6e8d7e2a2244b741715139ee50bc0e0aa2a203526256a307f4c09094406073d044fc7ee34592ff9fc55d469118dc27fa5b1a03ef9d5bbbf3986d69a2300f5dbf37705cfa1a071420d823ece9487e0034c847139013d102682a57101aac826c34ad34166fbacdae5fe9ed7dea90db0705717846d145ca3fab6e6432d4edbcfbe5ee8ef48e010f83e562ea3b5e80dca24f6f62e187148ad17e87fd522380b49fbc547c6d03437d3d3dadfed1e171fd7c7cfb04bc6a376a4a9a54c7035b531d3605d6e5c060963c221d26cfab8146022a5345346acbae60e7ac8a33251f2c7e8add3c5d7cb6074472a459aa536758ce5d22ff0571fd62518bf09a993997fde1aa2af53f749ee021948743901f969bac942ad4c4b843631dd1a8b06fc8f89abdc9faf4a21a65536d7de0520f9a603e318d8826b35fdc60f09eb46301b31705bc944752c7925b6fcc8818d31a4c74a3d1a7b068c6f543f621bd2fcadc99e5f8ae5480823e0a3b4169988e38f70850d2594a28d05f0bb42e6ea5fb4af5db16a4594849cfe87f8e9b46d7b191735af201cd10c2f1dd89ef4500f736bb8d80e802a3898a8beeb6c05e5b1e48833b3cba8aebc92a31703de7585f5a99b36fa49bb886f1923bfd04f7c8b6934b4ccce3bd90b629bf9ec4c388e55866aee213d15a0d80a076bc738173d6e2a9834aae3fe25d602fd7767a6264bc5fb88befc484cfe5a6f8eb303b18502e4e98b546a3393898577384799d8e5b9d3fff254629a6006b4e1f82ac7822fe470b9d8ef9b145b2583b6382877fc4cf850a2b38eaf8beafdcc0ee66c70270c580b6feb31b76eef2bd90c8af0edb554f380add8d7b3b65f553dc5ab1a2c8fd54e88ea797389a6f9bced82d5de3a8b81010372b430faf409eceb4985d829f1191650fbfa733b619e551b41a508f3fec249e5793b501f0a20166494559c4acac30de66fd1932f2e0f3c59895864103037acb71b30e0a9d9ae8e26fb8020e9b94c573781f5092ae929763b7fe6f86ab01ec3cb8a3fe53433b06674ca421a0c5e08aa372c7ee5cadbca63d23175b6df08ef451e0cc5edea925702f8f5fac90ab2c60acc5c13d3062adcddcf31e6b1bf3f0b65b43a55a40b9767f8a8774253484a419275c45bc53002622c5ecd979b21eb588f0fb10766f15c73feb3f710b92d9b795d637653f07f653d24eaf6a7b1a3223a2082d7ff33b2c5fa1184a6f35b820aad823008aea8acb5634a756983ed1e599f8d9fcb5b05c974a67cd8fe3085738da31f4cdd30cfaa6937421bd4ab2462af35de19c180fc871f973de2df396b6f582435c6e3ccdfa8e57b32a898b3d52bab3c546868c08591b4bb5df1f6e155124d522d736f6ae74838ed2a4a8f0a07791a023ac4f999cb0dfbe31607f34c7717c099ea87a24943be4f82dc77c54af11d7f4bd641a493a80f987aaba0ac59254d8fb2e9713e656836f6175b20431b767a3d9c8473be920696333118f5fd741a65e080b75e0d0c78bd4e1f5544ab38178183c5ef1afed30d7748f4a65f8876959bfc19a943edf7262d2b0bd817a5c2324223967ea97ef031b141bde749b0bb5d8ead1011a61d4d2d2fa42c8c6bafc66991b576054b0fd4da472299b1292c3c63192b7279fb792cd3b56ff4a3413b3568e2b00cf1c52e2a276d2b1f75509257d88afb56ccebfadb3c33ba6645bc49e5bc12908a31b934e7ba65e054f5020b69254a0b22e2eaf3a5c0fe05bb27e9bb2f6d06e5cecf41a139270146b5782b85f512afbcd812a0a5793d194537f663e1093776b75f7104816721c1334bfb459bd2df538041e2d7bca9e5a322f5c84c23fe07d560f535c6b4d52831e557cc096b42fb503eb6aa6758d2a8e56f1d890244ce83adda84c6d5d0090341e843b3471926befacc3216a4e2e9a89c6b0fb55248a7ba651381ddea913ec34f8c6eb39f60191c66a104b962f3cb4ad6fa9c01945cd6f5bb49a9de141b202c435a74757a929dafbdcccfeef70b121d222b36384a6c8c939498999ee0070f191a294b6983a2a6a9c7cfd3f3f5faff0727315b6a737a8e9bb4d0d6dcdde6fa0000000000000000000000000011213343";
+
+    // Deterministic HashML-DSA-44 signature with SHA-384 over MESSAGE with CONTEXT from the SEED key. OpenSSL 3.5 has no HashML-DSA,
+    // so it was produced by signing the FIPS 204 algorithm 4 message 0x01 || 0x0c || CONTEXT || OID(SHA-384) || SHA-384(MESSAGE) with
+    // `openssl pkeyutl -sign -rawin -pkeyopt message-encoding:0 -pkeyopt deterministic:1`.
+    const MLDSA44_HASH_SHA384_SIGNATURE: &str = "b79d7a593504412ed140d963fb888dfda40d486590db77bbe68a8bac5e79a280ca195321956f7e754fd9f5a2f591f7900f51f424a80d0f9b9a1379d6e9c29a730cad13f890c8a7d7f5a35fc21dfdc9922c346d04dce26aa6a849065ee40e6338dd67e54e937ea6fc9e2d9d9d61b35e0737698ebaaa9186a5070c9ccb4ec9f7e4b6561a28062a2559b8ee0abda640fda84768c9500cf0ba1eed5897dcdf1fa60eb2e4f5e7e60459fd94c97ad08e2327ff992dffbc0812d5089823af27799da606a102aef36e34c1f0cf18d763195d041ff827306492df5b72c4104e0aecc714186de9534332ed7efba80931bfb350566e9d562879cef8012f647a11b9ebf10a29c5997b912679a09a2be70780cc07d3ebc84f6468302478b2a0f4e79b82b3da55a28c1095804cb387845a16762e742e829b3912c4642baf18cc7a0f6626c0bf773ef941f6fa7406e4d93dcda01fe718db28993f4176491f7d975d686359716b106a136df12d8bf1e10728576748525b32df6eae8d9db8c3806f610404a7228672f5b277f7b7006c825943fe6431f7f2383766ff232423faa5be7cb51b96b5edbbd6905fa112c9afd89024b2c3c07a9901263bf5c5a2bbbe21b727f80f6f468cfd618de2840465c9cd11dbdec89a0318dd716a651e32a2b4be7eb2cf04c9a0014b6a7a35a3e14d7c8a492b7890941120195d0a386f6379765725fa7197ff51864fd42321dd10fb7c62733f7b30034d836723f1dcb6eacdbe22d501091154790d10ee3db3ec425e9d9b1a96914584f017c36f8491a662fca4ab4956b7e5c22be2033e27136d2a2e9c34cfb213f65f01c82944168347ea0ca3a660dba9039ed777d00404b6bb46c8b1602d275bcf8ba928378855820c793a1962de5a6a325d5d0b37d435a2e3cb3f5392359a2e98d9283613cf9bf397bdbdada4303f80f62410bcf018f58faba672b7c20d061ae9438193e4e019fb642e01cc8ee49a5bfbbf105e41c89a377f9bceb0ab679103031bc4eb092c0c601a4d2f010031fc30370093879a4265d4b85a0b2cf49e439f8c570ea8cb27a35f2cc0b26cbc2eaec0441f15b736fdddbb58a2d62cb7a0bb2a591273a4981e2b810fde4930a265a42e37a855f5542c11f41a27c7b38b97c86acab3f745f45630ad14a71262251d7395bdd16988a8a2fb24afa885f0ac1f66ee8b1ffadb460b9d2bd8d0d73cc74d79be00c9f6ed437f0a8bb4fe3ff689118d623d469dccc401da21a3561a5f597d8a2105b07cb54622712c0d47bf435af64bfbb1587b4718fa38d52301f5aed7418517cc2fe5966878124c9d5e8fe2879716994bb24f8f47cf1f186ee0a9394498cb5b5c67f79cf3d24ce8f134d37bf7870912642790ee61806bec995faefd9fde7223d2f3121ac8fbe7bb78d6e16a2edb94bcfdd79a4f145e25b3cb040ed3f4215b0338f0760ef5f5e11769e29104d4b5b19241150957035692a67304751c0a1f8f4f8fea1d385847df6259f53a14c551ddd6ae4e16a22334a535faa4d3af83d7279d93747266dbd6d2c2640f63bbd7388b7576939c85c32238617eb1ba5bf70b63ed2c35f2bc6162beb33238944f213474ddee3c7ba08718f3ae173b9dc9c04e3794aa5781350c76950578e02bf2b9e279c190ac2aea274ce87be9fce6aedb444b11913d654d8dcb98b186d572e5f70b2b110ee224d216628582e3e279e4ebcb90ca2e7514601e9072c53fc6dbe503f19ab7c72433a8f2c4d9ad88fe97faee07fb2ba899a9253f8539a363ba1cc8ff79b768804c7f6cff26d634cb98eefcf1f8cc3de5592b6d4fff653724bf2c27b7287af462baa4cf1dccc2d3c1098fe73becf81f6fe30545f8edc13ca8aeb32e84d533fd739f5e6479c5e485d27962f13f5d07bfaf8c969d0b0062f8ebbd08185a27fbc517290af109dc5bb7ecdb932c92e9719adbe02d5265b020082e550054d12ce88bf7e417c4fb06fb06beb108508b6e94ce4557d0494ff8597b933488831a073b565ad4e6b70493c13fde3ba0a469ced0e662c8154ba6e05f1432a3686b0efc95b0b6b19f0fe376a0382b81d2cc83fd8654db62e4ca645033e92858296345529c2b9c2ef8282b8568a4bf3e126e69096967dc044517f149663eba487d58094f7a73ef26b38d5df1b0d589164a51b4627559e60b31c5026f480e78276a92abe2d28e30c679c4514c2016db6ee86ff8c4efd4917587fb83961ee7c939133a4ea043deb65e214279c0d575ffc651355d5c3047f27dca73b314a6a1d74269099acfc3cc7d371c1a7629cc7ce7389c274762fd6495aea2966bd3e09f8cc6343794673459a42b09fe1bc3f321ed482bd95d3caf2eefe9afc19ef79bc455b6f3db20da00977a738a702f01428b1962495ceaf9dac8fa472f9a04ff3512f84998fc1133273f518e9f0a52b1446ecefd47b51f57aebec611292bbd8ea726f4d152103b78d031312bcbb851421f4eb2bc76c1186e0af418ce1dd67bd42acb6b03fa3b456f7b42301b16bc945067916862bdb38cf542c527bc1172835e13c3343feefc3d3143b0fc9f8999c20e251507411619330b2b76bacf7d3e1fd0dbdfc07ef6aaa67486d1bce51e42dc8dfb6f2d0cf5ed7e5f16611ac1479523e74e6e357619c1e4dc3a2d5a080bf8fc8a6ea278212ba96b85a4724da9bac47b0998115bd9c7ab852d94c21d791bb1f85634f2a77acb00a1331fffca1801201a76d50889499c1b4e399915a3a783e989279327351d9da26a92e656d8d567cb88b350813a5d71abd83c6ab477ea4fca753f4be424cb1fbb9957ba9bc3bceddbecd6b26eb0c05a7119e2295de322fa5f1c5d559a79e3489a8caf50e2a2db301ba2edc1c006bcb4297e0d0cfec4531d60279e8bbf750284d210ea606b574d077541a6180b1ee6e920b3051407618a594b34fa63d262b814c7db1e815002a41b1aa19efe0708dfdee56836dc8226af3d738407981cfc7e284aca39bd6a50988600becbe71e95abdd5e8b590cc5eff9121a2e31ee1dc96962b4491a254c61347486989b6d76119d79c27501dbd57cc5d123c5931196566bd1dfb0d577b5c7f6ec9786343fb254f6b21dd8c28df2a4997578b6bb9aa47a6439a7f7143c54f86343f98ad6fb1a1960838e0ac150eb2e5e5848246e9362b314a9d417fd1a880448dabb07c38e450a8aff41cd79683889c115374fd439c29c5eb3d0cd9d64b53ec3f1d2ce00381a6499b7a42a35bd80b15c39ed200429703b1950466540ba8dc11b476d613ea969aefe2641e49939acf510888487f859568f5e3be0dcedcc658e3e293b9c0c373b4054595c61667580bbc2c62142656a708b8c989c9da1c4cbcecfdee4edfb0d203c3e4050566a7b899caec5dde0fd181a3438424953687d7e7f9396a7abaeb2bcbdbed8dbdde1eb0000000000000e21314a";
+
+    fn seed() -> [u8; MLDSA_SEED_SIZE] {
+        hex::decode(SEED).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn test_mldsa_from_seed() {
+        for (params, expected) in [
+            (MlDsaParams::MlDsa44, MLDSA44_PUBLIC_KEY_SHA256),
+            (MlDsaParams::MlDsa65, MLDSA65_PUBLIC_KEY_SHA256),
+            (MlDsaParams::MlDsa87, MLDSA87_PUBLIC_KEY_SHA256),
+        ] {
+            let key = MlDsaKey::from_seed(params, &seed()).unwrap();
+            let public_key = key.public_key_bytes().unwrap();
+
+            assert!(key.has_private_key());
+            assert_eq!(public_key.len(), params.public_key_size());
+            assert_eq!(hex::encode(sha256(&public_key)), expected);
+        }
+    }
+
+    #[test]
+    fn test_mldsa_verify() {
+        let key = MlDsaKey::from_public_key(
+            MlDsaParams::MlDsa44,
+            &hex::decode(MLDSA44_PUBLIC_KEY).unwrap(),
+        )
+        .unwrap();
+        let signature = hex::decode(MLDSA44_SIGNATURE).unwrap();
+
+        assert!(!key.has_private_key());
+        key.verify(MESSAGE, CONTEXT, &signature).unwrap();
+    }
+
+    #[test]
+    fn test_mldsa_sign_deterministic_known_answer() {
+        let key = MlDsaKey::from_seed(MlDsaParams::MlDsa44, &seed()).unwrap();
+
+        assert_eq!(
+            hex::encode(key.public_key_bytes().unwrap()),
+            MLDSA44_PUBLIC_KEY
+        );
+        assert_eq!(
+            hex::encode(key.sign_deterministic(MESSAGE, CONTEXT).unwrap()),
+            MLDSA44_SIGNATURE
+        );
+    }
+
+    #[test]
+    fn test_mldsa_verify_failures() {
+        let key = MlDsaKey::from_public_key(
+            MlDsaParams::MlDsa44,
+            &hex::decode(MLDSA44_PUBLIC_KEY).unwrap(),
+        )
+        .unwrap();
+        let signature = hex::decode(MLDSA44_SIGNATURE).unwrap();
+        let mut modified_signature = signature.clone();
+        modified_signature[50] ^= 1;
+
+        for (message, context, signature) in [
+            (MESSAGE, CONTEXT, &modified_signature[..]),
+            (MESSAGE, CONTEXT, &signature[..signature.len() - 1]),
+            (MESSAGE, &b""[..], &signature[..]),
+            (&b"release-manifest.jsom"[..], CONTEXT, &signature[..]),
+        ] {
+            assert_eq!(
+                key.verify(message, context, signature).unwrap_err(),
+                SymCryptError::SignatureVerificationFailure
+            );
+        }
+    }
+
+    #[test]
+    fn test_mldsa_sign_and_verify() {
+        for params in [
+            MlDsaParams::MlDsa44,
+            MlDsaParams::MlDsa65,
+            MlDsaParams::MlDsa87,
+        ] {
+            let key = MlDsaKey::generate(params).unwrap();
+            let public_key =
+                MlDsaKey::from_public_key(params, &key.public_key_bytes().unwrap()).unwrap();
+
+            let signature = key.sign(MESSAGE, b"").unwrap();
+            assert_eq!(signature.len(), params.signature_size());
+            public_key.verify(MESSAGE, b"", &signature).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_mldsa_deterministic_and_hedged() {
+        let key = MlDsaKey::from_seed(MlDsaParams::MlDsa65, &seed()).unwrap();
+
+        let deterministic_1 = key.sign_deterministic(MESSAGE, CONTEXT).unwrap();
+        let deterministic_2 = key.sign_deterministic(MESSAGE, CONTEXT).unwrap();
+        let hedged_1 = key.sign(MESSAGE, CONTEXT).unwrap();
+        let hedged_2 = key.sign(MESSAGE, CONTEXT).unwrap();
+
+        assert_eq!(deterministic_1, deterministic_2);
+        assert_ne!(hedged_1, hedged_2);
+        for signature in [deterministic_1, hedged_1, hedged_2] {
+            key.verify(MESSAGE, CONTEXT, &signature).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_mldsa_private_key_round_trip() {
+        let key = MlDsaKey::from_seed(MlDsaParams::MlDsa87, &seed()).unwrap();
+        let private_key = key.private_key_bytes().unwrap();
+        assert_eq!(
+            private_key.as_bytes().len(),
+            MlDsaParams::MlDsa87.private_key_size()
+        );
+
+        let imported =
+            MlDsaKey::from_private_key(MlDsaParams::MlDsa87, private_key.as_bytes()).unwrap();
+        assert_eq!(
+            imported.public_key_bytes().unwrap(),
+            key.public_key_bytes().unwrap()
+        );
+        assert_eq!(
+            imported.sign_deterministic(MESSAGE, b"").unwrap(),
+            key.sign_deterministic(MESSAGE, b"").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_mldsa_prehashed() {
+        let key = MlDsaKey::from_seed(MlDsaParams::MlDsa44, &seed()).unwrap();
+        let digest = sha384(MESSAGE);
+
+        let signature = key
+            .sign_prehashed(HashAlgorithm::Sha384, &digest, CONTEXT)
+            .unwrap();
+        key.verify_prehashed(HashAlgorithm::Sha384, &digest, CONTEXT, &signature)
+            .unwrap();
+
+        // HashML-DSA signatures are domain separated from pure ML-DSA and from other hash algorithms.
+        assert_eq!(
+            key.verify(MESSAGE, CONTEXT, &signature).unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+        assert_eq!(
+            key.verify_prehashed(HashAlgorithm::Sha256, &digest[..32], CONTEXT, &signature)
+                .unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+    }
+
+    #[test]
+    fn test_mldsa_verify_prehashed_known_answer() {
+        let key = MlDsaKey::from_public_key(
+            MlDsaParams::MlDsa44,
+            &hex::decode(MLDSA44_PUBLIC_KEY).unwrap(),
+        )
+        .unwrap();
+        let signature = hex::decode(MLDSA44_HASH_SHA384_SIGNATURE).unwrap();
+        let digest = sha384(MESSAGE);
+
+        key.verify_prehashed(HashAlgorithm::Sha384, &digest, CONTEXT, &signature)
+            .unwrap();
+        assert_eq!(
+            key.verify(MESSAGE, CONTEXT, &signature).unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+    }
+
+    #[test]
+    fn test_mldsa_invalid_arguments() {
+        let key = MlDsaKey::from_seed(MlDsaParams::MlDsa44, &seed()).unwrap();
+        let long_context = [0u8; MLDSA_MAX_CONTEXT_SIZE + 1];
+
+        let result = key.sign(MESSAGE, &long_context);
+        assert_eq!(result.unwrap_err(), SymCryptError::InvalidArgument);
+
+        let result = key.sign_prehashed(HashAlgorithm::Sha256, &sha384(MESSAGE), CONTEXT);
+        assert_eq!(result.unwrap_err(), SymCryptError::WrongDataSize);
+
+        let result = MlDsaKey::from_public_key(MlDsaParams::MlDsa65, &[0u8; 1312]);
+        assert_eq!(result.err().unwrap(), SymCryptError::WrongKeySize);
+    }
+
+    #[test]
+    fn test_mldsa_sign_without_private_key() {
+        let key = MlDsaKey::from_public_key(
+            MlDsaParams::MlDsa44,
+            &hex::decode(MLDSA44_PUBLIC_KEY).unwrap(),
+        )
+        .unwrap();
+
+        assert!(key.sign(MESSAGE, CONTEXT).is_err());
+        assert!(key.private_key_bytes().is_err());
+    }
+}
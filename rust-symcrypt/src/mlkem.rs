@@ -0,0 +1,487 @@
+//! ML-KEM functions as defined in FIPS 203. For further documentation please refer to symcrypt.h
+
+use crate::errors::SymCryptError;
+use crate::secret::{SecretArray, SecretBytes};
+use crate::symcrypt_init;
+use symcrypt_sys;
+
+/// Size of the secret that is agreed on by [`MlKemKey::encapsulate()`] and [`MlKemKey::decapsulate()`].
+pub const MLKEM_SHARED_SECRET_SIZE: usize = 32;
+
+/// Size of the private seed d || z that a [`MlKemKey`] can be generated from.
+pub const MLKEM_SEED_SIZE: usize = 64;
+
+/// Size of the randomness m that is used by [`MlKemKey::encapsulate_with_randomness()`].
+pub const MLKEM_ENCAPSULATION_RANDOMNESS_SIZE: usize = 32;
+
+/// [`MlKemParams`] provides an enum of the ML-KEM parameter sets.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum MlKemParams {
+    MlKem512,
+    MlKem768,
+    MlKem1024,
+}
+
+/// Impl for [`MlKemParams`]
+///
+/// [`encapsulation_key_size()`], [`decapsulation_key_size()`] and [`ciphertext_size()`] return the encoded sizes in bytes as defined
+/// in FIPS 203 section 8.
+impl MlKemParams {
+    pub fn encapsulation_key_size(&self) -> usize {
+        match self {
+            MlKemParams::MlKem512 => 800,
+            MlKemParams::MlKem768 => 1184,
+            MlKemParams::MlKem1024 => 1568,
+        }
+    }
+
+    pub fn decapsulation_key_size(&self) -> usize {
+        match self {
+            MlKemParams::MlKem512 => 1632,
+            MlKemParams::MlKem768 => 2400,
+            MlKemParams::MlKem1024 => 3168,
+        }
+    }
+
+    pub fn ciphertext_size(&self) -> usize {
+        match self {
+            MlKemParams::MlKem512 => 768,
+            MlKemParams::MlKem768 => 1088,
+            MlKemParams::MlKem1024 => 1568,
+        }
+    }
+}
+
+/// convert_params takes in the friendly MlKemParams enum and returns the symcrypt equivalent.
+fn convert_params(params: MlKemParams) -> symcrypt_sys::SYMCRYPT_MLKEM_PARAMS {
+    match params {
+        MlKemParams::MlKem512 => {
+            symcrypt_sys::_SYMCRYPT_MLKEM_PARAMS_SYMCRYPT_MLKEM_PARAMS_MLKEM512
+        }
+        MlKemParams::MlKem768 => {
+            symcrypt_sys::_SYMCRYPT_MLKEM_PARAMS_SYMCRYPT_MLKEM_PARAMS_MLKEM768
+        }
+        MlKemParams::MlKem1024 => {
+            symcrypt_sys::_SYMCRYPT_MLKEM_PARAMS_SYMCRYPT_MLKEM_PARAMS_MLKEM1024
+        }
+    }
+}
+
+/// The ML-KEM shared secret. The secret is wiped when it leaves scope.
+pub type MlKemSharedSecret = SecretArray<MLKEM_SHARED_SECRET_SIZE>;
+
+/// An exported ML-KEM decapsulation key. The key is wiped when it leaves scope.
+pub type MlKemDecapsulationKey = SecretBytes;
+
+/// [`MlKemKey`] is a wrapper around symcrypt_sys::PSYMCRYPT_MLKEMKEY.
+///
+/// The key is allocated by SymCrypt and is wiped and freed when MlKemKey leaves scope.
+pub struct MlKemKey {
+    inner: symcrypt_sys::PSYMCRYPT_MLKEMKEY,
+    params: MlKemParams,
+}
+
+/// Impl for [`MlKemKey`]
+///
+/// [`generate()`] returns a new [`MlKemKey`] with a random decapsulation key.
+///
+/// [`from_seed()`] takes in the 64 byte private seed d || z and returns the [`MlKemKey`] that ML-KEM.KeyGen derives from it.
+///
+/// [`from_decapsulation_key()`] and [`from_encapsulation_key()`] take in an encoded key, a key created from an encapsulation key can only
+/// encapsulate.
+///
+/// [`encapsulation_key_bytes()`] returns the encoded encapsulation key. [`decapsulation_key_bytes()`] returns the encoded decapsulation
+/// key, and fails if the key was created from an encapsulation key.
+///
+/// [`encapsulate()`] returns the shared secret and the ciphertext to send to the holder of the decapsulation key.
+///
+/// [`encapsulate_with_randomness()`] is the derandomized ML-KEM.Encaps_internal, and is intended for known-answer tests.
+///
+/// [`decapsulate()`] takes in a ciphertext and returns the shared secret. As specified by FIPS 203 an invalid ciphertext does not fail,
+/// it instead returns an implicit rejection secret that will not match the secret of the sender.
+impl MlKemKey {
+    pub fn generate(params: MlKemParams) -> Result<Self, SymCryptError> {
+        let key = MlKemKey::allocate(params)?;
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptMlKemkeyGenerate(key.inner, 0) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(key),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn from_seed(
+        params: MlKemParams,
+        seed: &[u8; MLKEM_SEED_SIZE],
+    ) -> Result<Self, SymCryptError> {
+        MlKemKey::set_value(
+            params,
+            seed,
+            symcrypt_sys::_SYMCRYPT_MLKEMKEY_FORMAT_SYMCRYPT_MLKEMKEY_FORMAT_PRIVATE_SEED,
+        )
+    }
+
+    pub fn from_decapsulation_key(
+        params: MlKemParams,
+        decapsulation_key: &[u8],
+    ) -> Result<Self, SymCryptError> {
+        if decapsulation_key.len() != params.decapsulation_key_size() {
+            return Err(SymCryptError::WrongKeySize);
+        }
+        MlKemKey::set_value(
+            params,
+            decapsulation_key,
+            symcrypt_sys::_SYMCRYPT_MLKEMKEY_FORMAT_SYMCRYPT_MLKEMKEY_FORMAT_DECAPSULATION_KEY,
+        )
+    }
+
+    pub fn from_encapsulation_key(
+        params: MlKemParams,
+        encapsulation_key: &[u8],
+    ) -> Result<Self, SymCryptError> {
+        if encapsulation_key.len() != params.encapsulation_key_size() {
+            return Err(SymCryptError::WrongKeySize);
+        }
+        MlKemKey::set_value(
+            params,
+            encapsulation_key,
+            symcrypt_sys::_SYMCRYPT_MLKEMKEY_FORMAT_SYMCRYPT_MLKEMKEY_FORMAT_ENCAPSULATION_KEY,
+        )
+    }
+
+    pub fn params(&self) -> MlKemParams {
+        self.params
+    }
+
+    pub fn encapsulation_key_bytes(&self) -> Result<Vec<u8>, SymCryptError> {
+        let mut encapsulation_key = vec![0u8; self.params.encapsulation_key_size()];
+        self.get_value(
+            &mut encapsulation_key,
+            symcrypt_sys::_SYMCRYPT_MLKEMKEY_FORMAT_SYMCRYPT_MLKEMKEY_FORMAT_ENCAPSULATION_KEY,
+        )?;
+        Ok(encapsulation_key)
+    }
+
+    pub fn decapsulation_key_bytes(&self) -> Result<MlKemDecapsulationKey, SymCryptError> {
+        let mut decapsulation_key =
+            MlKemDecapsulationKey::zeroed(self.params.decapsulation_key_size());
+        self.get_value(
+            decapsulation_key.as_mut_bytes(),
+            symcrypt_sys::_SYMCRYPT_MLKEMKEY_FORMAT_SYMCRYPT_MLKEMKEY_FORMAT_DECAPSULATION_KEY,
+        )?;
+        Ok(decapsulation_key)
+    }
+
+    pub fn encapsulate(&self) -> Result<(MlKemSharedSecret, Vec<u8>), SymCryptError> {
+        let mut secret = MlKemSharedSecret::zeroed();
+        let mut ciphertext = vec![0u8; self.params.ciphertext_size()];
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptMlKemEncapsulate(
+                self.inner,
+                secret.as_mut_bytes().as_mut_ptr(),
+                secret.len() as symcrypt_sys::SIZE_T,
+                ciphertext.as_mut_ptr(),
+                ciphertext.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok((secret, ciphertext)),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn encapsulate_with_randomness(
+        &self,
+        randomness: &[u8; MLKEM_ENCAPSULATION_RANDOMNESS_SIZE],
+    ) -> Result<(MlKemSharedSecret, Vec<u8>), SymCryptError> {
+        let mut secret = MlKemSharedSecret::zeroed();
+        let mut ciphertext = vec![0u8; self.params.ciphertext_size()];
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptMlKemEncapsulateEx(
+                self.inner,
+                randomness.as_ptr(),
+                randomness.len() as symcrypt_sys::SIZE_T,
+                secret.as_mut_bytes().as_mut_ptr(),
+                secret.len() as symcrypt_sys::SIZE_T,
+                ciphertext.as_mut_ptr(),
+                ciphertext.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok((secret, ciphertext)),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn decapsulate(&self, ciphertext: &[u8]) -> Result<MlKemSharedSecret, SymCryptError> {
+        if ciphertext.len() != self.params.ciphertext_size() {
+            return Err(SymCryptError::WrongDataSize);
+        }
+        let mut secret = MlKemSharedSecret::zeroed();
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptMlKemDecapsulate(
+                self.inner,
+                ciphertext.as_ptr(),
+                ciphertext.len() as symcrypt_sys::SIZE_T,
+                secret.as_mut_bytes().as_mut_ptr(),
+                secret.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(secret),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    // SymCryptInit must be called before any ML-KEM operations are performed.
+    fn allocate(params: MlKemParams) -> Result<Self, SymCryptError> {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_init(); // Will only init once, subsequent calls to symcrypt_init() will be no-ops.
+
+            let key_ptr = symcrypt_sys::SymCryptMlKemkeyAllocate(convert_params(params));
+            if key_ptr.is_null() {
+                return Err(SymCryptError::MemoryAllocationFailure);
+            }
+            Ok(MlKemKey {
+                inner: key_ptr,
+                params,
+            })
+        }
+    }
+
+    fn set_value(
+        params: MlKemParams,
+        key: &[u8],
+        format: symcrypt_sys::SYMCRYPT_MLKEMKEY_FORMAT,
+    ) -> Result<Self, SymCryptError> {
+        let mlkem_key = MlKemKey::allocate(params)?;
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptMlKemkeySetValue(
+                key.as_ptr(),
+                key.len() as symcrypt_sys::SIZE_T,
+                format,
+                0,
+                mlkem_key.inner,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(mlkem_key),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    fn get_value(
+        &self,
+        buffer: &mut [u8],
+        format: symcrypt_sys::SYMCRYPT_MLKEMKEY_FORMAT,
+    ) -> Result<(), SymCryptError> {
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptMlKemkeyGetValue(
+                self.inner,
+                buffer.as_mut_ptr(),
+                buffer.len() as symcrypt_sys::SIZE_T,
+                format,
+                0, // No flags allowed
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(()),
+                err => Err(err.into()),
+            }
+        }
+    }
+}
+
+unsafe impl Send for MlKemKey {
+    // The key is only referenced through the SymCrypt allocated pointer, which does not move.
+}
+
+unsafe impl Sync for MlKemKey {
+    // Encapsulation and decapsulation only read the key.
+}
+
+impl Drop for MlKemKey {
+    fn drop(&mut self) {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptMlKemkeyFree(self.inner);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Known answers for ML-KEM.KeyGen_internal with seed d || z = 00..3f, ML-KEM.Encaps_internal with randomness m = 40..5f and
+    // ML-KEM.Decaps_internal of that ciphertext with its first bit flipped. They were produced by OpenSSL 3.5 with
+    // `openssl genpkey -algorithm ML-KEM-<n> -pkeyopt hexseed:00..3f`, `openssl pkeyutl -encap -pkeyopt hexikme:40..5f` and
+    // `openssl pkeyutl -decap`.
+    struct KnownAnswer {
+        params: MlKemParams,
+        encapsulation_key: &'static str,
+        decapsulation_key: &'static str,
+        ciphertext: &'static str,
+        shared_secret: &'static str,
+        implicit_rejection_secret: &'static str,
+    }
+
+    const KNOWN_ANSWERS: [KnownAnswer; 3] = [
+        KnownAnswer {
+            params: MlKemParams::MlKem512,
+            encapsulation_key: "3995815e597d104355cf29aa5333c93251869d5bcdbe487124f602b8b6a66c16c4761648ad765cf5d8006b515e905a7f0ac076b0c62efa328153e7ca5701699f1305f1e6bc6f90b0e49b693512b6ce992a8b8016ddfc1a662c7e3f9619cbd869dd771af30896ccd5918ac6cb77466c5e779996d67ff9aabc97503f2c7b7e2d000d86450fb1807ca4cabda465825a31c789a1b7a491ab3872765d320d0b71920fa213c94093416b83b8124e69f65e62cb5000dcc37aa9a0fff73970c4772f357d24189ca6f5305568c0e2376a3762a68c605e563c5d209572e0fc7532ca294729535567b5fc413c5e8792d2464536cc808f98add74664f141566f9016a90a541829a98a0464ce41a8bb44c2d4fa3c2c209460728ef14a1a7c4c9b98d12203b4cc3529160a9ab2d7838f7ff6b53ae05aa31a7d646b7afa6c45932526a3c3755619be994c211c2a31c05b3447836cb2150be1829dae6b04c5535cff546e392ba797411720f924f490a5ac5495f21356d550b782a64c1688b6b655bcc7842197a434c2f6563b5b7f09a78bcc488232783561d16f4cbab6755400050781570c66604b817ad1252294736e8b01861a4b5a74519b8b6fe51489a5072392e587626c713776575d33806a1c8e2732af97c2680f51666331c4eb8bbc0431c4f96832daf1b3c45528fba153f6c78b1c198702947ccd337727a46fb53ba11de5cb4191346859516cb6ad72400f3cf209b236aef35a580ac87eb3e30fafd66973ca8a7dd2675af41f7a17b61433cd1af80f7708869f665488497980b1ac10a0cdcb636a00ed8681b35e429124ca80350725b85f83a5eac3a4a3cc1600903e65293560b9b336e5af0d529dac1a048119302cb7a9bcc110b94851bf02117f199dc485a852b7473f09b831a6831d5b54c0b790d225cf6bb92d9462a26cdb33dda5123c7aaf0e26a0b83655eea28bf3a8074725018fd6bae4b601cf61baab71a7a3d35197a343e74b4a272c125d540896426d85b7958d3b38a6ba987ec37225c7b44cdb12dde4539b4ab082363683f04bf7a09cc5c41dfe830a1b162e0b324334362f084a14467723344badd000f8d8c537c48f998f05307cebd1ede0b81c3bc59a065a1b6d63b26c",
+            decapsulation_key: "70554fd436344f2785b1b3b1bac184b6679003336c26f15a7de878c4825c6be03f3c4a480f75b7486aad31d3a00518623fd207ab528dd62721495835ae0062c367b74a71baf10aad0e8a2902076be31348beb15ccc0957cdebb4aff226756bbc601b6568ab784acbaeb34702f0f86a26202118b22b23f83558776c79c14dba983379c803e0dcc3160a11757030e69c6919798d81eb698a9a4483a99e5a5cb2c31c9a661799f3cc89c790706ea041629045d42a83aed88860e394c69187e2105d28cc14ec393592d67dd00aa43fe8b4eae4414002866b5c713c6a8d7d16cf78b819d6f12e9e5a74233908f0b15e3c4ba8329c5cdda55c84928e3aa8063e5aa9676403f91735b11010c7f593091364dc86445bc804840a9a21724212469f8a7b0ce0ac698eb86cad39a7f4824d9a5163aac21ee6808b053c8a3facb0b6744b5262bbcb26a43f664c8732b64cfc7acf099605f41c796060976ac433833fe00343fb1828300a424741116e4b45bb276ea81129a0db4c6e60bce611101e8c625474925e0222679308a3e7708d1972a7b423eb232851c36d2ed53d3ed3bb7500637061a5dc2292fa1c466c07354683328bec2c1ed2cb5c99b78eca0969038cf7c34dd118724e31cae086206b34302b520f5d177aded5b3cce02acce808ea26bcc072625fdb93f17458a5fc1d4da394380a1f57e9cc66109438a075f0d2813fcc4a199cc76db3823f270b0061594192940411a37ffbafae2c150165cec5c6bf73c595fb92cd15312607da070778652bd9944bc48bc7d1a534338bad0bad6656c5d502ce7850ab1587244eeb58f439ab5e08574a718c8aac3d77c798bba1542733be73448f23fb70c0e5353a27c88322c5218493afbb38086434d6d60a56ba887dd498c3ab26a0870993815aa6a40975f218adca1582d64ffc8652fbb3a9a6fbc304f91945fa4aaef2878fd715df70113d2379f44886f812c83ff2b719a69e1ec74ae4b15accd3aed5a53ce76a7b0982471633b973cb40a1a0015d0a424fa11a479c023017436d2a2900e993eb5a0a067400c7f4aadf201fc4fa31264a63bae95cc8d65c3995815e597d104355cf29aa5333c93251869d5bcdbe487124f602b8b6a66c16c4761648ad765cf5d8006b515e905a7f0ac076b0c62efa328153e7ca5701699f1305f1e6bc6f90b0e49b693512b6ce992a8b8016ddfc1a662c7e3f9619cbd869dd771af30896ccd5918ac6cb77466c5e779996d67ff9aabc97503f2c7b7e2d000d86450fb1807ca4cabda465825a31c789a1b7a491ab3872765d320d0b71920fa213c94093416b83b8124e69f65e62cb5000dcc37aa9a0fff73970c4772f357d24189ca6f5305568c0e2376a3762a68c605e563c5d209572e0fc7532ca294729535567b5fc413c5e8792d2464536cc808f98add74664f141566f9016a90a541829a98a0464ce41a8bb44c2d4fa3c2c209460728ef14a1a7c4c9b98d12203b4cc3529160a9ab2d7838f7ff6b53ae05aa31a7d646b7afa6c45932526a3c3755619be994c211c2a31c05b3447836cb2150be1829dae6b04c5535cff546e392ba797411720f924f490a5ac5495f21356d550b782a64c1688b6b655bcc7842197a434c2f6563b5b7f09a78bcc488232783561d16f4cbab6755400050781570c66604b817ad1252294736e8b01861a4b5a74519b8b6fe51489a5072392e587626c713776575d33806a1c8e2732af97c2680f51666331c4eb8bbc0431c4f96832daf1b3c45528fba153f6c78b1c198702947ccd337727a46fb53ba11de5cb4191346859516cb6ad72400f3cf209b236aef35a580ac87eb3e30fafd66973ca8a7dd2675af41f7a17b61433cd1af80f7708869f665488497980b1ac10a0cdcb636a00ed8681b35e429124ca80350725b85f83a5eac3a4a3cc1600903e65293560b9b336e5af0d529dac1a048119302cb7a9bcc110b94851bf02117f199dc485a852b7473f09b831a6831d5b54c0b790d225cf6bb92d9462a26cdb33dda5123c7aaf0e26a0b83655eea28bf3a8074725018fd6bae4b601cf61baab71a7a3d35197a343e74b4a272c125d540896426d85b7958d3b38a6ba987ec37225c7b44cdb12dde4539b4ab082363683f04bf7a09cc5c41dfe830a1b162e0b324334362f084a14467723344badd000f8d8c537c48f998f05307cebd1ede0b81c3bc59a065a1b6d63b26c82f101ff648063b376e2bb6c5b7455f655a50c2feadade150efa0e0e6f365aea202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f",
+            ciphertext: "90d378b38f7fa38da486daeb005af67e384ffd83661756744487b4da04cf8ac945e990a0b7b9eaece37d10376a71a7050ad4d081d5f169d1707b7d58eb8ec35f081ffc4cb0ef2fe5fde8fb1c6a63ac64fcc235f93049b57827921e1c3148ecf7e847618f0b3b98ff4eff8cc69cfd9f19b15ddd47b7e977c373bba6c0f40b097096ee135aa6878f75a16dcea34588b51013ec3751b3f27ba60358fe18bb2680f96277da9b17eaae5feb51cacb8d05017686930dceda16ad78dfe9758dc2a7f645ab3586fbafcd1789cf662dce23d48250c9dc97baaab541d23b259fc68132b4ffdd5e92270f3cd057549416017f9ea88d97d71f8ed9870a4ede980614bc831a57a6d03e0588c5b2a3bad56d72c75537176297f26af50b780cee129a17fc95178462569a3620095e10c1bccb2ef84f6baeb2515cc2cd80d3229ecdc8d96abbc3078fa998d191d2b6b65402531f4a357c56a3233be4fa841c0c5b597cd3db0f3d7005b10db0bd4463128606a89a115cb0c1f36b3caabc1f4e0fadf6883bcb92515d84fe0d9d89b5c0e9d95b57625c51392018a3132fcbc3293a4cc457829a05053bbcd3d9a4bf4083d90eb4cc6404a261280d3c6d382107d79b51949211f71f5aa81b5a49575995b9de0303fb78a2054d9122bdc93ad6905170e0fce6001ce4df1ed585ddaf3d55a2675f74d07d640814b6e767a197b9b35f327cdf4953914732f9cd15a27b64436ecc7bceeb6a611fd7f8b918b7f32e3aabc3996b08a5990c89731027b9900e2a250e8de7aacb841f4aa44d059faf09e7214e02e454b4c0140dd935211448724e9217b92bd87e803ec6b612be1051f556e6a5ccab99adc770173744cd8f6a38826cf923ab35fc520bd12ffc1ae7d7c942191f0cbd9f80aaa25143859dc1eddff822308aeb2b030b9343790baad00d352202c5c43b47b6d75efe3f4f9b551db208f19bfc01b8f86244072832baa09223766df0a2c5f437bced624279f9fbc3d09f2cc7c04e8066983d31bc20f8b113155a32f950b2d1b2322dbf54ec3a39c2325e4a99f72f1a36213528d1d67d3cf48560498ca8c8edf4ad672a7a",
+            shared_secret: "14cace3e48771b316676afad2cfcfe8488daaa4fad954e57236caa3f24a42cf7",
+            implicit_rejection_secret: "32ee1fb3f7bd2915218e9c1b2d0d2da88f0edce6804278bab3a6123c5bb64fc4",
+        },
+        KnownAnswer {
+            params: MlKemParams::MlKem768,
+            encapsulation_key: "298aa10d423c8dda069d02bc59e6cdf03a096b8b3da4cab9b80ca4a14907672ccef1ec4faf234a0bc5b7e9d473f2b3133b3b26a1d175cb67a7805919699c02f76531b99c5f89180704bb4ca4535c5b8972679c660a07c5e514b87009c862eb8f5157695efb3fc40a9def6b81c1cc02a249ae4f094ad0d9bd3485c1c1c68080520a7c8c632032cee738154e5c5176c07da56024776a430fe76eacf665a3f7b832102215bc82f10939c8355704336a8fac1d81e4bb0485aa5d7c74d6b59bbe5c5e972a0d8bac411b55b5d5557cd680a1a8f71b4eb86bc48c9a0509731a54bd9d7290b27963e4372dc9b199cfdcac0b01acd28a62395112e4c43648d622c48c8234d01440e8cc376c927f23a5afc9ac0474c662274e424525c8552ece3b3fe26516de901bc7d515bde89558e626c95c80b93342f8010004f39e6c6c94871c5e344cab3966c835f9a96a59afd31c40286b38b1c1a78470bab947518934453ce86736a919f1f5a6d510a86f5454fc3980cb5c765bd2bd5f7b36b1410d6635c8ceb47c4dda0d76a28eac939c71c3024804866c71626658442163c2c22117e50acefce6378a985652302a4ef0c2ce0cc716b7796e2b6b2e3777dfa1ac3da259a31b5a9b530f8cb638a81a62ac301849abaf95a7301bda30068909bfdb7e67dbccbb38a5551a25b1a3a0f685748ad5753d8880f0016c627486166384c5571fe2365900364d038311e2d875db366686932b5ec602430a369e87a6ef5c338786657825bd4c057aceb923eb0935e6905e63b4ced7f80857a773dd64b150d26612ea9ac12052db2017bf1843ccb4b3281b690dc728adfa85c00281b8e3c09287335f856b4fc2892f69a2f57921ada01914c40988662d57769662a786351b9b66493dab79594d986de2100d65ba0ff4ea58b81538d24a4435a258fac25404aa7f41f658b1385065e158dcb60115732720f40459aaac15e406953a90ac52997d1ccd070060efc65db9e653354467fad56ec713c86e7540c423acf2669f52fa6f4ac6888d871ef3e847c029a8aafbb92e17b24aa079b1f419ba6175b442afb11909d4a56b70a0335b28739218aa7c9348e2c3c2f3eb3d15a41e6417c0dd94bfeb21419b311a7bb13a180bbe833218a9a6b17447cc85f225859587a73077049acbcfd44d0f025438e15d1538270d586e1bf83192a9459cf63c0e972f85297679831ecf121509851cb8340f6f107b0fa1a0efd1b36a8189bc085c4f5cb784e553f41b918f80397ce1956f785bee377ca9aa8be6998ada30c26b7c3d8c6b55254cc96203b20c42aee0ac4e1ebb408e49a9e3f879d0ab0785eb7025425d1305a2299c015e120d163b0e19494ce57253d0246d182745cb8197ab7438b3c1bb7972bec5a306eba3567855c014699fef65ae54c770a0d85c18400cf642aedc660777ba4b138502bd5a7812f621f84a48296b98dd4322b6f15828b8a8f0e00a8ba44a53c3a8b143571b0740abd567daf1cde9c79c204b6d5e259d1766a31bbbcb4e6a05cf4502176b301c1c2f41247750157bcec85e809b30a4d60d7747cdd0f5b99aa8c826987517793aaa8080a0b124a8558df72bbe37b75f4edbb6be8216d6c633fb2b2280e25113d8695e43481c3eeb397eb192505229b67a201ea893c3e2cb32da8bc342fa4dea0578",
+            decapsulation_key: "27d2a77f33756f61208ef113abe82595873d4abc730e5b5d679529bf6a4ceb6383427231a8612f41550515acba52e48ead8b942833bbe6865d13d14a79d2c5c3e07f0a056d8de7aadfcaba058c493c80b37cab8c562753bb3ba6b6ec8297f885eaa7540d530015a84406e55b1366b577e236ce58a26d8a1eb5a44d542323c2167d9bf4a47f985699ca05bae43b8dec617f02380a3890afd4b8c7ec7ede26553a025f3ce5bc5d7a62130304235cb1ad4836b566b5b863bd9bdb45a2844a7047b6c8d383e448525e040b4dc8a2b48c6c37c96d62d43f3fd88e2881c40a205c9e248f652b592781a779f86880f2a147b67863f391cc1a5a908c0095e07212291e2ef8a36eb9a9c0c6073225b34703a4af049382c47573da68fde9245ad444e31b1fbdb521f1f61f37bc0cef292067e670d28a1ffd904f6f1190a996918a13037a6cabf3c373bf8296cd37ab33ba7746809cc3f8ade1b3639bd57bfcc69650aaaf1de198fc4c0463299e52c461780cc428fc5d04a5c51850cba6c2a5274340675793dda09be44c29e6395c65f85d2a0a7c6df411e6911b1f2cb6c351cd2e875f51b638be776097e93e2f2b2f83da0beef4aa85ba9e763ab64502a0ca5222e9eab5b3b7088ed52060e8c8269b943a71ab0ae1c5b1b687d2e019cf8036bcf9bf6e7bac3aaa36e41660faa4540f2648cd93a189ec5c2dea70bacaaa4ffc906f90810ea1b67bf24f2c78cf6ba881aaea61c0652bff95b1bae4426d1773b9cc2ca82c21e38c636e3b1c523244986b0be8a83f5dd5cf2d54762fb3c5ebf59b8e885302b1ce47033edf760f4e029be40b6d566b19dd758acd5c7412878131244f90172c53f26663c21d905301d48baf91c917cc7779e9d8802cc10d89a3705099a2ad3a3a8896743c1144698093be257dacb66dc785228b912c8d965d14aa28342c3ac4a93fefa532b20945ddc1020139c14d638b908c4ddde9a0645b95b2e4414d40bb79f04413830f15a873c28bb7059c2741002015f20408f058e715b0bf995b5380b7dd325a056ab97e659a2be0cdf6c33731c683a634b771e8c92a139aee4bb0e49c7077321d42fc199f7c1f298ca625d223a5c263a03cc48159b7812665b78637e4e18720b2c29a6b99f42766a4cbc4dc508ba94ba83b89c3a5c78f8bb26bbd9b79beb8c8182490f5793ee5b96013b74b7e169e29d162f1315464ea7d72436d89b755161192c81cc2dd1c8b8bba795ef426ee1cc01c37aaa37b2cff8b0a378b47cbd0b4d49398cfc2712959699fa0bd8cd84666acc61f541b84fa96b9c854e4e75e9144addb44b8566a57dfbb545ce423c03346f2b2c1a91780d152a8de1a4d4c9cacde7392c996888cc2399c02c38b3353adf8acab283924da00a05b76e738c72c930d6cba09ae168990faa1fef2226e780861d416eff402f4f759fc648ab1f97100109087f96e4b148d2cb31e4805314ea0cd95fb023eac0d989474ba4201d7b41d26f5394b217eea5b34b71a8b37931c0e594271e0b7c733257240233e7ba735603e425a87dee77079e37cb28a21764594ce5350d8da2b62a07174943032ec89c98809c73b6423d30c1d283a766a64d89703c3d629b497828d48320c346210797a298aa10d423c8dda069d02bc59e6cdf03a096b8b3da4cab9b80ca4a14907672ccef1ec4faf234a0bc5b7e9d473f2b3133b3b26a1d175cb67a7805919699c02f76531b99c5f89180704bb4ca4535c5b8972679c660a07c5e514b87009c862eb8f5157695efb3fc40a9def6b81c1cc02a249ae4f094ad0d9bd3485c1c1c68080520a7c8c632032cee738154e5c5176c07da56024776a430fe76eacf665a3f7b832102215bc82f10939c8355704336a8fac1d81e4bb0485aa5d7c74d6b59bbe5c5e972a0d8bac411b55b5d5557cd680a1a8f71b4eb86bc48c9a0509731a54bd9d7290b27963e4372dc9b199cfdcac0b01acd28a62395112e4c43648d622c48c8234d01440e8cc376c927f23a5afc9ac0474c662274e424525c8552ece3b3fe26516de901bc7d515bde89558e626c95c80b93342f8010004f39e6c6c94871c5e344cab3966c835f9a96a59afd31c40286b38b1c1a78470bab947518934453ce86736a919f1f5a6d510a86f5454fc3980cb5c765bd2bd5f7b36b1410d6635c8ceb47c4dda0d76a28eac939c71c3024804866c71626658442163c2c22117e50acefce6378a985652302a4ef0c2ce0cc716b7796e2b6b2e3777dfa1ac3da259a31b5a9b530f8cb638a81a62ac301849abaf95a7301bda30068909bfdb7e67dbccbb38a5551a25b1a3a0f685748ad5753d8880f0016c627486166384c5571fe2365900364d038311e2d875db366686932b5ec602430a369e87a6ef5c338786657825bd4c057aceb923eb0935e6905e63b4ced7f80857a773dd64b150d26612ea9ac12052db2017bf1843ccb4b3281b690dc728adfa85c00281b8e3c09287335f856b4fc2892f69a2f57921ada01914c40988662d57769662a786351b9b66493dab79594d986de2100d65ba0ff4ea58b81538d24a4435a258fac25404aa7f41f658b1385065e158dcb60115732720f40459aaac15e406953a90ac52997d1ccd070060efc65db9e653354467fad56ec713c86e7540c423acf2669f52fa6f4ac6888d871ef3e847c029a8aafbb92e17b24aa079b1f419ba6175b442afb11909d4a56b70a0335b28739218aa7c9348e2c3c2f3eb3d15a41e6417c0dd94bfeb21419b311a7bb13a180bbe833218a9a6b17447cc85f225859587a73077049acbcfd44d0f025438e15d1538270d586e1bf83192a9459cf63c0e972f85297679831ecf121509851cb8340f6f107b0fa1a0efd1b36a8189bc085c4f5cb784e553f41b918f80397ce1956f785bee377ca9aa8be6998ada30c26b7c3d8c6b55254cc96203b20c42aee0ac4e1ebb408e49a9e3f879d0ab0785eb7025425d1305a2299c015e120d163b0e19494ce57253d0246d182745cb8197ab7438b3c1bb7972bec5a306eba3567855c014699fef65ae54c770a0d85c18400cf642aedc660777ba4b138502bd5a7812f621f84a48296b98dd4322b6f15828b8a8f0e00a8ba44a53c3a8b143571b0740abd567daf1cde9c79c204b6d5e259d1766a31bbbcb4e6a05cf4502176b301c1c2f41247750157bcec85e809b30a4d60d7747cdd0f5b99aa8c826987517793aaa8080a0b124a8558df72bbe37b75f4edbb6be8216d6c633fb2b2280e25113d8695e43481c3eeb397eb192505229b67a201ea893c3e2cb32da8bc342fa4dea0578a24e16d8f8f9383a95b77050f4d9fd2f5733eec1d63ef3c23ebf9918173669a7202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f",
+            ciphertext: "695a60d9c79f08343ed9ff5802582063c2ca3a648e543d924affbb39ef4de656591f0d7689e6626be7ea7fedaf134e2c27c6797c73a5edaf16808f141c8afcf31614e8ab665379573e4d0a2037cbf776048167ba53576001a2596402cf24b5d45362bc893ceaef3599f76b10812e626002e66db5c5b0f2b9a7080e32db68dcc8d04c24f8461a58bb7e47efe670d740ad8af9820033845ef5f880f26f0e00adb2abef876f5270477ebbb02de6787ce72ca8785fb181f46c3ff7ae3787c25c68ccceefb3551875b9d77c4d439b6050eb382aacf9e744227e8c46e0a9a55838ea7034f5b4bcb61f1023a80186e795f4b3d8ae93988994224fa2d83e21711670da01e2b3e272f81616c0bc88cc46f641d16e0d0c0924cf4a4a5c1a9128c226d4918aa39bef94199dfffa33876ef0bfa0d9560d25f5ba08068d5271f32d2f9d88bcf53c7dcf811a8d5efe617f5e05700d3478d3cb7932528d1bceb240198a4cf8752caea3d387f00759a1356b7a5bf1838d26c3573e92e69f0f57c06e8c25459eb83e12cdd75f541a81ce710eafce2984783f30e37b327ff93b72297c6cd8c78c185ad53864952069d7d6c3bc633ae5e1a5925855df0b7e714bbde245f68822e0950c23c96d6111753a6ed0c46cce437f53b6bb708c1a3e25979733198d9879e3237e769471f922e579f37cfd641d29bdcfdbaa81edae09aeb046366e0376d04282d17778a8d54774e8c9be3c822b1e90cd8895abc1db8951b7687f63fee50ec43faf23730b15189e7c982b22d896a972da3c2ee529bb5fe63630c9c2ddfb9d1e4263a3d49af2832053d97efa2bd1782f25d7b864d6fb3708bfb9d4bc6c2cc6458d4f1459995db387e8b503825a4496c735252aa630a1bcaa7a2674727396dcaf67030b53473951651dc26c22476bfd11d33206af0ff035ed035e34716c905e8ddf043a4cdae145238d8f612dbcb75e879653bb9e2657dab58b944ff34f977fe15ce907f6814a5f92338774e6f2ab5257d24917decdd158c6d4594189f42a9b7fa9159a8af6aa825ba904654e08c894901298ffb27239ddea8283dd45b876036c0aecf03583ba444529757444c857fff6e4f8ed48f8a180adea54979a678f16dc6ac8edcc8e72ed08e96082f0ff4520dc635d4a846a3026fd86a48b1297e0cdfc06008793e783bde1c3fc6a71871e66b1feb560495817aabbdc59f0149f3e76add9b5bd6ce34734de7593ed607efb84c6e732960c744c908a9cb8947375a55b55fa2f0cd6742b75c10f65522d3844bed9b05bd441bbbea17cfbabdaef9847a0edd9c8329a762e34e5396014d88b4d344f250aaddefd917bb2120d1169c79cb09f59bad21850752c1099fff98b71bcdaab76f7063323e78faa521cd243f74ddc7f7775aa79960622e13580a6831e69bb7f2321d141d35da88317719078d4db319f308594c26836503f62362c40005022937c1298a928c040879661349a7b5362d0a75f2893b97a2600d5337239a70a6b64a457e6dfd5c74d462e7e790bb9ef3cee1461",
+            shared_secret: "9cddd089ffe70e3996e76f7c8d06746df34d07e8657bc0fcf2bb0e1c3084aea1",
+            implicit_rejection_secret: "dcfc80c6db46ff7028e3a4398651c063ae7a42c107a6dc8cb07141861698ab92",
+        },
+        KnownAnswer {
+            params: MlKemParams::MlKem1024,
+            encapsulation_key: "4b94c29450111191823b3514c9ac1ea3d9825ccb86393a2dfb04654fa2192d37bfad1c497c6502eee5ca80a73bfce0baf5a54a88585a401397a3d232f426a7afb082bc21a44317090eaac7592c2ea88a653c4491ea193931335f52e989a3c4cc56d9c553732d57c470fb41ab759b65d2d04445382fcd9c4e344a1128fa9e11e04358e192ed014b23232a7ee2b22e23717f44111ee33575399c37646da9813ec9b212afe94e5dc5c2330a7294cc1f4234a6d3fbb4f1685ab8892c04acb17cd1c170d7b0611b6a7176c794cc8c67f55fc923c2ad203100f365991882c30243d77813843b5ec7c964032263706092ecf00c7516be64e4598ca4226c069bb5e67e4175cf2286c8dd5c488a6c5861f31baa0bd0269470e8b551dd3bcd38c86c12f9cdb176c77dc8b6c02a701f478902c8553f694c0d82727b4c4a5c2c1041212aa1274808b82111b377ec75214e9b1978f76004d4139d98613f4b8e98d20af7b534073a509a959b7a7564f9b40ca218bf61829320a8502017954d328d7ac6c769ec29700756e7b0685b340d5e118059504a49a9a50a10198eb10a5784678eb427d7b4babb9552933b062897973e1318eaf0a0eac37584a65401b1703e042accd837531483f241cadcd1c1d378119e694429db199ac891e4c5343757085bb3ae783667350c4458d97672e861e80b1d2679510ea3a6f2360c77a46942c7a06a554d228080c84b47aef14db17620cb16c06ab30a1be4cda7082be9f87e9c211c46916349a5ba8eaa5201c7294a3c0885b53b657452108825ec646c90a04612324ee7d031afe5343132cbef67b6efb1a5ec2809b773538ce77b3d8b04eb0b3c2256011e4c716c19a8ba0752bf71492117649f0615c3290fc29a46fde4bd52db9286d603388244259c15a7ac2b640a60cc03376a5841a3fb8a473568fa9b1a267215f34c01697b0f0e627175d72105b7707c29b9e614bdc33a6f6c818a95370b427882d7b476796a9ec6eb993274cd9b2391a82ba45e3393d2e9ae9721ca9d6c1b988b5827713f90a6585de9433528c02b03ce10bb5f720138d0fbb4c30c1266b918e52925dfe17b37f95d22bca54f475919ac859098c0f0d08ac5875ef29b56fd141e6ef15f700a0b66f39595c588177373c4669b21bc071e4c3aa5f0b4a31b6258f35da24ac3cd29c7f2092410c5078355b138fb53a6b9ae6e0b9c08243e7baa45c47376eb8c7f13d4cf51aa736fa31540c9241f370da544bf9f9c28d9a57e2f2a7ca95a4e4b466e641ab3bcc76adf1139d567a6f12b52f3a65e7ec0aae26bcaa8c55833b04e59998ebc9a1930fbb6d2233c53d2c1f8b9518e3c2de73a19dee6b380a5b32971cf64e129fd6c1fa6e75d4a234501e966dd3a540af5c8f4f34a6b4a253ee28492566d5e67c6f55855fcb0506fb06c156744d9a03a31a26fa94cad14f157b7f303d07a69c773768fcb4d079c09059703a0c3a94de4b99ea3a2f16583d0f9170a3950db07b4f0bc30802927f9f7961b6259892636a9502a2705303637799dd344da451c1cf7bf67840ceb3079ab8c6b8c1927f64053c612450c45c9e603bc16666e596b3471e103b6f15447424d17022048111ffbd37e1c670f64f14b8a7b32b94c1a49b45dd2fc38cd5289d910ad63602cf5e13042c64ac6797b89fb551ad08e05a92d200cccb7e712ef23c9312cb350f029ab537e287347fd3075ac10906a783f1c6c07ccb88f41228c4be1c640f790b5c3a5d5d3ca792495d74bc461562658c07ac600276b924ab5bc9be1f0494cb76f82f460a7480972663381e169996061d799859ec54d4f5ca5c411c01db1597b165977669de13a928a34afbac258fea8c4764239c9421dc3119bf5b47699206978327b1c5345ef746a7983841f056e2534100ab24d4e9abbd0b17c6a95bd4c3c0e40f69e1612aceeb28b99086c95116e7204273893390bf46b899b36286b0ebf1947bb9884f732ca27da82b19b5dc0cc7f8885714910888b2310c4f9319d410b34e6433b9003e2176bb995257456106e8952163b8ba592530cc5aa0aeb43ad398fe9e97baa523d7a4431677c3d3af0719e475db85ca95af5089beabeb05b2faab4896ba60f81c88472a57b46a828826a0cdfb446f8189182d2bf5eac4ec1cc5deaf599c8a13e48235406d17ffddc8344b6c66984a868aa92fa02227a086950eb0c8701ed58dc628776b983882e1175",
+            decapsulation_key: "f77b7f6b15c73fe2cc546b67fb774ca19b42cd463ea9fbb984ca477a77b6c71087cbf051abe4736a9072c6e870c8311c55963f500a3c7b1b8f2a58558f49c62527b6c594b5e7acb3bcf597273a5743517d151208bd4aa61e75ba67b0bd594a994919627ac0a804d489e171336bc339f4666706e5134412b366823d50318c8bf261ab120a28a04fec01cc15f2b71912cee54aa8eed854694b6ba886b5eb7661e6d56aac213cc1d814d592b395554fae74476d34371163129bf864527250606cc21a53746b20997077bba155733b28a4e7fa0776399524763eb481ceaa11366c3474a04685f40c3f08b0424f40bff949a0ac92704c3ba0c6eb36f1f5b621d8bf2b6327beb57cd3facb94186fe3fc9ab0a1434bb291d2c9bb70723057e2254059656f565919a32cf74579de89681cd2c5a935a52b4aaa2d24cb5d5c9e20729ec5492ec36961efb8a28cbc00ac303523295f3d8036abc1603307ce70d7848a35657a5687dd589927ea63731626abb26ec4e431b8eb6b3b0bc1e82573ee73b1a021183183528108ae2eacaddb95b464a0b98469c319cc27bfa01bc31054a68c05502b1662b879fe98a1711c3426f6436cb0214cea379ac3a7e5fb60184a37c1da1eda61c6c39c1dd4e847845811f2a358a43731528536d4a3291b04158c2c3dc641624882678bc7805f58a9d94c7104567846a2044e65aece2a225372b6024799a5477d60237504aa5c0ac57bc70a3558c08c4de687ef1302b4fcb5594413d22cb959bc31be423450403c6bc57dc411b3fefac1052ac4bb162c44545a4ca80892657fa13a0b2c482ced629cc4999d969c593d4aadf073cc3e3a458e78a8aa039408e652be93b20c8b42ec5b0e50239dac726052851a6d15312ec39ed208b72209a577c6b2770112895749d5260e7dd446c0b0118c1000be6801d2611fcf00792a9cc4f4b49922f9a2d4b9c8fa5a5d0d60506631a7e971cee840b08fa63c13729d7ea5aac70352a984cdb669331cba758fe87ec3931b3e3161fcc747aa749424689feae14bf7c9a2ffba1302b212b80372d8e9049db69a3a1261d0a2859a9b4d57899e0ba41607a1b67a7c0e12923689f8c6395377d970c7490a4129611a1d05c3b7813bed945420723f7f9525a87793fafbbfca982e66bb80681c83248a89da084c19882f48f31e7fc09093a49e9fd09691b021edf463afc519b62853816118346115fb0b882cc6482f3c5cbcc1c1894697e1239598b34b2a9a7acd15244d0690c88194097a9beda585e87c437124624c210768e6215d376482653eb89947877c118d370c696a6ffcc1018ae413a08a8d0ffaa819945da7a167c229913290cad1c80a369258762610ea253e62dc24226a30c892c12136c326f13f4446664712b0b90bc063b4028593cbde06cdc22289e240c7e296b59172c1aeda8c99e0512d1a0163a942ea33148e6937c026029424b81b996b1df22ea0623ec65c6bf093500cf3bf35374adc392035ca7c583b99685bca541a0807b163acd0888be0385dea820da46e4dbb44d2e462c734b83a473fed1364273159257cc259a8c5676c1c76d41d56b9907ec1c3599c9e8907403a27a705e3619b04b0ad046e8ec8169c17b460d44c0c0c4464d044c946186bc725965083a892bcc495c0540311ff9b3e5192c303d88f8ba46a901c782ef02388f1b2addab6a5350fc3639700e3154337337e4a178d351cd2b56ee1f0bfea34aacfa33d2ec791e50752d4d034cb1c951572caaa5c4d90947b6b175a6dd3c62a77bb8f7ac9ae24719b53c2b120a2876986e217b72bd7cee44a7265b11cee1ab2261762b31a3738386969c0825fb79452e652e1142fc73c9df6fba411795b4717922b29ba2d53abe5a8c0dcc1601b096c96d7938fd5a68a8797c7b9477a86a472eb5da250cb2fec318d83c8f43bbe8e11c35e377d349366c85c4382597f6fc27a0051c0fb00b02c01ca20f9a427f172599477ca690cc1327e0f025f80ec338a80a159e308c12a27db1a7e1b960a99d37dfc22872e51930f28c651ab221f53abaee20bad9a3eabcbab913251bf135beb29617b5754333c4daadb2238341c2ad9378186280f6449440b784ba78f5dac44d8f65b3b7421950397c3913a2dd23ec6d1cb717b36a5fc95af191e278296948c1254ea86b4ec004b94c29450111191823b3514c9ac1ea3d9825ccb86393a2dfb04654fa2192d37bfad1c497c6502eee5ca80a73bfce0baf5a54a88585a401397a3d232f426a7afb082bc21a44317090eaac7592c2ea88a653c4491ea193931335f52e989a3c4cc56d9c553732d57c470fb41ab759b65d2d04445382fcd9c4e344a1128fa9e11e04358e192ed014b23232a7ee2b22e23717f44111ee33575399c37646da9813ec9b212afe94e5dc5c2330a7294cc1f4234a6d3fbb4f1685ab8892c04acb17cd1c170d7b0611b6a7176c794cc8c67f55fc923c2ad203100f365991882c30243d77813843b5ec7c964032263706092ecf00c7516be64e4598ca4226c069bb5e67e4175cf2286c8dd5c488a6c5861f31baa0bd0269470e8b551dd3bcd38c86c12f9cdb176c77dc8b6c02a701f478902c8553f694c0d82727b4c4a5c2c1041212aa1274808b82111b377ec75214e9b1978f76004d4139d98613f4b8e98d20af7b534073a509a959b7a7564f9b40ca218bf61829320a8502017954d328d7ac6c769ec29700756e7b0685b340d5e118059504a49a9a50a10198eb10a5784678eb427d7b4babb9552933b062897973e1318eaf0a0eac37584a65401b1703e042accd837531483f241cadcd1c1d378119e694429db199ac891e4c5343757085bb3ae783667350c4458d97672e861e80b1d2679510ea3a6f2360c77a46942c7a06a554d228080c84b47aef14db17620cb16c06ab30a1be4cda7082be9f87e9c211c46916349a5ba8eaa5201c7294a3c0885b53b657452108825ec646c90a04612324ee7d031afe5343132cbef67b6efb1a5ec2809b773538ce77b3d8b04eb0b3c2256011e4c716c19a8ba0752bf71492117649f0615c3290fc29a46fde4bd52db9286d603388244259c15a7ac2b640a60cc03376a5841a3fb8a473568fa9b1a267215f34c01697b0f0e627175d72105b7707c29b9e614bdc33a6f6c818a95370b427882d7b476796a9ec6eb993274cd9b2391a82ba45e3393d2e9ae9721ca9d6c1b988b5827713f90a6585de9433528c02b03ce10bb5f720138d0fbb4c30c1266b918e52925dfe17b37f95d22bca54f475919ac859098c0f0d08ac5875ef29b56fd141e6ef15f700a0b66f39595c588177373c4669b21bc071e4c3aa5f0b4a31b6258f35da24ac3cd29c7f2092410c5078355b138fb53a6b9ae6e0b9c08243e7baa45c47376eb8c7f13d4cf51aa736fa31540c9241f370da544bf9f9c28d9a57e2f2a7ca95a4e4b466e641ab3bcc76adf1139d567a6f12b52f3a65e7ec0aae26bcaa8c55833b04e59998ebc9a1930fbb6d2233c53d2c1f8b9518e3c2de73a19dee6b380a5b32971cf64e129fd6c1fa6e75d4a234501e966dd3a540af5c8f4f34a6b4a253ee28492566d5e67c6f55855fcb0506fb06c156744d9a03a31a26fa94cad14f157b7f303d07a69c773768fcb4d079c09059703a0c3a94de4b99ea3a2f16583d0f9170a3950db07b4f0bc30802927f9f7961b6259892636a9502a2705303637799dd344da451c1cf7bf67840ceb3079ab8c6b8c1927f64053c612450c45c9e603bc16666e596b3471e103b6f15447424d17022048111ffbd37e1c670f64f14b8a7b32b94c1a49b45dd2fc38cd5289d910ad63602cf5e13042c64ac6797b89fb551ad08e05a92d200cccb7e712ef23c9312cb350f029ab537e287347fd3075ac10906a783f1c6c07ccb88f41228c4be1c640f790b5c3a5d5d3ca792495d74bc461562658c07ac600276b924ab5bc9be1f0494cb76f82f460a7480972663381e169996061d799859ec54d4f5ca5c411c01db1597b165977669de13a928a34afbac258fea8c4764239c9421dc3119bf5b47699206978327b1c5345ef746a7983841f056e2534100ab24d4e9abbd0b17c6a95bd4c3c0e40f69e1612aceeb28b99086c95116e7204273893390bf46b899b36286b0ebf1947bb9884f732ca27da82b19b5dc0cc7f8885714910888b2310c4f9319d410b34e6433b9003e2176bb995257456106e8952163b8ba592530cc5aa0aeb43ad398fe9e97baa523d7a4431677c3d3af0719e475db85ca95af5089beabeb05b2faab4896ba60f81c88472a57b46a828826a0cdfb446f8189182d2bf5eac4ec1cc5deaf599c8a13e48235406d17ffddc8344b6c66984a868aa92fa02227a086950eb0c8701ed58dc628776b983882e117561349e5c131a7e116a0463861d7d18663c5627c38c7147ddaadfd48acd7a4535202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f",
+            ciphertext: "3ce00ba803c544b01150a120e4afe3c9f42df12ac1502c16e38895f7a99b5730fca6f8fbc5e07c9e9d0e1a41e1b53ec6157770e34864d67f6faff1ccc5ebe7b521b79673aedb5565c41fd01d7d70783c9b358772f75408a7de50ff3c0f9035b3ea2813d2f7eb040f5a1f75e9f1c4f375c323dbc34a6a7c2b2509bd839e6ad4efe08a2310b51bb871bd3ebabe9aea8b72aa72494188500906d1d4cac87fa342a3c03e5ea8895ab431198627309d6825f4f852e2dfe98b4617bc4a58be50bccde430f7ca93c87439e5301c94166755666990eef4674ca3861f23bee45c72c86f0d3e9edacef6434bcd30f284f6eb551555c3c3d79ff2a782787ef6e825a4a891d9b6f88da68cf3a96bc6cbb14880b9f744b71b6d548f17073076b0369fc35f8dd30979003348448115b094ac44c66fe6842e55ca134118be4108d836fcee73e1d9bc650f94b7b944cf7d1f0a6344712d3f290457b26a0b9860cc012b71dc2bccd1710736d4d69b2d9c6a2d2f4e7639979ca805303dc283e055d8caad9edf08bc6bdfd2207b3f26e35f1f79361505d224bc18f36d688d2dbcefeb12cc98f2f7755b3b9974f80be19cdb9c8a7468cf0218b1f1173871c5b491119ddaae7a84327d7952c0d9c09100d3bbec74d141b386bd03a4003be055db61260e99f74c17f6de4cc8ea7860c970356cbf656fbd39521ee0620444365de5d8100f41f40820b108f430eabdf3beee999bd29d39733268cfb9670fbb4f6c28d41aa9012b6f6129231a37ab8c7f9cd4e2c52a8da6f76806e1df416999b11bcdc3e57e15418dbe04fe2857c41411ca5ac4871aefa2645868a84739f8bc481d4aa674216df2039409bd588d78c62ef993592da86abdb2560ce3d8f2bd48ab0a74418ccc75ccf0331faf1f2712cbb3d1ce0a4260b6487ecf1e05b6b8669df99d51b8cca088d4cd41ccf624347bcfb36a9883f7504710e246683b09ddc2a1dee31ceac2376609871085b057b2f1cc41e5e74c76b01d4ce796968a16e8e08c2c7497817d9d1bd1063daf5790bb331d25f6a434bff373d98719c555a640e983d8aef3a27cbdb58627fc2543088af450ddc4a2dae1936dbba16d6f6a6738ed51a36b93560623245547836ab6e5801bc854d6319ce486c634de2102e5f2006e5634b482e5cfcfd21a4953440267127a42fe7ff3f768d42124a9c98b3b513ebbc76740e06a1511d3e79765c2f9abbd4c12a10da8f0abf3f5062318b2104ac1c40866ae03ddbdf972ab7baeb0974a5db2a609dd0c02701f72db015fcfff6e451f49ee459cadc0be64f86803d595bbeab4f41ee7db7a5647f36e9b26861e21f72f3f1444f299833aefe6dbf6f8bb394d9a25081eb17d0fb8ac5955dd809cbec31eeeb45ba3390184ae25e16f5d4dbc53203f10d1ebf8a5799f2009486914537b2b15114f8b701b3727209d621c4f885c1b81f238d8e7c8e5c943d47f31090247856cc8bc0dc3392adc7c4c6b56e21ec8f59962b00617248735370e1888f3ab2145d42c1191fa70df98f157e4692dddf939a62bcb3d0599f73292b8e88456abf8cfd51a4271ea46b56a6c495c47d553a4acba045d3007dfeb8ae4be4f00c20e16bbc21cbbfc904a95de945c922a609a208ebb5a05668e601ce661b701c4ec565457a6c5124f7eea9420a80d81e1230ebe7e89ad78e4e2514eaf0f262416692f0711329762d1202b625fe2987539ca514947bb5e8895df355818c18e8f673246533b405f98e33c111cb4772fa16fed409b290ceb4f61240a8541ab78b461823f44cb5756d7539c5ce0f276c64d741ce9ba64c2d0377da5c4f2624c5afbebb4f93d8e7c6686e206adedb910b8e0185e29b47095e1d65c2c908087efafcf71fe852b0462b99a2628595b97eb0eb2d9c670e2d7ed708151bbc2f2305e35cb3ba1ac99ab9fc65c8e07922984df8dc144bbcb477045a663452c7bc989b3b46a84e00f92c6348b01f522ab60e47ef2863991d0022ed6304c7f3954ab61490d736aa5c2d1bd813b8461341704355ebaf8e70246954b5ab42f07293486a1835e39a8754c47a310ad37e2dcaa6849b80b47722b15cad6ecdac2c060f37b4fd506a08fbf6888ef70a3a9cfb79925db3193b514c17cb44d214978055577364099f016933ed9858273222b646e1db152b0004df451f503c499642a26c80e0da50e149be1bca32955093a8e2b42ed2b73b5484efc36c7",
+            shared_secret: "0ad8d1ea1b8dd788979b4379581218df9321bdce5567eca42ae6be7d395f1a54",
+            implicit_rejection_secret: "8f2c880890996c587aa500cf8b6da03372de706a9f96075744bb0956ea6fbaac",
+        },
+    ];
+
+    fn seed() -> [u8; MLKEM_SEED_SIZE] {
+        core::array::from_fn(|i| i as u8)
+    }
+
+    fn randomness() -> [u8; MLKEM_ENCAPSULATION_RANDOMNESS_SIZE] {
+        core::array::from_fn(|i| (i + 64) as u8)
+    }
+
+    #[test]
+    fn test_mlkem_known_answer_keygen() {
+        for known_answer in KNOWN_ANSWERS.iter() {
+            let key = MlKemKey::from_seed(known_answer.params, &seed()).unwrap();
+
+            let encapsulation_key = key.encapsulation_key_bytes().unwrap();
+            let decapsulation_key = key.decapsulation_key_bytes().unwrap();
+            assert_eq!(
+                hex::encode(&encapsulation_key),
+                known_answer.encapsulation_key
+            );
+            assert_eq!(
+                hex::encode(decapsulation_key.as_bytes()),
+                known_answer.decapsulation_key
+            );
+        }
+    }
+
+    #[test]
+    fn test_mlkem_known_answer_encapsulate_decapsulate() {
+        for known_answer in KNOWN_ANSWERS.iter() {
+            let key = MlKemKey::from_decapsulation_key(
+                known_answer.params,
+                &hex::decode(known_answer.decapsulation_key).unwrap(),
+            )
+            .unwrap();
+            let public_key = MlKemKey::from_encapsulation_key(
+                known_answer.params,
+                &hex::decode(known_answer.encapsulation_key).unwrap(),
+            )
+            .unwrap();
+
+            let (secret, ciphertext) = public_key
+                .encapsulate_with_randomness(&randomness())
+                .unwrap();
+            assert_eq!(hex::encode(&ciphertext), known_answer.ciphertext);
+            assert_eq!(hex::encode(secret.as_bytes()), known_answer.shared_secret);
+
+            let decapsulated = key.decapsulate(&ciphertext).unwrap();
+            assert_eq!(
+                hex::encode(decapsulated.as_bytes()),
+                known_answer.shared_secret
+            );
+        }
+    }
+
+    #[test]
+    fn test_mlkem_known_answer_implicit_rejection() {
+        for known_answer in KNOWN_ANSWERS.iter() {
+            let key = MlKemKey::from_seed(known_answer.params, &seed()).unwrap();
+            let (_, mut ciphertext) = key.encapsulate_with_randomness(&randomness()).unwrap();
+            ciphertext[0] ^= 1;
+
+            let rejected = key.decapsulate(&ciphertext).unwrap();
+            assert_eq!(
+                hex::encode(rejected.as_bytes()),
+                known_answer.implicit_rejection_secret
+            );
+        }
+    }
+
+    #[test]
+    fn test_mlkem_generate_round_trip() {
+        for params in [
+            MlKemParams::MlKem512,
+            MlKemParams::MlKem768,
+            MlKemParams::MlKem1024,
+        ] {
+            let key = MlKemKey::generate(params).unwrap();
+            let encapsulation_key = key.encapsulation_key_bytes().unwrap();
+            assert_eq!(encapsulation_key.len(), params.encapsulation_key_size());
+
+            let public_key = MlKemKey::from_encapsulation_key(params, &encapsulation_key).unwrap();
+            let (secret, ciphertext) = public_key.encapsulate().unwrap();
+            assert_eq!(ciphertext.len(), params.ciphertext_size());
+
+            let decapsulated = key.decapsulate(&ciphertext).unwrap();
+            assert_eq!(secret.as_bytes(), decapsulated.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_mlkem_decapsulation_key_round_trip() {
+        let key = MlKemKey::generate(MlKemParams::MlKem768).unwrap();
+        let decapsulation_key = key.decapsulation_key_bytes().unwrap();
+        let imported =
+            MlKemKey::from_decapsulation_key(MlKemParams::MlKem768, decapsulation_key.as_bytes())
+                .unwrap();
+
+        let (secret, ciphertext) = key.encapsulate().unwrap();
+        let decapsulated = imported.decapsulate(&ciphertext).unwrap();
+        assert_eq!(secret.as_bytes(), decapsulated.as_bytes());
+        assert_eq!(
+            imported.encapsulation_key_bytes().unwrap(),
+            key.encapsulation_key_bytes().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_mlkem_public_key_cannot_decapsulate() {
+        let key = MlKemKey::generate(MlKemParams::MlKem768).unwrap();
+        let public_key = MlKemKey::from_encapsulation_key(
+            MlKemParams::MlKem768,
+            &key.encapsulation_key_bytes().unwrap(),
+        )
+        .unwrap();
+
+        let (_, ciphertext) = key.encapsulate().unwrap();
+        assert!(public_key.decapsulate(&ciphertext).is_err());
+        assert!(public_key.decapsulation_key_bytes().is_err());
+    }
+
+    #[test]
+    fn test_mlkem_wrong_sizes() {
+        let key = MlKemKey::generate(MlKemParams::MlKem512).unwrap();
+
+        let result = MlKemKey::from_encapsulation_key(MlKemParams::MlKem768, &[0u8; 800]);
+        assert_eq!(result.err().unwrap(), SymCryptError::WrongKeySize);
+
+        let result = key.decapsulate(&[0u8; 1088]);
+        assert_eq!(result.err().unwrap(), SymCryptError::WrongDataSize);
+    }
+}
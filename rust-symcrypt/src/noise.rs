@@ -0,0 +1,1097 @@
+//! The Noise Protocol Framework, revision 34, for the `Noise_NK_25519_*_SHA256`, `Noise_IK_25519_*_SHA256` and
+//! `Noise_XX_25519_*_SHA256` protocols with the AESGCM and ChaChaPoly ciphers.
+//!
+//! [`CipherState`], [`SymmetricState`] and [`HandshakeState`] are the objects of section 5 of the specification. DH is [`EcDh`] with
+//! Curve25519, the hash is SHA-256 and HKDF is computed with [`HmacSha256State`].
+//!
+//! A [`HandshakeState`] is created for each side with [`HandshakeState::new()`]. The initiator writes the first message, and the two
+//! sides then take turns calling [`HandshakeState::write_message()`] and [`HandshakeState::read_message()`] until
+//! [`HandshakeState::is_finished()`] returns true. [`HandshakeState::into_transport()`] then returns the [`NoiseTransport`] that
+//! encrypts the rest of the session. A handshake that returned an error must be abandoned.
+
+use crate::block_ciphers::BlockCipherType;
+use crate::chacha::{chacha20_poly1305_decrypt_in_place, chacha20_poly1305_encrypt_in_place};
+use crate::ecdh::EcDh;
+use crate::eckey::CurveType;
+use crate::errors::SymCryptError;
+use crate::gcm::GcmExpandedKey;
+use crate::hash::{sha256, HashState, Sha256State};
+use crate::hmac::{HmacSha256State, HmacState};
+use crate::secret::{SecretArray, SecretBytes};
+
+/// Largest Noise message, for both handshake and transport messages.
+pub const NOISE_MAX_MESSAGE_SIZE: usize = 65535;
+
+/// Size of the tag that is added to every encrypted payload and static key.
+pub const NOISE_TAG_SIZE: usize = 16;
+
+const HASH_SIZE: usize = 32;
+const DH_SIZE: usize = 32;
+
+/// [`NoisePattern`] provides an enum of the supported handshake patterns.
+///
+/// [`NoisePattern::Nk`] and [`NoisePattern::Ik`] need the initiator to know the static key of the responder before the handshake,
+/// [`NoisePattern::Xx`] transmits both static keys. Only the initiator is authenticated in IK and XX.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum NoisePattern {
+    Nk,
+    Ik,
+    Xx,
+}
+
+/// [`Token`] is a token of a message pattern.
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Token {
+    E,
+    S,
+    Ee,
+    Es,
+    Se,
+    Ss,
+}
+
+impl NoisePattern {
+    fn name(&self) -> &'static str {
+        match self {
+            NoisePattern::Nk => "NK",
+            NoisePattern::Ik => "IK",
+            NoisePattern::Xx => "XX",
+        }
+    }
+
+    // The message patterns, the initiator sends the even messages and the responder the odd ones.
+    fn messages(&self) -> &'static [&'static [Token]] {
+        match self {
+            NoisePattern::Nk => &[&[Token::E, Token::Es], &[Token::E, Token::Ee]],
+            NoisePattern::Ik => &[
+                &[Token::E, Token::Es, Token::S, Token::Ss],
+                &[Token::E, Token::Ee, Token::Se],
+            ],
+            NoisePattern::Xx => &[
+                &[Token::E],
+                &[Token::E, Token::Ee, Token::S, Token::Es],
+                &[Token::S, Token::Se],
+            ],
+        }
+    }
+
+    // NK and IK have the "<- s" pre-message, the responder static key is known to the initiator.
+    fn responder_static_pre_message(&self) -> bool {
+        matches!(self, NoisePattern::Nk | NoisePattern::Ik)
+    }
+
+    fn initiator_has_static(&self) -> bool {
+        matches!(self, NoisePattern::Ik | NoisePattern::Xx)
+    }
+}
+
+/// [`NoiseCipher`] provides an enum of the supported cipher functions. AESGCM is AES-256-GCM.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum NoiseCipher {
+    AesGcm,
+    ChaChaPoly,
+}
+
+impl NoiseCipher {
+    fn name(&self) -> &'static str {
+        match self {
+            NoiseCipher::AesGcm => "AESGCM",
+            NoiseCipher::ChaChaPoly => "ChaChaPoly",
+        }
+    }
+
+    // AESGCM encodes the counter big-endian and ChaChaPoly little-endian, both after 4 zero bytes.
+    fn nonce(&self, counter: u64) -> [u8; 12] {
+        let mut nonce = [0u8; 12];
+        match self {
+            NoiseCipher::AesGcm => nonce[4..].copy_from_slice(&counter.to_be_bytes()),
+            NoiseCipher::ChaChaPoly => nonce[4..].copy_from_slice(&counter.to_le_bytes()),
+        }
+        nonce
+    }
+}
+
+/// [`NoiseProtocol`] holds the handshake pattern and cipher of a protocol. The DH function is always 25519 and the hash function
+/// always SHA256.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct NoiseProtocol {
+    pattern: NoisePattern,
+    cipher: NoiseCipher,
+}
+
+/// Impl for [`NoiseProtocol`]
+///
+/// [`new()`] returns the protocol for `pattern` and `cipher`.
+///
+/// [`from_name()`] parses a protocol name such as `Noise_XX_25519_ChaChaPoly_SHA256`, and fails with InvalidArgument if the protocol is
+/// not supported.
+///
+/// [`name()`] returns the protocol name, which is mixed into the handshake.
+impl NoiseProtocol {
+    pub fn new(pattern: NoisePattern, cipher: NoiseCipher) -> Self {
+        NoiseProtocol { pattern, cipher }
+    }
+
+    pub fn from_name(name: &str) -> Result<Self, SymCryptError> {
+        let parts: Vec<&str> = name.split('_').collect();
+        let (pattern, cipher) = match parts.as_slice() {
+            ["Noise", pattern, "25519", cipher, "SHA256"] => (*pattern, *cipher),
+            _ => return Err(SymCryptError::InvalidArgument),
+        };
+        let pattern = [NoisePattern::Nk, NoisePattern::Ik, NoisePattern::Xx]
+            .into_iter()
+            .find(|candidate| candidate.name() == pattern)
+            .ok_or(SymCryptError::InvalidArgument)?;
+        let cipher = [NoiseCipher::AesGcm, NoiseCipher::ChaChaPoly]
+            .into_iter()
+            .find(|candidate| candidate.name() == cipher)
+            .ok_or(SymCryptError::InvalidArgument)?;
+        Ok(NoiseProtocol { pattern, cipher })
+    }
+
+    pub fn name(&self) -> String {
+        format!(
+            "Noise_{}_25519_{}_SHA256",
+            self.pattern.name(),
+            self.cipher.name()
+        )
+    }
+
+    pub fn pattern(&self) -> NoisePattern {
+        self.pattern
+    }
+
+    pub fn cipher(&self) -> NoiseCipher {
+        self.cipher
+    }
+}
+
+/// [`CipherKey`] holds the key of a [`CipherState`].
+enum CipherKey {
+    Gcm(GcmExpandedKey),
+    ChaChaPoly(SecretArray<32>),
+}
+
+/// [`CipherState`] holds a key and a nonce counter, see section 5.1 of the specification.
+pub struct CipherState {
+    cipher: NoiseCipher,
+    key: Option<CipherKey>,
+    nonce: u64,
+}
+
+/// Impl for [`CipherState`]
+///
+/// [`new()`] returns a [`CipherState`] without a key. [`initialize_key()`] sets the key and resets the nonce to 0.
+///
+/// [`encrypt_with_ad()`] and [`decrypt_with_ad()`] return the input unchanged if no key is set. Otherwise [`encrypt_with_ad()`] returns
+/// the ciphertext followed by the [`NOISE_TAG_SIZE`] byte tag, and [`decrypt_with_ad()`] fails with AuthenticationFailure if the
+/// ciphertext or `ad` were modified. The nonce is incremented after every successful call, and both fail with ValueTooLarge once the
+/// nonce reaches 2^64 - 1, which is reserved.
+///
+/// [`set_nonce()`] sets the nonce, for protocols that handle out of order messages.
+///
+/// [`rekey()`] replaces the key with a key derived from it, as defined in section 4.2 of the specification.
+impl CipherState {
+    pub fn new(cipher: NoiseCipher) -> Self {
+        CipherState {
+            cipher,
+            key: None,
+            nonce: 0,
+        }
+    }
+
+    pub fn initialize_key(&mut self, key: &[u8; 32]) -> Result<(), SymCryptError> {
+        self.key = Some(match self.cipher {
+            NoiseCipher::AesGcm => {
+                CipherKey::Gcm(GcmExpandedKey::new(key, BlockCipherType::AesBlock)?)
+            }
+            NoiseCipher::ChaChaPoly => CipherKey::ChaChaPoly(SecretArray::new(*key)),
+        });
+        self.nonce = 0;
+        Ok(())
+    }
+
+    pub fn has_key(&self) -> bool {
+        self.key.is_some()
+    }
+
+    pub fn set_nonce(&mut self, nonce: u64) {
+        self.nonce = nonce;
+    }
+
+    pub fn encrypt_with_ad(
+        &mut self,
+        ad: &[u8],
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, SymCryptError> {
+        if self.key.is_none() {
+            return Ok(plaintext.to_vec());
+        }
+        if self.nonce == u64::MAX {
+            return Err(SymCryptError::ValueTooLarge);
+        }
+        let ciphertext = self.encrypt(self.nonce, ad, plaintext)?;
+        self.nonce += 1;
+        Ok(ciphertext)
+    }
+
+    pub fn decrypt_with_ad(
+        &mut self,
+        ad: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, SymCryptError> {
+        let key = match &self.key {
+            Some(key) => key,
+            None => return Ok(ciphertext.to_vec()),
+        };
+        if self.nonce == u64::MAX {
+            return Err(SymCryptError::ValueTooLarge);
+        }
+        if ciphertext.len() < NOISE_TAG_SIZE {
+            return Err(SymCryptError::WrongDataSize);
+        }
+        let nonce = self.cipher.nonce(self.nonce);
+        let (ciphertext, tag) = ciphertext.split_at(ciphertext.len() - NOISE_TAG_SIZE);
+        let tag: &[u8; NOISE_TAG_SIZE] = tag.try_into().unwrap(); // tag is NOISE_TAG_SIZE bytes per the split above
+        let mut buffer = ciphertext.to_vec();
+        match key {
+            CipherKey::Gcm(key) => key.decrypt_in_place(&nonce, ad, &mut buffer, tag),
+            CipherKey::ChaChaPoly(key) => {
+                chacha20_poly1305_decrypt_in_place(key.as_bytes(), &nonce, ad, &mut buffer, tag)
+            }
+        }?;
+        self.nonce += 1;
+        Ok(buffer)
+    }
+
+    pub fn rekey(&mut self) -> Result<(), SymCryptError> {
+        if self.key.is_none() {
+            return Err(SymCryptError::InvalidArgument);
+        }
+        let mut new_key = SecretArray::<32>::zeroed();
+        let ciphertext = SecretBytes::from(self.encrypt(u64::MAX, &[], &[0u8; 32])?);
+        new_key.as_mut_bytes().copy_from_slice(&ciphertext[..32]);
+        // initialize_key() resets the nonce, which is not part of REKEY.
+        let nonce = self.nonce;
+        self.initialize_key(&new_key)?;
+        self.nonce = nonce;
+        Ok(())
+    }
+
+    // ENCRYPT(k, n, ad, plaintext) from section 4.2. The caller checks that a key is set.
+    fn encrypt(&self, nonce: u64, ad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+        let nonce = self.cipher.nonce(nonce);
+        let mut buffer = plaintext.to_vec();
+        let mut tag = [0u8; NOISE_TAG_SIZE];
+        match &self.key {
+            Some(CipherKey::Gcm(key)) => key.encrypt_in_place(&nonce, ad, &mut buffer, &mut tag),
+            Some(CipherKey::ChaChaPoly(key)) => chacha20_poly1305_encrypt_in_place(
+                key.as_bytes(),
+                &nonce,
+                ad,
+                &mut buffer,
+                &mut tag,
+            )?,
+            None => return Err(SymCryptError::InvalidArgument),
+        }
+        buffer.extend_from_slice(&tag);
+        Ok(buffer)
+    }
+}
+
+/// [`SymmetricState`] holds the chaining key, the handshake hash and the [`CipherState`] of a handshake, see section 5.2 of the
+/// specification.
+pub struct SymmetricState {
+    cipher_state: CipherState,
+    chaining_key: SecretArray<HASH_SIZE>,
+    handshake_hash: [u8; HASH_SIZE],
+}
+
+/// Impl for [`SymmetricState`]
+///
+/// [`new()`] is InitializeSymmetric, it initializes the state with the name of `protocol`.
+///
+/// [`mix_key()`] mixes `input_key_material` into the chaining key and sets the key of the [`CipherState`]. [`mix_hash()`] mixes
+/// `data` into the handshake hash.
+///
+/// [`encrypt_and_hash()`] and [`decrypt_and_hash()`] encrypt or decrypt with the handshake hash as the associated data, and mix the
+/// ciphertext into the handshake hash.
+///
+/// [`handshake_hash()`] returns the handshake hash, which can be used for channel binding once the handshake is finished.
+///
+/// [`split()`] returns the two transport [`CipherState`]s, the first one for the messages of the initiator and the second one for the
+/// messages of the responder.
+impl SymmetricState {
+    pub fn new(protocol: NoiseProtocol) -> Self {
+        let name = protocol.name();
+        let mut handshake_hash = [0u8; HASH_SIZE];
+        if name.len() <= HASH_SIZE {
+            handshake_hash[..name.len()].copy_from_slice(name.as_bytes());
+        } else {
+            handshake_hash = sha256(name.as_bytes());
+        }
+        SymmetricState {
+            cipher_state: CipherState::new(protocol.cipher),
+            chaining_key: SecretArray::new(handshake_hash),
+            handshake_hash,
+        }
+    }
+
+    pub fn mix_key(&mut self, input_key_material: &[u8]) -> Result<(), SymCryptError> {
+        let (chaining_key, key) = hkdf2(&self.chaining_key, input_key_material)?;
+        self.chaining_key = chaining_key;
+        self.cipher_state.initialize_key(&key)
+    }
+
+    pub fn mix_hash(&mut self, data: &[u8]) {
+        let mut state = Sha256State::new();
+        state.append(&self.handshake_hash);
+        state.append(data);
+        self.handshake_hash = state.result();
+    }
+
+    pub fn encrypt_and_hash(&mut self, plaintext: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+        let ciphertext = self
+            .cipher_state
+            .encrypt_with_ad(&self.handshake_hash, plaintext)?;
+        self.mix_hash(&ciphertext);
+        Ok(ciphertext)
+    }
+
+    pub fn decrypt_and_hash(&mut self, ciphertext: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+        let plaintext = self
+            .cipher_state
+            .decrypt_with_ad(&self.handshake_hash, ciphertext)?;
+        self.mix_hash(ciphertext);
+        Ok(plaintext)
+    }
+
+    pub fn handshake_hash(&self) -> [u8; HASH_SIZE] {
+        self.handshake_hash
+    }
+
+    pub fn split(&self) -> Result<(CipherState, CipherState), SymCryptError> {
+        let (key_1, key_2) = hkdf2(&self.chaining_key, &[])?;
+        let mut initiator = CipherState::new(self.cipher_state.cipher);
+        initiator.initialize_key(&key_1)?;
+        let mut responder = CipherState::new(self.cipher_state.cipher);
+        responder.initialize_key(&key_2)?;
+        Ok((initiator, responder))
+    }
+}
+
+/// HKDF from section 4.3 of the specification with two outputs, which is RFC 5869 HKDF with the chaining key as the salt and no info.
+fn hkdf2(
+    chaining_key: &[u8; HASH_SIZE],
+    input_key_material: &[u8],
+) -> Result<(SecretArray<HASH_SIZE>, SecretArray<HASH_SIZE>), SymCryptError> {
+    let mut state = HmacSha256State::new(chaining_key)?;
+    state.append(input_key_material);
+    let temp_key = SecretArray::new(state.result());
+
+    let mut state = HmacSha256State::new(temp_key.as_bytes())?;
+    state.append(&[0x01]);
+    let output_1 = SecretArray::new(state.result());
+
+    let mut state = HmacSha256State::new(temp_key.as_bytes())?;
+    state.append(output_1.as_bytes());
+    state.append(&[0x02]);
+    let output_2 = SecretArray::new(state.result());
+    Ok((output_1, output_2))
+}
+
+/// dh runs the Curve25519 EcDh secret agreement with the public key `public`. An all zero result, from a small order public key, is
+/// rejected.
+fn dh(private: &EcDh, public: &[u8; DH_SIZE]) -> Result<SecretBytes, SymCryptError> {
+    let public = EcDh::from_public_key_bytes(CurveType::Curve25519, public)?;
+    let secret = EcDh::ecdh_secret_agreement(private, &public)?;
+    if secret.iter().all(|&byte| byte == 0) {
+        return Err(SymCryptError::InvalidArgument);
+    }
+    Ok(secret)
+}
+
+/// public_key returns the public key of a Curve25519 [`EcDh`].
+fn public_key(key: &EcDh) -> Result<[u8; DH_SIZE], SymCryptError> {
+    key.get_public_key_bytes()?
+        .try_into()
+        .map_err(|_| SymCryptError::InvalidArgument)
+}
+
+/// [`HandshakeState`] runs one side of a handshake, see section 5.3 of the specification.
+pub struct HandshakeState {
+    pattern: NoisePattern,
+    initiator: bool,
+    symmetric_state: SymmetricState,
+    s: Option<EcDh>,
+    e: Option<EcDh>,
+    rs: Option<[u8; DH_SIZE]>,
+    re: Option<[u8; DH_SIZE]>,
+    message_index: usize,
+}
+
+/// Impl for [`HandshakeState`]
+///
+/// [`new()`] is Initialize. `initiator` selects the side, `prologue` is data that both sides must agree on, `s` is the local static
+/// key pair and `rs` the static public key of the remote side. The initiator needs `s` for IK and XX and `rs` for NK and IK, the
+/// responder always needs `s`. It fails with InvalidArgument if a key the pattern needs is missing or is not a Curve25519 key.
+///
+/// [`write_message()`] returns the next handshake message with `payload` in it, and [`read_message()`] processes a message from the
+/// remote side and returns its payload. Both fail with InvalidArgument if it is not the turn of this side, or if the handshake is
+/// finished. Payloads of the first message of NK and IK, and of every message of XX after the first, are encrypted. The first XX
+/// message and the responder payload of NK, before the responder has been authenticated by a static key, have weaker guarantees,
+/// see section 7.7 of the specification.
+///
+/// [`is_finished()`] returns true once every handshake message has been written or read.
+///
+/// [`remote_static()`] returns the static public key of the remote side, if it is known.
+///
+/// [`handshake_hash()`] returns the current handshake hash.
+///
+/// [`into_transport()`] returns the [`NoiseTransport`] for the session, it fails with InvalidArgument if the handshake is not finished.
+impl HandshakeState {
+    pub fn new(
+        protocol: NoiseProtocol,
+        initiator: bool,
+        prologue: &[u8],
+        s: Option<EcDh>,
+        rs: Option<&[u8]>,
+    ) -> Result<Self, SymCryptError> {
+        let pattern = protocol.pattern;
+        let needs_s = !initiator || pattern.initiator_has_static();
+        let needs_rs = initiator && pattern.responder_static_pre_message();
+        if needs_s != s.is_some() || needs_rs != rs.is_some() {
+            return Err(SymCryptError::InvalidArgument);
+        }
+        if let Some(s) = &s {
+            if s.ec_key().curve_type() != CurveType::Curve25519 || !s.ec_key().has_private_key() {
+                return Err(SymCryptError::InvalidArgument);
+            }
+        }
+        let rs: Option<[u8; DH_SIZE]> = rs
+            .map(|rs| rs.try_into().map_err(|_| SymCryptError::WrongDataSize))
+            .transpose()?;
+
+        let mut symmetric_state = SymmetricState::new(protocol);
+        symmetric_state.mix_hash(prologue);
+        if pattern.responder_static_pre_message() {
+            let responder_static = match (&rs, &s) {
+                (Some(rs), _) if initiator => *rs,
+                (_, Some(s)) => public_key(s)?,
+                _ => return Err(SymCryptError::InvalidArgument),
+            };
+            symmetric_state.mix_hash(&responder_static);
+        }
+
+        Ok(HandshakeState {
+            pattern,
+            initiator,
+            symmetric_state,
+            s,
+            e: None,
+            rs,
+            re: None,
+            message_index: 0,
+        })
+    }
+
+    pub fn write_message(&mut self, payload: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+        let tokens = self.next_tokens(true)?;
+        let mut message = Vec::new();
+        for &token in tokens {
+            match token {
+                Token::E => {
+                    if self.e.is_none() {
+                        self.e = Some(EcDh::new(CurveType::Curve25519)?);
+                    }
+                    let e = public_key(self.e.as_ref().unwrap())?; // set above
+                    message.extend_from_slice(&e);
+                    self.symmetric_state.mix_hash(&e);
+                }
+                Token::S => {
+                    let s = public_key(self.s.as_ref().ok_or(SymCryptError::InvalidArgument)?)?;
+                    message.extend_from_slice(&self.symmetric_state.encrypt_and_hash(&s)?);
+                }
+                token => self.mix_dh(token)?,
+            }
+        }
+        message.extend_from_slice(&self.symmetric_state.encrypt_and_hash(payload)?);
+        if message.len() > NOISE_MAX_MESSAGE_SIZE {
+            return Err(SymCryptError::WrongDataSize);
+        }
+        self.message_index += 1;
+        Ok(message)
+    }
+
+    pub fn read_message(&mut self, message: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+        if message.len() > NOISE_MAX_MESSAGE_SIZE {
+            return Err(SymCryptError::WrongDataSize);
+        }
+        let tokens = self.next_tokens(false)?;
+        let mut message = message;
+        for &token in tokens {
+            match token {
+                Token::E => {
+                    let re = take(&mut message, DH_SIZE)?;
+                    self.symmetric_state.mix_hash(re);
+                    self.re = Some(re.try_into().unwrap()); // take() returned DH_SIZE bytes
+                }
+                Token::S => {
+                    let size = if self.symmetric_state.cipher_state.has_key() {
+                        DH_SIZE + NOISE_TAG_SIZE
+                    } else {
+                        DH_SIZE
+                    };
+                    let rs = self
+                        .symmetric_state
+                        .decrypt_and_hash(take(&mut message, size)?)?;
+                    self.rs = Some(rs.try_into().unwrap()); // the plaintext is DH_SIZE bytes
+                }
+                token => self.mix_dh(token)?,
+            }
+        }
+        let payload = self.symmetric_state.decrypt_and_hash(message)?;
+        self.message_index += 1;
+        Ok(payload)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.message_index == self.pattern.messages().len()
+    }
+
+    pub fn remote_static(&self) -> Option<&[u8; DH_SIZE]> {
+        self.rs.as_ref()
+    }
+
+    pub fn handshake_hash(&self) -> [u8; HASH_SIZE] {
+        self.symmetric_state.handshake_hash()
+    }
+
+    pub fn into_transport(self) -> Result<NoiseTransport, SymCryptError> {
+        if !self.is_finished() {
+            return Err(SymCryptError::InvalidArgument);
+        }
+        let (initiator, responder) = self.symmetric_state.split()?;
+        let (send, receive) = if self.initiator {
+            (initiator, responder)
+        } else {
+            (responder, initiator)
+        };
+        Ok(NoiseTransport {
+            send,
+            receive,
+            handshake_hash: self.symmetric_state.handshake_hash(),
+            remote_static: self.rs,
+        })
+    }
+
+    // Returns the tokens of the next message, and fails if it is not the turn of this side to write, or to read.
+    fn next_tokens(&self, write: bool) -> Result<&'static [Token], SymCryptError> {
+        let messages = self.pattern.messages();
+        let initiator_turn = self.message_index.is_multiple_of(2);
+        if self.is_finished() || (initiator_turn == self.initiator) != write {
+            return Err(SymCryptError::InvalidArgument);
+        }
+        Ok(messages[self.message_index])
+    }
+
+    // Runs the DH of a ee, es, se or ss token and mixes the result into the chaining key. The first letter is the key of the initiator
+    // and the second letter the key of the responder.
+    fn mix_dh(&mut self, token: Token) -> Result<(), SymCryptError> {
+        let (initiator_key, responder_key) = match token {
+            Token::Ee => (Token::E, Token::E),
+            Token::Es => (Token::E, Token::S),
+            Token::Se => (Token::S, Token::E),
+            Token::Ss => (Token::S, Token::S),
+            Token::E | Token::S => return Err(SymCryptError::InvalidArgument),
+        };
+        let (local, remote) = if self.initiator {
+            (initiator_key, responder_key)
+        } else {
+            (responder_key, initiator_key)
+        };
+        let private = match local {
+            Token::E => self.e.as_ref(),
+            _ => self.s.as_ref(),
+        };
+        let public = match remote {
+            Token::E => self.re.as_ref(),
+            _ => self.rs.as_ref(),
+        };
+        match (private, public) {
+            (Some(private), Some(public)) => {
+                let secret = dh(private, public)?;
+                self.symmetric_state.mix_key(&secret)
+            }
+            _ => Err(SymCryptError::InvalidArgument),
+        }
+    }
+}
+
+/// take splits the first `size` bytes off `message`, and fails with WrongDataSize if the message is too short.
+fn take<'a>(message: &mut &'a [u8], size: usize) -> Result<&'a [u8], SymCryptError> {
+    if message.len() < size {
+        return Err(SymCryptError::WrongDataSize);
+    }
+    let (head, tail) = message.split_at(size);
+    *message = tail;
+    Ok(head)
+}
+
+/// [`NoiseTransport`] holds the two [`CipherState`]s of a session once the handshake is finished.
+pub struct NoiseTransport {
+    send: CipherState,
+    receive: CipherState,
+    handshake_hash: [u8; HASH_SIZE],
+    remote_static: Option<[u8; DH_SIZE]>,
+}
+
+/// Impl for [`NoiseTransport`]
+///
+/// [`write_message()`] encrypts `payload` for the remote side, and [`read_message()`] decrypts a message from the remote side.
+/// Messages must be read in the order they were written. Both fail with WrongDataSize if the message would be larger than
+/// [`NOISE_MAX_MESSAGE_SIZE`].
+///
+/// [`rekey_send()`] and [`rekey_receive()`] call [`CipherState::rekey()`] on the matching [`CipherState`], the remote side must rekey
+/// the other direction at the same point in the session.
+///
+/// [`handshake_hash()`] and [`remote_static()`] are the values from the finished handshake.
+impl NoiseTransport {
+    pub fn write_message(&mut self, payload: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+        if payload.len() + NOISE_TAG_SIZE > NOISE_MAX_MESSAGE_SIZE {
+            return Err(SymCryptError::WrongDataSize);
+        }
+        self.send.encrypt_with_ad(&[], payload)
+    }
+
+    pub fn read_message(&mut self, message: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+        if message.len() > NOISE_MAX_MESSAGE_SIZE {
+            return Err(SymCryptError::WrongDataSize);
+        }
+        self.receive.decrypt_with_ad(&[], message)
+    }
+
+    pub fn rekey_send(&mut self) -> Result<(), SymCryptError> {
+        self.send.rekey()
+    }
+
+    pub fn rekey_receive(&mut self) -> Result<(), SymCryptError> {
+        self.receive.rekey()
+    }
+
+    pub fn handshake_hash(&self) -> [u8; HASH_SIZE] {
+        self.handshake_hash
+    }
+
+    pub fn remote_static(&self) -> Option<&[u8; DH_SIZE]> {
+        self.remote_static.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Imports a private key from the test vectors, which are not clamped. EcKey clamps X25519 private keys on import.
+    fn x25519_key(private_key: &str) -> EcDh {
+        let private_key = hex::decode(private_key).unwrap();
+        EcDh::from_private_key_bytes(CurveType::Curve25519, &private_key).unwrap()
+    }
+
+    struct CacophonyVector {
+        protocol_name: &'static str,
+        prologue: &'static str,
+        init_static: Option<&'static str>,
+        init_ephemeral: &'static str,
+        init_remote_static: Option<&'static str>,
+        resp_static: Option<&'static str>,
+        resp_ephemeral: &'static str,
+        handshake_hash: &'static str,
+        // (payload, ciphertext), the initiator sends the even messages and the responder the odd ones.
+        messages: &'static [(&'static str, &'static str)],
+    }
+
+    // The vectors for the supported protocols from the cacophony test suite.
+    const CACOPHONY_VECTORS: &[CacophonyVector] = &[
+        CacophonyVector {
+            protocol_name: "Noise_NK_25519_AESGCM_SHA256",
+            prologue: "4a6f686e2047616c74",
+            init_static: None,
+            init_ephemeral: "893e28b9dc6ca8d611ab664754b8ceb7bac5117349a4439a6b0569da977c464a",
+            init_remote_static: Some("31e0303fd6418d2f8c0e78b91f22e8caed0fbe48656dcf4767e4834f701b8f62"),
+            resp_static: Some("4a3acbfdb163dec651dfa3194dece676d437029c62a408b4c5ea9114246e4893"),
+            resp_ephemeral: "bbdb4cdbd309f1a1f2e1456967fe288cadd6f712d65dc7b7793d5e63da6b375b",
+            handshake_hash: "f8a87aa8add4fea6e33365b89637486c2f6564546ce29d1df9ce9abf78c507d7",
+            messages: &[
+                ("4c756477696720766f6e204d69736573", "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c794475ab4d66d222457dd414bc5f296bc7b4078cc7d72af5192628b68bca7d28844b"),
+                ("4d757272617920526f746862617264", "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f14480884303c7d89310502baa8299520ba451624c3c0492e2698f8d457c32400b91fd8a"),
+                ("462e20412e20486179656b", "304f70c37c93573099228016d54cb15213af94eb598d1b17df1153"),
+                ("4361726c204d656e676572", "a1bf6c954529f29b31d8ae9f67d2c18dbd332aa1a0918690c6d80b"),
+                ("4a65616e2d426170746973746520536179", "2e8f3e51888360b2b2d83a64dde9943c7dd3c5e84ac7c4b4e2d5cfc025b6c854d3"),
+                ("457567656e2042f6686d20766f6e2042617765726b", "8498bf41212a8b87c9eeb408274c75b3558fd0530865b5a7932d4b3af812d85b3df27e6f33"),
+            ],
+        },
+        CacophonyVector {
+            protocol_name: "Noise_NK_25519_ChaChaPoly_SHA256",
+            prologue: "4a6f686e2047616c74",
+            init_static: None,
+            init_ephemeral: "893e28b9dc6ca8d611ab664754b8ceb7bac5117349a4439a6b0569da977c464a",
+            init_remote_static: Some("31e0303fd6418d2f8c0e78b91f22e8caed0fbe48656dcf4767e4834f701b8f62"),
+            resp_static: Some("4a3acbfdb163dec651dfa3194dece676d437029c62a408b4c5ea9114246e4893"),
+            resp_ephemeral: "bbdb4cdbd309f1a1f2e1456967fe288cadd6f712d65dc7b7793d5e63da6b375b",
+            handshake_hash: "2efa38a9c7c93ac98f3a097af25c2f58b9e7673787717bc27e98827118c2c1a5",
+            messages: &[
+                ("4c756477696720766f6e204d69736573", "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c79448134d00711fdb390a0d178fa008f6d47d2891e5ea18ae136c3b4c23ac384efb0"),
+                ("4d757272617920526f746862617264", "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f1448088438ea16e3701bc0d77744f117bee22451c9afa7f4cdbbcff00c04a8ee0913c88"),
+                ("462e20412e20486179656b", "a62de29ce27cb80245d440d986ed816c156e9d757d7008df2198b0"),
+                ("4361726c204d656e676572", "174a35f11c689f4530d7208618e0564ae12f2f50ba8eb4df5382ff"),
+                ("4a65616e2d426170746973746520536179", "337e475ebb8eae60f91974c4e455a5af38d1d8628d1803b160d60442874b0a1777"),
+                ("457567656e2042f6686d20766f6e2042617765726b", "047e80e060b7bb08b53c5a23dfe9920cae135b9d1dc6302fc475003062723700366346ac9d"),
+            ],
+        },
+        CacophonyVector {
+            protocol_name: "Noise_IK_25519_AESGCM_SHA256",
+            prologue: "4a6f686e2047616c74",
+            init_static: Some("e61ef9919cde45dd5f82166404bd08e38bceb5dfdfded0a34c8df7ed542214d1"),
+            init_ephemeral: "893e28b9dc6ca8d611ab664754b8ceb7bac5117349a4439a6b0569da977c464a",
+            init_remote_static: Some("31e0303fd6418d2f8c0e78b91f22e8caed0fbe48656dcf4767e4834f701b8f62"),
+            resp_static: Some("4a3acbfdb163dec651dfa3194dece676d437029c62a408b4c5ea9114246e4893"),
+            resp_ephemeral: "bbdb4cdbd309f1a1f2e1456967fe288cadd6f712d65dc7b7793d5e63da6b375b",
+            handshake_hash: "669c8640d9e42a3cda2f232f78597ceefb01daa6e3df81181ccce6fc6b5026bf",
+            messages: &[
+                ("4c756477696720766f6e204d69736573", "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c79444e417bc55c7a8166c993356c1be41ef67818a292426f301556c7f26b21d25ddb097153891a9a956cff47b83e63ad8d701c1342c209cff1ca5ecd43402762ac249e3bd3a4c0a145fe07cb5dae28ea13a3"),
+                ("4d757272617920526f746862617264", "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f144808843af2ccf9972e22afc67aeafcd25162f7f98c363b7762e3e4cb7d272e39f27a5"),
+                ("462e20412e20486179656b", "66acfc92e3197de166809e6d4d5d003dcc819a84bc3522ca53c9d9"),
+                ("4361726c204d656e676572", "71f89aa6533a6de70b0826864dd75f60806ee40170c16290189eb3"),
+                ("4a65616e2d426170746973746520536179", "4795a3423550c8bf00386bd496a3e2c76c10669d2a75ab8f79b5094c5412a25705"),
+                ("457567656e2042f6686d20766f6e2042617765726b", "aa0bb39097555c918e40be82abc2b909eb79d9eb87adb07e268fc37323a6cf904fd01fb391"),
+            ],
+        },
+        CacophonyVector {
+            protocol_name: "Noise_IK_25519_ChaChaPoly_SHA256",
+            prologue: "4a6f686e2047616c74",
+            init_static: Some("e61ef9919cde45dd5f82166404bd08e38bceb5dfdfded0a34c8df7ed542214d1"),
+            init_ephemeral: "893e28b9dc6ca8d611ab664754b8ceb7bac5117349a4439a6b0569da977c464a",
+            init_remote_static: Some("31e0303fd6418d2f8c0e78b91f22e8caed0fbe48656dcf4767e4834f701b8f62"),
+            resp_static: Some("4a3acbfdb163dec651dfa3194dece676d437029c62a408b4c5ea9114246e4893"),
+            resp_ephemeral: "bbdb4cdbd309f1a1f2e1456967fe288cadd6f712d65dc7b7793d5e63da6b375b",
+            handshake_hash: "0b0f68fb0c27e03ce9b97565995ed4838cc0581b762ef72b062f6a546419fad7",
+            messages: &[
+                ("4c756477696720766f6e204d69736573", "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c7944718da798efbcd91528520204f904b9bd6c7413dccdc214d951e15253e39987f18146e8cd0873654207148333479d4d16c289f0294b29960a72f48e0b7bba2e89083169825e59642148d492020664ccf7"),
+                ("4d757272617920526f746862617264", "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f1448088435361e70b2ed446e6c9ec387d1d6b3b840f194e373979d241b203c4acafccf5"),
+                ("462e20412e20486179656b", "050e9f3c8fac16b68dbce8f8c4bfbf6617c897f9ada4aa29aa19c8"),
+                ("4361726c204d656e676572", "344233a6cabb7141d80f3da2fedc311d9646bbb0f505afe403a667"),
+                ("4a65616e2d426170746973746520536179", "62cdeeb172ad7ade7aa7d9e069da5790f12331bfa00177787a1d0810c67dc3b2b4"),
+                ("457567656e2042f6686d20766f6e2042617765726b", "029bead1b40992327044d409d9a1f3ad8f36c3c452775d557e18bbeb2e8dfcead32d514024"),
+            ],
+        },
+        CacophonyVector {
+            protocol_name: "Noise_XX_25519_AESGCM_SHA256",
+            prologue: "4a6f686e2047616c74",
+            init_static: Some("e61ef9919cde45dd5f82166404bd08e38bceb5dfdfded0a34c8df7ed542214d1"),
+            init_ephemeral: "893e28b9dc6ca8d611ab664754b8ceb7bac5117349a4439a6b0569da977c464a",
+            init_remote_static: None,
+            resp_static: Some("4a3acbfdb163dec651dfa3194dece676d437029c62a408b4c5ea9114246e4893"),
+            resp_ephemeral: "bbdb4cdbd309f1a1f2e1456967fe288cadd6f712d65dc7b7793d5e63da6b375b",
+            handshake_hash: "1b7aefb1125762aa21a252890d00af54519638b76437444538f9a52f21e2e0dc",
+            messages: &[
+                ("4c756477696720766f6e204d69736573", "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c79444c756477696720766f6e204d69736573"),
+                ("4d757272617920526f746862617264", "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f144808843757117acceb05bd7a45733bc22015c97a9d0cbaf41b80446d5988ff5127235d76b79eade70f473d6a4ef521fdcbeda5340d01e028ba793fc059f2724a83af05f12dda0448a7621a926b379a92477fd"),
+                ("462e20412e20486179656b", "c90f1cf77eba4e50edb038991565e36c9758943a989229b6051244dc4fbecb6946744b401af2ee1a5881b65fbb87fd07cb6a328ececc9ce6ce84c399dc332d4fd521fa4bb7f467ce909395"),
+                ("4361726c204d656e676572", "bc3fa77f6aca3e8466d7dc6bea10013e88a6a29add5132b461806c"),
+                ("4a65616e2d426170746973746520536179", "250b01074cdfe0df2ecf8ccbf1737b15a2ddb5b52fd9a396604e9c793cee3b3bb9"),
+                ("457567656e2042f6686d20766f6e2042617765726b", "449d4d433b3cdc3d02bf6fc881774b9df54366ebcffb9689bb13f14709822cd7ef42bcdb4d"),
+            ],
+        },
+        CacophonyVector {
+            protocol_name: "Noise_XX_25519_ChaChaPoly_SHA256",
+            prologue: "4a6f686e2047616c74",
+            init_static: Some("e61ef9919cde45dd5f82166404bd08e38bceb5dfdfded0a34c8df7ed542214d1"),
+            init_ephemeral: "893e28b9dc6ca8d611ab664754b8ceb7bac5117349a4439a6b0569da977c464a",
+            init_remote_static: None,
+            resp_static: Some("4a3acbfdb163dec651dfa3194dece676d437029c62a408b4c5ea9114246e4893"),
+            resp_ephemeral: "bbdb4cdbd309f1a1f2e1456967fe288cadd6f712d65dc7b7793d5e63da6b375b",
+            handshake_hash: "c8e5f64e846193be2a834104c2a009868d6c9f3bd3c186299888b488b2f1f58e",
+            messages: &[
+                ("4c756477696720766f6e204d69736573", "ca35def5ae56cec33dc2036731ab14896bc4c75dbb07a61f879f8e3afa4c79444c756477696720766f6e204d69736573"),
+                ("4d757272617920526f746862617264", "95ebc60d2b1fa672c1f46a8aa265ef51bfe38e7ccb39ec5be34069f14480884381cbad1f276e038c48378ffce2b65285e08d6b68aaa3629a5a8639392490e5b9bd5269c2f1e4f488ed8831161f19b7815528f8982ffe09be9b5c412f8a0db50f8814c7194e83f23dbd8d162c9326ad"),
+                ("462e20412e20486179656b", "c7195ffacac1307ff99046f219750fc47693e23c3cb08b89c2af808b444850a80ae475b9df0f169ae80a89be0865b57f58c9fea0d4ec82a286427402f113e4b6ae769a1d95941d49b25030"),
+                ("4361726c204d656e676572", "96763ed773f8e47bb3712f0e29b3060ffc956ffc146cee53d5e1df"),
+                ("4a65616e2d426170746973746520536179", "3e40f15f6f3a46ae446b253bf8b1d9ffb6ed9b174d272328ff91a7e2e5c79c07f5"),
+                ("457567656e2042f6686d20766f6e2042617765726b", "eb3f3515110702e047a6c9da4478b6ead94873c11c0f2d710ddb3f09fce024b3a58502ae3f"),
+            ],
+        },
+    ];
+
+    #[test]
+    fn test_noise_cacophony_vectors() {
+        for vector in CACOPHONY_VECTORS {
+            let protocol = NoiseProtocol::from_name(vector.protocol_name).unwrap();
+            assert_eq!(protocol.name(), vector.protocol_name);
+            let prologue = hex::decode(vector.prologue).unwrap();
+            let init_remote_static = vector.init_remote_static.map(|rs| hex::decode(rs).unwrap());
+
+            let mut initiator = HandshakeState::new(
+                protocol,
+                true,
+                &prologue,
+                vector.init_static.map(x25519_key),
+                init_remote_static.as_deref(),
+            )
+            .unwrap();
+            initiator.e = Some(x25519_key(vector.init_ephemeral));
+            let mut responder = HandshakeState::new(
+                protocol,
+                false,
+                &prologue,
+                vector.resp_static.map(x25519_key),
+                None,
+            )
+            .unwrap();
+            responder.e = Some(x25519_key(vector.resp_ephemeral));
+
+            let handshake_messages = protocol.pattern().messages().len();
+            let (handshake, transport) = vector.messages.split_at(handshake_messages);
+            for (i, (payload, ciphertext)) in handshake.iter().enumerate() {
+                let payload = hex::decode(payload).unwrap();
+                let (sender, receiver) = if i.is_multiple_of(2) {
+                    (&mut initiator, &mut responder)
+                } else {
+                    (&mut responder, &mut initiator)
+                };
+                let message = sender.write_message(&payload).unwrap();
+                assert_eq!(
+                    hex::encode(&message),
+                    *ciphertext,
+                    "{}",
+                    vector.protocol_name
+                );
+                assert_eq!(receiver.read_message(&message).unwrap(), payload);
+            }
+            assert!(initiator.is_finished() && responder.is_finished());
+            assert_eq!(
+                hex::encode(initiator.handshake_hash()),
+                vector.handshake_hash
+            );
+            assert_eq!(initiator.handshake_hash(), responder.handshake_hash());
+
+            let mut initiator = initiator.into_transport().unwrap();
+            let mut responder = responder.into_transport().unwrap();
+            for (i, (payload, ciphertext)) in transport.iter().enumerate() {
+                let payload = hex::decode(payload).unwrap();
+                let (sender, receiver) = if (handshake_messages + i).is_multiple_of(2) {
+                    (&mut initiator, &mut responder)
+                } else {
+                    (&mut responder, &mut initiator)
+                };
+                let message = sender.write_message(&payload).unwrap();
+                assert_eq!(
+                    hex::encode(&message),
+                    *ciphertext,
+                    "{}",
+                    vector.protocol_name
+                );
+                assert_eq!(receiver.read_message(&message).unwrap(), payload);
+            }
+        }
+    }
+
+    fn handshake(
+        protocol: NoiseProtocol,
+    ) -> (NoiseTransport, NoiseTransport, [u8; DH_SIZE], [u8; DH_SIZE]) {
+        let initiator_static = EcDh::new(CurveType::Curve25519).unwrap();
+        let responder_static = EcDh::new(CurveType::Curve25519).unwrap();
+        let initiator_public = public_key(&initiator_static).unwrap();
+        let responder_public = public_key(&responder_static).unwrap();
+
+        let pattern = protocol.pattern();
+        let mut initiator = HandshakeState::new(
+            protocol,
+            true,
+            b"prologue",
+            pattern.initiator_has_static().then_some(initiator_static),
+            pattern
+                .responder_static_pre_message()
+                .then_some(&responder_public[..]),
+        )
+        .unwrap();
+        let mut responder =
+            HandshakeState::new(protocol, false, b"prologue", Some(responder_static), None)
+                .unwrap();
+
+        let mut turn = 0usize;
+        while !initiator.is_finished() {
+            let (sender, receiver) = if turn.is_multiple_of(2) {
+                (&mut initiator, &mut responder)
+            } else {
+                (&mut responder, &mut initiator)
+            };
+            let message = sender.write_message(b"payload").unwrap();
+            assert_eq!(receiver.read_message(&message).unwrap(), b"payload");
+            turn += 1;
+        }
+        assert!(responder.is_finished());
+        (
+            initiator.into_transport().unwrap(),
+            responder.into_transport().unwrap(),
+            initiator_public,
+            responder_public,
+        )
+    }
+
+    #[test]
+    fn test_noise_handshakes() {
+        for pattern in [NoisePattern::Nk, NoisePattern::Ik, NoisePattern::Xx] {
+            for cipher in [NoiseCipher::AesGcm, NoiseCipher::ChaChaPoly] {
+                let protocol = NoiseProtocol::new(pattern, cipher);
+                let (mut initiator, mut responder, initiator_public, responder_public) =
+                    handshake(protocol);
+                assert_eq!(initiator.handshake_hash(), responder.handshake_hash());
+                assert_eq!(initiator.remote_static(), Some(&responder_public));
+                if pattern.initiator_has_static() {
+                    assert_eq!(responder.remote_static(), Some(&initiator_public));
+                } else {
+                    assert_eq!(responder.remote_static(), None);
+                }
+
+                let message = initiator.write_message(b"ping").unwrap();
+                assert_eq!(message.len(), 4 + NOISE_TAG_SIZE);
+                assert_eq!(responder.read_message(&message).unwrap(), b"ping");
+                let message = responder.write_message(b"pong").unwrap();
+                assert_eq!(initiator.read_message(&message).unwrap(), b"pong");
+            }
+        }
+    }
+
+    #[test]
+    fn test_noise_rekey() {
+        let protocol = NoiseProtocol::new(NoisePattern::Xx, NoiseCipher::AesGcm);
+        let (mut initiator, mut responder, _, _) = handshake(protocol);
+        let before = initiator.write_message(b"message").unwrap();
+        assert_eq!(responder.read_message(&before).unwrap(), b"message");
+
+        initiator.rekey_send().unwrap();
+        let after = initiator.write_message(b"message").unwrap();
+        assert!(responder.read_message(&after).is_err());
+        responder.rekey_receive().unwrap();
+        // The failed read did not move the nonce forward.
+        assert_eq!(responder.read_message(&after).unwrap(), b"message");
+    }
+
+    #[test]
+    fn test_noise_prologue_mismatch() {
+        let protocol = NoiseProtocol::new(NoisePattern::Xx, NoiseCipher::ChaChaPoly);
+        let mut initiator = HandshakeState::new(
+            protocol,
+            true,
+            b"prologue",
+            Some(EcDh::new(CurveType::Curve25519).unwrap()),
+            None,
+        )
+        .unwrap();
+        let mut responder = HandshakeState::new(
+            protocol,
+            false,
+            b"other",
+            Some(EcDh::new(CurveType::Curve25519).unwrap()),
+            None,
+        )
+        .unwrap();
+        // The first XX message is not encrypted, the second one fails to decrypt.
+        let message = initiator.write_message(&[]).unwrap();
+        responder.read_message(&message).unwrap();
+        let message = responder.write_message(&[]).unwrap();
+        assert_eq!(
+            initiator.read_message(&message).unwrap_err(),
+            SymCryptError::AuthenticationFailure
+        );
+    }
+
+    #[test]
+    fn test_noise_invalid_usage() {
+        let protocol = NoiseProtocol::new(NoisePattern::Nk, NoiseCipher::AesGcm);
+        let responder_static = EcDh::new(CurveType::Curve25519).unwrap();
+        let responder_public = public_key(&responder_static).unwrap();
+
+        // NK needs the responder static key on the initiator side.
+        assert!(HandshakeState::new(protocol, true, &[], None, None).is_err());
+        assert!(HandshakeState::new(protocol, false, &[], None, None).is_err());
+        // The static key must be a Curve25519 key.
+        assert!(HandshakeState::new(
+            protocol,
+            false,
+            &[],
+            Some(EcDh::new(CurveType::NistP256).unwrap()),
+            None
+        )
+        .is_err());
+
+        let mut initiator =
+            HandshakeState::new(protocol, true, &[], None, Some(&responder_public)).unwrap();
+        let mut responder =
+            HandshakeState::new(protocol, false, &[], Some(responder_static), None).unwrap();
+        // The responder can not write first, and the initiator can not read first.
+        assert_eq!(
+            responder.write_message(&[]).unwrap_err(),
+            SymCryptError::InvalidArgument
+        );
+        assert_eq!(
+            initiator.read_message(&[]).unwrap_err(),
+            SymCryptError::InvalidArgument
+        );
+        let message = initiator.write_message(&[]).unwrap();
+        assert_eq!(
+            responder.read_message(&message[..DH_SIZE - 1]).unwrap_err(),
+            SymCryptError::WrongDataSize
+        );
+        assert!(responder.into_transport().is_err());
+    }
+
+    #[test]
+    fn test_noise_cipher_state() {
+        let mut cipher_state = CipherState::new(NoiseCipher::ChaChaPoly);
+        assert!(!cipher_state.has_key());
+        assert_eq!(
+            cipher_state.encrypt_with_ad(b"ad", b"data").unwrap(),
+            b"data"
+        );
+        assert!(cipher_state.rekey().is_err());
+
+        cipher_state.initialize_key(&[7u8; 32]).unwrap();
+        cipher_state.set_nonce(u64::MAX);
+        assert_eq!(
+            cipher_state.encrypt_with_ad(b"ad", b"data").unwrap_err(),
+            SymCryptError::ValueTooLarge
+        );
+
+        let mut sender = CipherState::new(NoiseCipher::AesGcm);
+        let mut receiver = CipherState::new(NoiseCipher::AesGcm);
+        sender.initialize_key(&[9u8; 32]).unwrap();
+        receiver.initialize_key(&[9u8; 32]).unwrap();
+        let ciphertext = sender.encrypt_with_ad(b"ad", b"data").unwrap();
+        assert!(receiver.decrypt_with_ad(b"other", &ciphertext).is_err());
+        assert_eq!(
+            receiver.decrypt_with_ad(b"ad", &ciphertext).unwrap(),
+            b"data"
+        );
+    }
+
+    #[test]
+    fn test_noise_protocol_names() {
+        let protocol = NoiseProtocol::from_name("Noise_IK_25519_ChaChaPoly_SHA256").unwrap();
+        assert_eq!(protocol.pattern(), NoisePattern::Ik);
+        assert_eq!(protocol.cipher(), NoiseCipher::ChaChaPoly);
+        for name in [
+            "Noise_XX_448_ChaChaPoly_SHA256",
+            "Noise_XX_25519_ChaChaPoly_BLAKE2s",
+            "Noise_KK_25519_AESGCM_SHA256",
+            "Noise_XXpsk3_25519_AESGCM_SHA256",
+            "Noise_XX_25519_AESGCM",
+        ] {
+            assert_eq!(
+                NoiseProtocol::from_name(name).unwrap_err(),
+                SymCryptError::InvalidArgument
+            );
+        }
+    }
+}
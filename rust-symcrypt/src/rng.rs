@@ -0,0 +1,323 @@
+//! Random number generation. For further documentation please refer to symcrypt.h
+//!
+//! [`fill_bytes()`] fills a buffer from the SymCrypt system RNG, which is seeded and reseeded by SymCrypt.
+//!
+//! [`AesCtrDrbg`] is the AES-256 CTR_DRBG with derivation function from SP 800-90A, seeded by the caller. It is meant for reproducible
+//! test fixtures and for callers that bring their own entropy source. Its output is only as unpredictable as the entropy it is given.
+//!
+//! With the `rand_core` feature, [`SymCryptRng`] provides the SymCrypt system RNG to code that is generic over
+//! `R: RngCore + CryptoRng`.
+
+use crate::errors::SymCryptError;
+use crate::secret::SecretBytes;
+use crate::symcrypt_init;
+use std::pin::Pin;
+use symcrypt_sys;
+
+/// Minimum size of the seed material for [`AesCtrDrbg::instantiate()`], which is the entropy input, nonce and personalization string.
+pub const AES_CTR_DRBG_MIN_INSTANTIATE_SIZE: usize =
+    symcrypt_sys::SYMCRYPT_RNG_AES_MIN_INSTANTIATE_SIZE as usize;
+
+/// Minimum size of the seed material for [`AesCtrDrbg::reseed()`], which is the entropy input and additional input.
+pub const AES_CTR_DRBG_MIN_RESEED_SIZE: usize =
+    symcrypt_sys::SYMCRYPT_RNG_AES_MIN_RESEED_SIZE as usize;
+
+/// Maximum size of the seed material for [`AesCtrDrbg::instantiate()`] and [`AesCtrDrbg::reseed()`].
+pub const AES_CTR_DRBG_MAX_SEED_SIZE: usize = symcrypt_sys::SYMCRYPT_RNG_AES_MAX_SEED_SIZE as usize;
+
+/// Maximum number of bytes returned by a single call to [`AesCtrDrbg::generate_with_additional_input()`].
+pub const AES_CTR_DRBG_MAX_REQUEST_SIZE: usize =
+    symcrypt_sys::SYMCRYPT_RNG_AES_MAX_REQUEST_SIZE as usize;
+
+/// Fills `buffer` with random bytes from the SymCrypt system RNG.
+pub fn fill_bytes(buffer: &mut [u8]) {
+    symcrypt_init();
+    unsafe {
+        // SAFETY: FFI calls
+        symcrypt_sys::SymCryptRandom(buffer.as_mut_ptr(), buffer.len() as symcrypt_sys::SIZE_T);
+    }
+}
+
+/// [`SymCryptRng`] implements `rand_core::RngCore` and `rand_core::CryptoRng` on top of [`fill_bytes()`]. It holds no state, every
+/// call draws from the SymCrypt system RNG.
+#[cfg(feature = "rand_core")]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SymCryptRng;
+
+#[cfg(feature = "rand_core")]
+impl rand_core::RngCore for SymCryptRng {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rand_core")]
+impl rand_core::CryptoRng for SymCryptRng {}
+
+/// [`AesCtrDrbg`] is a wrapper around symcrypt_sys::SYMCRYPT_RNG_AES_STATE. The state is uninstantiated, which wipes it, when
+/// [`AesCtrDrbg`] leaves scope.
+///
+/// The state is Pin<Box<>>'d since the memory address for Self is moved around when returning from AesCtrDrbg::instantiate().
+/// SymCrypt expects the address for its structs to stay static through the structs lifetime to guarantee that structs are not memcpy'd as
+/// doing so would lead to use-after-free and inconsistent states.
+pub struct AesCtrDrbg(Pin<Box<symcrypt_sys::SYMCRYPT_RNG_AES_STATE>>);
+
+/// Impl for [`AesCtrDrbg`]
+///
+/// [`instantiate()`] takes in the `entropy_input`, `nonce` and `personalization_string` as defined in SP 800-90A section 10.2.1.3.2.
+/// Their combined size must be between [`AES_CTR_DRBG_MIN_INSTANTIATE_SIZE`] and [`AES_CTR_DRBG_MAX_SEED_SIZE`] bytes, otherwise
+/// [`SymCryptError::InvalidArgument`] is returned. The nonce and personalization string can be empty.
+///
+/// [`reseed()`] takes in a new `entropy_input` and an optional `additional_input`, with a combined size between
+/// [`AES_CTR_DRBG_MIN_RESEED_SIZE`] and [`AES_CTR_DRBG_MAX_SEED_SIZE`] bytes.
+///
+/// [`generate()`] fills `output` with the next bytes of the DRBG.
+///
+/// [`generate_with_additional_input()`] fills `output` with the next bytes of the DRBG after mixing `additional_input` into the state,
+/// as defined in SP 800-90A section 10.2.1.5.2. `output` can be at most [`AES_CTR_DRBG_MAX_REQUEST_SIZE`] bytes and `additional_input`
+/// at most [`AES_CTR_DRBG_MAX_SEED_SIZE`] bytes, otherwise [`SymCryptError::InvalidArgument`] is returned.
+impl AesCtrDrbg {
+    pub fn instantiate(
+        entropy_input: &[u8],
+        nonce: &[u8],
+        personalization_string: &[u8],
+    ) -> Result<Self, SymCryptError> {
+        let seed_material = concat_seed_material(
+            &[entropy_input, nonce, personalization_string],
+            AES_CTR_DRBG_MIN_INSTANTIATE_SIZE,
+        )?;
+        symcrypt_init();
+        let mut state = Box::pin(symcrypt_sys::SYMCRYPT_RNG_AES_STATE::default()); // boxing here so that the memory is not moved
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptRngAesInstantiate(
+                &mut *state,
+                seed_material.as_ptr(),
+                seed_material.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(AesCtrDrbg(state)),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn reseed(
+        &mut self,
+        entropy_input: &[u8],
+        additional_input: &[u8],
+    ) -> Result<(), SymCryptError> {
+        let seed_material = concat_seed_material(
+            &[entropy_input, additional_input],
+            AES_CTR_DRBG_MIN_RESEED_SIZE,
+        )?;
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptRngAesReseed(
+                &mut *self.0,
+                seed_material.as_ptr(),
+                seed_material.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(()),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn generate(&mut self, output: &mut [u8]) {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptRngAesGenerate(
+                &mut *self.0,
+                output.as_mut_ptr(),
+                output.len() as symcrypt_sys::SIZE_T,
+            );
+        }
+    }
+
+    pub fn generate_with_additional_input(
+        &mut self,
+        additional_input: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), SymCryptError> {
+        if output.len() > AES_CTR_DRBG_MAX_REQUEST_SIZE
+            || additional_input.len() > AES_CTR_DRBG_MAX_SEED_SIZE
+        {
+            return Err(SymCryptError::InvalidArgument);
+        }
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptRngAesGenerateSmall(
+                &mut *self.0,
+                output.as_mut_ptr(),
+                output.len() as symcrypt_sys::SIZE_T,
+                additional_input.as_ptr(),
+                additional_input.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(()),
+                err => Err(err.into()),
+            }
+        }
+    }
+}
+
+impl Drop for AesCtrDrbg {
+    fn drop(&mut self) {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptRngAesUninstantiate(&mut *self.0);
+        }
+    }
+}
+
+/// concat_seed_material concatenates the inputs of instantiate or reseed, and checks the size limits of SymCrypt.
+fn concat_seed_material(inputs: &[&[u8]], min_size: usize) -> Result<SecretBytes, SymCryptError> {
+    let size: usize = inputs.iter().map(|input| input.len()).sum();
+    if !(min_size..=AES_CTR_DRBG_MAX_SEED_SIZE).contains(&size) {
+        return Err(SymCryptError::InvalidArgument);
+    }
+    Ok(SecretBytes::from(inputs.concat()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn generate_twice(drbg: &mut AesCtrDrbg) -> String {
+        // CAVP vectors return the output of the second generate call.
+        let mut output = [0u8; 64];
+        drbg.generate(&mut output);
+        drbg.generate(&mut output);
+        hex::encode(output)
+    }
+
+    #[test]
+    fn test_fill_bytes() {
+        let mut buffer_1 = [0u8; 32];
+        let mut buffer_2 = [0u8; 32];
+        fill_bytes(&mut buffer_1);
+        fill_bytes(&mut buffer_2);
+        assert_ne!(buffer_1, buffer_2);
+    }
+
+    #[cfg(feature = "rand_core")]
+    #[test]
+    fn test_symcrypt_rng() {
+        use rand_core::{CryptoRng, RngCore};
+
+        fn random_id<R: RngCore + CryptoRng>(rng: &mut R) -> [u8; 16] {
+            let mut id = [0u8; 16];
+            rng.fill_bytes(&mut id);
+            id
+        }
+
+        let mut rng = SymCryptRng;
+        assert_ne!(random_id(&mut rng), random_id(&mut rng));
+        assert_ne!(rng.next_u64(), rng.next_u64());
+
+        let mut buffer = [0u8; 64];
+        rng.try_fill_bytes(&mut buffer).unwrap();
+        assert_ne!(buffer, [0u8; 64]);
+    }
+
+    #[test]
+    fn test_aes_ctr_drbg_cavp_no_reseed() {
+        // CAVP drbgvectors_no_reseed CTR_DRBG.rsp, [AES-256 use df] [PredictionResistance = False], COUNT = 0.
+        let entropy_input =
+            hex::decode("36401940fa8b1fba91a1661f211d78a0b9389a74e5bccfece8d766af1a6d3b14")
+                .unwrap();
+        let nonce = hex::decode("496f25b0f1301b4f501be30380a137eb").unwrap();
+        let expected = "5862eb38bd558dd978a696e6df164782ddd887e7e9a6c9f3f1fbafb78941b535a64912dfd224c6dc7454e5250b3d97165e16260c2faf1cc7735cb75fb4f07e1d";
+
+        let mut drbg = AesCtrDrbg::instantiate(&entropy_input, &nonce, &[]).unwrap();
+        assert_eq!(generate_twice(&mut drbg), expected);
+    }
+
+    #[test]
+    fn test_aes_ctr_drbg_reseed() {
+        // CAVP drbgvectors_pr_false CTR_DRBG.rsp, [AES-256 use df] [PredictionResistance = False], COUNT = 0.
+        let entropy_input =
+            hex::decode("5a194d5e2b31581454def675fb7958fec7db873e5689fc9d03217c68d8033820")
+                .unwrap();
+        let nonce = hex::decode("1b54b8ff0642bff521f15c1c0b665f3f").unwrap();
+        let entropy_input_reseed =
+            hex::decode("f9e65e04d856f3a9c44a4cbdc1d00846f5983d771c1b137e4e0f9d8ef409f92e")
+                .unwrap();
+        let expected = "b4c2edf16db04f3b4f57a678b2ceef27d2acc6e59c4884ac2d5a05574214d7f0f2b0b4d746d891368cdc7af47d6840b1607b3e43eaed1717f29067caa71054b3";
+
+        let mut drbg = AesCtrDrbg::instantiate(&entropy_input, &nonce, &[]).unwrap();
+        drbg.reseed(&entropy_input_reseed, &[]).unwrap();
+        assert_eq!(generate_twice(&mut drbg), expected);
+    }
+
+    #[test]
+    fn test_aes_ctr_drbg_additional_input() {
+        // CAVP CTR_DRBG.rsp, [AES-256 use df] [PredictionResistance = False] [EntropyInputLen = 256] [NonceLen = 128]
+        // [PersonalizationStringLen = 256] [AdditionalInputLen = 256] [ReturnedBitsLen = 128], COUNT = 0.
+        // The same vector is in the mbed TLS test_suite_ctr_drbg.data as "CTR_DRBG NIST Validation (AES-256 use df,False,256,128,256,256) #0".
+        let entropy_input =
+            hex::decode("a53e371017439193591e475087aaddd5c1c386cdca0ddb68e002d80fdc401a47")
+                .unwrap();
+        let nonce = hex::decode("a94da55afdc50ce51c9a3b8a4c448440").unwrap();
+        let personalization_string =
+            hex::decode("8b52a24a93c34ea71e1ca705eb829ba65de4d4e07fa3d86b37845ff1c7d5f6d2")
+                .unwrap();
+        let additional_input_1 =
+            hex::decode("20f422edf85ca16a01cfbe5f8d6c947fae12a857db2aa9bfc7b36581808d0d46")
+                .unwrap();
+        let entropy_input_reseed =
+            hex::decode("dd40e5987b2716731568d276bf0c6715757903d3dede914642ddd467c879c81e")
+                .unwrap();
+        let additional_input_reseed =
+            hex::decode("7fd81fbd2ab51c115d834e99f65ca54020ed388ed59ee07593fe125e5d73fb75")
+                .unwrap();
+        let additional_input_2 =
+            hex::decode("cd2cff14693e4c9efdfe260de986004930bab1c65057772a62392c3b74ebc90d")
+                .unwrap();
+
+        let mut drbg =
+            AesCtrDrbg::instantiate(&entropy_input, &nonce, &personalization_string).unwrap();
+        let mut output = [0u8; 16];
+        drbg.generate_with_additional_input(&additional_input_1, &mut output)
+            .unwrap();
+        drbg.reseed(&entropy_input_reseed, &additional_input_reseed)
+            .unwrap();
+        drbg.generate_with_additional_input(&additional_input_2, &mut output)
+            .unwrap();
+        assert_eq!(hex::encode(output), "4f78beb94d978ce9d097feadfafd355e");
+    }
+
+    #[test]
+    fn test_aes_ctr_drbg_seed_size() {
+        let result = AesCtrDrbg::instantiate(&[0u8; 16], &[0u8; 8], &[]);
+        assert_eq!(result.err().unwrap(), SymCryptError::InvalidArgument);
+
+        let result = AesCtrDrbg::instantiate(&[0u8; 32], &[0u8; 16], &[0u8; 256]);
+        assert_eq!(result.err().unwrap(), SymCryptError::InvalidArgument);
+
+        let mut drbg = AesCtrDrbg::instantiate(&[0u8; 32], &[], &[]).unwrap();
+        assert_eq!(
+            drbg.reseed(&[0u8; 16], &[]).unwrap_err(),
+            SymCryptError::InvalidArgument
+        );
+
+        let mut output = vec![0u8; AES_CTR_DRBG_MAX_REQUEST_SIZE + 1];
+        assert_eq!(
+            drbg.generate_with_additional_input(&[], &mut output)
+                .unwrap_err(),
+            SymCryptError::InvalidArgument
+        );
+    }
+}
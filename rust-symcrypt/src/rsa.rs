@@ -0,0 +1,413 @@
+//! RSA functions. For further documentation please refer to symcrypt.h
+//!
+//! Only signing and verifying with PKCS#1 v1.5 and PSS padding are provided. Keys are imported from their big-endian components, see
+//! [`crate::key_encoding`] to decode them from DER or PEM.
+
+use crate::errors::{map_verify_error, SymCryptError};
+use crate::hash::{convert_hash, HashAlgorithm};
+use crate::key_encoding::{RsaPrivateKey, RsaPublicKey};
+use symcrypt_sys;
+
+/// [`RsaKey`] holds the SymCrypt RsaKey, which is allocated by SymCrypt and freed when RsaKey leaves scope.
+pub struct RsaKey {
+    inner: symcrypt_sys::PSYMCRYPT_RSAKEY,
+}
+
+/// Impl for RsaKey struct.
+///
+/// [`from_public_key()`] takes in the big-endian modulus and public exponent, and returns a [`RsaKey`] that can only verify. The public
+/// exponent must fit in 64 bits.
+///
+/// [`from_key_pair()`] takes in the big-endian modulus, public exponent and the two primes, and returns a [`RsaKey`] that can sign and
+/// verify. SymCrypt computes the private exponent from the primes.
+///
+/// [`from_rsa_public_key()`] and [`from_rsa_private_key()`] import the keys decoded by [`crate::key_encoding`].
+///
+/// [`modulus_size()`] returns the size of the modulus in bytes, which is also the size of a signature.
+///
+/// [`has_private_key()`] returns true if the key was imported with its primes.
+///
+/// [`pkcs1_sign()`] takes in a hashed message and the [`HashAlgorithm`] that hashed it, and returns the PKCS#1 v1.5 signature.
+///
+/// [`pkcs1_verify()`] takes in a hashed message, a PKCS#1 v1.5 signature and the [`HashAlgorithm`]. The DigestInfo is accepted with or
+/// without the NULL parameters.
+///
+/// [`pss_sign()`] takes in a hashed message, the [`HashAlgorithm`] that is also used for MGF1, and the salt size in bytes, and returns the
+/// PSS signature.
+///
+/// [`pss_verify()`] takes in a hashed message, a PSS signature, the [`HashAlgorithm`] and the salt size in bytes.
+///
+/// Any failure to verify, including a malformed signature, returns [`SymCryptError::SignatureVerificationFailure`].
+impl RsaKey {
+    pub fn from_public_key(modulus: &[u8], public_exponent: &[u8]) -> Result<Self, SymCryptError> {
+        RsaKey::import(modulus, public_exponent, &[])
+    }
+
+    pub fn from_key_pair(
+        modulus: &[u8],
+        public_exponent: &[u8],
+        prime1: &[u8],
+        prime2: &[u8],
+    ) -> Result<Self, SymCryptError> {
+        if prime1.is_empty() || prime2.is_empty() {
+            return Err(SymCryptError::InvalidArgument);
+        }
+        RsaKey::import(modulus, public_exponent, &[prime1, prime2])
+    }
+
+    pub fn from_rsa_public_key(key: &RsaPublicKey) -> Result<Self, SymCryptError> {
+        RsaKey::from_public_key(&key.modulus, &key.public_exponent)
+    }
+
+    pub fn from_rsa_private_key(key: &RsaPrivateKey) -> Result<Self, SymCryptError> {
+        RsaKey::from_key_pair(&key.modulus, &key.public_exponent, &key.prime1, &key.prime2)
+    }
+
+    fn import(
+        modulus: &[u8],
+        public_exponent: &[u8],
+        primes: &[&[u8]],
+    ) -> Result<Self, SymCryptError> {
+        let modulus = strip_leading_zeros(modulus);
+        let public_exponent = u64_from_be_bytes(public_exponent)?;
+        if modulus.is_empty() {
+            return Err(SymCryptError::InvalidArgument);
+        }
+        let bits_of_modulus = modulus.len() * 8 - modulus[0].leading_zeros() as usize;
+        let params = symcrypt_sys::SYMCRYPT_RSA_PARAMS {
+            version: 1,
+            nBitsOfModulus: bits_of_modulus as symcrypt_sys::UINT32,
+            nPrimes: 2,
+            nPubExp: 1,
+        };
+        let mut prime_ptrs: Vec<symcrypt_sys::PCBYTE> = primes.iter().map(|p| p.as_ptr()).collect();
+        let mut prime_sizes: Vec<symcrypt_sys::SIZE_T> = primes
+            .iter()
+            .map(|p| p.len() as symcrypt_sys::SIZE_T)
+            .collect();
+
+        unsafe {
+            // SAFETY: FFI calls
+            let key_ptr = symcrypt_sys::SymCryptRsakeyAllocate(&params, 0);
+            if key_ptr.is_null() {
+                return Err(SymCryptError::MemoryAllocationFailure);
+            }
+            // Key is wrapped first so that it is freed if setting the value fails.
+            let rsa_key = RsaKey { inner: key_ptr };
+
+            match symcrypt_sys::SymCryptRsakeySetValue(
+                modulus.as_ptr(),
+                modulus.len() as symcrypt_sys::SIZE_T,
+                &public_exponent,
+                1,
+                if primes.is_empty() {
+                    std::ptr::null_mut() // primes set to null since only the public key is imported
+                } else {
+                    prime_ptrs.as_mut_ptr()
+                },
+                if primes.is_empty() {
+                    std::ptr::null_mut()
+                } else {
+                    prime_sizes.as_mut_ptr()
+                },
+                primes.len() as symcrypt_sys::UINT32,
+                symcrypt_sys::_SYMCRYPT_NUMBER_FORMAT_SYMCRYPT_NUMBER_FORMAT_MSB_FIRST,
+                symcrypt_sys::SYMCRYPT_FLAG_RSAKEY_SIGN,
+                rsa_key.inner,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(rsa_key),
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn modulus_size(&self) -> usize {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptRsakeySizeofModulus(self.inner) as usize
+        }
+    }
+
+    pub fn has_private_key(&self) -> bool {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptRsakeyHasPrivateKey(self.inner) != 0
+        }
+    }
+
+    pub fn pkcs1_sign(
+        &self,
+        hashed_message: &[u8],
+        hash_algorithm: HashAlgorithm,
+    ) -> Result<Vec<u8>, SymCryptError> {
+        let mut signature = vec![0u8; self.modulus_size()];
+        let mut signature_size: symcrypt_sys::SIZE_T = 0;
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptRsaPkcs1Sign(
+                self.inner,
+                hashed_message.as_ptr(),
+                hashed_message.len() as symcrypt_sys::SIZE_T,
+                hash_oids(hash_algorithm).as_ptr(),
+                1, // Only the first OID, which has the NULL parameters, is used to sign
+                0, // No flags
+                symcrypt_sys::_SYMCRYPT_NUMBER_FORMAT_SYMCRYPT_NUMBER_FORMAT_MSB_FIRST,
+                signature.as_mut_ptr(),
+                signature.len() as symcrypt_sys::SIZE_T,
+                &mut signature_size,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => {
+                    signature.truncate(signature_size as usize);
+                    Ok(signature)
+                }
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn pkcs1_verify(
+        &self,
+        hashed_message: &[u8],
+        signature: &[u8],
+        hash_algorithm: HashAlgorithm,
+    ) -> Result<(), SymCryptError> {
+        let oids = hash_oids(hash_algorithm);
+        unsafe {
+            // SAFETY: FFI calls
+            map_verify_error(symcrypt_sys::SymCryptRsaPkcs1Verify(
+                self.inner,
+                hashed_message.as_ptr(),
+                hashed_message.len() as symcrypt_sys::SIZE_T,
+                signature.as_ptr(),
+                signature.len() as symcrypt_sys::SIZE_T,
+                symcrypt_sys::_SYMCRYPT_NUMBER_FORMAT_SYMCRYPT_NUMBER_FORMAT_MSB_FIRST,
+                oids.as_ptr(),
+                oids.len() as symcrypt_sys::SIZE_T,
+                0, // No flags
+            ))
+        }
+    }
+
+    pub fn pss_sign(
+        &self,
+        hashed_message: &[u8],
+        hash_algorithm: HashAlgorithm,
+        salt_size: usize,
+    ) -> Result<Vec<u8>, SymCryptError> {
+        let mut signature = vec![0u8; self.modulus_size()];
+        let mut signature_size: symcrypt_sys::SIZE_T = 0;
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptRsaPssSign(
+                self.inner,
+                hashed_message.as_ptr(),
+                hashed_message.len() as symcrypt_sys::SIZE_T,
+                convert_hash(hash_algorithm),
+                salt_size as symcrypt_sys::SIZE_T,
+                0, // No flags
+                symcrypt_sys::_SYMCRYPT_NUMBER_FORMAT_SYMCRYPT_NUMBER_FORMAT_MSB_FIRST,
+                signature.as_mut_ptr(),
+                signature.len() as symcrypt_sys::SIZE_T,
+                &mut signature_size,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => {
+                    signature.truncate(signature_size as usize);
+                    Ok(signature)
+                }
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn pss_verify(
+        &self,
+        hashed_message: &[u8],
+        signature: &[u8],
+        hash_algorithm: HashAlgorithm,
+        salt_size: usize,
+    ) -> Result<(), SymCryptError> {
+        unsafe {
+            // SAFETY: FFI calls
+            map_verify_error(symcrypt_sys::SymCryptRsaPssVerify(
+                self.inner,
+                hashed_message.as_ptr(),
+                hashed_message.len() as symcrypt_sys::SIZE_T,
+                signature.as_ptr(),
+                signature.len() as symcrypt_sys::SIZE_T,
+                symcrypt_sys::_SYMCRYPT_NUMBER_FORMAT_SYMCRYPT_NUMBER_FORMAT_MSB_FIRST,
+                convert_hash(hash_algorithm),
+                salt_size as symcrypt_sys::SIZE_T,
+                0, // No flags
+            ))
+        }
+    }
+}
+
+unsafe impl Send for RsaKey {
+    // The RsaKey is only referenced through a SymCrypt allocated pointer, which does not move.
+}
+
+unsafe impl Sync for RsaKey {
+    // Signing and verifying only read the RsaKey.
+}
+
+impl Drop for RsaKey {
+    fn drop(&mut self) {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptRsakeyFree(self.inner);
+        }
+    }
+}
+
+/// hash_oids returns the DigestInfo OIDs for PKCS#1 v1.5, the first one has the NULL parameters and the second one omits them.
+fn hash_oids(hash_algorithm: HashAlgorithm) -> &'static [symcrypt_sys::SYMCRYPT_OID; 2] {
+    match hash_algorithm {
+        HashAlgorithm::Sha256 => unsafe { &symcrypt_sys::SymCryptSha256OidList }, // SAFETY: FFI calls
+        HashAlgorithm::Sha384 => unsafe { &symcrypt_sys::SymCryptSha384OidList }, // SAFETY: FFI calls
+    }
+}
+
+fn strip_leading_zeros(value: &[u8]) -> &[u8] {
+    let start = value.iter().position(|&b| b != 0).unwrap_or(value.len());
+    &value[start..]
+}
+
+/// u64_from_be_bytes converts the public exponent, SymCrypt only supports exponents that fit in 64 bits.
+fn u64_from_be_bytes(value: &[u8]) -> Result<u64, SymCryptError> {
+    let value = strip_leading_zeros(value);
+    if value.is_empty() || value.len() > 8 {
+        return Err(SymCryptError::InvalidArgument);
+    }
+    Ok(value.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::hash::{sha256, sha384};
+
+    // 2048-bit key with signatures of "sample". The PKCS#1 v1.5 signatures are the ones OpenSSL 3.5 produces with
+    // `openssl dgst -sha256 -sign` and `-sha384 -sign`, and the PSS signature (salt size 32) verifies with
+    // `openssl dgst -sha256 -verify -sigopt rsa_padding_mode:pss -sigopt rsa_pss_saltlen:32`.
+    const MODULUS: &str = "cfff2b39974f6de5636ac4920485cd786e5ff082e70bf267336a133cdfdf7f57c048351157d98d7e2b7658b5e9021e4f9a780597503b8e6be6a0be5171833f8c875c8086b03ce6f3fac8cc74be0d74a162a4107c1edee81029a64bf6ac6e3dcff048fc51ab04a7a698f2636e36113c700c666874491821a137a051a5e754574f21217441ec76509736e1cea85a1922bfeeb41d76bf10021652e0b01098381d6a10f8e9ac53feb75e5e58bcbe49ca28076e7291ad75042e0d5e65610967b58d653bb665e2f1d4e23e65b22b82f5876525eb961a434c6bed499f094d85a90e8c714171c512fd027b768d271c66d0963df7da968fa870862491e61d392912524e75";
+    const PRIME1: &str = "f4697a5391391afd1be53cc9e63cddf9d42ecaac1666738189602e58ec13259cce5afceb0ad3b10466b4c4a5fa5c34188e80d517ce55f6edec532b1c547da43bd30d067069c8237f8ba677783fbace43afad9332866210fcf62172b0a29c6fc3ddd9c0597d771053ddbc730673bf39d244dab1d324039115aaa2c2ca21a4310d";
+    const PRIME2: &str = "d9dbb47864d522ebc40b31aee24f00f9bfa204d3b195b207a485d746ca31b366853b76efa8223557a79d3c462c66dd733fee05eb5322b51baccdf6d24cdfc668c211d939df26851d781ab693a6b86cfcfba334bcd1e5731c686fabd7efdc6a846b0b9bc18f827794a46920a89a00afcea79c014f039d16c304f25ffcb317a909";
+    const PKCS1_SHA256_SIGNATURE: &str = "0be6e818255e99017a929664328fe353b962cd1364d2df19be2b5b756d8e3fa5fef5f1bd86d7222eca233146dd6ec5a173276f49db712fdf3e38e139c300af9716e458993a0eba082fdc680f78421dbab31ae9cd728961e680928f646667fa194273edd47fcf6338f3c9f0b7b58a698fb53b2e36438722a2ed3ef511a7a0f1efe7eca9fd8063938805582d1de80701eb7e22513b3924fbee304b2b504aaae89faf40e7cbeaab532d34f7735d17a82184e9722783e905b431668d5ba6eaf43be65444c5cd6a05e032634b16eef76a96030f00c12c58666c28859c226a283718d0dad65b65433ccf4b976e2d2e182d19c6df4c36fc6f1f398bc3093f09483aabd0";
+    const PKCS1_SHA384_SIGNATURE: &str = "3ae8ff928eac71251e29701697731ff84131d7b2ae42a29811452673d6ad8fa1b70b599d1d26ebd48d9e96c696df58bf3fc15e463844cd95bca3092aba33c89cfef52bbfed3b039463d6ab363538eb26cf89d150e5e0728b828d60bfc74584fa5528323501f322ee078c6ee7474fc38cd5eaa68b4d73dd6ac230c89a67a45095ec28dddc995e9bd7d50473e09ddf00a73afe975558e5d7c7ce6436800096b889030802564534cfa0b77b63f0f7e75df5864406c72549db4c90fa8331dee8be0f59b058079f445cf516ba5ff95cc9e0a54a951775cc0a9814f3e5a8b604fac213d9562a9ae10b19ab4e9e8242ab9f504d695ac46d302c1e6689a9b4d25eb4031e";
+    const PSS_SHA256_SIGNATURE: &str = "0e32b24b6ed23597b061594e8c73898e5f1acec4b50ef97f768e759e154e6ba639215baf26e37737e8534cc7d01184b8fbcc71b44892744592e4d2adc83885b66d6eaa2844ae2adad560a8f9a62bcd192caf37173d6f660b0be505c4b17af8ec7bfba155fecb4fe9bbb561a4736f0fe74ce20c7999c42e849449564eaecb6db46944d42622fc5e84a55b50d4f651cee323695893677eebc203b9db50a93e234ac6caa89e8672defb41ff37a1620936181b9f0c834a904edf1f1edcfca2ff02e16caf65238da8b8f15f8c44e9fe181635d00acf3499902d677e779fdfec8f3553d5477ce959fe506150946ed346c1d48510f7f3e6fd21b6da518b960744c02da4";
+    const PUBLIC_EXPONENT: &[u8] = &[0x01, 0x00, 0x01];
+
+    fn key(with_private_key: bool) -> RsaKey {
+        let modulus = hex::decode(MODULUS).unwrap();
+        if with_private_key {
+            RsaKey::from_key_pair(
+                &modulus,
+                PUBLIC_EXPONENT,
+                &hex::decode(PRIME1).unwrap(),
+                &hex::decode(PRIME2).unwrap(),
+            )
+            .unwrap()
+        } else {
+            RsaKey::from_public_key(&modulus, PUBLIC_EXPONENT).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_rsa_pkcs1_verify() {
+        let key = key(false);
+        assert!(!key.has_private_key());
+        assert_eq!(key.modulus_size(), 256);
+
+        let signature = hex::decode(PKCS1_SHA256_SIGNATURE).unwrap();
+        key.pkcs1_verify(&sha256(b"sample"), &signature, HashAlgorithm::Sha256)
+            .unwrap();
+        let signature = hex::decode(PKCS1_SHA384_SIGNATURE).unwrap();
+        key.pkcs1_verify(&sha384(b"sample"), &signature, HashAlgorithm::Sha384)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_rsa_pkcs1_sign() {
+        let key = key(true);
+        assert!(key.has_private_key());
+
+        // PKCS#1 v1.5 signatures are deterministic.
+        let signature = key
+            .pkcs1_sign(&sha256(b"sample"), HashAlgorithm::Sha256)
+            .unwrap();
+        assert_eq!(hex::encode(signature), PKCS1_SHA256_SIGNATURE);
+        let signature = key
+            .pkcs1_sign(&sha384(b"sample"), HashAlgorithm::Sha384)
+            .unwrap();
+        assert_eq!(hex::encode(signature), PKCS1_SHA384_SIGNATURE);
+    }
+
+    #[test]
+    fn test_rsa_pss() {
+        let signature = hex::decode(PSS_SHA256_SIGNATURE).unwrap();
+        key(false)
+            .pss_verify(&sha256(b"sample"), &signature, HashAlgorithm::Sha256, 32)
+            .unwrap();
+
+        // PSS signatures are randomized, so they are checked by verifying them.
+        let signature = key(true)
+            .pss_sign(&sha256(b"message"), HashAlgorithm::Sha256, 32)
+            .unwrap();
+        assert_eq!(signature.len(), 256);
+        key(false)
+            .pss_verify(&sha256(b"message"), &signature, HashAlgorithm::Sha256, 32)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_rsa_verify_failures() {
+        let key = key(false);
+        let hashed_message = sha256(b"sample");
+        let mut signature = hex::decode(PKCS1_SHA256_SIGNATURE).unwrap();
+        assert_eq!(
+            key.pkcs1_verify(&hashed_message, &signature, HashAlgorithm::Sha384)
+                .unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+        assert_eq!(
+            key.pss_verify(&hashed_message, &signature, HashAlgorithm::Sha256, 32)
+                .unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+        signature[10] ^= 1;
+        assert_eq!(
+            key.pkcs1_verify(&hashed_message, &signature, HashAlgorithm::Sha256)
+                .unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+        assert_eq!(
+            key.pkcs1_verify(&hashed_message, &signature[1..], HashAlgorithm::Sha256)
+                .unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+    }
+
+    #[test]
+    fn test_rsa_invalid_keys() {
+        let modulus = hex::decode(MODULUS).unwrap();
+        assert!(key(false)
+            .pkcs1_sign(&sha256(b"sample"), HashAlgorithm::Sha256)
+            .is_err());
+        assert_eq!(
+            RsaKey::from_public_key(&modulus, &[0u8; 9]).err(),
+            Some(SymCryptError::InvalidArgument)
+        );
+        assert_eq!(
+            RsaKey::from_public_key(&modulus, &[0x01; 9]).err(),
+            Some(SymCryptError::InvalidArgument)
+        );
+        assert_eq!(
+            RsaKey::from_key_pair(&modulus, PUBLIC_EXPONENT, &[], &[]).err(),
+            Some(SymCryptError::InvalidArgument)
+        );
+        // The primes must multiply to the modulus.
+        let prime1 = hex::decode(PRIME1).unwrap();
+        assert!(RsaKey::from_key_pair(&modulus, PUBLIC_EXPONENT, &prime1, &prime1).is_err());
+    }
+}
@@ -0,0 +1,1481 @@
+//! rustls `CryptoProvider` backed by SymCrypt. This module is available with the `rustls-provider` feature.
+//!
+//! The TLS 1.3 and TLS 1.2 cipher suites use the AES-GCM and ChaCha20-Poly1305 functions from gcm.rs and chacha.rs, the key exchange
+//! groups use the EcDh functions from ecdh.rs, the transcript hash is built on tls13.rs, HKDF uses hkdf.rs and the TLS 1.2 PRF uses
+//! tls_prf.rs. Randomness comes from [`crate::rng::fill_bytes()`].
+//!
+//! Certificate and handshake signatures are verified with [`SUPPORTED_SIG_ALGS`] and created by [`KEY_PROVIDER`], which use the ECDSA
+//! functions from ecdsa.rs for P-256 and P-384 and the RSA PKCS#1 v1.5 and PSS functions from rsa.rs. Only SHA-256 and SHA-384 are
+//! supported, so the SHA-512 signature schemes, Ed25519 and P-521 keys are not. [`default_provider()`] uses them, and
+//! [`provider_with_signatures()`] lets the caller provide other signature algorithms, for example the ones from another rustls provider.
+
+use crate::block_ciphers::BlockCipherType;
+use crate::chacha::{chacha20_poly1305_decrypt_in_place, chacha20_poly1305_encrypt_in_place};
+use crate::ecdh::EcDh;
+use crate::ecdsa::EcDsa;
+use crate::eckey::{CurveType, PointFormat};
+use crate::errors::SymCryptError;
+use crate::gcm::GcmExpandedKey;
+use crate::hash::{sha256, sha384, HashAlgorithm};
+use crate::hkdf::{hkdf_expand, hkdf_extract};
+use crate::hmac::{hmac_sha256, hmac_sha384, HmacAlgorithm};
+use crate::key_encoding::{
+    decode_rsa_private_key, decode_rsa_public_key, ec_public_key_to_spki_der,
+    ecdsa_signature_from_der, ecdsa_signature_to_der, PrivateKey, PublicKey,
+};
+use crate::rng;
+use crate::rsa::RsaKey;
+use crate::secret::{SecretArray, SecretBytes};
+use crate::tls13::TranscriptHash;
+use crate::tls_prf::tls_prf_1_2;
+use rustls::crypto::cipher::{
+    make_tls12_aad, make_tls13_aad, AeadKey, InboundOpaqueMessage, InboundPlainMessage, Iv,
+    KeyBlockShape, MessageDecrypter, MessageEncrypter, Nonce, OutboundOpaqueMessage,
+    OutboundPlainMessage, PrefixedPayload, Tls12AeadAlgorithm, Tls13AeadAlgorithm,
+    UnsupportedOperationError, NONCE_LEN,
+};
+use rustls::crypto::tls13::{HkdfExpander, OkmBlock, OutputLengthError};
+use rustls::crypto::{
+    hash, hmac, tls12, tls13, ActiveKeyExchange, CryptoProvider, GetRandomFailed,
+    KeyExchangeAlgorithm, KeyProvider, SecureRandom, SharedSecret, SupportedKxGroup,
+    WebPkiSupportedAlgorithms,
+};
+use rustls::pki_types::{
+    alg_id, AlgorithmIdentifier, InvalidSignature, PrivateKeyDer, SignatureVerificationAlgorithm,
+    SubjectPublicKeyInfoDer,
+};
+use rustls::sign::{Signer, SigningKey};
+use rustls::{
+    CipherSuite, CipherSuiteCommon, ConnectionTrafficSecrets, ContentType, Error, NamedGroup,
+    PeerMisbehaved, ProtocolVersion, SignatureAlgorithm, SignatureScheme, SupportedCipherSuite,
+    Tls12CipherSuite, Tls13CipherSuite,
+};
+use std::fmt;
+use std::sync::Arc;
+
+/// Size of the AES-GCM and ChaCha20-Poly1305 tags.
+const TAG_SIZE: usize = 16;
+
+/// Size of the explicit nonce that TLS 1.2 AES-GCM records carry, see RFC 5288 section 3.
+const GCM_EXPLICIT_NONCE_SIZE: usize = 8;
+
+/// Largest plaintext a TLS record can carry, see RFC 8446 section 5.1.
+const MAX_FRAGMENT_SIZE: usize = 16384;
+
+/// Smallest and largest RSA modulus in bits that certificate and handshake signatures are verified with.
+const MIN_RSA_MODULUS_BITS: usize = 2048;
+const MAX_RSA_MODULUS_BITS: usize = 8192;
+
+/// Returns a [`CryptoProvider`] with all the cipher suites, key exchange groups and signature algorithms of this module.
+pub fn default_provider() -> CryptoProvider {
+    provider_with_signatures(SUPPORTED_SIG_ALGS, KEY_PROVIDER)
+}
+
+/// Returns a [`CryptoProvider`] with all the cipher suites and key exchange groups of this module, and with the provided signature
+/// algorithms.
+///
+/// `signature_verification_algorithms` are used to verify the peer's certificate chain and handshake signature, and
+/// `key_provider` loads the private key of the local certificate.
+pub fn provider_with_signatures(
+    signature_verification_algorithms: WebPkiSupportedAlgorithms,
+    key_provider: &'static dyn KeyProvider,
+) -> CryptoProvider {
+    CryptoProvider {
+        cipher_suites: ALL_CIPHER_SUITES.to_vec(),
+        kx_groups: ALL_KX_GROUPS.to_vec(),
+        signature_verification_algorithms,
+        secure_random: &SymCryptRandom,
+        key_provider,
+    }
+}
+
+/// All the cipher suites of this module, in order of preference.
+pub static ALL_CIPHER_SUITES: &[SupportedCipherSuite] = &[
+    TLS13_AES_256_GCM_SHA384,
+    TLS13_AES_128_GCM_SHA256,
+    TLS13_CHACHA20_POLY1305_SHA256,
+    TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384,
+    TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256,
+    TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256,
+    TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384,
+    TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256,
+    TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256,
+];
+
+/// All the key exchange groups of this module, in order of preference.
+pub static ALL_KX_GROUPS: &[&dyn SupportedKxGroup] = &[X25519, SECP256R1, SECP384R1];
+
+static SHA256: Hash = Hash(HashAlgorithm::Sha256);
+static SHA384: Hash = Hash(HashAlgorithm::Sha384);
+static HKDF_SHA256: Hkdf = Hkdf(HmacAlgorithm::HmacSha256);
+static HKDF_SHA384: Hkdf = Hkdf(HmacAlgorithm::HmacSha384);
+static PRF_SHA256: Prf = Prf(HmacAlgorithm::HmacSha256);
+static PRF_SHA384: Prf = Prf(HmacAlgorithm::HmacSha384);
+
+/// The TLS 1.3 cipher suite TLS_AES_128_GCM_SHA256.
+pub static TLS13_AES_128_GCM_SHA256: SupportedCipherSuite =
+    SupportedCipherSuite::Tls13(&Tls13CipherSuite {
+        common: CipherSuiteCommon {
+            suite: CipherSuite::TLS13_AES_128_GCM_SHA256,
+            hash_provider: &SHA256,
+            confidentiality_limit: 1 << 24,
+        },
+        hkdf_provider: &HKDF_SHA256,
+        aead_alg: &AeadAlgorithm::Aes128Gcm,
+        quic: None,
+    });
+
+/// The TLS 1.3 cipher suite TLS_AES_256_GCM_SHA384.
+pub static TLS13_AES_256_GCM_SHA384: SupportedCipherSuite =
+    SupportedCipherSuite::Tls13(&Tls13CipherSuite {
+        common: CipherSuiteCommon {
+            suite: CipherSuite::TLS13_AES_256_GCM_SHA384,
+            hash_provider: &SHA384,
+            confidentiality_limit: 1 << 24,
+        },
+        hkdf_provider: &HKDF_SHA384,
+        aead_alg: &AeadAlgorithm::Aes256Gcm,
+        quic: None,
+    });
+
+/// The TLS 1.3 cipher suite TLS_CHACHA20_POLY1305_SHA256.
+pub static TLS13_CHACHA20_POLY1305_SHA256: SupportedCipherSuite =
+    SupportedCipherSuite::Tls13(&Tls13CipherSuite {
+        common: CipherSuiteCommon {
+            suite: CipherSuite::TLS13_CHACHA20_POLY1305_SHA256,
+            hash_provider: &SHA256,
+            confidentiality_limit: u64::MAX,
+        },
+        hkdf_provider: &HKDF_SHA256,
+        aead_alg: &AeadAlgorithm::ChaCha20Poly1305,
+        quic: None,
+    });
+
+/// tls12_suite defines a TLS 1.2 ECDHE cipher suite, `sign` is the list of signature schemes the suite can be used with.
+macro_rules! tls12_suite {
+    ($name:ident, $hash:expr, $prf:expr, $sign:expr, $aead:expr, $limit:expr) => {
+        pub static $name: SupportedCipherSuite = SupportedCipherSuite::Tls12(&Tls12CipherSuite {
+            common: CipherSuiteCommon {
+                suite: CipherSuite::$name,
+                hash_provider: $hash,
+                confidentiality_limit: $limit,
+            },
+            kx: KeyExchangeAlgorithm::ECDHE,
+            sign: $sign,
+            aead_alg: $aead,
+            prf_provider: $prf,
+        });
+    };
+}
+
+tls12_suite!(
+    TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256,
+    &SHA256,
+    &PRF_SHA256,
+    TLS12_ECDSA_SCHEMES,
+    &AeadAlgorithm::Aes128Gcm,
+    1 << 24
+);
+tls12_suite!(
+    TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384,
+    &SHA384,
+    &PRF_SHA384,
+    TLS12_ECDSA_SCHEMES,
+    &AeadAlgorithm::Aes256Gcm,
+    1 << 24
+);
+tls12_suite!(
+    TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256,
+    &SHA256,
+    &PRF_SHA256,
+    TLS12_ECDSA_SCHEMES,
+    &AeadAlgorithm::ChaCha20Poly1305,
+    u64::MAX
+);
+tls12_suite!(
+    TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256,
+    &SHA256,
+    &PRF_SHA256,
+    TLS12_RSA_SCHEMES,
+    &AeadAlgorithm::Aes128Gcm,
+    1 << 24
+);
+tls12_suite!(
+    TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384,
+    &SHA384,
+    &PRF_SHA384,
+    TLS12_RSA_SCHEMES,
+    &AeadAlgorithm::Aes256Gcm,
+    1 << 24
+);
+tls12_suite!(
+    TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256,
+    &SHA256,
+    &PRF_SHA256,
+    TLS12_RSA_SCHEMES,
+    &AeadAlgorithm::ChaCha20Poly1305,
+    u64::MAX
+);
+
+static TLS12_ECDSA_SCHEMES: &[SignatureScheme] = &[
+    SignatureScheme::ED25519,
+    SignatureScheme::ECDSA_NISTP521_SHA512,
+    SignatureScheme::ECDSA_NISTP384_SHA384,
+    SignatureScheme::ECDSA_NISTP256_SHA256,
+];
+
+static TLS12_RSA_SCHEMES: &[SignatureScheme] = &[
+    SignatureScheme::RSA_PSS_SHA512,
+    SignatureScheme::RSA_PSS_SHA384,
+    SignatureScheme::RSA_PSS_SHA256,
+    SignatureScheme::RSA_PKCS1_SHA512,
+    SignatureScheme::RSA_PKCS1_SHA384,
+    SignatureScheme::RSA_PKCS1_SHA256,
+];
+
+/// The signature algorithms of this module, for certificates and for the handshake signatures of TLS 1.3 and TLS 1.2.
+///
+/// As in TLS 1.2, the ECDSA signature schemes of TLS 1.3 are mapped to both curves, rustls checks that the curve of the certificate
+/// matches the scheme for TLS 1.3. The RSA algorithms accept moduli of 2048 to 8192 bits.
+pub static SUPPORTED_SIG_ALGS: WebPkiSupportedAlgorithms = WebPkiSupportedAlgorithms {
+    all: &[
+        ECDSA_P256_SHA256,
+        ECDSA_P256_SHA384,
+        ECDSA_P384_SHA256,
+        ECDSA_P384_SHA384,
+        RSA_PSS_SHA256,
+        RSA_PSS_SHA384,
+        RSA_PKCS1_SHA256,
+        RSA_PKCS1_SHA384,
+    ],
+    mapping: &[
+        (
+            SignatureScheme::ECDSA_NISTP384_SHA384,
+            &[ECDSA_P384_SHA384, ECDSA_P256_SHA384],
+        ),
+        (
+            SignatureScheme::ECDSA_NISTP256_SHA256,
+            &[ECDSA_P256_SHA256, ECDSA_P384_SHA256],
+        ),
+        (SignatureScheme::RSA_PSS_SHA384, &[RSA_PSS_SHA384]),
+        (SignatureScheme::RSA_PSS_SHA256, &[RSA_PSS_SHA256]),
+        (SignatureScheme::RSA_PKCS1_SHA384, &[RSA_PKCS1_SHA384]),
+        (SignatureScheme::RSA_PKCS1_SHA256, &[RSA_PKCS1_SHA256]),
+    ],
+};
+
+static ECDSA_P256_SHA256: &dyn SignatureVerificationAlgorithm = &EcdsaVerify {
+    curve: CurveType::NistP256,
+    hash: HashAlgorithm::Sha256,
+    public_key_alg_id: alg_id::ECDSA_P256,
+    signature_alg_id: alg_id::ECDSA_SHA256,
+};
+
+static ECDSA_P256_SHA384: &dyn SignatureVerificationAlgorithm = &EcdsaVerify {
+    curve: CurveType::NistP256,
+    hash: HashAlgorithm::Sha384,
+    public_key_alg_id: alg_id::ECDSA_P256,
+    signature_alg_id: alg_id::ECDSA_SHA384,
+};
+
+static ECDSA_P384_SHA256: &dyn SignatureVerificationAlgorithm = &EcdsaVerify {
+    curve: CurveType::NistP384,
+    hash: HashAlgorithm::Sha256,
+    public_key_alg_id: alg_id::ECDSA_P384,
+    signature_alg_id: alg_id::ECDSA_SHA256,
+};
+
+static ECDSA_P384_SHA384: &dyn SignatureVerificationAlgorithm = &EcdsaVerify {
+    curve: CurveType::NistP384,
+    hash: HashAlgorithm::Sha384,
+    public_key_alg_id: alg_id::ECDSA_P384,
+    signature_alg_id: alg_id::ECDSA_SHA384,
+};
+
+static RSA_PSS_SHA256: &dyn SignatureVerificationAlgorithm = &RsaVerify {
+    padding: RsaPadding::Pss,
+    hash: HashAlgorithm::Sha256,
+    signature_alg_id: alg_id::RSA_PSS_SHA256,
+};
+
+static RSA_PSS_SHA384: &dyn SignatureVerificationAlgorithm = &RsaVerify {
+    padding: RsaPadding::Pss,
+    hash: HashAlgorithm::Sha384,
+    signature_alg_id: alg_id::RSA_PSS_SHA384,
+};
+
+static RSA_PKCS1_SHA256: &dyn SignatureVerificationAlgorithm = &RsaVerify {
+    padding: RsaPadding::Pkcs1,
+    hash: HashAlgorithm::Sha256,
+    signature_alg_id: alg_id::RSA_PKCS1_SHA256,
+};
+
+static RSA_PKCS1_SHA384: &dyn SignatureVerificationAlgorithm = &RsaVerify {
+    padding: RsaPadding::Pkcs1,
+    hash: HashAlgorithm::Sha384,
+    signature_alg_id: alg_id::RSA_PKCS1_SHA384,
+};
+
+/// The RSA signature schemes that [`KEY_PROVIDER`] signs with, in order of preference.
+static RSA_SIGNING_SCHEMES: &[(SignatureScheme, RsaPadding, HashAlgorithm)] = &[
+    (
+        SignatureScheme::RSA_PSS_SHA384,
+        RsaPadding::Pss,
+        HashAlgorithm::Sha384,
+    ),
+    (
+        SignatureScheme::RSA_PSS_SHA256,
+        RsaPadding::Pss,
+        HashAlgorithm::Sha256,
+    ),
+    (
+        SignatureScheme::RSA_PKCS1_SHA384,
+        RsaPadding::Pkcs1,
+        HashAlgorithm::Sha384,
+    ),
+    (
+        SignatureScheme::RSA_PKCS1_SHA256,
+        RsaPadding::Pkcs1,
+        HashAlgorithm::Sha256,
+    ),
+];
+
+/// Loads ECDSA P-256 and P-384 and RSA private keys, from PKCS#8, SEC1 or PKCS#1. Other keys fail with [`Error::General`].
+pub static KEY_PROVIDER: &dyn KeyProvider = &SymCryptKeyProvider;
+
+/// hash_message returns the hash of a message that is signed or verified.
+fn hash_message(hash: HashAlgorithm, message: &[u8]) -> Vec<u8> {
+    match hash {
+        HashAlgorithm::Sha256 => sha256(message).to_vec(),
+        HashAlgorithm::Sha384 => sha384(message).to_vec(),
+    }
+}
+
+/// [`EcdsaVerify`] verifies ECDSA signatures, which are DER encoded `Ecdsa-Sig-Value`s, with a public key that is an uncompressed
+/// point as required by RFC 8446 section 4.2.3.
+#[derive(Debug)]
+struct EcdsaVerify {
+    curve: CurveType,
+    hash: HashAlgorithm,
+    public_key_alg_id: AlgorithmIdentifier,
+    signature_alg_id: AlgorithmIdentifier,
+}
+
+impl SignatureVerificationAlgorithm for EcdsaVerify {
+    fn verify_signature(
+        &self,
+        public_key: &[u8],
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), InvalidSignature> {
+        let key = EcDsa::from_public_key_bytes_with_format(
+            self.curve,
+            public_key,
+            PointFormat::Sec1Uncompressed,
+        )
+        .map_err(|_| InvalidSignature)?;
+        let signature = ecdsa_signature_from_der(signature, self.curve.field_element_size())
+            .map_err(|_| InvalidSignature)?;
+        key.verify(&hash_message(self.hash, message), &signature)
+            .map_err(|_| InvalidSignature)
+    }
+
+    fn public_key_alg_id(&self) -> AlgorithmIdentifier {
+        self.public_key_alg_id
+    }
+
+    fn signature_alg_id(&self) -> AlgorithmIdentifier {
+        self.signature_alg_id
+    }
+}
+
+/// [`RsaPadding`] is the padding of an RSA signature. PSS uses a salt of the size of the hash, as required by RFC 8446 section 4.2.3.
+#[derive(Copy, Clone, Debug)]
+enum RsaPadding {
+    Pkcs1,
+    Pss,
+}
+
+/// [`RsaVerify`] verifies RSA signatures with a public key that is a PKCS#1 `RSAPublicKey`.
+#[derive(Debug)]
+struct RsaVerify {
+    padding: RsaPadding,
+    hash: HashAlgorithm,
+    signature_alg_id: AlgorithmIdentifier,
+}
+
+impl SignatureVerificationAlgorithm for RsaVerify {
+    fn verify_signature(
+        &self,
+        public_key: &[u8],
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<(), InvalidSignature> {
+        let public_key = decode_rsa_public_key(public_key).map_err(|_| InvalidSignature)?;
+        let modulus_bits = match public_key.modulus.first() {
+            Some(first) => public_key.modulus.len() * 8 - first.leading_zeros() as usize,
+            None => 0,
+        };
+        if !(MIN_RSA_MODULUS_BITS..=MAX_RSA_MODULUS_BITS).contains(&modulus_bits) {
+            return Err(InvalidSignature);
+        }
+        let key = RsaKey::from_rsa_public_key(&public_key).map_err(|_| InvalidSignature)?;
+        let hashed_message = hash_message(self.hash, message);
+        match self.padding {
+            RsaPadding::Pkcs1 => key.pkcs1_verify(&hashed_message, signature, self.hash),
+            RsaPadding::Pss => key.pss_verify(
+                &hashed_message,
+                signature,
+                self.hash,
+                self.hash.result_size(),
+            ),
+        }
+        .map_err(|_| InvalidSignature)
+    }
+
+    fn public_key_alg_id(&self) -> AlgorithmIdentifier {
+        alg_id::RSA_ENCRYPTION
+    }
+
+    fn signature_alg_id(&self) -> AlgorithmIdentifier {
+        self.signature_alg_id
+    }
+}
+
+/// [`SymCryptKeyProvider`] loads the private key of the local certificate, see [`KEY_PROVIDER`].
+#[derive(Debug)]
+struct SymCryptKeyProvider;
+
+impl KeyProvider for SymCryptKeyProvider {
+    fn load_private_key(
+        &self,
+        key_der: PrivateKeyDer<'static>,
+    ) -> Result<Arc<dyn SigningKey>, Error> {
+        let key = match &key_der {
+            PrivateKeyDer::Pkcs8(der) => PrivateKey::from_pkcs8_der(der.secret_pkcs8_der()),
+            PrivateKeyDer::Sec1(der) => PrivateKey::from_sec1_der(der.secret_sec1_der()),
+            PrivateKeyDer::Pkcs1(der) => decode_rsa_private_key(der.secret_pkcs1_der()),
+            _ => Err(SymCryptError::IncompatibleFormat),
+        }
+        .map_err(load_error)?;
+
+        match key {
+            PrivateKey::Ec(key) => {
+                let (scheme, hash) = match key.curve_type() {
+                    CurveType::NistP256 => (
+                        SignatureScheme::ECDSA_NISTP256_SHA256,
+                        HashAlgorithm::Sha256,
+                    ),
+                    CurveType::NistP384 => (
+                        SignatureScheme::ECDSA_NISTP384_SHA384,
+                        HashAlgorithm::Sha384,
+                    ),
+                    _ => return Err(load_error(SymCryptError::IncompatibleFormat)),
+                };
+                let public_key = ec_public_key_to_spki_der(&key).map_err(load_error)?;
+                Ok(Arc::new(EcdsaSigningKey {
+                    key: Arc::new(EcDsa::from_ec_key(key).map_err(load_error)?),
+                    public_key,
+                    scheme,
+                    hash,
+                }))
+            }
+            PrivateKey::Rsa(key) => {
+                let public_key = PublicKey::Rsa(key.public_key())
+                    .to_spki_der()
+                    .map_err(load_error)?;
+                Ok(Arc::new(RsaSigningKey {
+                    key: Arc::new(RsaKey::from_rsa_private_key(&key).map_err(load_error)?),
+                    public_key,
+                }))
+            }
+            PrivateKey::Ed25519(_) => Err(load_error(SymCryptError::IncompatibleFormat)),
+        }
+    }
+}
+
+fn load_error(err: SymCryptError) -> Error {
+    Error::General(format!("failed to load private key: {}", err))
+}
+
+/// [`EcdsaSigningKey`] signs with the one signature scheme that matches the curve of the key. It is also the [`Signer`] for that
+/// scheme.
+#[derive(Clone)]
+struct EcdsaSigningKey {
+    key: Arc<EcDsa>,
+    public_key: Vec<u8>,
+    scheme: SignatureScheme,
+    hash: HashAlgorithm,
+}
+
+impl SigningKey for EcdsaSigningKey {
+    fn choose_scheme(&self, offered: &[SignatureScheme]) -> Option<Box<dyn Signer>> {
+        if offered.contains(&self.scheme) {
+            Some(Box::new(self.clone()))
+        } else {
+            None
+        }
+    }
+
+    fn public_key(&self) -> Option<SubjectPublicKeyInfoDer<'_>> {
+        Some(SubjectPublicKeyInfoDer::from(self.public_key.as_slice()))
+    }
+
+    fn algorithm(&self) -> SignatureAlgorithm {
+        SignatureAlgorithm::ECDSA
+    }
+}
+
+impl Signer for EcdsaSigningKey {
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let signature = self
+            .key
+            .sign(&hash_message(self.hash, message))
+            .map_err(|err| Error::General(err.to_string()))?;
+        Ok(ecdsa_signature_to_der(&signature))
+    }
+
+    fn scheme(&self) -> SignatureScheme {
+        self.scheme
+    }
+}
+
+impl fmt::Debug for EcdsaSigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EcdsaSigningKey")
+            .field("scheme", &self.scheme)
+            .finish_non_exhaustive()
+    }
+}
+
+/// [`RsaSigningKey`] signs with the first of [`RSA_SIGNING_SCHEMES`] that the peer offers.
+struct RsaSigningKey {
+    key: Arc<RsaKey>,
+    public_key: Vec<u8>,
+}
+
+impl SigningKey for RsaSigningKey {
+    fn choose_scheme(&self, offered: &[SignatureScheme]) -> Option<Box<dyn Signer>> {
+        RSA_SIGNING_SCHEMES
+            .iter()
+            .find(|(scheme, _, _)| offered.contains(scheme))
+            .map(|&(scheme, padding, hash)| {
+                Box::new(RsaSigner {
+                    key: self.key.clone(),
+                    scheme,
+                    padding,
+                    hash,
+                }) as Box<dyn Signer>
+            })
+    }
+
+    fn public_key(&self) -> Option<SubjectPublicKeyInfoDer<'_>> {
+        Some(SubjectPublicKeyInfoDer::from(self.public_key.as_slice()))
+    }
+
+    fn algorithm(&self) -> SignatureAlgorithm {
+        SignatureAlgorithm::RSA
+    }
+}
+
+impl fmt::Debug for RsaSigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RsaSigningKey").finish_non_exhaustive()
+    }
+}
+
+/// [`RsaSigner`] signs with one of [`RSA_SIGNING_SCHEMES`].
+struct RsaSigner {
+    key: Arc<RsaKey>,
+    scheme: SignatureScheme,
+    padding: RsaPadding,
+    hash: HashAlgorithm,
+}
+
+impl Signer for RsaSigner {
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let hashed_message = hash_message(self.hash, message);
+        match self.padding {
+            RsaPadding::Pkcs1 => self.key.pkcs1_sign(&hashed_message, self.hash),
+            RsaPadding::Pss => {
+                self.key
+                    .pss_sign(&hashed_message, self.hash, self.hash.result_size())
+            }
+        }
+        .map_err(|err| Error::General(err.to_string()))
+    }
+
+    fn scheme(&self) -> SignatureScheme {
+        self.scheme
+    }
+}
+
+impl fmt::Debug for RsaSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RsaSigner")
+            .field("scheme", &self.scheme)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Ephemeral X25519 key exchange, using [`CurveType::Curve25519`].
+pub static X25519: &dyn SupportedKxGroup = &KxGroup {
+    name: NamedGroup::X25519,
+    curve: CurveType::Curve25519,
+    format: PointFormat::X,
+};
+
+/// Ephemeral ECDH key exchange on NIST P-256, using [`CurveType::NistP256`].
+pub static SECP256R1: &dyn SupportedKxGroup = &KxGroup {
+    name: NamedGroup::secp256r1,
+    curve: CurveType::NistP256,
+    format: PointFormat::Sec1Uncompressed,
+};
+
+/// Ephemeral ECDH key exchange on NIST P-384, using [`CurveType::NistP384`].
+pub static SECP384R1: &dyn SupportedKxGroup = &KxGroup {
+    name: NamedGroup::secp384r1,
+    curve: CurveType::NistP384,
+    format: PointFormat::Sec1Uncompressed,
+};
+
+/// [`KxGroup`] is a key exchange group on one of the [`CurveType`]s, with public keys encoded in `format` as required by
+/// RFC 8446 section 4.2.8.2.
+#[derive(Debug)]
+struct KxGroup {
+    name: NamedGroup,
+    curve: CurveType,
+    format: PointFormat,
+}
+
+impl SupportedKxGroup for KxGroup {
+    fn start(&self) -> Result<Box<dyn ActiveKeyExchange>, Error> {
+        let key = EcDh::new(self.curve).map_err(|err| Error::General(err.to_string()))?;
+        let public_key = key
+            .get_public_key_bytes_with_format(self.format)
+            .map_err(|err| Error::General(err.to_string()))?;
+        Ok(Box::new(KeyExchange {
+            name: self.name,
+            curve: self.curve,
+            format: self.format,
+            key,
+            public_key,
+        }))
+    }
+
+    fn name(&self) -> NamedGroup {
+        self.name
+    }
+}
+
+/// [`KeyExchange`] holds the ephemeral [`EcDh`] key of an in progress key exchange.
+struct KeyExchange {
+    name: NamedGroup,
+    curve: CurveType,
+    format: PointFormat,
+    key: EcDh,
+    public_key: Vec<u8>,
+}
+
+impl ActiveKeyExchange for KeyExchange {
+    fn complete(self: Box<Self>, peer_pub_key: &[u8]) -> Result<SharedSecret, Error> {
+        let peer = EcDh::from_public_key_bytes_with_format(self.curve, peer_pub_key, self.format)
+            .map_err(|_| PeerMisbehaved::InvalidKeyShare)?;
+        let secret = EcDh::ecdh_secret_agreement(&self.key, &peer)
+            .map_err(|_| PeerMisbehaved::InvalidKeyShare)?;
+        Ok(SharedSecret::from(secret.as_bytes()))
+    }
+
+    fn pub_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    fn group(&self) -> NamedGroup {
+        self.name
+    }
+}
+
+/// [`Hash`] provides the transcript hash of a cipher suite, it is built on [`TranscriptHash`].
+struct Hash(HashAlgorithm);
+
+impl hash::Hash for Hash {
+    fn start(&self) -> Box<dyn hash::Context> {
+        Box::new(HashContext(TranscriptHash::new(self.0)))
+    }
+
+    fn hash(&self, data: &[u8]) -> hash::Output {
+        let mut state = TranscriptHash::new(self.0);
+        state.append(data);
+        hash::Output::new(&state.current_hash())
+    }
+
+    fn output_len(&self) -> usize {
+        self.0.result_size()
+    }
+
+    fn algorithm(&self) -> hash::HashAlgorithm {
+        match self.0 {
+            HashAlgorithm::Sha256 => hash::HashAlgorithm::SHA256,
+            HashAlgorithm::Sha384 => hash::HashAlgorithm::SHA384,
+        }
+    }
+}
+
+struct HashContext(TranscriptHash);
+
+impl hash::Context for HashContext {
+    fn fork_finish(&self) -> hash::Output {
+        hash::Output::new(&self.0.current_hash())
+    }
+
+    fn fork(&self) -> Box<dyn hash::Context> {
+        Box::new(HashContext(self.0.clone()))
+    }
+
+    fn finish(self: Box<Self>) -> hash::Output {
+        hash::Output::new(&self.0.current_hash())
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.append(data);
+    }
+}
+
+/// [`Hkdf`] provides HKDF to the TLS 1.3 cipher suites, it is built on [`hkdf_extract()`] and [`hkdf_expand()`].
+///
+/// The rustls traits do not return errors for HKDF. Extract and HMAC do not fail for any key size, so the unwraps below only cover
+/// expand, which can only fail when asked for more than 255 times the hash length.
+struct Hkdf(HmacAlgorithm);
+
+impl tls13::Hkdf for Hkdf {
+    fn extract_from_zero_ikm(&self, salt: Option<&[u8]>) -> Box<dyn HkdfExpander> {
+        self.extract_from_secret(salt, &vec![0u8; self.0.result_size()])
+    }
+
+    fn extract_from_secret(&self, salt: Option<&[u8]>, secret: &[u8]) -> Box<dyn HkdfExpander> {
+        // An empty salt is treated as a string of zeros the size of the hash, which is what rustls expects for None.
+        let prk = hkdf_extract(self.0, secret, salt.unwrap_or(&[])).unwrap();
+        Box::new(HkdfPrk {
+            hmac_algorithm: self.0,
+            prk,
+        })
+    }
+
+    fn expander_for_okm(&self, okm: &OkmBlock) -> Box<dyn HkdfExpander> {
+        Box::new(HkdfPrk {
+            hmac_algorithm: self.0,
+            prk: SecretBytes::from(okm.as_ref().to_vec()),
+        })
+    }
+
+    fn hmac_sign(&self, key: &OkmBlock, message: &[u8]) -> hmac::Tag {
+        match self.0 {
+            HmacAlgorithm::HmacSha256 => {
+                hmac::Tag::new(&hmac_sha256(key.as_ref(), message).unwrap())
+            }
+            HmacAlgorithm::HmacSha384 => {
+                hmac::Tag::new(&hmac_sha384(key.as_ref(), message).unwrap())
+            }
+        }
+    }
+}
+
+/// [`HkdfPrk`] holds the pseudorandom key of HKDF, which is wiped when it leaves scope.
+struct HkdfPrk {
+    hmac_algorithm: HmacAlgorithm,
+    prk: SecretBytes,
+}
+
+impl HkdfExpander for HkdfPrk {
+    fn expand_slice(&self, info: &[&[u8]], output: &mut [u8]) -> Result<(), OutputLengthError> {
+        let okm = hkdf_expand(self.hmac_algorithm, &self.prk, &info.concat(), output.len())
+            .map_err(|_| OutputLengthError)?;
+        output.copy_from_slice(&okm);
+        Ok(())
+    }
+
+    fn expand_block(&self, info: &[&[u8]]) -> OkmBlock {
+        let okm = hkdf_expand(
+            self.hmac_algorithm,
+            &self.prk,
+            &info.concat(),
+            self.hash_len(),
+        )
+        .unwrap();
+        OkmBlock::new(&okm)
+    }
+
+    fn hash_len(&self) -> usize {
+        self.hmac_algorithm.result_size()
+    }
+}
+
+/// [`Prf`] provides the TLS 1.2 PRF to the TLS 1.2 cipher suites, it is built on [`tls_prf_1_2()`].
+struct Prf(HmacAlgorithm);
+
+impl tls12::Prf for Prf {
+    fn for_key_exchange(
+        &self,
+        output: &mut [u8; 48],
+        kx: Box<dyn ActiveKeyExchange>,
+        peer_pub_key: &[u8],
+        label: &[u8],
+        seed: &[u8],
+    ) -> Result<(), Error> {
+        let secret = kx.complete_for_tls_version(peer_pub_key, &rustls::version::TLS12)?;
+        let result = tls_prf_1_2(self.0, secret.secret_bytes(), label, seed, output.len())
+            .map_err(|err| Error::General(err.to_string()))?;
+        output.copy_from_slice(&result);
+        Ok(())
+    }
+
+    fn for_secret(&self, output: &mut [u8], secret: &[u8], label: &[u8], seed: &[u8]) {
+        // rustls only uses its own fixed labels and seeds, which SymCrypt accepts.
+        let result = tls_prf_1_2(self.0, secret, label, seed, output.len()).unwrap();
+        output.copy_from_slice(&result);
+    }
+}
+
+/// [`SymCryptRandom`] provides randomness to rustls from [`rng::fill_bytes()`].
+#[derive(Debug)]
+struct SymCryptRandom;
+
+impl SecureRandom for SymCryptRandom {
+    fn fill(&self, buf: &mut [u8]) -> Result<(), GetRandomFailed> {
+        rng::fill_bytes(buf);
+        Ok(())
+    }
+}
+
+/// [`AeadAlgorithm`] provides the record protection of the TLS 1.3 and TLS 1.2 cipher suites.
+enum AeadAlgorithm {
+    Aes128Gcm,
+    Aes256Gcm,
+    ChaCha20Poly1305,
+}
+
+impl AeadAlgorithm {
+    fn key_len(&self) -> usize {
+        match self {
+            AeadAlgorithm::Aes128Gcm => 16,
+            AeadAlgorithm::Aes256Gcm | AeadAlgorithm::ChaCha20Poly1305 => 32,
+        }
+    }
+
+    // rustls derives a key of key_len() bytes, so creating the key can not fail.
+    fn message_key(&self, key: &AeadKey) -> MessageKey {
+        match self {
+            AeadAlgorithm::Aes128Gcm | AeadAlgorithm::Aes256Gcm => MessageKey::Gcm(
+                GcmExpandedKey::new(key.as_ref(), BlockCipherType::AesBlock).unwrap(),
+            ),
+            AeadAlgorithm::ChaCha20Poly1305 => {
+                MessageKey::ChaCha20Poly1305(SecretArray::new(key.as_ref().try_into().unwrap()))
+            }
+        }
+    }
+
+    fn traffic_secrets(&self, key: AeadKey, iv: Iv) -> ConnectionTrafficSecrets {
+        match self {
+            AeadAlgorithm::Aes128Gcm => ConnectionTrafficSecrets::Aes128Gcm { key, iv },
+            AeadAlgorithm::Aes256Gcm => ConnectionTrafficSecrets::Aes256Gcm { key, iv },
+            AeadAlgorithm::ChaCha20Poly1305 => {
+                ConnectionTrafficSecrets::Chacha20Poly1305 { key, iv }
+            }
+        }
+    }
+
+    // TLS 1.2 AES-GCM records carry part of the nonce, see RFC 5288 section 3. ChaCha20-Poly1305 builds the nonce like TLS 1.3, see
+    // RFC 7905 section 2.
+    fn explicit_nonce_len(&self) -> usize {
+        match self {
+            AeadAlgorithm::Aes128Gcm | AeadAlgorithm::Aes256Gcm => GCM_EXPLICIT_NONCE_SIZE,
+            AeadAlgorithm::ChaCha20Poly1305 => 0,
+        }
+    }
+}
+
+impl Tls13AeadAlgorithm for AeadAlgorithm {
+    fn encrypter(&self, key: AeadKey, iv: Iv) -> Box<dyn MessageEncrypter> {
+        Box::new(Tls13MessageEncrypter {
+            key: self.message_key(&key),
+            iv,
+        })
+    }
+
+    fn decrypter(&self, key: AeadKey, iv: Iv) -> Box<dyn MessageDecrypter> {
+        Box::new(Tls13MessageDecrypter {
+            key: self.message_key(&key),
+            iv,
+        })
+    }
+
+    fn key_len(&self) -> usize {
+        AeadAlgorithm::key_len(self)
+    }
+
+    fn extract_keys(
+        &self,
+        key: AeadKey,
+        iv: Iv,
+    ) -> Result<ConnectionTrafficSecrets, UnsupportedOperationError> {
+        Ok(self.traffic_secrets(key, iv))
+    }
+}
+
+impl Tls12AeadAlgorithm for AeadAlgorithm {
+    fn encrypter(&self, key: AeadKey, iv: &[u8], explicit: &[u8]) -> Box<dyn MessageEncrypter> {
+        Box::new(Tls12MessageEncrypter {
+            key: self.message_key(&key),
+            iv: tls12_iv(iv, explicit),
+            explicit_nonce_len: self.explicit_nonce_len(),
+        })
+    }
+
+    fn decrypter(&self, key: AeadKey, iv: &[u8]) -> Box<dyn MessageDecrypter> {
+        Box::new(Tls12MessageDecrypter {
+            key: self.message_key(&key),
+            iv: tls12_iv(
+                iv,
+                &[0u8; GCM_EXPLICIT_NONCE_SIZE][..self.explicit_nonce_len()],
+            ),
+            explicit_nonce_len: self.explicit_nonce_len(),
+        })
+    }
+
+    fn key_block_shape(&self) -> KeyBlockShape {
+        KeyBlockShape {
+            enc_key_len: AeadAlgorithm::key_len(self),
+            fixed_iv_len: NONCE_LEN - self.explicit_nonce_len(),
+            explicit_nonce_len: self.explicit_nonce_len(),
+        }
+    }
+
+    fn extract_keys(
+        &self,
+        key: AeadKey,
+        iv: &[u8],
+        explicit: &[u8],
+    ) -> Result<ConnectionTrafficSecrets, UnsupportedOperationError> {
+        Ok(self.traffic_secrets(key, tls12_iv(iv, explicit)))
+    }
+}
+
+/// tls12_iv joins the fixed IV from the key block with the explicit part, which is empty for ChaCha20-Poly1305. Like rustls, the
+/// explicit part of outgoing AES-GCM nonces is the starting IV xored with the sequence number.
+fn tls12_iv(fixed: &[u8], explicit: &[u8]) -> Iv {
+    let mut iv = [0u8; NONCE_LEN];
+    iv[..fixed.len()].copy_from_slice(fixed);
+    iv[fixed.len()..].copy_from_slice(explicit);
+    Iv::new(iv)
+}
+
+/// [`MessageKey`] holds the key of one direction of a connection.
+enum MessageKey {
+    Gcm(GcmExpandedKey),
+    ChaCha20Poly1305(SecretArray<32>),
+}
+
+impl MessageKey {
+    fn seal(
+        &self,
+        nonce: &[u8; NONCE_LEN],
+        aad: &[u8],
+        buffer: &mut [u8],
+        tag: &mut [u8; TAG_SIZE],
+    ) -> Result<(), Error> {
+        match self {
+            MessageKey::Gcm(key) => {
+                key.encrypt_in_place(nonce, aad, buffer, tag);
+                Ok(())
+            }
+            MessageKey::ChaCha20Poly1305(key) => {
+                chacha20_poly1305_encrypt_in_place(key.as_bytes(), nonce, aad, buffer, tag)
+                    .map_err(|_| Error::EncryptError)
+            }
+        }
+    }
+
+    fn open(
+        &self,
+        nonce: &[u8; NONCE_LEN],
+        aad: &[u8],
+        buffer: &mut [u8],
+        tag: &[u8; TAG_SIZE],
+    ) -> Result<(), Error> {
+        match self {
+            MessageKey::Gcm(key) => key.decrypt_in_place(nonce, aad, buffer, tag),
+            MessageKey::ChaCha20Poly1305(key) => {
+                chacha20_poly1305_decrypt_in_place(key.as_bytes(), nonce, aad, buffer, tag)
+            }
+        }
+        .map_err(|_| Error::DecryptError)
+    }
+
+    /// open_with_tag splits the tag from the end of `payload` and decrypts the rest in place, returning the plaintext length.
+    fn open_with_tag(
+        &self,
+        nonce: &[u8; NONCE_LEN],
+        aad: &[u8],
+        payload: &mut [u8],
+    ) -> Result<usize, Error> {
+        let plaintext_len = payload
+            .len()
+            .checked_sub(TAG_SIZE)
+            .ok_or(Error::DecryptError)?;
+        let (ciphertext, tag) = payload.split_at_mut(plaintext_len);
+        self.open(nonce, aad, ciphertext, (&*tag).try_into().unwrap())?;
+        Ok(plaintext_len)
+    }
+}
+
+/// [`Tls13MessageEncrypter`] protects records as defined in RFC 8446 section 5.2.
+struct Tls13MessageEncrypter {
+    key: MessageKey,
+    iv: Iv,
+}
+
+impl MessageEncrypter for Tls13MessageEncrypter {
+    fn encrypt(
+        &mut self,
+        msg: OutboundPlainMessage<'_>,
+        seq: u64,
+    ) -> Result<OutboundOpaqueMessage, Error> {
+        let total_len = self.encrypted_payload_len(msg.payload.len());
+        let mut payload = PrefixedPayload::with_capacity(total_len);
+        payload.extend_from_chunks(&msg.payload);
+        payload.extend_from_slice(&msg.typ.to_array());
+
+        let nonce = Nonce::new(&self.iv, seq).0;
+        let mut tag = [0u8; TAG_SIZE];
+        self.key.seal(
+            &nonce,
+            &make_tls13_aad(total_len),
+            payload.as_mut(),
+            &mut tag,
+        )?;
+        payload.extend_from_slice(&tag);
+
+        // All TLS 1.3 records use TLS 1.2 as the legacy record version, see RFC 8446 section 5.1.
+        Ok(OutboundOpaqueMessage::new(
+            ContentType::ApplicationData,
+            ProtocolVersion::TLSv1_2,
+            payload,
+        ))
+    }
+
+    fn encrypted_payload_len(&self, payload_len: usize) -> usize {
+        payload_len + 1 + TAG_SIZE
+    }
+}
+
+/// [`Tls13MessageDecrypter`] removes the record protection added by [`Tls13MessageEncrypter`].
+struct Tls13MessageDecrypter {
+    key: MessageKey,
+    iv: Iv,
+}
+
+impl MessageDecrypter for Tls13MessageDecrypter {
+    fn decrypt<'a>(
+        &mut self,
+        mut msg: InboundOpaqueMessage<'a>,
+        seq: u64,
+    ) -> Result<InboundPlainMessage<'a>, Error> {
+        let nonce = Nonce::new(&self.iv, seq).0;
+        let aad = make_tls13_aad(msg.payload.len());
+        let plaintext_len = self.key.open_with_tag(&nonce, &aad, &mut msg.payload)?;
+        msg.payload.truncate(plaintext_len);
+        msg.into_tls13_unpadded_message()
+    }
+}
+
+/// [`Tls12MessageEncrypter`] protects records as defined in RFC 5246 section 6.2.3.3, with the nonces of RFC 5288 for AES-GCM and of
+/// RFC 7905 for ChaCha20-Poly1305.
+struct Tls12MessageEncrypter {
+    key: MessageKey,
+    iv: Iv,
+    explicit_nonce_len: usize,
+}
+
+impl MessageEncrypter for Tls12MessageEncrypter {
+    fn encrypt(
+        &mut self,
+        msg: OutboundPlainMessage<'_>,
+        seq: u64,
+    ) -> Result<OutboundOpaqueMessage, Error> {
+        let total_len = self.encrypted_payload_len(msg.payload.len());
+        let mut payload = PrefixedPayload::with_capacity(total_len);
+
+        let nonce = Nonce::new(&self.iv, seq).0;
+        let aad = make_tls12_aad(seq, msg.typ, msg.version, msg.payload.len());
+        payload.extend_from_slice(&nonce[NONCE_LEN - self.explicit_nonce_len..]);
+        payload.extend_from_chunks(&msg.payload);
+
+        let mut tag = [0u8; TAG_SIZE];
+        self.key.seal(
+            &nonce,
+            &aad,
+            &mut payload.as_mut()[self.explicit_nonce_len..],
+            &mut tag,
+        )?;
+        payload.extend_from_slice(&tag);
+
+        Ok(OutboundOpaqueMessage::new(msg.typ, msg.version, payload))
+    }
+
+    fn encrypted_payload_len(&self, payload_len: usize) -> usize {
+        self.explicit_nonce_len + payload_len + TAG_SIZE
+    }
+}
+
+/// [`Tls12MessageDecrypter`] removes the record protection added by [`Tls12MessageEncrypter`].
+struct Tls12MessageDecrypter {
+    key: MessageKey,
+    iv: Iv,
+    explicit_nonce_len: usize,
+}
+
+impl MessageDecrypter for Tls12MessageDecrypter {
+    fn decrypt<'a>(
+        &mut self,
+        mut msg: InboundOpaqueMessage<'a>,
+        seq: u64,
+    ) -> Result<InboundPlainMessage<'a>, Error> {
+        if msg.payload.len() < self.explicit_nonce_len + TAG_SIZE {
+            return Err(Error::DecryptError);
+        }
+
+        let mut nonce = Nonce::new(&self.iv, seq).0;
+        if self.explicit_nonce_len > 0 {
+            nonce[NONCE_LEN - self.explicit_nonce_len..]
+                .copy_from_slice(&msg.payload[..self.explicit_nonce_len]);
+        }
+        let aad = make_tls12_aad(
+            seq,
+            msg.typ,
+            msg.version,
+            msg.payload.len() - self.explicit_nonce_len - TAG_SIZE,
+        );
+
+        let plaintext_len =
+            self.key
+                .open_with_tag(&nonce, &aad, &mut msg.payload[self.explicit_nonce_len..])?;
+        if plaintext_len > MAX_FRAGMENT_SIZE {
+            return Err(Error::PeerSentOversizedRecord);
+        }
+
+        let start = self.explicit_nonce_len;
+        Ok(msg.into_plain_message_range(start..start + plaintext_len))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::key_encoding::ec_private_key_to_pkcs8_der;
+    use rustls::pki_types::{CertificateDer, PrivatePkcs8KeyDer};
+    use rustls::{
+        ClientConfig, ClientConnection, Connection, RootCertStore, ServerConfig, ServerConnection,
+    };
+    use std::io::{Read, Write};
+
+    // ECDSA P-256 certificates generated with OpenSSL. The CA signs the certificate for "localhost".
+    const CA_CERT: &str = "308201a83082014da0030201020214578f4f8c23377003e840677d5c221205f6a94959300a06082a8648ce3d0403023020311e301c06035504030c15727573742d73796d637279707420746573742043413020170d3236313031383137343632365a180f32313236303932343137343632365a3020311e301c06035504030c15727573742d73796d637279707420746573742043413059301306072a8648ce3d020106082a8648ce3d03010703420004cd77ae740f12eb101323d0158c552dcffad9fe3bc5079c746010e96167c5cb9cf41d86d8747851035995ce36ba251a50cc64ce6efe321f7c50bf59ee5655407da3633061301d0603551d0e04160414b5a835f1acef0c388d90fea87b750db64caddbc6301f0603551d23041830168014b5a835f1acef0c388d90fea87b750db64caddbc6300f0603551d130101ff040530030101ff300e0603551d0f0101ff040403020204300a06082a8648ce3d0403020349003046022100fd3df73d7512865aace99b00dc90a40e862417d387df732252ab4505d3b086ab022100d4b5c102a0ff9cdc4f8733fc2d5a394a2576cc8602ccbce8298423f073f17152";
+    const SERVER_CERT: &str = "308201b330820159a00302010202141300812e227be85a63f324c6fc86a37541f55dad300a06082a8648ce3d0403023020311e301c06035504030c15727573742d73796d637279707420746573742043413020170d3236313031383137343632365a180f32313236303932343137343632365a30143112301006035504030c096c6f63616c686f73743059301306072a8648ce3d020106082a8648ce3d03010703420004db6c1f941549ff1fbecb00e3ea024fa95dae8c802c6e7a3728eb24e71a8448cd06a33f1e72f9e261fce0b5fc4665522dd0be3f3e46326347389b4f65a921ecd9a37b307930140603551d11040d300b82096c6f63616c686f7374300c0603551d130101ff0402300030130603551d25040c300a06082b06010505070301301d0603551d0e04160414a37c54235976b6f22eb052e4775586e4c1af1076301f0603551d23041830168014b5a835f1acef0c388d90fea87b750db64caddbc6300a06082a8648ce3d0403020348003045022055c01d426893204426e2869bfdeee121cb4471dcc2595b7b3269a276c55eb8b302210080c4bf106e211ac7f117cb3d4f72a35773c8a25317e23f3100c853172cdc9f1a";
+    const SERVER_KEY: &str = "308187020100301306072a8648ce3d020106082a8648ce3d030107046d306b02010104204194d140652478189f26e4ed1357afb3e0015be4c745241bb71ffdd3a333e1aaa14403420004db6c1f941549ff1fbecb00e3ea024fa95dae8c802c6e7a3728eb24e71a8448cd06a33f1e72f9e261fce0b5fc4665522dd0be3f3e46326347389b4f65a921ecd9";
+
+    // RSA 2048 certificates generated with OpenSSL, signed with PKCS#1 v1.5 and SHA-256. The CA signs the certificate for "localhost".
+    const RSA_CA_CERT: &str = "3082033b30820223a003020102021446a5d629e0e5dd023d0067b94986db335236a8fb300d06092a864886f70d01010b050030243122302006035504030c19727573742d73796d63727970742074657374205253412043413020170d3236313031383230303934375a180f32313236303932343230303934375a30243122302006035504030c19727573742d73796d637279707420746573742052534120434130820122300d06092a864886f70d01010105000382010f003082010a0282010100dd2facfd69e9d6ef1d8a75932625edab359a593958af67da5a045013cda130362dce546b7fb82380051c8d9fc376a119abd028f3357054c55dd3d7df6b593aa9efa3c796bf30ac30b2eca670d22dc4ecb71a9312e13a23f63b348d6b5f6f3bb88b396fe2ff2ead63d791c812df9baf20a28b9f1f52e1c9628437732bf4d51f6027cd2748507abcb902cbe83f97a3d95586c7de1713eb42fca92aed8a7080649f9283b1fecdab3720bf33e90480bbe22f76eb8f7b796eae25da4e4d7d326a8a60e46893da97064c34ba1e9748980c562b1a1cb07aa1d20ae2baec8fdfae1a44c2e1ffea43c0a6718baf39e61c89f3f6a96fe7a72c602dc58a89ac1a26fb7c7bf90203010001a3633061301d0603551d0e04160414d77947570c91000f76bf8fff6a065bbcc2a1347a301f0603551d23041830168014d77947570c91000f76bf8fff6a065bbcc2a1347a300f0603551d130101ff040530030101ff300e0603551d0f0101ff040403020204300d06092a864886f70d01010b050003820101003e5c14264f19059e89b86d89285b0324e119a9c8d32cf327050e6dd7a32cc428fde70e9faa912227ac03dde30f03ea6bbeb8cdc8e87289205c3798bb94f32694b7d4b65697d77092b34b3e72eaa738280c8ef99223a0fc88721c945792d081707e4a2a82c16097df1e0bfd89833af6662853383738b7656444876035b026fd71012a4636ff2f58a18bab10a8a6573c3fe65332b7e98c8f368f9f7066759361682c149d7140f6467410157753b2144af6898107e81a93d3af4bcabebdbc6db1d890f4b3f12fd8bb87ec3ae5e556b8fc9388e9ef7cad8659f847fc5162b508b52c7461cf480451751d4036501ee19a1c260e3938f0e5f38434807439e315e5bf57";
+    const RSA_SERVER_CERT: &str = "308203433082022ba003020102021412efbebe1b6523b410b91920f256a655e9766205300d06092a864886f70d01010b050030243122302006035504030c19727573742d73796d63727970742074657374205253412043413020170d3236313031383230303934375a180f32313236303932343230303934375a30143112301006035504030c096c6f63616c686f737430820122300d06092a864886f70d01010105000382010f003082010a0282010100e6cb0eb22c044fbd93185e2004ef88834b688795a21ff9da2e60d31c32ef1f6cb3a35fbdee9ca12365b1b8261eaa8b5be6f34acc572cb8b7e018f22f5d76805cdc4258915e7ccc469072dd227357cf5f01289ecd0b9eaf7031159c701d43312f3ced60eb676923bd86c62e3f21cc1c36370cf56f14958f43f7c82ed2a9aedc129edaff3575682596b24d59a29f76d5154b78b27c8067e2e1cee2180c55fb32826ec29d763538c737a285bac2463d69a521b39cea66ff39d9fa0612110e51481a34c1fc26c032036aa135be3876282f9ccd52cabe8dec6a955a93b0f00c291eb93cb1d4f18b2792fea2bfb0e91e17fe9ede8daefb823510ceef1e6ba8a17a5db70203010001a37b307930140603551d11040d300b82096c6f63616c686f7374300c0603551d130101ff0402300030130603551d25040c300a06082b06010505070301301d0603551d0e041604140349d5adb8a2bc7cdb71477f0feb8567ca786af2301f0603551d23041830168014d77947570c91000f76bf8fff6a065bbcc2a1347a300d06092a864886f70d01010b050003820101001cbb71c27b5a00ebbd7257d3dc766626f71a77d58ececc152771e23aff4db7325091d3810c5f085ee64f5be8905b73953418ef405a79a77e8a5211987dd61c68417207cb9a65cd402c687073b437322cb3618e13f0b4fdc39fb901691963e4da8556a37d6d66ed98da1e7ae089b84551199a24f2fc9764c2e922816695a6dc3460952c4b9fd7c2e170a6f9dc13e04d49f64cb49141661a4e8dc082b8defe1b317bd21de31060ab460f19fe27df2cff226defecb58579085e966afc4508424ea2712e50ba01fbaee9dd3fc201ecaa748815b473fb960c8d1763ea9b4e231492086c610410a90f1ba26af199ad88d79a2b5e56e6ff9ecfb6a49d8eb7f8add02a7e";
+    const RSA_SERVER_KEY: &str = "308204bd020100300d06092a864886f70d0101010500048204a7308204a30201000282010100e6cb0eb22c044fbd93185e2004ef88834b688795a21ff9da2e60d31c32ef1f6cb3a35fbdee9ca12365b1b8261eaa8b5be6f34acc572cb8b7e018f22f5d76805cdc4258915e7ccc469072dd227357cf5f01289ecd0b9eaf7031159c701d43312f3ced60eb676923bd86c62e3f21cc1c36370cf56f14958f43f7c82ed2a9aedc129edaff3575682596b24d59a29f76d5154b78b27c8067e2e1cee2180c55fb32826ec29d763538c737a285bac2463d69a521b39cea66ff39d9fa0612110e51481a34c1fc26c032036aa135be3876282f9ccd52cabe8dec6a955a93b0f00c291eb93cb1d4f18b2792fea2bfb0e91e17fe9ede8daefb823510ceef1e6ba8a17a5db7020301000102820100341750d1d62d82f1fee959155107df8002dd2cfd1102b69a102dba787ae68de9330284f19191b8ad0c9ef83962628be1ca56cf631fa9f067502551446f8bf79cfdf9db93b407e6f3665706a267f641cfe7ab0b6cac5abcae4de5dad153497f28725001b8203712e20c315eeed0ea08d413c8d0fca960b85d56e7cfe9ac72d328d2f89adae408eb6bdab9ccfb0aaad1d11b2d163eab671e18f5113296e026844da1ce4e150160a064af721ce2b344c53ecdbc59749b32bfaa0c49350b9c00167f0a0a21dcb99222b9900c334f15cf56c341a915248533ea0b3d7a49626cff70f2a755f1962b00f64feca2e15fc764c88b3e2e06afeedf6606ff323dcd8882db8102818100fb5246715d2da8a4791b000b44f55808ed9aae292b5e7ae35614ac4e1b9a839ba8434a1b49a00bf370e39d1d48ce8d5f3d5637853d76959a0ff5201309e5ff59d5e26a439c966be1c4a97e4c995241687d9e2e9149a43a57b5ee106369e5425207934294416b2321ee6dd737fe71c451cd2076ac7fc36f2ad43ca24acf29d18902818100eb16f368df89001e01b7af7ca35e85499d72d45b0e9a33cbbe44c603684035448a7a9ec13f1ff83b795f71e11374ce16c43069abb15e164089c8ed33949f938802c81a8f74350fecc6d972d0b4d996ef13d19147cf249e76d525fe335c09782073f783103a8572c119e556646842208c5d0008dac04659656f0a44511cef253f028180685949ec49deea5597ff10ee9cec2d684f9758d6b92e1a6207dcbf8a12c5fdfd0449241cde5df8785497fd252a94b07829d4816aee112d9e711fbaf88cb495686c78fcdc23c904f4119eba54fd97fc967109db95f178a1ccc73a184ddeab14f55035e180030583e9d3c0818abb37eb66778012261f4a3aff126e36884f29b2d9028180405efb6d76efd37963ed00ff36d895d48f6936de6c042b3499b7c9247bb24123b06b5cf6fb2aecd4942a5f4b4b018bc0ce1d9781acc25f837276f30451a8d443c4c7380699c241c70ab7d5e971ebb699662ad705fba14246b59c7e9b5f93343266ae9a53a24292266fc9cd3b17152c4662dcb48ff96009f06d54395aa07a970f02818100c52e2adfddfc0fdae6fdd85cd2c663c26171a19555df28a7f1a80249961e08e80ed217fb6b16439ed8afa157d05a41d75c711b2cac2bbfa6777cf4342ab467647e6449fa3f1282925340ab4f23c9ef754161dfd08a0c9cc2d4f432c6b01363eba71fbdbca8da7deedae3e8cdbc5b80acc8bc9df551f675467a3834fb137a079e";
+
+    /// [`TestCertificate`] is a CA certificate and the certificate and PKCS#8 private key for "localhost" that it signed.
+    #[derive(Copy, Clone)]
+    struct TestCertificate {
+        ca: &'static str,
+        server: &'static str,
+        server_key: &'static str,
+    }
+
+    const ECDSA_CERTIFICATE: TestCertificate = TestCertificate {
+        ca: CA_CERT,
+        server: SERVER_CERT,
+        server_key: SERVER_KEY,
+    };
+
+    const RSA_CERTIFICATE: TestCertificate = TestCertificate {
+        ca: RSA_CA_CERT,
+        server: RSA_SERVER_CERT,
+        server_key: RSA_SERVER_KEY,
+    };
+
+    // All crypto, including the certificate and handshake signatures, is from this module.
+    fn test_provider(
+        suite: SupportedCipherSuite,
+        kx_group: &'static dyn SupportedKxGroup,
+    ) -> Arc<CryptoProvider> {
+        Arc::new(CryptoProvider {
+            cipher_suites: vec![suite],
+            kx_groups: vec![kx_group],
+            ..default_provider()
+        })
+    }
+
+    fn connect(
+        suite: SupportedCipherSuite,
+        kx_group: &'static dyn SupportedKxGroup,
+        certificate: TestCertificate,
+    ) -> (Connection, Connection) {
+        let provider = test_provider(suite, kx_group);
+        let versions = [suite.version()];
+
+        let mut roots = RootCertStore::empty();
+        roots
+            .add(CertificateDer::from(hex::decode(certificate.ca).unwrap()))
+            .unwrap();
+        let client_config = ClientConfig::builder_with_provider(provider.clone())
+            .with_protocol_versions(&versions)
+            .unwrap()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+
+        let server_config = ServerConfig::builder_with_provider(provider)
+            .with_protocol_versions(&versions)
+            .unwrap()
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![CertificateDer::from(
+                    hex::decode(certificate.server).unwrap(),
+                )],
+                PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(
+                    hex::decode(certificate.server_key).unwrap(),
+                )),
+            )
+            .unwrap();
+
+        let client =
+            ClientConnection::new(Arc::new(client_config), "localhost".try_into().unwrap())
+                .unwrap();
+        let server = ServerConnection::new(Arc::new(server_config)).unwrap();
+        (Connection::Client(client), Connection::Server(server))
+    }
+
+    // Moves all pending TLS records from one connection to the other, there is no network involved.
+    fn transfer(from: &mut Connection, to: &mut Connection) {
+        let mut records = Vec::new();
+        while from.wants_write() {
+            from.write_tls(&mut records).unwrap();
+        }
+        let mut records = records.as_slice();
+        while !records.is_empty() {
+            to.read_tls(&mut records).unwrap();
+            to.process_new_packets().unwrap();
+        }
+    }
+
+    fn check_data(from: &mut Connection, to: &mut Connection, data: &[u8]) {
+        from.writer().write_all(data).unwrap();
+        transfer(from, to);
+        let mut received = vec![0u8; data.len()];
+        to.reader().read_exact(&mut received).unwrap();
+        assert_eq!(received, data);
+    }
+
+    fn check_handshake(
+        suite: SupportedCipherSuite,
+        kx_group: &'static dyn SupportedKxGroup,
+        certificate: TestCertificate,
+    ) {
+        let (mut client, mut server) = connect(suite, kx_group, certificate);
+        for _ in 0..10 {
+            if !client.is_handshaking() && !server.is_handshaking() {
+                break;
+            }
+            transfer(&mut client, &mut server);
+            transfer(&mut server, &mut client);
+        }
+        assert!(!client.is_handshaking() && !server.is_handshaking());
+
+        for connection in [&client, &server] {
+            assert_eq!(connection.negotiated_cipher_suite(), Some(suite));
+            assert_eq!(
+                connection.negotiated_key_exchange_group().unwrap().name(),
+                kx_group.name()
+            );
+        }
+
+        check_data(&mut client, &mut server, b"hello from the client");
+        check_data(&mut server, &mut client, &[0x5a; 20000]);
+    }
+
+    #[test]
+    fn test_tls13_handshake() {
+        for suite in [
+            TLS13_AES_128_GCM_SHA256,
+            TLS13_AES_256_GCM_SHA384,
+            TLS13_CHACHA20_POLY1305_SHA256,
+        ] {
+            for kx_group in ALL_KX_GROUPS {
+                check_handshake(suite, *kx_group, ECDSA_CERTIFICATE);
+            }
+            check_handshake(suite, X25519, RSA_CERTIFICATE);
+        }
+    }
+
+    #[test]
+    fn test_tls12_handshake() {
+        for suite in [
+            TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256,
+            TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384,
+            TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256,
+        ] {
+            for kx_group in ALL_KX_GROUPS {
+                check_handshake(suite, *kx_group, ECDSA_CERTIFICATE);
+            }
+        }
+        for suite in [
+            TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256,
+            TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384,
+            TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256,
+        ] {
+            check_handshake(suite, X25519, RSA_CERTIFICATE);
+        }
+    }
+
+    // Signs with every scheme a key supports and verifies with the algorithms mapped to the scheme, using the public key from the
+    // SubjectPublicKeyInfo of the key as webpki does.
+    fn check_signing_key(key: Arc<dyn SigningKey>, schemes: &[SignatureScheme]) {
+        let spki = key.public_key().unwrap();
+        let public_key = match PublicKey::from_spki_der(&spki).unwrap() {
+            PublicKey::Ec(key) => key
+                .public_key_bytes_with_format(PointFormat::Sec1Uncompressed)
+                .unwrap(),
+            // The SubjectPublicKeyInfo of a 2048-bit key has a 24 byte header before the `RSAPublicKey`.
+            PublicKey::Rsa(_) => spki[24..].to_vec(),
+            PublicKey::Ed25519(_) => unreachable!(),
+        };
+        for &scheme in schemes {
+            let signer = key.choose_scheme(&[scheme]).unwrap();
+            assert_eq!(signer.scheme(), scheme);
+            let signature = signer.sign(b"message").unwrap();
+            let (_, algorithms) = SUPPORTED_SIG_ALGS
+                .mapping
+                .iter()
+                .find(|(mapped, _)| *mapped == scheme)
+                .unwrap();
+            algorithms[0]
+                .verify_signature(&public_key, b"message", &signature)
+                .unwrap();
+            algorithms[0]
+                .verify_signature(&public_key, b"another message", &signature)
+                .unwrap_err();
+        }
+    }
+
+    #[test]
+    fn test_key_provider() {
+        for (curve, scheme) in [
+            (CurveType::NistP256, SignatureScheme::ECDSA_NISTP256_SHA256),
+            (CurveType::NistP384, SignatureScheme::ECDSA_NISTP384_SHA384),
+        ] {
+            let ec_dsa = EcDsa::new(curve).unwrap();
+            let pkcs8 = ec_private_key_to_pkcs8_der(ec_dsa.ec_key()).unwrap();
+            let key = KEY_PROVIDER
+                .load_private_key(PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(
+                    pkcs8.as_bytes().to_vec(),
+                )))
+                .unwrap();
+            assert_eq!(key.algorithm(), SignatureAlgorithm::ECDSA);
+            assert!(key
+                .choose_scheme(&[SignatureScheme::ECDSA_NISTP521_SHA512])
+                .is_none());
+            check_signing_key(key, &[scheme]);
+        }
+
+        let key = KEY_PROVIDER
+            .load_private_key(PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(
+                hex::decode(RSA_SERVER_KEY).unwrap(),
+            )))
+            .unwrap();
+        assert_eq!(key.algorithm(), SignatureAlgorithm::RSA);
+        // PSS is preferred when the peer offers both paddings.
+        assert_eq!(
+            key.choose_scheme(&[
+                SignatureScheme::RSA_PKCS1_SHA256,
+                SignatureScheme::RSA_PSS_SHA256
+            ])
+            .unwrap()
+            .scheme(),
+            SignatureScheme::RSA_PSS_SHA256
+        );
+        check_signing_key(
+            key,
+            &[
+                SignatureScheme::RSA_PSS_SHA384,
+                SignatureScheme::RSA_PSS_SHA256,
+                SignatureScheme::RSA_PKCS1_SHA384,
+                SignatureScheme::RSA_PKCS1_SHA256,
+            ],
+        );
+
+        // P-521 keys have no signature scheme with SHA-256 or SHA-384.
+        let ec_dsa = EcDsa::new(CurveType::NistP521).unwrap();
+        let pkcs8 = ec_private_key_to_pkcs8_der(ec_dsa.ec_key()).unwrap();
+        assert!(KEY_PROVIDER
+            .load_private_key(PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(
+                pkcs8.as_bytes().to_vec(),
+            )))
+            .is_err());
+    }
+
+    #[test]
+    fn test_hkdf_and_prf() {
+        use rustls::crypto::tls12::Prf as _;
+        use rustls::crypto::tls13::Hkdf as _;
+
+        // RFC 5869 appendix A.1.
+        let key_material = hex::decode("0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b").unwrap();
+        let salt = hex::decode("000102030405060708090a0b0c").unwrap();
+        let info = hex::decode("f0f1f2f3f4f5f6f7f8f9").unwrap();
+        let expander = HKDF_SHA256.extract_from_secret(Some(&salt[..]), &key_material);
+        let mut okm = [0u8; 42];
+        expander
+            .expand_slice(&[&info[..5], &info[5..]], &mut okm)
+            .unwrap();
+        assert_eq!(
+            hex::encode(okm),
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+        );
+        let mut too_long = vec![0u8; 255 * 32 + 1];
+        assert!(expander.expand_slice(&[&info[..]], &mut too_long).is_err());
+
+        // TLS 1.2 PRF known answer test, see tls_prf.rs.
+        let secret = hex::decode("9bbe436ba940f017b17652849a71db35").unwrap();
+        let seed = hex::decode("a0ba9f936cda311827a6f796ffd5198c").unwrap();
+        let mut output = [0u8; 100];
+        PRF_SHA256.for_secret(&mut output, &secret, b"test label", &seed);
+        assert_eq!(
+            hex::encode(output),
+            "e3f229ba727be17b8d122620557cd453c2aab21d07c3d495329b52d4e61edb5a6b301791e90d35c9c9a46b4e14baf9af0fa022f7077def17abfd3797c0564bab4fbc91666e9def9b97fce34f796789baa48082d122ee42c5a72e5a5110fff70187347b66"
+        );
+    }
+
+    #[test]
+    fn test_kx_group() {
+        for kx_group in ALL_KX_GROUPS {
+            let alice = kx_group.start().unwrap();
+            let bob = kx_group.start().unwrap();
+            let alice_public_key = alice.pub_key().to_vec();
+            let bob_public_key = bob.pub_key().to_vec();
+            let alice_secret = alice.complete(&bob_public_key).unwrap();
+            let bob_secret = bob.complete(&alice_public_key).unwrap();
+            assert_eq!(alice_secret.secret_bytes(), bob_secret.secret_bytes());
+
+            // A key share that is not a valid public key for the group is rejected.
+            let invalid = kx_group.start().unwrap().complete(&[0x02; 33]);
+            assert!(matches!(
+                invalid,
+                Err(Error::PeerMisbehaved(PeerMisbehaved::InvalidKeyShare))
+            ));
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! Types for secret values such as shared secrets, derived keys and exported private keys.
+//!
+//! [`SecretBytes`] and [`SecretArray`] are wiped with SymCryptWipe when they leave scope, do not print their contents with Debug, do
+//! not implement Clone and compare in constant time.
+
+use core::ffi::c_void;
+use std::fmt;
+use std::ops::Deref;
+use symcrypt_sys;
+
+/// Wipes a secret before the memory is released.
+pub(crate) fn wipe(buffer: &mut [u8]) {
+    unsafe {
+        // SAFETY: FFI calls
+        symcrypt_sys::SymCryptWipe(
+            buffer.as_mut_ptr() as *mut c_void,
+            buffer.len() as symcrypt_sys::SIZE_T,
+        );
+    }
+}
+
+/// constant_time_eq compares two buffers without branching on their contents. The lengths are not treated as secret.
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    unsafe {
+        // SAFETY: FFI calls
+        symcrypt_sys::SymCryptEqual(a.as_ptr(), b.as_ptr(), a.len() as symcrypt_sys::SIZE_T) != 0
+    }
+}
+
+/// [`SecretBytes`] holds a variable length secret.
+pub struct SecretBytes(Vec<u8>);
+
+/// Impl for [`SecretBytes`]
+///
+/// [`as_bytes()`] returns the secret, [`SecretBytes`] also derefs to `[u8]`.
+///
+/// [`clone_secret()`] returns a copy of the secret, which is also wiped when it leaves scope. [`SecretBytes`] does not implement Clone
+/// so that copies are only made on purpose.
+impl SecretBytes {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn clone_secret(&self) -> Self {
+        SecretBytes(self.0.clone())
+    }
+
+    // Returns a zeroed secret of the provided length, to be filled in by SymCrypt.
+    pub(crate) fn zeroed(len: usize) -> Self {
+        SecretBytes(vec![0u8; len])
+    }
+
+    pub(crate) fn as_mut_bytes(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+/// Takes ownership of the Vec, which will be wiped when the [`SecretBytes`] leaves scope. Drop only wipes the length of the secret, so a
+/// Vec with spare capacity, which can hold earlier contents of the Vec, is copied into an exact-size buffer and its whole allocation
+/// is wiped before it is released.
+impl From<Vec<u8>> for SecretBytes {
+    fn from(mut bytes: Vec<u8>) -> Self {
+        if bytes.len() == bytes.capacity() {
+            return SecretBytes(bytes);
+        }
+        let secret = SecretBytes(bytes.as_slice().to_vec());
+        // Resizing up to the capacity does not reallocate, so the spare capacity is wiped in place.
+        bytes.resize(bytes.capacity(), 0);
+        wipe(&mut bytes);
+        secret
+    }
+}
+
+impl Deref for SecretBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for SecretBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl PartialEq for SecretBytes {
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SecretBytes {}
+
+impl fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretBytes([REDACTED; {}])", self.0.len())
+    }
+}
+
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        wipe(&mut self.0);
+    }
+}
+
+/// [`SecretArray`] holds a secret of a fixed size [`N`].
+pub struct SecretArray<const N: usize>([u8; N]);
+
+/// Impl for [`SecretArray`]
+///
+/// [`new()`] takes ownership of the array. The caller is responsible for wiping any other copies of it.
+///
+/// [`as_bytes()`] returns the secret, [`SecretArray`] also derefs to `[u8; N]`.
+///
+/// [`clone_secret()`] returns a copy of the secret, see [`SecretBytes::clone_secret()`].
+impl<const N: usize> SecretArray<N> {
+    pub fn new(bytes: [u8; N]) -> Self {
+        SecretArray(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+
+    pub fn clone_secret(&self) -> Self {
+        SecretArray(self.0)
+    }
+
+    // Returns a zeroed secret, to be filled in by SymCrypt.
+    pub(crate) fn zeroed() -> Self {
+        SecretArray([0u8; N])
+    }
+
+    pub(crate) fn as_mut_bytes(&mut self) -> &mut [u8; N] {
+        &mut self.0
+    }
+}
+
+impl<const N: usize> Deref for SecretArray<N> {
+    type Target = [u8; N];
+
+    fn deref(&self) -> &[u8; N] {
+        &self.0
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for SecretArray<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> PartialEq for SecretArray<N> {
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(&self.0, &other.0)
+    }
+}
+
+impl<const N: usize> Eq for SecretArray<N> {}
+
+impl<const N: usize> fmt::Debug for SecretArray<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretArray([REDACTED; {}])", N)
+    }
+}
+
+impl<const N: usize> Drop for SecretArray<N> {
+    fn drop(&mut self) {
+        wipe(&mut self.0);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_secret_bytes() {
+        let secret = SecretBytes::from(vec![0x42u8; 16]);
+        assert_eq!(secret.as_bytes(), &[0x42u8; 16]);
+        assert_eq!(secret.len(), 16);
+
+        let copy = secret.clone_secret();
+        assert_eq!(copy, secret);
+        assert_ne!(SecretBytes::from(vec![0x42u8; 15]), secret);
+        assert_ne!(SecretBytes::from(vec![0x43u8; 16]), secret);
+    }
+
+    #[test]
+    fn test_secret_bytes_from_vec_with_spare_capacity() {
+        let mut bytes = Vec::with_capacity(64);
+        bytes.extend_from_slice(&[0x42u8; 48]);
+        bytes.truncate(16);
+
+        let secret = SecretBytes::from(bytes);
+        assert_eq!(secret.as_bytes(), &[0x42u8; 16]);
+        assert_eq!(secret.0.capacity(), 16);
+    }
+
+    #[test]
+    fn test_secret_array() {
+        let secret = SecretArray::new([7u8; 32]);
+        assert_eq!(secret.as_bytes(), &[7u8; 32]);
+        assert_eq!(secret.clone_secret(), secret);
+
+        let mut other = SecretArray::<32>::zeroed();
+        other.as_mut_bytes()[31] = 7;
+        assert_ne!(other, secret);
+    }
+
+    #[test]
+    fn test_debug_is_redacted() {
+        let secret = SecretBytes::from(vec![0xabu8; 4]);
+        assert_eq!(format!("{:?}", secret), "SecretBytes([REDACTED; 4])");
+
+        let secret = SecretArray::new([0xabu8; 4]);
+        assert_eq!(format!("{:?}", secret), "SecretArray([REDACTED; 4])");
+    }
+}
@@ -0,0 +1,516 @@
+//! Common signature traits. [`Signer`] and [`Verifier`] let callers sign and verify without knowing the signature algorithm, and
+//! [`SigningKey`] and [`VerifyingKey`] hold a key of any of the signature algorithms in this crate.
+//!
+//! Algorithms that sign a digest, such as DSA, ECDSA and RSA, hash the message internally with the [`HashAlgorithm`] the key was
+//! created with. Algorithms that take a context string, such as ML-DSA, sign with an empty context.
+//!
+//! EdDSA is not supported. SymCrypt has no Ed25519 or Ed448, so there is no [`SigningKey`] or [`VerifyingKey`] for them, and
+//! [`crate::key_encoding`] only returns the raw Ed25519 key material, which has to be used with another library.
+
+use crate::dsa::DsaKey;
+use crate::ecdsa::EcDsa;
+use crate::errors::SymCryptError;
+use crate::hash::{HashAlgorithm, HashState, Sha256State, Sha384State};
+use crate::hbs::{HssPublicKey, LmsPublicKey, XmssPublicKey};
+use crate::mldsa::MlDsaKey;
+use crate::rsa::RsaKey;
+
+/// [`Signer`] signs a message.
+pub trait Signer {
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, SymCryptError>;
+}
+
+/// [`Verifier`] verifies the signature of a message.
+///
+/// Any failure to verify, including a malformed signature, returns [`SymCryptError::SignatureVerificationFailure`].
+pub trait Verifier {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), SymCryptError>;
+}
+
+/// hash_message hashes the message with the provided [`HashAlgorithm`] through its [`HashState`].
+fn hash_message(hash_algorithm: HashAlgorithm, message: &[u8]) -> Vec<u8> {
+    fn digest<H: HashState>(mut state: H, message: &[u8]) -> Vec<u8>
+    where
+        H::Result: AsRef<[u8]>,
+    {
+        state.append(message);
+        state.result().as_ref().to_vec()
+    }
+
+    match hash_algorithm {
+        HashAlgorithm::Sha256 => digest(Sha256State::new(), message),
+        HashAlgorithm::Sha384 => digest(Sha384State::new(), message),
+    }
+}
+
+/// [`RsaPadding`] is the signature scheme of a RSA key in a [`SigningKey`] or [`VerifyingKey`].
+///
+/// [`RsaPadding::Pss`] uses the hash algorithm of the key for MGF1, with a salt of `salt_size` bytes.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum RsaPadding {
+    Pkcs1,
+    Pss { salt_size: usize },
+}
+
+/// [`SigningKey`] holds a private key of any of the signature algorithms that can sign through [`Signer`].
+///
+/// Stateful hash-based signature keys are not included since every signature must advance and persist the key state, see
+/// [`crate::hbs`].
+pub enum SigningKey {
+    Dsa {
+        key: DsaKey,
+        hash_algorithm: HashAlgorithm,
+    },
+    EcDsa {
+        key: EcDsa,
+        hash_algorithm: HashAlgorithm,
+    },
+    Rsa {
+        key: RsaKey,
+        hash_algorithm: HashAlgorithm,
+        padding: RsaPadding,
+    },
+    MlDsa(MlDsaKey),
+}
+
+impl Signer for SigningKey {
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+        match self {
+            SigningKey::Dsa {
+                key,
+                hash_algorithm,
+            } => key.sign(&hash_message(*hash_algorithm, message)),
+            SigningKey::EcDsa {
+                key,
+                hash_algorithm,
+            } => key.sign(&hash_message(*hash_algorithm, message)),
+            SigningKey::Rsa {
+                key,
+                hash_algorithm,
+                padding,
+            } => sign_rsa(key, *hash_algorithm, *padding, message),
+            SigningKey::MlDsa(key) => Signer::sign(key, message),
+        }
+    }
+}
+
+/// A [`SigningKey`] can also verify the signatures it produced.
+impl Verifier for SigningKey {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), SymCryptError> {
+        match self {
+            SigningKey::Dsa {
+                key,
+                hash_algorithm,
+            } => verify_dsa(key, *hash_algorithm, message, signature),
+            SigningKey::EcDsa {
+                key,
+                hash_algorithm,
+            } => verify_ecdsa(key, *hash_algorithm, message, signature),
+            SigningKey::Rsa {
+                key,
+                hash_algorithm,
+                padding,
+            } => verify_rsa(key, *hash_algorithm, *padding, message, signature),
+            SigningKey::MlDsa(key) => Verifier::verify(key, message, signature),
+        }
+    }
+}
+
+/// [`VerifyingKey`] holds a public key of any of the signature algorithms that can verify through [`Verifier`].
+pub enum VerifyingKey {
+    Dsa {
+        key: DsaKey,
+        hash_algorithm: HashAlgorithm,
+    },
+    EcDsa {
+        key: EcDsa,
+        hash_algorithm: HashAlgorithm,
+    },
+    Rsa {
+        key: RsaKey,
+        hash_algorithm: HashAlgorithm,
+        padding: RsaPadding,
+    },
+    MlDsa(MlDsaKey),
+    Lms(LmsPublicKey),
+    Hss(HssPublicKey),
+    Xmss(XmssPublicKey),
+}
+
+impl Verifier for VerifyingKey {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), SymCryptError> {
+        match self {
+            VerifyingKey::Dsa {
+                key,
+                hash_algorithm,
+            } => verify_dsa(key, *hash_algorithm, message, signature),
+            VerifyingKey::EcDsa {
+                key,
+                hash_algorithm,
+            } => verify_ecdsa(key, *hash_algorithm, message, signature),
+            VerifyingKey::Rsa {
+                key,
+                hash_algorithm,
+                padding,
+            } => verify_rsa(key, *hash_algorithm, *padding, message, signature),
+            VerifyingKey::MlDsa(key) => Verifier::verify(key, message, signature),
+            VerifyingKey::Lms(key) => Verifier::verify(key, message, signature),
+            VerifyingKey::Hss(key) => Verifier::verify(key, message, signature),
+            VerifyingKey::Xmss(key) => Verifier::verify(key, message, signature),
+        }
+    }
+}
+
+fn verify_dsa(
+    key: &DsaKey,
+    hash_algorithm: HashAlgorithm,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<(), SymCryptError> {
+    key.verify(&hash_message(hash_algorithm, message), signature)
+}
+
+fn verify_ecdsa(
+    key: &EcDsa,
+    hash_algorithm: HashAlgorithm,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<(), SymCryptError> {
+    key.verify(&hash_message(hash_algorithm, message), signature)
+}
+
+fn sign_rsa(
+    key: &RsaKey,
+    hash_algorithm: HashAlgorithm,
+    padding: RsaPadding,
+    message: &[u8],
+) -> Result<Vec<u8>, SymCryptError> {
+    let hashed_message = hash_message(hash_algorithm, message);
+    match padding {
+        RsaPadding::Pkcs1 => key.pkcs1_sign(&hashed_message, hash_algorithm),
+        RsaPadding::Pss { salt_size } => key.pss_sign(&hashed_message, hash_algorithm, salt_size),
+    }
+}
+
+fn verify_rsa(
+    key: &RsaKey,
+    hash_algorithm: HashAlgorithm,
+    padding: RsaPadding,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<(), SymCryptError> {
+    let hashed_message = hash_message(hash_algorithm, message);
+    match padding {
+        RsaPadding::Pkcs1 => key.pkcs1_verify(&hashed_message, signature, hash_algorithm),
+        RsaPadding::Pss { salt_size } => {
+            key.pss_verify(&hashed_message, signature, hash_algorithm, salt_size)
+        }
+    }
+}
+
+/// ML-DSA signs with a hedged signature and an empty context.
+impl Signer for MlDsaKey {
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, SymCryptError> {
+        MlDsaKey::sign(self, message, &[])
+    }
+}
+
+impl Verifier for MlDsaKey {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), SymCryptError> {
+        MlDsaKey::verify(self, message, &[], signature)
+    }
+}
+
+impl Verifier for LmsPublicKey {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), SymCryptError> {
+        LmsPublicKey::verify(self, message, signature)
+    }
+}
+
+impl Verifier for HssPublicKey {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), SymCryptError> {
+        HssPublicKey::verify(self, message, signature)
+    }
+}
+
+impl Verifier for XmssPublicKey {
+    fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), SymCryptError> {
+        XmssPublicKey::verify(self, message, signature)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::eckey::CurveType;
+    use crate::hash::{sha256, sha384};
+    use crate::hbs::{
+        HssPrivateKey, LmotsAlgorithm, LmsAlgorithm, LmsPrivateKey, XmssAlgorithm, XmssPrivateKey,
+    };
+    use crate::mldsa::MlDsaParams;
+
+    // DSA key from RFC 6979 appendix A.2.2, L = 2048, N = 256, as in dsa.rs.
+    const DSA_P: &str = "9db6fb5951b66bb6fe1e140f1d2ce5502374161fd6538df1648218642f0b5c48c8f7a41aadfa187324b87674fa1822b00f1ecf8136943d7c55757264e5a1a44ffe012e9936e00c1d3e9310b01c7d179805d3058b2a9f4bb6f9716bfe6117c6b5b3cc4d9be341104ad4a80ad6c94e005f4b993e14f091eb51743bf33050c38de235567e1b34c3d6a5c0ceaa1a0f368213c3d19843d0b4b09dcb9fc72d39c8de41f1bf14d4bb4563ca28371621cad3324b6a2d392145bebfac748805236f5ca2fe92b871cd8f9c36d3292b5509ca8caa77a2adfc7bfd77dda6f71125a7456fea153e433256a2261c6a06ed3693797e7995fad5aabbcfbe3eda2741e375404ae25b";
+    const DSA_Q: &str = "f2c3119374ce76c9356990b465374a17f23f9ed35089bd969f61c6dde9998c1f";
+    const DSA_G: &str = "5c7ff6b06f8f143fe8288433493e4769c4d988ace5be25a0e24809670716c613d7b0cee6932f8faa7c44d2cb24523da53fbe4f6ec3595892d1aa58c4328a06c46a15662e7eaa703a1decf8bbb2d05dbe2eb956c142a338661d10461c0d135472085057f3494309ffa73c611f78b32adbb5740c361c9f35be90997db2014e2ef5aa61782f52abeb8bd6432c4dd097bc5423b285dafb60dc364e8161f4a2a35aca3a10b1c4d203cc76a470a33afdcbdd92959859abd8b56e1725252d78eac66e71ba9ae3f1dd2487199874393cd4d832186800654760e1e34c09e4d155179f9ec0dc4473f996bdce6eed1cabed8b6f116f7ad9cf505df0f998e34ab27514b0ffe7";
+    const DSA_Y: &str = "667098c654426c78d7f8201eac6c203ef030d43605032c2f1fa937e5237dbd949f34a0a2564fe126dc8b715c5141802ce0979c8246463c40e6b6bdaa2513fa611728716c2e4fd53bc95b89e69949d96512e873b9c8f8dfd499cc312882561adecb31f658e934c0c197f2c4d96b05cbad67381e7b768891e4da3843d24d94cdfb5126e9b8bf21e8358ee0e0a30ef13fd6a664c0dce3731f7fb49a4845a4fd8254687972a2d382599c9bac4e0ed7998193078913032558134976410b89d2c171d123ac35fd977219597aa7d15c1a9a428e59194f75c721ebcbcfae44696a499afa74e04299f132026601638cb87ab79190d4a0986315da8eec6561c938996beadf";
+    const DSA_X: &str = "69c7548c21d0dfea6b9a51c9ead4e27c33d3b3f180316e5bcab92c933f0e4dbc";
+
+    // RFC 6979 signature of "sample" with SHA-256.
+    const DSA_SIGNATURE_SAMPLE: &str = "eace8bdbbe353c432a795d9ec556c6d021f7a03f42c36e9bc87e4ac7932cc8097081e175455f9247b812b74583e9e94f9ea79bd640dc962533b0680793a38d53";
+
+    // ECDSA key from RFC 6979 appendix A.2.5 and its signature of "sample" with SHA-256, as in ecdsa.rs.
+    const P256_PRIVATE_KEY: &str =
+        "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721";
+    const P256_PUBLIC_KEY: &str = "60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb67903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299";
+    const P256_SIGNATURE_SAMPLE: &str = "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8";
+
+    // RSA key from RFC 7520 section 3.4 and the RS256 signature of section 4.1, which signs the JWS signing input.
+    const RSA_MODULUS: &str = "9f810fb4038273d02591e4073f31d2b6001b82cedb4d92f050165d47cfcab8a3c41cb778ac7553793f8ef975768d1a2374d8712564c3bcd77b9ea434544899407cff0099920a931a24c4414852ab29bdb0a95c0653f36c60e60bf90b6258dda56f37047ba5c2d1d029af9c9d40bac7aa41c78a0dd1068add699e808fea011ea1441d8a4f7bb4e97be39f55f1ddd44e9c4ba335159703d4d34b603e65147a4f23d6d3c0996c75edee846a82d190ae10783c961cf0387aed2106d2d0555b6fd937fad5535387e0ff72ffbe78941402b0b822ea2a74b6058c1dabf9b34a76cb63b87faa2c6847b8e2837fff91186e6b1c14911cf989a89092a81ce601ddacd3f9cf";
+    const RSA_PRIME1: &str = "dd297183f0f04d725c6fad3de51a17ca0402019e519c0bd9967a35ca11ed9d47b1fdfa7b019ffd9d168eec75fff9215f1907aeb5aa364c38c301653856ea64f2bc3d251d00cd9d0dd9fbee2009abfd60ac986a5e36a4277afd53ec8c84b2787c50cb7e9f909a7e1922933844b2b9a7747e8bc4eaef44996c3e9e99bfc6d4ab49";
+    const RSA_PRIME2: &str = "b8a136761f9c4dfe84445e24e1efe3cbbf067cf61421a532a12489b81ce9dc2b9b937382aacea0ad3f1b47f72ed039b5319c169ad76a0f223de47ad47aadcc3f5e6f30c38df251d3799bb69662afc2a5bb6a757953384cd6267bcf8c8c92e530156a01bf263cf7c117bd10fe85da91c47952a80675f76cc1de9545274b3ba457";
+    const RSA_PUBLIC_EXPONENT: &[u8] = &[0x01, 0x00, 0x01];
+    const RS256_SIGNING_INPUT: &str = "eyJhbGciOiJSUzI1NiIsImtpZCI6ImJpbGJvLmJhZ2dpbnNAaG9iYml0b24uZXhhbXBsZSJ9.SXTigJlzIGEgZGFuZ2Vyb3VzIGJ1c2luZXNzLCBGcm9kbywgZ29pbmcgb3V0IHlvdXIgZG9vci4gWW91IHN0ZXAgb250byB0aGUgcm9hZCwgYW5kIGlmIHlvdSBkb24ndCBrZWVwIHlvdXIgZmVldCwgdGhlcmXigJlzIG5vIGtub3dpbmcgd2hlcmUgeW91IG1pZ2h0IGJlIHN3ZXB0IG9mZiB0by4";
+    const RS256_SIGNATURE: &str = "3118dd925cbbffea133d34b70173f8d6240818a6bcd00d19993b95e4c11a1e8c675b67b9099e4d94ab5a8a7a0598a668a5d1ccd4ed94e26c3325d431f7dea2be9f37c6e825208ecf3438bce309c1f810e4a01c00efcd7ce615fe12ce092309832c92b795f5916be5ed2f446796db98bfbe3a8984e1fea1d10faf0136dd6e48d0ab5024c90ede5a004eb1f305b52adf5e468e103e871269d9c75c5c67b7c22cf54ce911157478f3d074bb96dc84bf75edf1c88ac368b19d8e5b8fc540216fa2f669789cd54d76a6874653428b510484fe823e66798a277178b8e6439dde9065626eb21678d40c5ffdf7087bcbbd8a91fce28828ade7bbbe36d4e72d7c903aae0e";
+
+    const MESSAGE: &[u8] = b"certificate to be signed";
+
+    fn dsa_key(with_private_key: bool) -> DsaKey {
+        let p = hex::decode(DSA_P).unwrap();
+        let q = hex::decode(DSA_Q).unwrap();
+        let g = hex::decode(DSA_G).unwrap();
+        let y = hex::decode(DSA_Y).unwrap();
+        if with_private_key {
+            DsaKey::from_key_pair(&p, &q, &g, &y, &hex::decode(DSA_X).unwrap()).unwrap()
+        } else {
+            DsaKey::from_public_key(&p, &q, &g, &y).unwrap()
+        }
+    }
+
+    fn rsa_key(with_private_key: bool) -> RsaKey {
+        let modulus = hex::decode(RSA_MODULUS).unwrap();
+        if with_private_key {
+            RsaKey::from_key_pair(
+                &modulus,
+                RSA_PUBLIC_EXPONENT,
+                &hex::decode(RSA_PRIME1).unwrap(),
+                &hex::decode(RSA_PRIME2).unwrap(),
+            )
+            .unwrap()
+        } else {
+            RsaKey::from_public_key(&modulus, RSA_PUBLIC_EXPONENT).unwrap()
+        }
+    }
+
+    // Written once against the traits, as certificate or token code would be.
+    fn sign_and_verify(signer: &impl Signer, verifier: &impl Verifier) {
+        let signature = signer.sign(MESSAGE).unwrap();
+        verifier.verify(MESSAGE, &signature).unwrap();
+        assert_eq!(
+            verifier
+                .verify(b"certificate to be signec", &signature)
+                .unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+    }
+
+    #[test]
+    fn test_hash_message() {
+        assert_eq!(
+            hash_message(HashAlgorithm::Sha256, MESSAGE),
+            sha256(MESSAGE).to_vec()
+        );
+        assert_eq!(
+            hash_message(HashAlgorithm::Sha384, MESSAGE),
+            sha384(MESSAGE).to_vec()
+        );
+    }
+
+    #[test]
+    fn test_dsa_signing_key() {
+        let signing_key = SigningKey::Dsa {
+            key: dsa_key(true),
+            hash_algorithm: HashAlgorithm::Sha256,
+        };
+        let verifying_key = VerifyingKey::Dsa {
+            key: dsa_key(false),
+            hash_algorithm: HashAlgorithm::Sha256,
+        };
+        sign_and_verify(&signing_key, &verifying_key);
+        sign_and_verify(&signing_key, &signing_key);
+
+        // The message is hashed internally, so the signature is over the SHA-256 digest.
+        let signature = signing_key.sign(MESSAGE).unwrap();
+        dsa_key(false).verify(&sha256(MESSAGE), &signature).unwrap();
+        verifying_key
+            .verify(b"sample", &hex::decode(DSA_SIGNATURE_SAMPLE).unwrap())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_ecdsa_signing_key() {
+        let signing_key = SigningKey::EcDsa {
+            key: EcDsa::from_private_key_bytes(
+                CurveType::NistP256,
+                &hex::decode(P256_PRIVATE_KEY).unwrap(),
+            )
+            .unwrap(),
+            hash_algorithm: HashAlgorithm::Sha256,
+        };
+        let verifying_key = VerifyingKey::EcDsa {
+            key: EcDsa::from_public_key_bytes(
+                CurveType::NistP256,
+                &hex::decode(P256_PUBLIC_KEY).unwrap(),
+            )
+            .unwrap(),
+            hash_algorithm: HashAlgorithm::Sha256,
+        };
+        sign_and_verify(&signing_key, &verifying_key);
+        sign_and_verify(&signing_key, &signing_key);
+        verifying_key
+            .verify(b"sample", &hex::decode(P256_SIGNATURE_SAMPLE).unwrap())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_rsa_signing_key() {
+        let signing_key = SigningKey::Rsa {
+            key: rsa_key(true),
+            hash_algorithm: HashAlgorithm::Sha256,
+            padding: RsaPadding::Pkcs1,
+        };
+        let verifying_key = VerifyingKey::Rsa {
+            key: rsa_key(false),
+            hash_algorithm: HashAlgorithm::Sha256,
+            padding: RsaPadding::Pkcs1,
+        };
+        sign_and_verify(&signing_key, &verifying_key);
+
+        // PKCS#1 v1.5 signatures are deterministic.
+        let signature = signing_key.sign(RS256_SIGNING_INPUT.as_bytes()).unwrap();
+        assert_eq!(hex::encode(&signature), RS256_SIGNATURE);
+        verifying_key
+            .verify(RS256_SIGNING_INPUT.as_bytes(), &signature)
+            .unwrap();
+
+        let signing_key = SigningKey::Rsa {
+            key: rsa_key(true),
+            hash_algorithm: HashAlgorithm::Sha384,
+            padding: RsaPadding::Pss { salt_size: 48 },
+        };
+        let verifying_key = VerifyingKey::Rsa {
+            key: rsa_key(false),
+            hash_algorithm: HashAlgorithm::Sha384,
+            padding: RsaPadding::Pss { salt_size: 48 },
+        };
+        sign_and_verify(&signing_key, &verifying_key);
+        sign_and_verify(&signing_key, &signing_key);
+
+        // A PSS signature does not verify as PKCS#1 v1.5.
+        let signature = signing_key.sign(MESSAGE).unwrap();
+        let pkcs1_key = VerifyingKey::Rsa {
+            key: rsa_key(false),
+            hash_algorithm: HashAlgorithm::Sha384,
+            padding: RsaPadding::Pkcs1,
+        };
+        assert_eq!(
+            pkcs1_key.verify(MESSAGE, &signature).unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+    }
+
+    #[test]
+    fn test_mldsa_signing_key() {
+        let key = MlDsaKey::generate(MlDsaParams::MlDsa44).unwrap();
+        let public_key =
+            MlDsaKey::from_public_key(MlDsaParams::MlDsa44, &key.public_key_bytes().unwrap())
+                .unwrap();
+
+        let signature = SigningKey::MlDsa(key).sign(MESSAGE).unwrap();
+        let verifying_key = VerifyingKey::MlDsa(public_key);
+        verifying_key.verify(MESSAGE, &signature).unwrap();
+    }
+
+    #[test]
+    fn test_mldsa_traits() {
+        let key = MlDsaKey::generate(MlDsaParams::MlDsa65).unwrap();
+        sign_and_verify(&key, &key);
+
+        // The traits sign with an empty context.
+        let signature = Signer::sign(&key, MESSAGE).unwrap();
+        key.verify(MESSAGE, b"", &signature).unwrap();
+    }
+
+    #[test]
+    fn test_lms_verifying_key() {
+        let mut private_key = LmsPrivateKey::generate(
+            LmsAlgorithm::Sha256M32H5,
+            LmotsAlgorithm::Sha256N32W8,
+            |_| Ok(()),
+        )
+        .unwrap();
+        let verifying_key = VerifyingKey::Lms(private_key.public_key().unwrap());
+        let signature = private_key.sign(MESSAGE, |_| Ok(())).unwrap();
+
+        verifying_key.verify(MESSAGE, &signature).unwrap();
+    }
+
+    #[test]
+    fn test_hss_verifying_key() {
+        let levels = [(LmsAlgorithm::Sha256M32H5, LmotsAlgorithm::Sha256N32W8); 2];
+        let mut private_key = HssPrivateKey::generate(&levels, |_| Ok(())).unwrap();
+        let verifying_key = VerifyingKey::Hss(private_key.public_key().unwrap());
+        let signature = private_key.sign(MESSAGE, |_| Ok(())).unwrap();
+
+        verifying_key.verify(MESSAGE, &signature).unwrap();
+        assert_eq!(
+            verifying_key
+                .verify(b"certificate to be signec", &signature)
+                .unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+    }
+
+    #[test]
+    fn test_xmss_verifying_key() {
+        let mut private_key =
+            XmssPrivateKey::generate(XmssAlgorithm::Sha2_10_256, |_| Ok(())).unwrap();
+        let verifying_key = VerifyingKey::Xmss(private_key.public_key().unwrap());
+        let signature = private_key.sign(MESSAGE, |_| Ok(())).unwrap();
+
+        verifying_key.verify(MESSAGE, &signature).unwrap();
+        assert_eq!(
+            verifying_key
+                .verify(b"certificate to be signec", &signature)
+                .unwrap_err(),
+            SymCryptError::SignatureVerificationFailure
+        );
+    }
+
+    #[test]
+    fn test_uniform_verification_error() {
+        let dsa = VerifyingKey::Dsa {
+            key: dsa_key(false),
+            hash_algorithm: HashAlgorithm::Sha384,
+        };
+        let mldsa = VerifyingKey::MlDsa(MlDsaKey::generate(MlDsaParams::MlDsa44).unwrap());
+        let ecdsa = VerifyingKey::EcDsa {
+            key: EcDsa::new(CurveType::NistP384).unwrap(),
+            hash_algorithm: HashAlgorithm::Sha384,
+        };
+        let rsa = VerifyingKey::Rsa {
+            key: rsa_key(false),
+            hash_algorithm: HashAlgorithm::Sha256,
+            padding: RsaPadding::Pss { salt_size: 32 },
+        };
+
+        for key in [dsa, mldsa, ecdsa, rsa] {
+            for signature in [&[][..], &[0u8; 3][..], &[0xffu8; 4627][..]] {
+                assert_eq!(
+                    key.verify(MESSAGE, signature).unwrap_err(),
+                    SymCryptError::SignatureVerificationFailure
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,377 @@
+//! SSH key derivation functions as defined in RFC 4253 section 7.2. For further documentation please refer to symcrypt.h
+
+use crate::errors::SymCryptError;
+use crate::secret::SecretBytes;
+use core::ffi::c_void;
+use std::mem;
+use std::pin::Pin;
+use symcrypt_sys;
+
+/// [`SshKdfHashAlgorithm`] provides an enum of the hash algorithms that can be used with the SSH KDF. SHA-1 is only provided for
+/// key exchange methods such as diffie-hellman-group14-sha1 that are still negotiated by older peers.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SshKdfHashAlgorithm {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+/// convert_hash takes in the friendly SshKdfHashAlgorithm enum and returns the symcrypt equivalent.
+fn convert_hash(hash: SshKdfHashAlgorithm) -> symcrypt_sys::PCSYMCRYPT_HASH {
+    match hash {
+        SshKdfHashAlgorithm::Sha1 => unsafe { symcrypt_sys::SymCryptSha1Algorithm }, // SAFETY: FFI calls
+        SshKdfHashAlgorithm::Sha256 => unsafe { symcrypt_sys::SymCryptSha256Algorithm }, // SAFETY: FFI calls
+        SshKdfHashAlgorithm::Sha384 => unsafe { symcrypt_sys::SymCryptSha384Algorithm }, // SAFETY: FFI calls
+        SshKdfHashAlgorithm::Sha512 => unsafe { symcrypt_sys::SymCryptSha512Algorithm }, // SAFETY: FFI calls
+    }
+}
+
+/// [`SshKdfLabel`] provides an enum of the labels that select which value is derived from the shared secret.
+///
+/// Each label maps to the single character that RFC 4253 section 7.2 appends to the hash input, 'A' through 'F'.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SshKdfLabel {
+    IvClientToServer,
+    IvServerToClient,
+    EncryptionKeyClientToServer,
+    EncryptionKeyServerToClient,
+    IntegrityKeyClientToServer,
+    IntegrityKeyServerToClient,
+}
+
+/// convert_label takes in the friendly SshKdfLabel enum and returns the symcrypt equivalent.
+fn convert_label(label: SshKdfLabel) -> symcrypt_sys::BYTE {
+    let label = match label {
+        SshKdfLabel::IvClientToServer => symcrypt_sys::SYMCRYPT_SSHKDF_IV_CLIENT_TO_SERVER,
+        SshKdfLabel::IvServerToClient => symcrypt_sys::SYMCRYPT_SSHKDF_IV_SERVER_TO_CLIENT,
+        SshKdfLabel::EncryptionKeyClientToServer => {
+            symcrypt_sys::SYMCRYPT_SSHKDF_ENCRYPTION_KEY_CLIENT_TO_SERVER
+        }
+        SshKdfLabel::EncryptionKeyServerToClient => {
+            symcrypt_sys::SYMCRYPT_SSHKDF_ENCRYPTION_KEY_SERVER_TO_CLIENT
+        }
+        SshKdfLabel::IntegrityKeyClientToServer => {
+            symcrypt_sys::SYMCRYPT_SSHKDF_INTEGRITY_KEY_CLIENT_TO_SERVER
+        }
+        SshKdfLabel::IntegrityKeyServerToClient => {
+            symcrypt_sys::SYMCRYPT_SSHKDF_INTEGRITY_KEY_SERVER_TO_CLIENT
+        }
+    };
+    label as symcrypt_sys::BYTE
+}
+
+/// [`SshKdfExpandedKey`] holds the shared secret K after it has been absorbed into the hash state by SymCrypt.
+///
+/// The expanded key is Pin<Box<>>'d since the memory address for Self is moved around when returning from
+/// SshKdfExpandedKey::new(). A single expanded key can be re-used to derive every IV and key of a key exchange.
+///
+/// SymCrypt expects the address for its structs to stay static through the structs lifetime to guarantee that structs are not memcpy'd as
+/// doing so would lead to use-after-free and inconsistent states.
+pub struct SshKdfExpandedKey(Pin<Box<symcrypt_sys::SYMCRYPT_SSHKDF_EXPANDED_KEY>>);
+
+/// Impl for the SshKdfExpandedKey struct.
+///
+/// [`new()`] takes in a [`SshKdfHashAlgorithm`] and the shared secret K. K must already be encoded as an SSH mpint, including the 4 byte
+/// length prefix, exactly as it is fed into the exchange hash.
+///
+/// [`derive()`] takes in the exchange hash H, a [`SshKdfLabel`], the session id and the number of bytes to derive and returns the
+/// derived value as [`SecretBytes`] so it is wiped when it leaves scope. For the first key exchange of a connection the session id is
+/// equal to H.
+impl SshKdfExpandedKey {
+    pub fn new(hash: SshKdfHashAlgorithm, shared_secret: &[u8]) -> Result<Self, SymCryptError> {
+        let mut expanded_key = Box::pin(symcrypt_sys::SYMCRYPT_SSHKDF_EXPANDED_KEY::default()); // boxing here so that the memory is not moved
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptSshKdfExpandKey(
+                &mut *expanded_key,
+                convert_hash(hash),
+                shared_secret.as_ptr(),
+                shared_secret.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => {
+                    Ok(SshKdfExpandedKey(expanded_key))
+                }
+                err => Err(err.into()),
+            }
+        }
+    }
+
+    pub fn derive(
+        &self,
+        hash_value: &[u8],
+        label: SshKdfLabel,
+        session_id: &[u8],
+        output_length: usize,
+    ) -> Result<SecretBytes, SymCryptError> {
+        let mut output = SecretBytes::zeroed(output_length);
+        unsafe {
+            // SAFETY: FFI calls
+            match symcrypt_sys::SymCryptSshKdfDerive(
+                &*self.0,
+                hash_value.as_ptr(),
+                hash_value.len() as symcrypt_sys::SIZE_T,
+                convert_label(label),
+                session_id.as_ptr(),
+                session_id.len() as symcrypt_sys::SIZE_T,
+                output.as_mut_bytes().as_mut_ptr(),
+                output.len() as symcrypt_sys::SIZE_T,
+            ) {
+                symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(output),
+                err => Err(err.into()),
+            }
+        }
+    }
+}
+
+unsafe impl Send for SshKdfExpandedKey {
+    // pHashFunc points to a static SymCrypt hash table that is never mutated.
+}
+
+unsafe impl Sync for SshKdfExpandedKey {
+    // SymCryptSshKdfDerive only reads the expanded key.
+}
+
+impl Drop for SshKdfExpandedKey {
+    fn drop(&mut self) {
+        unsafe {
+            // SAFETY: FFI calls
+            symcrypt_sys::SymCryptWipe(
+                &mut *self.0 as *mut _ as *mut c_void,
+                mem::size_of::<symcrypt_sys::SYMCRYPT_SSHKDF_EXPANDED_KEY>()
+                    as symcrypt_sys::SIZE_T,
+            );
+        }
+    }
+}
+
+/// Stateless SSH KDF function. This is equivalent to creating a [`SshKdfExpandedKey`] and calling [`SshKdfExpandedKey::derive()`] once.
+///
+/// `shared_secret` is the shared secret K encoded as an SSH mpint.
+/// `hash_value` is the exchange hash H.
+/// `session_id` is the session identifier, which is the exchange hash H of the first key exchange.
+pub fn ssh_kdf(
+    hash: SshKdfHashAlgorithm,
+    shared_secret: &[u8],
+    hash_value: &[u8],
+    label: SshKdfLabel,
+    session_id: &[u8],
+    output_length: usize,
+) -> Result<SecretBytes, SymCryptError> {
+    let mut output = SecretBytes::zeroed(output_length);
+    unsafe {
+        // SAFETY: FFI calls
+        match symcrypt_sys::SymCryptSshKdf(
+            convert_hash(hash),
+            shared_secret.as_ptr(),
+            shared_secret.len() as symcrypt_sys::SIZE_T,
+            hash_value.as_ptr(),
+            hash_value.len() as symcrypt_sys::SIZE_T,
+            convert_label(label),
+            session_id.as_ptr(),
+            session_id.len() as symcrypt_sys::SIZE_T,
+            output.as_mut_bytes().as_mut_ptr(),
+            output.len() as symcrypt_sys::SIZE_T,
+        ) {
+            symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(output),
+            err => Err(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Shared secret, encoded as an mpint, and exchange hashes used by every test, with the session id equal to the exchange hash. The
+    // exchange hashes for SHA-1 and SHA-512 are the hash of "ssh kdf exchange hash". These are not CAVP vectors, every expected output
+    // was computed with OpenSSL 3.5's SSHKDF (`openssl kdf -kdfopt digest:.. -kdfopt hexkey:.. -kdfopt hexxcghash:..
+    // -kdfopt hexsession_id:.. -kdfopt type:A..F SSHKDF`) and matches RFC 4253 section 7.2 computed with Python's hashlib.
+    const SHARED_SECRET: &str = "0000004100a3b1c2d4e5f60718293a4b5c6d7e8f90a3b1c2d4e5f60718293a4b5c6d7e8f90a3b1c2d4e5f60718293a4b5c6d7e8f90a3b1c2d4e5f60718293a4b5c6d7e8f90";
+    const HASH_VALUE_SHA256: &str =
+        "7bde03b6174b06ac22d902ada587e02f46b15e82e96fcc34d951b86efe0642f8";
+    const HASH_VALUE_SHA384: &str = "ded7a301a6d2959a8bda80276bd05bff91b8582c5f0cbe432ed6427d297b7d56a2a35da20734acfc1148a13e6f928158";
+    const HASH_VALUE_SHA1: &str = "315130782ffa2e2cb8df253dc37ab70abdb3d8ee";
+    const HASH_VALUE_SHA512: &str = "df85a9a7ce49f07ad82293b45ccc467535d414b90346e4ab6824e66761f44c8901449532c97c752a03c0fbc53b64aa18e701a4407b362a9bdecaad57af851213";
+
+    #[test]
+    fn test_ssh_kdf_sha256_all_labels() {
+        let shared_secret = hex::decode(SHARED_SECRET).unwrap();
+        let hash_value = hex::decode(HASH_VALUE_SHA256).unwrap();
+        let expanded_key =
+            SshKdfExpandedKey::new(SshKdfHashAlgorithm::Sha256, &shared_secret).unwrap();
+
+        let expected = [
+            (
+                SshKdfLabel::IvClientToServer,
+                16,
+                "1be112665479168c4c6a7ccd6629ae19",
+            ),
+            (
+                SshKdfLabel::IvServerToClient,
+                16,
+                "962ede1118a81130a5b84d8b2368b5c2",
+            ),
+            (
+                SshKdfLabel::EncryptionKeyClientToServer,
+                32,
+                "0c6bd0cada1329f6500b9f136f625d7eed1112421119e15333c74f48f7778691",
+            ),
+            (
+                SshKdfLabel::EncryptionKeyServerToClient,
+                32,
+                "989a808c228fc06c08afddb4affac5ec315ee5361f4de2f4b580c55a508d8bd8",
+            ),
+            (
+                SshKdfLabel::IntegrityKeyClientToServer,
+                32,
+                "e31d04fd7a4052ce9c2fd6ccf670acc344f1b2acc92787c529934f64c3206ec6",
+            ),
+            (
+                SshKdfLabel::IntegrityKeyServerToClient,
+                32,
+                "0a6e8b0aa95a09c4ebb4bf35cd1ccaa7e5bdac47255b9ed2ec43acbaa51f09e3",
+            ),
+        ];
+
+        for (label, length, expected_output) in expected {
+            let output = expanded_key
+                .derive(&hash_value, label, &hash_value, length)
+                .unwrap();
+            assert_eq!(hex::encode(output), expected_output);
+        }
+    }
+
+    #[test]
+    fn test_ssh_kdf_sha256_output_longer_than_hash() {
+        let shared_secret = hex::decode(SHARED_SECRET).unwrap();
+        let hash_value = hex::decode(HASH_VALUE_SHA256).unwrap();
+        let expected = "e31d04fd7a4052ce9c2fd6ccf670acc344f1b2acc92787c529934f64c3206ec629478ca012c4b0d1692a4023a571303f6a23e35af89fe72b0d2427c57eaa92c4";
+
+        let output = ssh_kdf(
+            SshKdfHashAlgorithm::Sha256,
+            &shared_secret,
+            &hash_value,
+            SshKdfLabel::IntegrityKeyClientToServer,
+            &hash_value,
+            64,
+        )
+        .unwrap();
+        assert_eq!(hex::encode(output), expected);
+    }
+
+    #[test]
+    fn test_ssh_kdf_sha384() {
+        let shared_secret = hex::decode(SHARED_SECRET).unwrap();
+        let hash_value = hex::decode(HASH_VALUE_SHA384).unwrap();
+        let expanded_key =
+            SshKdfExpandedKey::new(SshKdfHashAlgorithm::Sha384, &shared_secret).unwrap();
+
+        let iv = expanded_key
+            .derive(&hash_value, SshKdfLabel::IvClientToServer, &hash_value, 16)
+            .unwrap();
+        let encryption_key = expanded_key
+            .derive(
+                &hash_value,
+                SshKdfLabel::EncryptionKeyClientToServer,
+                &hash_value,
+                48,
+            )
+            .unwrap();
+        let integrity_key = expanded_key
+            .derive(
+                &hash_value,
+                SshKdfLabel::IntegrityKeyClientToServer,
+                &hash_value,
+                48,
+            )
+            .unwrap();
+
+        assert_eq!(hex::encode(iv), "8a1b32e4ba700659a430a742151c246d");
+        assert_eq!(hex::encode(encryption_key), "d2405a791b53ebec6f33601af082e45f3a609fecce07eb5d8b2a0ec607c8dfe2360a39e924e6650de19a0e0b3b15714f");
+        assert_eq!(hex::encode(integrity_key), "9fa9e03b2e988aec00a30408bb0fa1457744d8308123c291f6e670acd553f77a5bca7ac2eb8914903cfa0bab22d4568d");
+    }
+
+    // Outputs that are longer than the hash, so the key is extended as defined in RFC 4253 section 7.2.
+    #[test]
+    fn test_ssh_kdf_sha1() {
+        let shared_secret = hex::decode(SHARED_SECRET).unwrap();
+        let hash_value = hex::decode(HASH_VALUE_SHA1).unwrap();
+        let expanded_key =
+            SshKdfExpandedKey::new(SshKdfHashAlgorithm::Sha1, &shared_secret).unwrap();
+
+        let expected = [
+            (
+                SshKdfLabel::IvClientToServer,
+                "b216eb6c67599b0e884a19fe8eda32ec29a2453ffb8d1edaec837b2f9011e09d936b073ca8c35120",
+            ),
+            (
+                SshKdfLabel::EncryptionKeyClientToServer,
+                "818b92517ed35a28584c8afe28a40bedc47de52a66ce7045f5163bb3fe47827def1fd2d29db85e93",
+            ),
+            (
+                SshKdfLabel::IntegrityKeyClientToServer,
+                "3d16f11ffa7f437662014078da6c6af7177a3d871d878d1cdd3b7171d7797ff6c4acd4f804f47bc9",
+            ),
+        ];
+
+        for (label, expected_output) in expected {
+            let output = expanded_key
+                .derive(&hash_value, label, &hash_value, 40)
+                .unwrap();
+            assert_eq!(hex::encode(output), expected_output);
+        }
+    }
+
+    #[test]
+    fn test_ssh_kdf_sha512() {
+        let shared_secret = hex::decode(SHARED_SECRET).unwrap();
+        let hash_value = hex::decode(HASH_VALUE_SHA512).unwrap();
+        let expanded_key =
+            SshKdfExpandedKey::new(SshKdfHashAlgorithm::Sha512, &shared_secret).unwrap();
+
+        let expected = [
+            (
+                SshKdfLabel::IvClientToServer,
+                "47dd42bdcccba1cea9070ab81ce72a139aa9f5f15add20ce88e4f5755f0725b7f046df3f4a47847fff7faba24965bf81a1e00d92dc83edbe1dba8b1516a9f52831fdd9eef6e6499bd3e6171ff7f2291c9dbfeecf2f395a148cf6a3a92f4801c3",
+            ),
+            (
+                SshKdfLabel::EncryptionKeyClientToServer,
+                "0970e42f1359c95e1b0ba3497094f906eaa283fd71c2b3a948e50678f65ccfdb02eed425fa622287fdc4544d1ae7d11fd0e51980fc9a28b45751a5ae41638b6e406211558549a2c4da645a50682b68af8e73b4aaf4b03c44d6406f0ddf59bb19",
+            ),
+            (
+                SshKdfLabel::IntegrityKeyClientToServer,
+                "d0e36803aa4a82a57c78b3c24787846a2176e5879d09045b36f6dadf4c083362fcc573e8aad979d3f27451e7400350fe57ae9abba7d62d6ce020275237ac190a1a554dff13235dd3c40e9113e58c0126af82e07a052ec1ff9adc5920084b0525",
+            ),
+        ];
+
+        for (label, expected_output) in expected {
+            let output = expanded_key
+                .derive(&hash_value, label, &hash_value, 96)
+                .unwrap();
+            assert_eq!(hex::encode(output), expected_output);
+        }
+    }
+
+    #[test]
+    fn test_ssh_kdf_stateless_matches_expanded_key() {
+        let shared_secret = hex::decode(SHARED_SECRET).unwrap();
+        let hash_value = hex::decode(HASH_VALUE_SHA384).unwrap();
+        let expanded_key =
+            SshKdfExpandedKey::new(SshKdfHashAlgorithm::Sha384, &shared_secret).unwrap();
+
+        let stateful = expanded_key
+            .derive(&hash_value, SshKdfLabel::IvServerToClient, &hash_value, 16)
+            .unwrap();
+        let stateless = ssh_kdf(
+            SshKdfHashAlgorithm::Sha384,
+            &shared_secret,
+            &hash_value,
+            SshKdfLabel::IvServerToClient,
+            &hash_value,
+            16,
+        )
+        .unwrap();
+        assert_eq!(stateful, stateless);
+    }
+}
@@ -0,0 +1,674 @@
+//! Streaming encryption with AES-GCM or ChaCha20-Poly1305, using the STREAM construction of Hoang, Reyhanitabar, Rogaway and Vizár
+//! ("Online Authenticated-Encryption and its Nonce-Reuse Misuse-Resistance"), in the style of the age file format.
+//!
+//! [`EncryptingWriter`] splits the plaintext into chunks of a fixed size and seals every chunk with its own nonce.
+//! [`DecryptingReader`] opens the chunks one at a time, so neither side has to hold the whole message in memory.
+//!
+//! The stream starts with a [`STREAM_HEADER_SIZE`] byte header:
+//!
+//! | Offset | Size | Field                                                                     |
+//! |--------|------|---------------------------------------------------------------------------|
+//! | 0      | 4    | Magic, `SCST`                                                             |
+//! | 4      | 1    | Format version, [`STREAM_VERSION`]                                        |
+//! | 5      | 1    | [`StreamAlgorithm`] identifier                                            |
+//! | 6      | 4    | Plaintext chunk size in bytes, big-endian                                 |
+//! | 10     | 7    | Random nonce prefix                                                       |
+//!
+//! The header is followed by the chunks. Every chunk except the last one holds exactly chunk size bytes of plaintext, and the last
+//! chunk holds between 0 and chunk size bytes. Each chunk is the ciphertext followed by its 16 byte tag.
+//!
+//! The 12 byte nonce of chunk `i` is the nonce prefix, followed by `i` as a 4 byte big-endian counter, followed by a flag byte that
+//! is 0x01 for the last chunk and 0x00 otherwise. The whole header is the associated data of every chunk. Reordered or dropped
+//! chunks fail authentication because of the counter, a stream truncated at a chunk boundary fails because the chunk that is read
+//! last was not sealed with the last chunk flag, and a modified header fails every chunk.
+//!
+//! The nonce prefix is random, so a key can be used for several streams, but a fresh key per stream is preferred. A stream holds at
+//! most 2^32 chunks.
+
+use crate::block_ciphers::BlockCipherType;
+use crate::chacha::{chacha20_poly1305_decrypt_in_place, chacha20_poly1305_encrypt_in_place};
+use crate::errors::SymCryptError;
+use crate::gcm::GcmExpandedKey;
+use crate::rng;
+use crate::secret::SecretArray;
+use std::io::{self, Read, Write};
+
+/// Version of the stream format written by [`EncryptingWriter`], and the only version [`DecryptingReader`] accepts.
+pub const STREAM_VERSION: u8 = 1;
+
+/// Size of the stream header in bytes.
+pub const STREAM_HEADER_SIZE: usize = 17;
+
+/// Chunk size used by [`EncryptingWriter::new()`].
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Largest chunk size that can be written or read, this bounds the memory a [`DecryptingReader`] allocates for a chunk.
+pub const MAX_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+const STREAM_MAGIC: &[u8; 4] = b"SCST";
+const NONCE_PREFIX_SIZE: usize = 7;
+const TAG_SIZE: usize = 16;
+
+/// [`StreamAlgorithm`] provides an enum of the AEADs a stream can be encrypted with. The value of each variant is its identifier in
+/// the stream header.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum StreamAlgorithm {
+    Aes128Gcm = 1,
+    Aes256Gcm = 2,
+    ChaCha20Poly1305 = 3,
+}
+
+impl StreamAlgorithm {
+    /// Returns the size of the key in bytes.
+    pub fn key_size(&self) -> usize {
+        match self {
+            StreamAlgorithm::Aes128Gcm => 16,
+            StreamAlgorithm::Aes256Gcm | StreamAlgorithm::ChaCha20Poly1305 => 32,
+        }
+    }
+
+    fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(StreamAlgorithm::Aes128Gcm),
+            2 => Some(StreamAlgorithm::Aes256Gcm),
+            3 => Some(StreamAlgorithm::ChaCha20Poly1305),
+            _ => None,
+        }
+    }
+}
+
+/// [`ChunkCipher`] holds the key of a stream.
+enum ChunkCipher {
+    Gcm(GcmExpandedKey),
+    ChaCha20Poly1305(SecretArray<32>),
+}
+
+impl ChunkCipher {
+    fn new(algorithm: StreamAlgorithm, key: &[u8]) -> io::Result<Self> {
+        if key.len() != algorithm.key_size() {
+            return Err(invalid_input(SymCryptError::WrongKeySize));
+        }
+        match algorithm {
+            StreamAlgorithm::Aes128Gcm | StreamAlgorithm::Aes256Gcm => {
+                let key =
+                    GcmExpandedKey::new(key, BlockCipherType::AesBlock).map_err(invalid_input)?;
+                Ok(ChunkCipher::Gcm(key))
+            }
+            StreamAlgorithm::ChaCha20Poly1305 => {
+                // The key size was checked above.
+                Ok(ChunkCipher::ChaCha20Poly1305(SecretArray::new(
+                    key.try_into().unwrap(),
+                )))
+            }
+        }
+    }
+
+    fn seal(
+        &self,
+        nonce: &[u8; 12],
+        header: &[u8],
+        buffer: &mut [u8],
+    ) -> io::Result<[u8; TAG_SIZE]> {
+        let mut tag = [0u8; TAG_SIZE];
+        match self {
+            ChunkCipher::Gcm(key) => key.encrypt_in_place(nonce, header, buffer, &mut tag),
+            ChunkCipher::ChaCha20Poly1305(key) => {
+                chacha20_poly1305_encrypt_in_place(key.as_bytes(), nonce, header, buffer, &mut tag)
+                    .map_err(invalid_input)?
+            }
+        }
+        Ok(tag)
+    }
+
+    fn open(
+        &self,
+        nonce: &[u8; 12],
+        header: &[u8],
+        buffer: &mut [u8],
+        tag: &[u8; TAG_SIZE],
+    ) -> io::Result<()> {
+        match self {
+            ChunkCipher::Gcm(key) => key.decrypt_in_place(nonce, header, buffer, tag),
+            ChunkCipher::ChaCha20Poly1305(key) => {
+                chacha20_poly1305_decrypt_in_place(key.as_bytes(), nonce, header, buffer, tag)
+            }
+        }
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+/// chunk_nonce returns the nonce of chunk `counter`, see the module documentation.
+fn chunk_nonce(prefix: &[u8; NONCE_PREFIX_SIZE], counter: u32, last: bool) -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    nonce[..NONCE_PREFIX_SIZE].copy_from_slice(prefix);
+    nonce[NONCE_PREFIX_SIZE..11].copy_from_slice(&counter.to_be_bytes());
+    nonce[11] = last as u8;
+    nonce
+}
+
+fn invalid_input(err: SymCryptError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, err)
+}
+
+fn check_chunk_size(chunk_size: usize) -> bool {
+    (1..=MAX_CHUNK_SIZE).contains(&chunk_size)
+}
+
+/// [`EncryptingWriter`] encrypts everything written to it and writes the stream to the inner writer.
+///
+/// A chunk is only written once it is known whether it is the last one, so up to one chunk of plaintext is buffered. [`finish()`]
+/// must be called once all the data has been written, it seals the last chunk. A stream that is dropped without calling [`finish()`]
+/// is incomplete and will be rejected by [`DecryptingReader`].
+///
+/// If a chunk can not be written, part of it may already be in the inner writer, so the stream can not be continued. The writer is
+/// poisoned and every later `write()` and [`finish()`] fails.
+pub struct EncryptingWriter<W: Write> {
+    writer: W,
+    cipher: ChunkCipher,
+    header: [u8; STREAM_HEADER_SIZE],
+    nonce_prefix: [u8; NONCE_PREFIX_SIZE],
+    counter: u32,
+    chunk_size: usize,
+    buffer: Vec<u8>,
+    poisoned: bool,
+}
+
+/// Impl for [`EncryptingWriter`]
+///
+/// [`new()`] writes the header for `algorithm` with [`DEFAULT_CHUNK_SIZE`] to `writer`. [`with_chunk_size()`] uses the provided
+/// chunk size instead, which must be between 1 and [`MAX_CHUNK_SIZE`]. Both fail with `InvalidInput` if the key does not have the size
+/// of `algorithm`.
+///
+/// [`finish()`] seals and writes the last chunk, flushes the inner writer and returns it.
+impl<W: Write> EncryptingWriter<W> {
+    pub fn new(writer: W, algorithm: StreamAlgorithm, key: &[u8]) -> io::Result<Self> {
+        EncryptingWriter::with_chunk_size(writer, algorithm, key, DEFAULT_CHUNK_SIZE)
+    }
+
+    pub fn with_chunk_size(
+        mut writer: W,
+        algorithm: StreamAlgorithm,
+        key: &[u8],
+        chunk_size: usize,
+    ) -> io::Result<Self> {
+        if !check_chunk_size(chunk_size) {
+            return Err(invalid_input(SymCryptError::InvalidArgument));
+        }
+        let cipher = ChunkCipher::new(algorithm, key)?;
+
+        let mut nonce_prefix = [0u8; NONCE_PREFIX_SIZE];
+        rng::fill_bytes(&mut nonce_prefix);
+
+        let mut header = [0u8; STREAM_HEADER_SIZE];
+        header[..4].copy_from_slice(STREAM_MAGIC);
+        header[4] = STREAM_VERSION;
+        header[5] = algorithm as u8;
+        header[6..10].copy_from_slice(&(chunk_size as u32).to_be_bytes());
+        header[10..].copy_from_slice(&nonce_prefix);
+        writer.write_all(&header)?;
+
+        Ok(EncryptingWriter {
+            writer,
+            cipher,
+            header,
+            nonce_prefix,
+            counter: 0,
+            chunk_size,
+            buffer: Vec::with_capacity(chunk_size + TAG_SIZE),
+            poisoned: false,
+        })
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_chunk(true)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn check_poisoned(&self) -> io::Result<()> {
+        if self.poisoned {
+            return Err(io::Error::other(
+                "stream is incomplete after an earlier error",
+            ));
+        }
+        Ok(())
+    }
+
+    fn write_chunk(&mut self, last: bool) -> io::Result<()> {
+        self.check_poisoned()?;
+        let result = self.seal_and_write_chunk(last);
+        self.poisoned = result.is_err();
+        result
+    }
+
+    fn seal_and_write_chunk(&mut self, last: bool) -> io::Result<()> {
+        // The last chunk may use the final counter value, other chunks need a counter left for the chunks after them.
+        if !last && self.counter == u32::MAX {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "stream has too many chunks",
+            ));
+        }
+        let nonce = chunk_nonce(&self.nonce_prefix, self.counter, last);
+        let tag = self.cipher.seal(&nonce, &self.header, &mut self.buffer)?;
+        self.buffer.extend_from_slice(&tag);
+        self.writer.write_all(&self.buffer)?;
+        self.buffer.clear();
+        self.counter = self.counter.wrapping_add(1);
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for EncryptingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.check_poisoned()?;
+        let mut remaining = buf;
+        while !remaining.is_empty() {
+            // A full chunk is only sealed once more data arrives, since it could still be the last chunk.
+            if self.buffer.len() == self.chunk_size {
+                self.write_chunk(false)?;
+            }
+            let take = remaining.len().min(self.chunk_size - self.buffer.len());
+            self.buffer.extend_from_slice(&remaining[..take]);
+            remaining = &remaining[take..];
+        }
+        Ok(buf.len())
+    }
+
+    /// Flushes the inner writer. Buffered plaintext is not written until its chunk is complete or [`EncryptingWriter::finish()`] is
+    /// called.
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// [`DecryptingReader`] reads a stream written by [`EncryptingWriter`] from the inner reader and returns the plaintext.
+///
+/// Plaintext is only returned once the chunk that holds it has been authenticated. Errors with `InvalidData` are returned if a chunk
+/// fails authentication, or if the stream is truncated or reordered. An error can be returned after the plaintext of earlier chunks,
+/// so callers must not act on the data before the reader has returned end of file.
+pub struct DecryptingReader<R: Read> {
+    reader: R,
+    cipher: ChunkCipher,
+    header: [u8; STREAM_HEADER_SIZE],
+    nonce_prefix: [u8; NONCE_PREFIX_SIZE],
+    counter: u32,
+    chunk_size: usize,
+    // Ciphertext read ahead of the current chunk, at most one chunk and one byte.
+    ciphertext: Vec<u8>,
+    plaintext: Vec<u8>,
+    position: usize,
+    finished: bool,
+}
+
+/// Impl for [`DecryptingReader`]
+///
+/// [`new()`] reads and checks the header from `reader`. It fails with `InvalidData` if the header is not a version
+/// [`STREAM_VERSION`] header for `algorithm`, and with `InvalidInput` if the key does not have the size of `algorithm`.
+///
+/// [`into_inner()`] returns the inner reader.
+impl<R: Read> DecryptingReader<R> {
+    pub fn new(mut reader: R, algorithm: StreamAlgorithm, key: &[u8]) -> io::Result<Self> {
+        let cipher = ChunkCipher::new(algorithm, key)?;
+
+        let mut header = [0u8; STREAM_HEADER_SIZE];
+        reader.read_exact(&mut header)?;
+        if &header[..4] != STREAM_MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not an encrypted stream",
+            ));
+        }
+        if header[4] != STREAM_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "unsupported stream version",
+            ));
+        }
+        if StreamAlgorithm::from_id(header[5]) != Some(algorithm) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream algorithm does not match",
+            ));
+        }
+        let chunk_size = u32::from_be_bytes(header[6..10].try_into().unwrap()) as usize;
+        if !check_chunk_size(chunk_size) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid stream chunk size",
+            ));
+        }
+        let mut nonce_prefix = [0u8; NONCE_PREFIX_SIZE];
+        nonce_prefix.copy_from_slice(&header[10..]);
+
+        Ok(DecryptingReader {
+            reader,
+            cipher,
+            header,
+            nonce_prefix,
+            counter: 0,
+            chunk_size,
+            ciphertext: Vec::with_capacity(chunk_size + TAG_SIZE + 1),
+            plaintext: Vec::with_capacity(chunk_size),
+            position: 0,
+            finished: false,
+        })
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    // Reads until there is more than one sealed chunk of ciphertext, or until end of file. Returns false at end of file.
+    fn fill_ciphertext(&mut self) -> io::Result<bool> {
+        let wanted = self.chunk_size + TAG_SIZE + 1;
+        let mut buffer = [0u8; 8192];
+        while self.ciphertext.len() < wanted {
+            let read_size = buffer.len().min(wanted - self.ciphertext.len());
+            match self.reader.read(&mut buffer[..read_size]) {
+                Ok(0) => return Ok(false),
+                Ok(read) => self.ciphertext.extend_from_slice(&buffer[..read]),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(true)
+    }
+
+    fn read_chunk(&mut self) -> io::Result<()> {
+        // The chunk is the last one if the stream ends before the start of the next chunk.
+        let last = !self.fill_ciphertext()?;
+        let sealed_len = if last {
+            self.ciphertext.len()
+        } else {
+            self.chunk_size + TAG_SIZE
+        };
+        if sealed_len < TAG_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "truncated stream",
+            ));
+        }
+
+        let plaintext_len = sealed_len - TAG_SIZE;
+        let mut tag = [0u8; TAG_SIZE];
+        tag.copy_from_slice(&self.ciphertext[plaintext_len..sealed_len]);
+        self.plaintext.clear();
+        self.plaintext
+            .extend_from_slice(&self.ciphertext[..plaintext_len]);
+        self.ciphertext.drain(..sealed_len);
+
+        if !last && self.counter == u32::MAX {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream has too many chunks",
+            ));
+        }
+        let nonce = chunk_nonce(&self.nonce_prefix, self.counter, last);
+        if let Err(err) = self
+            .cipher
+            .open(&nonce, &self.header, &mut self.plaintext, &tag)
+        {
+            self.plaintext.clear();
+            return Err(err);
+        }
+        self.position = 0;
+        self.finished = last;
+        self.counter = self.counter.wrapping_add(1);
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DecryptingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position == self.plaintext.len() {
+            if self.finished || buf.is_empty() {
+                return Ok(0);
+            }
+            self.read_chunk()?;
+        }
+        let read = buf.len().min(self.plaintext.len() - self.position);
+        buf[..read].copy_from_slice(&self.plaintext[self.position..self.position + read]);
+        self.position += read;
+        Ok(read)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ALGORITHMS: [StreamAlgorithm; 3] = [
+        StreamAlgorithm::Aes128Gcm,
+        StreamAlgorithm::Aes256Gcm,
+        StreamAlgorithm::ChaCha20Poly1305,
+    ];
+
+    fn test_key(algorithm: StreamAlgorithm) -> Vec<u8> {
+        (0..algorithm.key_size()).map(|i| i as u8).collect()
+    }
+
+    fn encrypt(algorithm: StreamAlgorithm, chunk_size: usize, plaintext: &[u8]) -> Vec<u8> {
+        let key = test_key(algorithm);
+        let mut writer =
+            EncryptingWriter::with_chunk_size(Vec::new(), algorithm, &key, chunk_size).unwrap();
+        // Write in uneven pieces so chunk boundaries do not line up with writes.
+        for piece in plaintext.chunks(7) {
+            writer.write_all(piece).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    fn decrypt(algorithm: StreamAlgorithm, stream: &[u8]) -> io::Result<Vec<u8>> {
+        let key = test_key(algorithm);
+        let mut reader = DecryptingReader::new(stream, algorithm, &key)?;
+        let mut plaintext = Vec::new();
+        reader.read_to_end(&mut plaintext)?;
+        Ok(plaintext)
+    }
+
+    #[test]
+    fn test_stream_round_trip() {
+        for algorithm in ALGORITHMS {
+            for length in [0, 1, 15, 16, 17, 32, 33, 1000] {
+                let plaintext: Vec<u8> = (0..length).map(|i| (i * 3) as u8).collect();
+                let stream = encrypt(algorithm, 16, &plaintext);
+
+                let chunks = length / 16 + 1 - usize::from(length > 0 && length % 16 == 0);
+                assert_eq!(
+                    stream.len(),
+                    STREAM_HEADER_SIZE + length + chunks * TAG_SIZE
+                );
+                assert_eq!(&stream[..4], b"SCST");
+                assert_eq!(stream[4], STREAM_VERSION);
+                assert_eq!(stream[5], algorithm as u8);
+                assert_eq!(&stream[6..10], &16u32.to_be_bytes());
+
+                assert_eq!(decrypt(algorithm, &stream).unwrap(), plaintext);
+            }
+        }
+    }
+
+    #[test]
+    fn test_stream_default_chunk_size() {
+        let key = test_key(StreamAlgorithm::Aes256Gcm);
+        let plaintext = vec![0x42u8; DEFAULT_CHUNK_SIZE * 2 + 5];
+
+        let mut writer =
+            EncryptingWriter::new(Vec::new(), StreamAlgorithm::Aes256Gcm, &key).unwrap();
+        io::copy(&mut plaintext.as_slice(), &mut writer).unwrap();
+        let stream = writer.finish().unwrap();
+        assert_eq!(
+            stream.len(),
+            STREAM_HEADER_SIZE + plaintext.len() + 3 * TAG_SIZE
+        );
+
+        let mut reader =
+            DecryptingReader::new(stream.as_slice(), StreamAlgorithm::Aes256Gcm, &key).unwrap();
+        let mut decrypted = Vec::new();
+        io::copy(&mut reader, &mut decrypted).unwrap();
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn test_stream_nonce_prefix_is_random() {
+        let first = encrypt(StreamAlgorithm::ChaCha20Poly1305, 16, b"same plaintext");
+        let second = encrypt(StreamAlgorithm::ChaCha20Poly1305, 16, b"same plaintext");
+        assert_ne!(
+            first[10..STREAM_HEADER_SIZE],
+            second[10..STREAM_HEADER_SIZE]
+        );
+        assert_ne!(first[STREAM_HEADER_SIZE..], second[STREAM_HEADER_SIZE..]);
+    }
+
+    #[test]
+    fn test_stream_truncation() {
+        let plaintext = vec![0x11u8; 48];
+        for algorithm in ALGORITHMS {
+            let stream = encrypt(algorithm, 16, &plaintext);
+            let chunk = 16 + TAG_SIZE;
+
+            // Dropping whole chunks leaves a stream without a last chunk.
+            for chunks in 0..3 {
+                let truncated = &stream[..STREAM_HEADER_SIZE + chunks * chunk];
+                assert_eq!(
+                    decrypt(algorithm, truncated).unwrap_err().kind(),
+                    io::ErrorKind::InvalidData
+                );
+            }
+            assert!(decrypt(algorithm, &stream[..stream.len() - 1]).is_err());
+            assert!(decrypt(algorithm, &stream[..STREAM_HEADER_SIZE - 1]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_stream_reordering_and_tampering() {
+        let plaintext: Vec<u8> = (0..48).collect();
+        let stream = encrypt(StreamAlgorithm::Aes128Gcm, 16, &plaintext);
+        let chunk = 16 + TAG_SIZE;
+
+        let mut swapped = stream.clone();
+        let (first, second) =
+            swapped[STREAM_HEADER_SIZE..STREAM_HEADER_SIZE + 2 * chunk].split_at_mut(chunk);
+        first.swap_with_slice(second);
+        assert!(decrypt(StreamAlgorithm::Aes128Gcm, &swapped).is_err());
+
+        let mut tampered = stream.clone();
+        tampered[STREAM_HEADER_SIZE + 20] ^= 1;
+        assert!(decrypt(StreamAlgorithm::Aes128Gcm, &tampered).is_err());
+
+        // The nonce prefix is authenticated through the header.
+        let mut tampered_header = stream.clone();
+        tampered_header[12] ^= 1;
+        assert!(decrypt(StreamAlgorithm::Aes128Gcm, &tampered_header).is_err());
+
+        let mut extended = stream.clone();
+        extended.push(0);
+        assert!(decrypt(StreamAlgorithm::Aes128Gcm, &extended).is_err());
+    }
+
+    #[test]
+    fn test_stream_header_checks() {
+        let stream = encrypt(StreamAlgorithm::Aes256Gcm, 16, b"header checks");
+        let key = test_key(StreamAlgorithm::Aes256Gcm);
+
+        // A stream must be read with the algorithm it was written with.
+        assert!(
+            DecryptingReader::new(stream.as_slice(), StreamAlgorithm::ChaCha20Poly1305, &key)
+                .is_err()
+        );
+
+        let mut wrong_version = stream.clone();
+        wrong_version[4] = 2;
+        assert!(
+            DecryptingReader::new(wrong_version.as_slice(), StreamAlgorithm::Aes256Gcm, &key)
+                .is_err()
+        );
+
+        let mut wrong_magic = stream.clone();
+        wrong_magic[0] = b'X';
+        assert!(
+            DecryptingReader::new(wrong_magic.as_slice(), StreamAlgorithm::Aes256Gcm, &key)
+                .is_err()
+        );
+
+        let mut huge_chunks = stream.clone();
+        huge_chunks[6..10].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(
+            DecryptingReader::new(huge_chunks.as_slice(), StreamAlgorithm::Aes256Gcm, &key)
+                .is_err()
+        );
+    }
+
+    // FailOnceWriter accepts a few bytes of the write after the header and then fails it.
+    #[derive(Debug)]
+    struct FailOnceWriter {
+        output: Vec<u8>,
+        fail: bool,
+    }
+
+    impl Write for FailOnceWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.output.len() > STREAM_HEADER_SIZE && self.fail {
+                self.fail = false;
+                return Err(io::Error::new(io::ErrorKind::WouldBlock, "fail once"));
+            }
+            let written = if self.output.len() == STREAM_HEADER_SIZE {
+                buf.len().min(5)
+            } else {
+                buf.len()
+            };
+            self.output.extend_from_slice(&buf[..written]);
+            Ok(written)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_stream_poisoned_after_failed_write() {
+        let key = test_key(StreamAlgorithm::Aes128Gcm);
+        let inner = FailOnceWriter {
+            output: Vec::new(),
+            fail: true,
+        };
+        let mut writer =
+            EncryptingWriter::with_chunk_size(inner, StreamAlgorithm::Aes128Gcm, &key, 16).unwrap();
+
+        // The first chunk is sealed once the 17th byte arrives, and the inner writer fails after part of it was written.
+        writer.write_all(&[0x55u8; 16]).unwrap();
+        let err = writer.write(&[0x55u8]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+        assert_eq!(writer.writer.output.len(), STREAM_HEADER_SIZE + 5);
+
+        // The inner writer works again, but the stream can not be continued.
+        assert_eq!(
+            writer.write(&[0x55u8]).unwrap_err().kind(),
+            io::ErrorKind::Other
+        );
+        assert_eq!(writer.write(&[]).unwrap_err().kind(), io::ErrorKind::Other);
+        assert_eq!(writer.finish().unwrap_err().kind(), io::ErrorKind::Other);
+    }
+
+    #[test]
+    fn test_stream_invalid_parameters() {
+        let key = test_key(StreamAlgorithm::Aes128Gcm);
+        let err = EncryptingWriter::new(Vec::new(), StreamAlgorithm::Aes256Gcm, &key)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(
+            EncryptingWriter::with_chunk_size(Vec::new(), StreamAlgorithm::Aes128Gcm, &key, 0)
+                .is_err()
+        );
+        assert!(EncryptingWriter::with_chunk_size(
+            Vec::new(),
+            StreamAlgorithm::Aes128Gcm,
+            &key,
+            MAX_CHUNK_SIZE + 1
+        )
+        .is_err());
+    }
+}
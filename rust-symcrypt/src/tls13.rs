@@ -0,0 +1,524 @@
+//! TLS 1.3 key schedule as defined in RFC 8446 section 7. The key schedule is built on the HKDF functions from hkdf.rs.
+//!
+//! The secrets follow the ladder of the RFC. An [`EarlySecret`] is turned into a [`HandshakeSecret`] once the (EC)DHE shared
+//! secret is known, and the [`HandshakeSecret`] is turned into a [`MasterSecret`] once the handshake traffic keys are no longer needed.
+//! Each stage is consumed by the next one, so a secret can not be re-used by mistake once the handshake has moved on.
+
+use crate::errors::SymCryptError;
+use crate::hash::{HashAlgorithm, HashState, Sha256State, Sha384State};
+use crate::hkdf::{hkdf_expand, hkdf_extract};
+use crate::hmac::{hmac_sha256, hmac_sha384, HmacAlgorithm};
+use crate::secret::SecretBytes;
+
+/// The nonce length used by every TLS 1.3 AEAD.
+pub const TLS13_IV_SIZE: usize = 12;
+
+/// convert_hash takes in a HashAlgorithm and returns the Hmac algorithm that TLS 1.3 pairs with it.
+fn convert_hash(hash: HashAlgorithm) -> HmacAlgorithm {
+    match hash {
+        HashAlgorithm::Sha256 => HmacAlgorithm::HmacSha256,
+        HashAlgorithm::Sha384 => HmacAlgorithm::HmacSha384,
+    }
+}
+
+/// HKDF-Expand-Label as defined in RFC 8446 section 7.1. The "tls13 " prefix is added to `label` by this function.
+pub fn hkdf_expand_label(
+    hash: HashAlgorithm,
+    secret: &[u8],
+    label: &[u8],
+    context: &[u8],
+    length: u16,
+) -> Result<SecretBytes, SymCryptError> {
+    const LABEL_PREFIX: &[u8] = b"tls13 ";
+    if LABEL_PREFIX.len() + label.len() > 255 || context.len() > 255 {
+        return Err(SymCryptError::InvalidArgument);
+    }
+
+    // struct { uint16 length; opaque label<7..255>; opaque context<0..255>; } HkdfLabel;
+    let mut hkdf_label = Vec::with_capacity(4 + LABEL_PREFIX.len() + label.len() + context.len());
+    hkdf_label.extend_from_slice(&length.to_be_bytes());
+    hkdf_label.push((LABEL_PREFIX.len() + label.len()) as u8);
+    hkdf_label.extend_from_slice(LABEL_PREFIX);
+    hkdf_label.extend_from_slice(label);
+    hkdf_label.push(context.len() as u8);
+    hkdf_label.extend_from_slice(context);
+
+    hkdf_expand(convert_hash(hash), secret, &hkdf_label, length as usize)
+}
+
+/// Derive-Secret as defined in RFC 8446 section 7.1. `transcript_hash` is the hash of the handshake messages, which can be obtained
+/// from [`TranscriptHash::current_hash()`].
+pub fn derive_secret(
+    hash: HashAlgorithm,
+    secret: &[u8],
+    label: &[u8],
+    transcript_hash: &[u8],
+) -> Result<SecretBytes, SymCryptError> {
+    hkdf_expand_label(
+        hash,
+        secret,
+        label,
+        transcript_hash,
+        hash.result_size() as u16,
+    )
+}
+
+/// [`TranscriptHash`] is a running hash over the handshake messages.
+///
+/// [`current_hash()`] clones the inner hash state before calling result() so the transcript can keep being appended to after a
+/// Derive-Secret call.
+#[derive(Clone)]
+pub enum TranscriptHash {
+    Sha256(Sha256State),
+    Sha384(Sha384State),
+}
+
+impl TranscriptHash {
+    pub fn new(hash: HashAlgorithm) -> Self {
+        match hash {
+            HashAlgorithm::Sha256 => TranscriptHash::Sha256(Sha256State::new()),
+            HashAlgorithm::Sha384 => TranscriptHash::Sha384(Sha384State::new()),
+        }
+    }
+
+    pub fn append(&mut self, message: &[u8]) {
+        match self {
+            TranscriptHash::Sha256(state) => state.append(message),
+            TranscriptHash::Sha384(state) => state.append(message),
+        }
+    }
+
+    pub fn current_hash(&self) -> Vec<u8> {
+        match self {
+            TranscriptHash::Sha256(state) => state.clone().result().to_vec(),
+            TranscriptHash::Sha384(state) => state.clone().result().to_vec(),
+        }
+    }
+
+    pub fn hash_algorithm(&self) -> HashAlgorithm {
+        match self {
+            TranscriptHash::Sha256(_) => HashAlgorithm::Sha256,
+            TranscriptHash::Sha384(_) => HashAlgorithm::Sha384,
+        }
+    }
+}
+
+/// [`TrafficKeys`] holds the AEAD key and IV derived from a traffic secret. Both are wiped on drop.
+pub struct TrafficKeys {
+    key: SecretBytes,
+    iv: SecretBytes,
+}
+
+impl TrafficKeys {
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    pub fn iv(&self) -> &[u8; TLS13_IV_SIZE] {
+        self.iv.as_bytes().try_into().unwrap() // iv is always derived with TLS13_IV_SIZE bytes
+    }
+}
+
+/// Derives the AEAD key and IV for a traffic secret. `key_length` is 16 for AES-128-GCM and 32 for AES-256-GCM and ChaCha20-Poly1305.
+pub fn traffic_keys(
+    hash: HashAlgorithm,
+    traffic_secret: &[u8],
+    key_length: u16,
+) -> Result<TrafficKeys, SymCryptError> {
+    let key = hkdf_expand_label(hash, traffic_secret, b"key", &[], key_length)?;
+    let iv = hkdf_expand_label(hash, traffic_secret, b"iv", &[], TLS13_IV_SIZE as u16)?;
+    Ok(TrafficKeys { key, iv })
+}
+
+/// Derives application_traffic_secret_N+1 from application_traffic_secret_N, as done on a KeyUpdate.
+pub fn next_traffic_secret(
+    hash: HashAlgorithm,
+    traffic_secret: &[u8],
+) -> Result<SecretBytes, SymCryptError> {
+    hkdf_expand_label(
+        hash,
+        traffic_secret,
+        b"traffic upd",
+        &[],
+        hash.result_size() as u16,
+    )
+}
+
+/// Derives the finished_key from a handshake traffic secret or a binder key.
+pub fn finished_key(hash: HashAlgorithm, base_key: &[u8]) -> Result<SecretBytes, SymCryptError> {
+    hkdf_expand_label(hash, base_key, b"finished", &[], hash.result_size() as u16)
+}
+
+/// Computes the verify_data of a Finished message, which is the Hmac of the `transcript_hash` keyed with the finished_key of `base_key`.
+pub fn finished_verify_data(
+    hash: HashAlgorithm,
+    base_key: &[u8],
+    transcript_hash: &[u8],
+) -> Result<Vec<u8>, SymCryptError> {
+    let finished_key = finished_key(hash, base_key)?;
+    match hash {
+        HashAlgorithm::Sha256 => hmac_sha256(&finished_key, transcript_hash).map(|r| r.to_vec()),
+        HashAlgorithm::Sha384 => hmac_sha384(&finished_key, transcript_hash).map(|r| r.to_vec()),
+    }
+}
+
+/// Derives the pre-shared key for a NewSessionTicket from the resumption_master_secret and the ticket_nonce.
+pub fn resumption_psk(
+    hash: HashAlgorithm,
+    resumption_master_secret: &[u8],
+    ticket_nonce: &[u8],
+) -> Result<SecretBytes, SymCryptError> {
+    hkdf_expand_label(
+        hash,
+        resumption_master_secret,
+        b"resumption",
+        ticket_nonce,
+        hash.result_size() as u16,
+    )
+}
+
+/// Computes Derive-Secret(secret, "derived", "") followed by HKDF-Extract, which moves the key schedule to its next stage.
+fn next_stage(
+    hash: HashAlgorithm,
+    secret: &[u8],
+    key_material: Option<&[u8]>,
+) -> Result<SecretBytes, SymCryptError> {
+    let empty_hash = TranscriptHash::new(hash).current_hash();
+    let derived = derive_secret(hash, secret, b"derived", &empty_hash)?;
+    let zeros = vec![0u8; hash.result_size()];
+    hkdf_extract(convert_hash(hash), key_material.unwrap_or(&zeros), &derived)
+}
+
+/// [`EarlySecret`] is the first stage of the key schedule, derived from an optional PSK.
+pub struct EarlySecret {
+    hash: HashAlgorithm,
+    secret: SecretBytes,
+}
+
+/// Impl for [`EarlySecret`]
+///
+/// [`new()`] takes in the [`HashAlgorithm`] of the cipher suite and an optional PSK. When no PSK is used the early secret is derived
+/// from a string of zeros, as defined in RFC 8446.
+///
+/// [`binder_key()`], [`client_early_traffic_secret()`] and [`early_exporter_master_secret()`] derive the secrets of this stage.
+///
+/// [`into_handshake_secret()`] consumes the [`EarlySecret`] and returns the [`HandshakeSecret`]. `shared_secret` is the (EC)DHE shared
+/// secret, use None for PSK-only handshakes.
+impl EarlySecret {
+    pub fn new(hash: HashAlgorithm, psk: Option<&[u8]>) -> Result<Self, SymCryptError> {
+        let zeros = vec![0u8; hash.result_size()];
+        let secret = hkdf_extract(convert_hash(hash), psk.unwrap_or(&zeros), &[])?;
+        Ok(EarlySecret { hash, secret })
+    }
+
+    pub fn binder_key(&self, resumption: bool) -> Result<SecretBytes, SymCryptError> {
+        let label: &[u8] = if resumption {
+            b"res binder"
+        } else {
+            b"ext binder"
+        };
+        let empty_hash = TranscriptHash::new(self.hash).current_hash();
+        derive_secret(self.hash, &self.secret, label, &empty_hash)
+    }
+
+    pub fn client_early_traffic_secret(
+        &self,
+        transcript_hash: &[u8],
+    ) -> Result<SecretBytes, SymCryptError> {
+        derive_secret(self.hash, &self.secret, b"c e traffic", transcript_hash)
+    }
+
+    pub fn early_exporter_master_secret(
+        &self,
+        transcript_hash: &[u8],
+    ) -> Result<SecretBytes, SymCryptError> {
+        derive_secret(self.hash, &self.secret, b"e exp master", transcript_hash)
+    }
+
+    pub fn into_handshake_secret(
+        self,
+        shared_secret: Option<&[u8]>,
+    ) -> Result<HandshakeSecret, SymCryptError> {
+        let secret = next_stage(self.hash, &self.secret, shared_secret)?;
+        Ok(HandshakeSecret {
+            hash: self.hash,
+            secret,
+        })
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.secret
+    }
+}
+
+/// [`HandshakeSecret`] is the second stage of the key schedule, derived from the [`EarlySecret`] and the (EC)DHE shared secret.
+///
+/// [`into_master_secret()`] consumes the [`HandshakeSecret`] and returns the [`MasterSecret`].
+pub struct HandshakeSecret {
+    hash: HashAlgorithm,
+    secret: SecretBytes,
+}
+
+impl HandshakeSecret {
+    pub fn client_handshake_traffic_secret(
+        &self,
+        transcript_hash: &[u8],
+    ) -> Result<SecretBytes, SymCryptError> {
+        derive_secret(self.hash, &self.secret, b"c hs traffic", transcript_hash)
+    }
+
+    pub fn server_handshake_traffic_secret(
+        &self,
+        transcript_hash: &[u8],
+    ) -> Result<SecretBytes, SymCryptError> {
+        derive_secret(self.hash, &self.secret, b"s hs traffic", transcript_hash)
+    }
+
+    pub fn into_master_secret(self) -> Result<MasterSecret, SymCryptError> {
+        let secret = next_stage(self.hash, &self.secret, None)?;
+        Ok(MasterSecret {
+            hash: self.hash,
+            secret,
+        })
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.secret
+    }
+}
+
+/// [`MasterSecret`] is the last stage of the key schedule, the application traffic, exporter and resumption secrets are derived from it.
+pub struct MasterSecret {
+    hash: HashAlgorithm,
+    secret: SecretBytes,
+}
+
+impl MasterSecret {
+    pub fn client_application_traffic_secret(
+        &self,
+        transcript_hash: &[u8],
+    ) -> Result<SecretBytes, SymCryptError> {
+        derive_secret(self.hash, &self.secret, b"c ap traffic", transcript_hash)
+    }
+
+    pub fn server_application_traffic_secret(
+        &self,
+        transcript_hash: &[u8],
+    ) -> Result<SecretBytes, SymCryptError> {
+        derive_secret(self.hash, &self.secret, b"s ap traffic", transcript_hash)
+    }
+
+    pub fn exporter_master_secret(
+        &self,
+        transcript_hash: &[u8],
+    ) -> Result<SecretBytes, SymCryptError> {
+        derive_secret(self.hash, &self.secret, b"exp master", transcript_hash)
+    }
+
+    pub fn resumption_master_secret(
+        &self,
+        transcript_hash: &[u8],
+    ) -> Result<SecretBytes, SymCryptError> {
+        derive_secret(self.hash, &self.secret, b"res master", transcript_hash)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.secret
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // ClientHello and ServerHello from the "Simple 1-RTT Handshake" trace of RFC 8448 section 3.
+    const CLIENT_HELLO: &str = "010000c00303cb34ecb1e78163ba1c38c6dacb196a6dffa21a8d9912ec18a2ef6283024dece7000006130113031302010000910000000b0009000006736572766572ff01000100000a00140012001d0017001800190100010101020103010400230000003300260024001d002099381de560e4bd43d23d8e435a7dbafeb3c06e51c13cae4d5413691e529aaf2c002b0003020304000d0020001e040305030603020308040805080604010501060102010402050206020202002d00020101001c00024001";
+    const SERVER_HELLO: &str = "020000560303a6af06a4121860dc5e6e60249cd34c95930c8ac5cb1434dac155772ed3e2692800130100002e00330024001d0020c9828876112095fe66762bdbf7c672e156d6cc253b833df1dd69b1b04e751f0f002b00020304";
+    const ECDHE_SHARED_SECRET: &str =
+        "8bd4054fb55b9d63fdfbacf9f04b9f0d35e6d63f537563efd46272900f89492d";
+
+    #[test]
+    fn test_tls13_rfc8448_handshake_secrets() {
+        let mut transcript = TranscriptHash::new(HashAlgorithm::Sha256);
+        transcript.append(&hex::decode(CLIENT_HELLO).unwrap());
+        transcript.append(&hex::decode(SERVER_HELLO).unwrap());
+        let transcript_hash = transcript.current_hash();
+        assert_eq!(
+            hex::encode(&transcript_hash),
+            "860c06edc07858ee8e78f0e7428c58edd6b43f2ca3e6e95f02ed063cf0e1cad8"
+        );
+
+        let early_secret = EarlySecret::new(HashAlgorithm::Sha256, None).unwrap();
+        assert_eq!(
+            hex::encode(early_secret.as_bytes()),
+            "33ad0a1c607ec03b09e6cd9893680ce210adf300aa1f2660e1b22e10f170f92a"
+        );
+
+        let shared_secret = hex::decode(ECDHE_SHARED_SECRET).unwrap();
+        let handshake_secret = early_secret
+            .into_handshake_secret(Some(&shared_secret))
+            .unwrap();
+        assert_eq!(
+            hex::encode(handshake_secret.as_bytes()),
+            "1dc826e93606aa6fdc0aadc12f741b01046aa6b99f691ed221a9f0ca043fbeac"
+        );
+
+        let client_secret = handshake_secret
+            .client_handshake_traffic_secret(&transcript_hash)
+            .unwrap();
+        let server_secret = handshake_secret
+            .server_handshake_traffic_secret(&transcript_hash)
+            .unwrap();
+        assert_eq!(
+            hex::encode(&client_secret),
+            "b3eddb126e067f35a780b3abf45e2d8f3b1a950738f52e9600746a0e27a55a21"
+        );
+        assert_eq!(
+            hex::encode(&server_secret),
+            "b67b7d690cc16c4e75e54213cb2d37b4e9c912bcded9105d42befd59d391ad38"
+        );
+
+        let server_keys = traffic_keys(HashAlgorithm::Sha256, &server_secret, 16).unwrap();
+        assert_eq!(
+            hex::encode(server_keys.key()),
+            "3fce516009c21727d0f2e4e86ee403bc"
+        );
+        assert_eq!(hex::encode(server_keys.iv()), "5d313eb2671276ee13000b30");
+
+        let server_finished_key = finished_key(HashAlgorithm::Sha256, &server_secret).unwrap();
+        assert_eq!(
+            hex::encode(server_finished_key),
+            "008d3b66f816ea559f96b537e885c31fc068bf492c652f01f288a1d8cdc19fc8"
+        );
+
+        let master_secret = handshake_secret.into_master_secret().unwrap();
+        assert_eq!(
+            hex::encode(master_secret.as_bytes()),
+            "18df06843d13a08bf2a449844c5f8a478001bc4d4c627984d5a41da8d0402919"
+        );
+    }
+
+    #[test]
+    fn test_tls13_rfc8448_application_traffic_keys() {
+        let server_secret =
+            hex::decode("a11af9f05531f856ad47116b45a950328204b4f44bfb6b3a4b4f1f3fcb631643")
+                .unwrap();
+        let client_secret =
+            hex::decode("9e40646ce79a7f9dc05af8889bce6552875afa0b06df0087f792ebb7c17504a5")
+                .unwrap();
+
+        let server_keys = traffic_keys(HashAlgorithm::Sha256, &server_secret, 16).unwrap();
+        assert_eq!(
+            hex::encode(server_keys.key()),
+            "9f02283b6c9c07efc26bb9f2ac92e356"
+        );
+        assert_eq!(hex::encode(server_keys.iv()), "cf782b88dd83549aadf1e984");
+
+        let client_keys = traffic_keys(HashAlgorithm::Sha256, &client_secret, 16).unwrap();
+        assert_eq!(
+            hex::encode(client_keys.key()),
+            "17422dda596ed5d9acd890e3c63f5051"
+        );
+        assert_eq!(hex::encode(client_keys.iv()), "5b78923dee08579033e523d9");
+    }
+
+    #[test]
+    fn test_tls13_rfc8448_resumption_psk() {
+        // Resumption master secret and PSK (ticket_nonce 0000) from RFC 8448 sections 3 and 4.
+        let resumption_master_secret =
+            hex::decode("7df235f2031d2a051287d02b0241b0bfdaf86cc856231f2d5aba46c434ec196c")
+                .unwrap();
+
+        let psk =
+            resumption_psk(HashAlgorithm::Sha256, &resumption_master_secret, &[0, 0]).unwrap();
+        assert_eq!(
+            hex::encode(psk),
+            "4ecd0eb6ec3b4d87f5d6028f922ca4c5851a277fd41311c9e62d2c9492e1c4f3"
+        );
+    }
+
+    #[test]
+    fn test_tls13_key_update() {
+        let server_secret =
+            hex::decode("a11af9f05531f856ad47116b45a950328204b4f44bfb6b3a4b4f1f3fcb631643")
+                .unwrap();
+
+        let next_secret = next_traffic_secret(HashAlgorithm::Sha256, &server_secret).unwrap();
+        assert_eq!(
+            hex::encode(next_secret),
+            "51921b8aa3001976eb401d0a4319a8516416a6c56001a357e5d162031e84f916"
+        );
+    }
+
+    #[test]
+    fn test_tls13_binder_keys() {
+        // PSK, early secret and "res binder" key from the "Resumed 0-RTT Handshake" trace of RFC 8448 section 4.
+        let psk = hex::decode("4ecd0eb6ec3b4d87f5d6028f922ca4c5851a277fd41311c9e62d2c9492e1c4f3")
+            .unwrap();
+        let early_secret = EarlySecret::new(HashAlgorithm::Sha256, Some(&psk)).unwrap();
+        assert_eq!(
+            hex::encode(early_secret.as_bytes()),
+            "9b2188e9b2fc6d64d71dc329900e20bb41915000f678aa839cbb797cb7d8332c"
+        );
+
+        assert_eq!(
+            hex::encode(early_secret.binder_key(true).unwrap()),
+            "69fe131a3bbad5d63c64eebcc30e395b9d8107726a13d074e389dbc8a4e47256"
+        );
+        assert_ne!(
+            early_secret.binder_key(false).unwrap(),
+            early_secret.binder_key(true).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_tls13_sha384_key_schedule() {
+        // RFC 8448 only covers SHA-256, so these values were computed with the OpenSSL 3.5 TLS13-KDF
+        // (`openssl kdf -kdfopt digest:SHA384 ... TLS13-KDF`) over the same inputs.
+        let mut transcript = TranscriptHash::new(HashAlgorithm::Sha384);
+        transcript.append(b"client hello");
+        transcript.append(b"server hello");
+        let transcript_hash = transcript.current_hash();
+        assert_eq!(hex::encode(&transcript_hash), "0e4abd0248aeb4c86dc29b097cc71af1b81567c43de7a0c475cb3885819325d04d1569466a8c24c31b742b3839cdc41a");
+
+        let early_secret = EarlySecret::new(HashAlgorithm::Sha384, None).unwrap();
+        assert_eq!(hex::encode(early_secret.as_bytes()), "7ee8206f5570023e6dc7519eb1073bc4e791ad37b5c382aa10ba18e2357e716971f9362f2c2fe2a76bfd78dfec4ea9b5");
+
+        let shared_secret = hex::decode(ECDHE_SHARED_SECRET).unwrap();
+        let handshake_secret = early_secret
+            .into_handshake_secret(Some(&shared_secret))
+            .unwrap();
+        assert_eq!(hex::encode(handshake_secret.as_bytes()), "984e65f4ea6ac0dece14762ac3752b71867a045c60d3fe7808b31949d2ce27d3142e6da6d92a68437f77c26509ce0b2b");
+
+        let client_secret = handshake_secret
+            .client_handshake_traffic_secret(&transcript_hash)
+            .unwrap();
+        assert_eq!(hex::encode(&client_secret), "9938c08fa826c581f28c460fe40a6f638e302cd03607a7575c855f2a957a40cc9aba5bceb118a1e00e578e7da1da752b");
+
+        let client_keys = traffic_keys(HashAlgorithm::Sha384, &client_secret, 32).unwrap();
+        assert_eq!(
+            hex::encode(client_keys.key()),
+            "d1cefcfc65c1681203d89f041ceb0e43621f4403f7180488c4b73222ab42b8b5"
+        );
+        assert_eq!(hex::encode(client_keys.iv()), "b0af1d9d3a8ce231132e5d70");
+
+        let next_secret = next_traffic_secret(HashAlgorithm::Sha384, &client_secret).unwrap();
+        assert_eq!(hex::encode(next_secret), "637e2db334445dbf39142110d6ec40fbe7658c5f30ee85b914ebac3c55c4b277a2626ba6c74d9a7ac4b92d8e13c79774");
+
+        let master_secret = handshake_secret.into_master_secret().unwrap();
+        assert_eq!(hex::encode(master_secret.as_bytes()), "2915f95014de3957dad1c2764430fa490ffbe027a09be69e4da30a27969b40081308dbd17cb65a35332215cfc8cf4a2f");
+    }
+
+    #[test]
+    fn test_tls13_transcript_hash_keeps_running() {
+        let mut transcript = TranscriptHash::new(HashAlgorithm::Sha256);
+        transcript.append(&hex::decode(CLIENT_HELLO).unwrap());
+        let _ = transcript.current_hash();
+        transcript.append(&hex::decode(SERVER_HELLO).unwrap());
+
+        assert_eq!(
+            hex::encode(transcript.current_hash()),
+            "860c06edc07858ee8e78f0e7428c58edd6b43f2ca3e6e95f02ed063cf0e1cad8"
+        );
+    }
+}
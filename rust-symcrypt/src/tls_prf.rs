@@ -0,0 +1,81 @@
+//! TLS 1.2 PRF as defined in RFC 5246 section 5. For further documentation please refer to symcrypt.h
+
+use crate::errors::SymCryptError;
+use crate::hmac::{convert_mac, HmacAlgorithm};
+use crate::secret::SecretBytes;
+use symcrypt_sys;
+
+/// Stateless TLS 1.2 PRF function, PRF(secret, label, seed) with P_hash built on the provided [`HmacAlgorithm`].
+///
+/// `secret` is the secret, for example the pre-master secret or the master secret.
+/// `label` is the ASCII label, such as `b"master secret"`, without a length or a terminating zero.
+/// `seed` is the seed, for example the client random followed by the server random.
+/// `output_length` is the number of bytes to derive.
+///
+/// The derived value is returned as [`SecretBytes`] so it is wiped when it leaves scope.
+pub fn tls_prf_1_2(
+    hmac_algorithm: HmacAlgorithm,
+    secret: &[u8],
+    label: &[u8],
+    seed: &[u8],
+    output_length: usize,
+) -> Result<SecretBytes, SymCryptError> {
+    let mut output = SecretBytes::zeroed(output_length);
+    unsafe {
+        // SAFETY: FFI calls
+        match symcrypt_sys::SymCryptTlsPrf1_2(
+            convert_mac(hmac_algorithm),
+            secret.as_ptr(),
+            secret.len() as symcrypt_sys::SIZE_T,
+            label.as_ptr(),
+            label.len() as symcrypt_sys::SIZE_T,
+            seed.as_ptr(),
+            seed.len() as symcrypt_sys::SIZE_T,
+            output.as_mut_bytes().as_mut_ptr(),
+            output.len() as symcrypt_sys::SIZE_T,
+        ) {
+            symcrypt_sys::SYMCRYPT_ERROR_SYMCRYPT_NO_ERROR => Ok(output),
+            err => Err(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Known answer tests posted to the IETF TLS mailing list, https://mailarchive.ietf.org/arch/msg/tls/fzVCzk-z3FShgGJ6DOXqM1ydxms/
+    #[test]
+    fn test_tls_prf_1_2_sha256() {
+        let secret = hex::decode("9bbe436ba940f017b17652849a71db35").unwrap();
+        let seed = hex::decode("a0ba9f936cda311827a6f796ffd5198c").unwrap();
+        let expected = "e3f229ba727be17b8d122620557cd453c2aab21d07c3d495329b52d4e61edb5a6b301791e90d35c9c9a46b4e14baf9af0fa022f7077def17abfd3797c0564bab4fbc91666e9def9b97fce34f796789baa48082d122ee42c5a72e5a5110fff70187347b66";
+
+        let result = tls_prf_1_2(
+            HmacAlgorithm::HmacSha256,
+            &secret,
+            b"test label",
+            &seed,
+            100,
+        )
+        .unwrap();
+        assert_eq!(hex::encode(result), expected);
+    }
+
+    #[test]
+    fn test_tls_prf_1_2_sha384() {
+        let secret = hex::decode("b80b733d6ceefcdc71566ea48e5567df").unwrap();
+        let seed = hex::decode("cd665cf6a8447dd6ff8b27555edb7465").unwrap();
+        let expected = "7b0c18e9ced410ed1804f2cfa34a336a1c14dffb4900bb5fd7942107e81c83cde9ca0faa60be9fe34f82b1233c9146a0e534cb400fed2700884f9dc236f80edd8bfa961144c9e8d792eca722a7b32fc3d416d473ebc2c5fd4abfdad05d9184259b5bf8cd4d90fa0d31e2dec479e4f1a26066f2eea9a69236a3e52655c9e9aee691c8f3a26854308d5eaa3be85e0990703d73e56f";
+
+        let result = tls_prf_1_2(
+            HmacAlgorithm::HmacSha384,
+            &secret,
+            b"test label",
+            &seed,
+            148,
+        )
+        .unwrap();
+        assert_eq!(hex::encode(result), expected);
+    }
+}
@@ -26,9 +26,9 @@ fn main() {
     // Since we are pulling in symcrypt as a submodule, it should be pretty easy to run a vendored build, this would allow us
     // to hard stop at a commit and ensure that there is no discrepancy between build version and header version
 
-    // TODO: Factor out binding generation. Bindgen should only be run manually with updates to underlying SymCrypt code that
-    // we decide what to take.
-    println!("cargo:rerun-if-changed=inc/wrapper.h");
+    // The bindings are always generated from the headers that are built against, see lib.rs. The SymCrypt submodule should be checked
+    // out at the release in README.md.
+    println!("cargo:rerun-if-changed=wrapper.h");
 
     // TODO: Discuss if factoring the .allowlist_functions to another file is better approach
 
@@ -45,9 +45,16 @@ fn main() {
         .allowlist_function("^(SymCryptSha384.*)$")
         .allowlist_var("SYMCRYPT_SHA256_RESULT_SIZE")
         .allowlist_var("SYMCRYPT_SHA384_RESULT_SIZE")
+        .allowlist_var("SymCryptSha256Algorithm")
+        .allowlist_var("SymCryptSha384Algorithm")
+        // SHA-1 and SHA-512 are only used through the SSH KDF
+        .allowlist_var("SymCryptSha1Algorithm")
+        .allowlist_var("SymCryptSha512Algorithm")
         // HMAC FUNCTIONS
         .allowlist_function("^(SymCryptHmacSha256.*)$")
         .allowlist_function("^(SymCryptHmacSha384.*)$")
+        .allowlist_var("SymCryptHmacSha256Algorithm")
+        .allowlist_var("SymCryptHmacSha384Algorithm")
         // GCM FUNCTIONS
         .allowlist_function("^(SymCryptGcm.*)$")
         .allowlist_function("SymCryptChaCha20Poly1305Encrypt")
@@ -56,19 +63,69 @@ fn main() {
         .allowlist_function("SymCryptTlsPrf1_2Derive")
         .allowlist_function("SymCryptTlsPrf1_2")
         .allowlist_var("SymCryptAesBlockCipher")
+        .allowlist_function("SymCryptAesExpandKey")
+        .allowlist_function("SymCryptAesEncrypt")
+        .allowlist_function("SymCryptAesDecrypt")
         // HKDF functions
         .allowlist_function("^(SymCryptHkdf.*)$")
+        // SSH KDF and SRTP KDF functions
+        .allowlist_function("^(SymCryptSshKdf.*)$")
+        .allowlist_function("^(SymCryptSrtpKdf.*)$")
+        .allowlist_var("^(SYMCRYPT_SSHKDF_.*)$")
+        .allowlist_var("^(SYMCRYPT_SRTP_.*)$")
+        .allowlist_var("^(SYMCRYPT_SRTCP_.*)$")
         // ECDH Key Agreement
         .allowlist_var("SymCryptEcurveParamsNistP256")
         .allowlist_var("SymCryptEcurveParamsNistP384")
+        .allowlist_var("SymCryptEcurveParamsNistP521")
         .allowlist_var("SymCryptEcurveParamsCurve25519")
+        .allowlist_var("SYMCRYPT_FLAG_ECKEY_ECDSA")
         .allowlist_var("SYMCRYPT_FLAG_ECKEY_ECDH")
         .allowlist_function("^(SymCryptEcurve.*)$")
         .allowlist_function("^(SymCryptEckey.*)$")
         .allowlist_function("SymCryptEcDhSecretAgreement")
         .allowlist_function("SymCryptSizeofEckeyFromCurve")
+        // Modular arithmetic, used to decompress points in the field of a curve
+        .allowlist_function("^(SymCryptModElement.*)$")
+        .allowlist_function("SymCryptModAdd")
+        .allowlist_function("SymCryptModMul")
+        .allowlist_function("SymCryptModNeg")
+        // DH Key Agreement
+        .allowlist_var("SYMCRYPT_FLAG_DLKEY_DH")
+        .allowlist_function("^(SymCryptDlgroup.*)$")
+        .allowlist_function("^(SymCryptDlkey.*)$")
+        .allowlist_function("SymCryptDhSecretAgreement")
+        // DSA functions
+        .allowlist_var("SYMCRYPT_FLAG_DLKEY_DSA")
+        .allowlist_function("SymCryptDsaSign")
+        .allowlist_function("SymCryptDsaVerify")
+        // ECDSA functions
+        .allowlist_function("SymCryptEcDsaSign")
+        .allowlist_function("SymCryptEcDsaVerify")
+        // RSA functions
+        .allowlist_var("SYMCRYPT_FLAG_RSAKEY_SIGN")
+        .allowlist_var("SYMCRYPT_FLAG_RSAKEY_ENCRYPT")
+        .allowlist_var("SymCryptSha256OidList")
+        .allowlist_var("SymCryptSha384OidList")
+        .allowlist_function("^(SymCryptRsakey.*)$")
+        .allowlist_function("SymCryptRsaPkcs1Sign")
+        .allowlist_function("SymCryptRsaPkcs1Verify")
+        .allowlist_function("SymCryptRsaPssSign")
+        .allowlist_function("SymCryptRsaPssVerify")
+        // ML-KEM functions
+        .allowlist_function("^(SymCryptMlKem.*)$")
+        // ML-DSA functions
+        .allowlist_function("^(SymCryptMlDsa.*)$")
+        .allowlist_function("^(SymCryptHashMlDsa.*)$")
+        // LMS and XMSS functions
+        .allowlist_function("^(SymCryptLms.*)$")
+        .allowlist_function("^(SymCryptXmss.*)$")
+        // AES-CTR-DRBG functions
+        .allowlist_function("^(SymCryptRngAes.*)$")
+        .allowlist_var("^(SYMCRYPT_RNG_AES_.*)$")
         // Utility functions
         .allowlist_function("SymCryptWipe")
+        .allowlist_function("SymCryptEqual")
         .allowlist_function("SymCryptRandom")
         
         .generate_comments(true)
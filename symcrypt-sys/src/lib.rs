@@ -5,5 +5,12 @@
 
 extern crate libc;
 
-mod symcrypt_bindings;
-pub use symcrypt_bindings::*;
+// The bindings are generated by build.rs from the SymCrypt headers that are linked against, so the size of every struct that is
+// allocated on the Rust side matches the library.
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+// ML-KEM, ML-DSA, LMS and XMSS were added in SymCrypt 103.8.0.
+const _: () = assert!(
+    SYMCRYPT_CODE_VERSION_API == 103 && SYMCRYPT_CODE_VERSION_MINOR >= 8,
+    "symcrypt-sys requires SymCrypt 103.8.0 or later"
+);